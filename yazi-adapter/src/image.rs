@@ -1,10 +1,11 @@
-use std::{fs::File, io::BufReader, path::{Path, PathBuf}};
+use std::{fs::File, io::{BufReader, Cursor}, path::{Path, PathBuf}};
 
 use anyhow::Result;
 use exif::{In, Tag};
-use image::{codecs::jpeg::JpegEncoder, imageops::{self, FilterType}, DynamicImage, Limits};
+use image::{codecs::jpeg::JpegEncoder, imageops::{self, FilterType}, DynamicImage, ImageFormat, Limits};
 use ratatui::layout::Rect;
 use yazi_config::{PREVIEW, TASKS};
+use yazi_shared::spawn_blocking_nice;
 
 use crate::Dimension;
 
@@ -15,7 +16,7 @@ impl Image {
 		let orientation = Self::orientation(path).await?;
 
 		let path = path.to_owned();
-		let mut img = tokio::task::spawn_blocking(move || {
+		let mut img = spawn_blocking_nice(PREVIEW.worker_nice, move || {
 			Self::set_limits(image::ImageReader::open(path)?.with_guessed_format()?).decode()
 		})
 		.await??;
@@ -25,7 +26,7 @@ impl Image {
 			(w, h) = (h, w);
 		}
 
-		let buf = tokio::task::spawn_blocking(move || {
+		let buf = spawn_blocking_nice(PREVIEW.worker_nice, move || {
 			if img.width() > w || img.height() > h {
 				img = img.resize(w, h, Self::filter());
 			}
@@ -49,11 +50,33 @@ impl Image {
 		Ok(tokio::fs::write(cache, buf).await?)
 	}
 
+	// Encodes an image for the clipboard, as a PNG, or a BMP on Windows, downscaled
+	// to fit within `max` pixels on each side unless it's 0
+	pub async fn clipboard(path: &Path, max: u32) -> Result<Vec<u8>> {
+		let orientation = Self::orientation(path).await?;
+
+		let path = path.to_owned();
+		spawn_blocking_nice(PREVIEW.worker_nice, move || {
+			let mut img =
+				Self::set_limits(image::ImageReader::open(path)?.with_guessed_format()?).decode()?;
+			if max > 0 && (img.width() > max || img.height() > max) {
+				img = img.resize(max, max, Self::filter());
+			}
+			img = Self::rotate(img, orientation);
+
+			let format = if cfg!(windows) { ImageFormat::Bmp } else { ImageFormat::Png };
+			let mut buf = Cursor::new(Vec::new());
+			img.write_to(&mut buf, format)?;
+			Ok(buf.into_inner())
+		})
+		.await?
+	}
+
 	pub(super) async fn downscale(path: &Path, rect: Rect) -> Result<DynamicImage> {
 		let orientation = Self::orientation(path).await?;
 
 		let path = path.to_owned();
-		let mut img = tokio::task::spawn_blocking(move || {
+		let mut img = spawn_blocking_nice(PREVIEW.worker_nice, move || {
 			Self::set_limits(image::ImageReader::open(path)?.with_guessed_format()?).decode()
 		})
 		.await??;
@@ -68,7 +91,7 @@ impl Image {
 			return Ok(img);
 		}
 
-		tokio::task::spawn_blocking(move || {
+		spawn_blocking_nice(PREVIEW.worker_nice, move || {
 			if img.width() > w || img.height() > h {
 				img = img.resize(w, h, Self::filter())
 			}
@@ -55,3 +55,16 @@ pub fn init() {
 	ADAPTOR.init(Adapter::matches());
 	ADAPTOR.start();
 }
+
+// For the tests of other crates, which have no terminal to detect the adapter
+// from, so no image is ever shown
+#[doc(hidden)]
+pub fn init_tests() {
+	TMUX.init(false);
+	START.init("\x1b");
+	CLOSE.init("");
+	ESCAPE.init("\x1b");
+
+	SHOWN.with(<_>::default);
+	ADAPTOR.init(Adapter::Kitty);
+}
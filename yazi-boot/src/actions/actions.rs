@@ -3,6 +3,14 @@ use std::process;
 pub(crate) struct Actions;
 
 impl Actions {
+	// Handled before the config is loaded, as it'd fail to load on the errors
+	// these are about
+	pub(crate) fn act_early(args: &crate::Args) {
+		if args.check_config {
+			Self::check_config(args.json);
+		}
+	}
+
 	pub(crate) fn act(args: &crate::Args) {
 		if args.debug {
 			println!("{}", Self::debug().unwrap());
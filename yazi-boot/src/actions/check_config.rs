@@ -0,0 +1,22 @@
+use std::process;
+
+use yazi_config::check::{check, Level};
+use yazi_shared::Xdg;
+
+use super::Actions;
+
+impl Actions {
+	pub(super) fn check_config(json: bool) -> ! {
+		let diags = check(&std::env::current_dir().unwrap_or_default(), &Xdg::config_dir());
+
+		if json {
+			println!("{}", serde_json::to_string_pretty(&diags).unwrap());
+		} else if diags.is_empty() {
+			println!("No problems found");
+		} else {
+			diags.iter().for_each(|d| println!("{d}"));
+		}
+
+		process::exit(if diags.iter().any(|d| d.level == Level::Error) { 1 } else { 0 });
+	}
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::module_inception)]
 
 mod actions;
+mod check_config;
 mod clear_cache;
 mod debug;
 mod version;
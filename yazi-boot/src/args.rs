@@ -34,6 +34,13 @@ pub struct Args {
 	#[arg(long)]
 	pub debug: bool,
 
+	/// Check the config files for errors, and exit
+	#[arg(long)]
+	pub check_config: bool,
+	/// Report the problems found by `--check-config` in JSON
+	#[arg(long, requires = "check_config")]
+	pub json:         bool,
+
 	/// Print version
 	#[arg(short = 'V', long)]
 	pub version: bool,
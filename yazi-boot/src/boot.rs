@@ -1,6 +1,7 @@
 use std::{collections::HashSet, ffi::OsString, path::PathBuf};
 
 use serde::Serialize;
+use yazi_config::MANAGER;
 use yazi_shared::{fs::{current_cwd, expand_path}, Xdg};
 
 #[derive(Debug, Default, Serialize)]
@@ -8,6 +9,8 @@ pub struct Boot {
 	pub cwds:  Vec<PathBuf>,
 	pub files: Vec<OsString>,
 
+	pub warnings: Vec<String>,
+
 	pub local_events:  HashSet<String>,
 	pub remote_events: HashSet<String>,
 
@@ -18,15 +21,21 @@ pub struct Boot {
 }
 
 impl Boot {
-	fn parse_entries(entries: &[PathBuf]) -> (Vec<PathBuf>, Vec<OsString>) {
-		if entries.is_empty() {
-			return (vec![current_cwd().unwrap()], vec![OsString::new()]);
-		}
-
+	// Each entry is opened in a tab of its own, up to `max` of them. A directory
+	// is entered, a file has its parent entered with itself hovered, and those
+	// that don't exist are skipped.
+	fn parse_entries(entries: &[PathBuf], max: usize) -> (Vec<PathBuf>, Vec<OsString>, Vec<String>) {
 		let mut cwds = Vec::with_capacity(entries.len());
 		let mut files = Vec::with_capacity(entries.len());
-		for entry in entries.iter().map(expand_path) {
-			if let Some(p) = entry.parent().filter(|_| !entry.is_dir()) {
+		let mut warnings = vec![];
+
+		for (i, entry) in entries.iter().map(expand_path).enumerate() {
+			if cwds.len() >= max {
+				warnings.push(format!("Only {max} tabs can be opened, skipped {} more", entries.len() - i));
+				break;
+			} else if entry.symlink_metadata().is_err() {
+				warnings.push(format!("`{}` does not exist, skipped", entry.display()));
+			} else if let Some(p) = entry.parent().filter(|_| !entry.is_dir()) {
 				cwds.push(p.to_owned());
 				files.push(entry.file_name().unwrap().to_owned());
 			} else {
@@ -35,14 +44,18 @@ impl Boot {
 			}
 		}
 
-		(cwds, files)
+		if cwds.is_empty() {
+			cwds.push(current_cwd().unwrap());
+			files.push(OsString::new());
+		}
+		(cwds, files, warnings)
 	}
 }
 
 impl From<&crate::Args> for Boot {
 	fn from(args: &crate::Args) -> Self {
 		let config_dir = Xdg::config_dir();
-		let (cwds, files) = Self::parse_entries(&args.entries);
+		let (cwds, files, warnings) = Self::parse_entries(&args.entries, MANAGER.max_tabs);
 
 		let local_events = args
 			.local_events
@@ -59,6 +72,8 @@ impl From<&crate::Args> for Boot {
 			cwds,
 			files,
 
+			warnings,
+
 			local_events,
 			remote_events,
 
@@ -69,3 +84,30 @@ impl From<&crate::Args> for Boot {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_entries() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		std::fs::create_dir_all(dir.join("a")).unwrap();
+		std::fs::create_dir_all(dir.join("b")).unwrap();
+		std::fs::write(dir.join("b/file"), "").unwrap();
+
+		let entries = ["a", "nope", "b/file", "b", "a"].map(|s| dir.join(s));
+		let (cwds, files, warnings) = Boot::parse_entries(&entries, 3);
+		assert_eq!(cwds, [dir.join("a"), dir.join("b"), dir.join("b")]);
+		assert_eq!(files, [OsString::new(), OsString::from("file"), OsString::new()]);
+		assert_eq!(warnings, [
+			format!("`{}` does not exist, skipped", dir.join("nope").display()),
+			"Only 3 tabs can be opened, skipped 1 more".to_owned(),
+		]);
+
+		let (cwds, files, warnings) = Boot::parse_entries(&[dir.join("nope")], 3);
+		assert_eq!((cwds, files), (vec![current_cwd().unwrap()], vec![OsString::new()]));
+		assert_eq!(warnings.len(), 1);
+	}
+}
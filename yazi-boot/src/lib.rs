@@ -11,8 +11,13 @@ pub use boot::*;
 pub static ARGS: RoCell<Args> = RoCell::new();
 pub static BOOT: RoCell<Boot> = RoCell::new();
 
-pub fn init() {
+pub fn init_args() {
 	ARGS.with(<_>::parse);
+
+	actions::Actions::act_early(&ARGS);
+}
+
+pub fn init() {
 	BOOT.init(From::from(&*ARGS));
 
 	actions::Actions::act(&ARGS);
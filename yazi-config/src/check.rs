@@ -0,0 +1,361 @@
+use std::{fmt::{self, Display}, path::{Path, PathBuf}, str::FromStr};
+
+use serde::Serialize;
+use toml::{Table, Value};
+use yazi_shared::Layer;
+
+use crate::{keymap::{Control, Keymap}, log::Log, manager::Manager, open::Open, plugin::{Plugin, PRESET_PLUGINS}, popup::{Confirm, Input, Select}, preview::Preview, tasks::Tasks, theme::Theme, which::Which, Preset};
+
+// Sections whose keys are named by the user
+const FREEFORM: &[&str] = &["opener"];
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+	pub level:   Level,
+	pub file:    PathBuf,
+	pub line:    Option<usize>,
+	pub column:  Option<usize>,
+	pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+	Error,
+	Warning,
+}
+
+impl Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.file.display())?;
+		if let Some(line) = self.line {
+			write!(f, ":{line}")?;
+		}
+		if let Some(column) = self.column {
+			write!(f, ":{column}")?;
+		}
+
+		let level = if self.level == Level::Error { "error" } else { "warning" };
+		write!(f, ": {level}: {}", self.message)
+	}
+}
+
+// Checks the `yazi.toml`, `keymap.toml` and `theme.toml` of each directory they
+// are layered from, as `crate::init()` does for `cwd`, without loading them.
+// Each layer is checked merged with the ones below it.
+pub fn check(cwd: &Path, config_dir: &Path) -> Vec<Diagnostic> {
+	let dirs = Preset::dirs(cwd, config_dir);
+	let mut c = Checker { config_dir, out: vec![] };
+
+	for i in 0..dirs.len() {
+		c.yazi(&dirs[..=i]);
+		c.keymap(&dirs[..=i]);
+		c.theme(&dirs[..=i]);
+	}
+	c.out
+}
+
+struct Checker<'a> {
+	config_dir: &'a Path,
+	out:        Vec<Diagnostic>,
+}
+
+struct File {
+	path: PathBuf,
+	text: String,
+}
+
+impl Checker<'_> {
+	// `dirs` are the layers up to the one being checked, which is the last
+	fn yazi(&mut self, dirs: &[PathBuf]) {
+		let Some(file) =
+			self.load(&dirs[dirs.len() - 1], "yazi.toml", include_str!("../preset/yazi.toml"))
+		else {
+			return;
+		};
+		let Some(merged) = self.merge(&file, Preset::yazi(dirs).map(|s| s.into_owned())) else {
+			return;
+		};
+
+		self.parse::<Manager>(&file, &merged);
+		self.parse::<Preview>(&file, &merged);
+		self.parse::<Open>(&file, &merged);
+		self.parse::<Tasks>(&file, &merged);
+		self.parse::<Input>(&file, &merged);
+		self.parse::<Confirm>(&file, &merged);
+		self.parse::<Select>(&file, &merged);
+		self.parse::<Which>(&file, &merged);
+		self.parse::<Log>(&file, &merged);
+
+		if let Some(plugin) = self.parse::<Plugin>(&file, &merged) {
+			let fetchers = plugin.fetchers.iter().map(|f| &f.run);
+			let preloaders = plugin.preloaders.iter().map(|p| &p.run);
+			let previewers = plugin.previewers.iter().map(|p| &p.run);
+			for cmd in fetchers.chain(preloaders).chain(previewers) {
+				self.plugin(&file, &cmd.name);
+			}
+		}
+	}
+
+	fn keymap(&mut self, dirs: &[PathBuf]) {
+		let Some(file) =
+			self.load(&dirs[dirs.len() - 1], "keymap.toml", include_str!("../preset/keymap.toml"))
+		else {
+			return;
+		};
+
+		let merged = Preset::keymap(dirs).map(|s| s.into_owned());
+		let Some(keymap) = self.merge(&file, merged).and_then(|m| self.parse::<Keymap>(&file, &m))
+		else {
+			return;
+		};
+
+		let layers = [
+			&keymap.manager,
+			&keymap.tasks,
+			&keymap.select,
+			&keymap.input,
+			&keymap.confirm,
+			&keymap.help,
+			&keymap.completion,
+		];
+		let runs = layers.into_iter().flatten().flat_map(|c| &c.run);
+		let names = runs.filter(|r| r.name == "plugin").filter_map(|r| r.first()?.as_str());
+		for name in names {
+			self.plugin(&file, name);
+		}
+		for (layer, ctrl) in keymap.misplaced() {
+			self.misplaced(&file, layer, ctrl);
+		}
+	}
+
+	fn theme(&mut self, dirs: &[PathBuf]) {
+		let Some(file) =
+			self.load(&dirs[dirs.len() - 1], "theme.toml", include_str!("../preset/theme.toml"))
+		else {
+			return;
+		};
+
+		let merged = Preset::theme(dirs).map(|s| s.into_owned());
+		if let Some(merged) = self.merge(&file, merged) {
+			self.parse::<Theme>(&file, &merged);
+		}
+	}
+
+	// Reads and parses a file, and warns about the keys that aren't in `preset`
+	fn load(&mut self, dir: &Path, name: &str, preset: &str) -> Option<File> {
+		let path = dir.join(name);
+		let text = std::fs::read_to_string(&path).ok()?;
+		let file = File { path, text };
+
+		let user: Table = match file.text.parse() {
+			Ok(t) => t,
+			Err(e) => {
+				let e: toml::de::Error = e;
+				let pos = e.span().map(|s| position(&file.text, s.start));
+				self.push(Level::Error, &file, pos, e.message().to_owned());
+				return None;
+			}
+		};
+
+		let preset: Table = preset.parse().unwrap();
+		for (section, v) in &user {
+			if section == "$schema" {
+				continue;
+			}
+			let Some(known) = preset.get(section) else {
+				let pos = locate(&file.text, None, section);
+				self.push(Level::Warning, &file, pos, format!("Unknown section `{section}`"));
+				continue;
+			};
+			let (Value::Table(v), Value::Table(known)) = (v, known) else { continue };
+			if FREEFORM.contains(&section.as_str()) {
+				continue;
+			}
+
+			for key in v.keys().filter(|&k| !Self::known(known, k)) {
+				let pos = locate(&file.text, Some(section), key);
+				self.push(Level::Warning, &file, pos, format!("Unknown key `{section}.{key}`"));
+			}
+		}
+
+		Some(file)
+	}
+
+	fn known(preset: &Table, key: &str) -> bool {
+		let base = key.strip_prefix("prepend_").or_else(|| key.strip_prefix("append_"));
+		preset.contains_key(key) || base.is_some_and(|b| preset.contains_key(b))
+	}
+
+	fn merge(&mut self, file: &File, merged: anyhow::Result<String>) -> Option<String> {
+		merged.map_err(|e| self.push(Level::Error, file, None, format!("{e:#}"))).ok()
+	}
+
+	fn parse<T>(&mut self, file: &File, merged: &str) -> Option<T>
+	where
+		T: FromStr,
+		T::Err: Into<anyhow::Error>,
+	{
+		let e: anyhow::Error = match T::from_str(merged) {
+			Ok(v) => return Some(v),
+			Err(e) => e.into(),
+		};
+
+		// The error points into the merged string, so find the key it's about in the
+		// user's file instead
+		let Some(e) = e.downcast_ref::<toml::de::Error>() else {
+			self.push(Level::Error, file, None, format!("{e:#}"));
+			return None;
+		};
+
+		let pos = e.span().and_then(|s| {
+			let line = merged[..s.start].rsplit('\n').next().unwrap_or_default();
+			let key = line.split('=').next().unwrap_or_default().trim();
+			locate(&file.text, None, key)
+		});
+		self.push(Level::Error, file, pos, e.message().to_owned());
+		None
+	}
+
+	fn plugin(&mut self, file: &File, name: &str) {
+		if PRESET_PLUGINS.contains(&name)
+			|| self.config_dir.join(format!("plugins/{name}.yazi/init.lua")).is_file()
+		{
+			return;
+		}
+
+		let pos = file
+			.text
+			.lines()
+			.position(|l| l.contains(&format!("\"{name}\"")) || l.contains(&format!("plugin {name}")))
+			.map(|i| (i + 1, 1));
+		self.push(Level::Warning, file, pos, format!("Plugin `{name}` is used but not installed"));
+	}
+
+	// The line of the binding is looked for after the `[layer]` header by its mode
+	fn misplaced(&mut self, file: &File, layer: Layer, ctrl: &Control) {
+		let Some(mode) = ctrl.mode else { return };
+		let quoted = format!("\"{mode}\"");
+		let pos = locate(&file.text, None, &layer.to_string()).and_then(|(l, _)| {
+			let i = file.text.lines().skip(l).position(|s| s.contains("mode") && s.contains(&quoted))?;
+			Some((l + i + 1, 1))
+		});
+
+		let message =
+			format!("Key `{}` never fires, as `[{layer}]` is never in {mode} mode", ctrl.on());
+		self.push(Level::Warning, file, pos, message);
+	}
+
+	// One that isn't found in `file` comes from a layer below it when it's been
+	// reported already, so it isn't repeated
+	fn push(&mut self, level: Level, file: &File, pos: Option<(usize, usize)>, message: String) {
+		if pos.is_none() && self.out.iter().any(|d| d.level == level && d.message == message) {
+			return;
+		}
+		self.out.push(Diagnostic {
+			level,
+			file: file.path.clone(),
+			line: pos.map(|p| p.0),
+			column: pos.map(|p| p.1),
+			message,
+		});
+	}
+}
+
+// The 1-based line and column of `offset` in `text`
+fn position(text: &str, offset: usize) -> (usize, usize) {
+	let before = &text[..offset.min(text.len())];
+	let line = before.matches('\n').count() + 1;
+	let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+	(line, column)
+}
+
+// The position of `key`, or of the `[key]` header if it's a section, in `text`.
+// Only the lines after the header of `section` are looked at if given.
+fn locate(text: &str, section: Option<&str>, key: &str) -> Option<(usize, usize)> {
+	let mut lines = text.lines().enumerate();
+	if let Some(section) = section {
+		let header = format!("[{section}]");
+		lines.find(|(_, l)| l.trim() == header)?;
+	}
+
+	let header = format!("[{key}]");
+	lines.find_map(|(i, l)| {
+		let trimmed = l.trim_start();
+		let column = l.len() - trimmed.len() + 1;
+		if trimmed.trim_end() == header {
+			return Some((i + 1, column));
+		}
+
+		let rest = trimmed.strip_prefix(key)?;
+		rest.trim_start().starts_with('=').then_some((i + 1, column))
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_check() {
+		crate::init_tests();
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+
+		std::fs::write(
+			dir.join("yazi.toml"),
+			"[manager]\nshow_symlink = true\n  show_hidden = \"yes\"\n\n[colors]\n",
+		)
+		.unwrap();
+		std::fs::write(
+			dir.join("keymap.toml"),
+			"[manager]\nprepend_keymap = [ { on = \"x\", run = \"plugin nope\" } ]\n\n[tasks]\nprepend_keymap = [\n\t{ on = \"v\", run = \"close\", mode = \"visual\" },\n]\n",
+		)
+		.unwrap();
+		std::fs::write(dir.join("theme.toml"), "[manager\n").unwrap();
+
+		let diags: Vec<_> = check(dir, dir).iter().map(|d| d.to_string()).collect();
+		let file = |name: &str| dir.join(name).display().to_string();
+		assert_eq!(diags, [
+			format!("{}:5:1: warning: Unknown section `colors`", file("yazi.toml")),
+			format!("{}:3:3: error: invalid type: string \"yes\", expected a boolean", file("yazi.toml")),
+			format!("{}:2:1: warning: Plugin `nope` is used but not installed", file("keymap.toml")),
+			format!(
+				"{}:6:1: warning: Key `v` never fires, as `[tasks]` is never in visual mode",
+				file("keymap.toml")
+			),
+			format!("{}:1:9: error: invalid table header\nexpected `.`, `]`", file("theme.toml")),
+		]);
+	}
+
+	#[test]
+	fn test_check_layers() {
+		crate::init_tests();
+		let tmp = tempfile::tempdir().unwrap();
+		let (config, layer) = (tmp.path().join("config"), tmp.path().join("layer"));
+		std::fs::create_dir_all(&config).unwrap();
+		std::fs::create_dir_all(&layer).unwrap();
+
+		let yazi = format!("[manager]\nshow_hidden = 1\n\n[project]\nlayers = [ {layer:?} ]\n");
+		std::fs::write(config.join("yazi.toml"), yazi).unwrap();
+		std::fs::write(layer.join("yazi.toml"), "[preview]\n\ttab_size = \"4\"\n").unwrap();
+
+		// The error of the config directory isn't repeated for the layer above it
+		let diags: Vec<_> = check(tmp.path(), &config).iter().map(|d| d.to_string()).collect();
+		let file = |dir: &Path| dir.join("yazi.toml").display().to_string();
+		assert_eq!(diags, [
+			format!("{}:2:1: error: invalid type: integer `1`, expected a boolean", file(&config)),
+			format!("{}:2:2: error: invalid type: string \"4\", expected u8", file(&layer)),
+		]);
+	}
+
+	#[test]
+	fn test_locate() {
+		let text = "a = 1\n[x]\n  b = 2\n[y]\nb=3\n";
+		assert_eq!(locate(text, None, "a"), Some((1, 1)));
+		assert_eq!(locate(text, None, "b"), Some((3, 3)));
+		assert_eq!(locate(text, Some("y"), "b"), Some((5, 1)));
+		assert_eq!(locate(text, None, "y"), Some((4, 1)));
+		assert_eq!(locate(text, Some("z"), "b"), None);
+	}
+}
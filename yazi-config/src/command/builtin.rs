@@ -0,0 +1,130 @@
+use super::Command;
+
+// Finding the drives that aren't mounted yet is only done on Linux
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const MOUNTS_FLAGS: &[&str] = &["mount", "unmount", "eject"];
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const MOUNTS_FLAGS: &[&str] = &["unmount", "eject"];
+
+pub(super) fn commands() -> Vec<Command> {
+	vec![
+		Command::new("escape", "Exit visual mode, clear selected, or cancel search")
+			.with_flags(&["all", "find", "visual", "filter", "select", "search"]),
+		Command::new("quit", "Exit the process, without asking about running tasks if forced")
+			.with_flags(&["no-cwd-file", "force"]),
+		Command::new("close", "Close the current tab, or quit if it is last tab")
+			.with_flags(&["no-cwd-file"]),
+		Command::new("suspend", "Suspend the process"),
+		Command::new("command", "Run an internal command"),
+		Command::new("palette", "List all commands with their keys, and run one"),
+		Command::new("arrow", "Move cursor").with_args(),
+		Command::new("leave", "Go back to the parent directory"),
+		Command::new("enter", "Enter the hovered directory, or a file as set by `enter_file`"),
+		Command::new("back", "Go back to the previous directory"),
+		Command::new("forward", "Go forward to the next directory"),
+		Command::new("cd", "Change the current directory")
+			.with_flags(&["interactive", "collapse"])
+			.with_path(),
+		Command::new("fuzzy", "Find a file under the current directory by fuzzy matching")
+			.with_flags(&["depth", "hidden"]),
+		Command::new("reveal", "Reveal a file in the current directory").with_path(),
+		Command::new("reveal_in_gui", "Show the hovered file in the system file manager"),
+		Command::new("goto", "Go to a directory, or reveal a file").with_path(),
+		Command::new("mounts", "Go to a mounted filesystem, or mount/unmount a removable drive")
+			.with_flags(MOUNTS_FLAGS),
+		Command::new("peek", "Peek the hovered file").with_flags(&["force"]),
+		Command::new("seek", "Seek the preview").with_args(),
+		Command::new("select", "Toggle the current selection state").with_flags(&["state"]),
+		Command::new("select_all", "Select all files").with_flags(&["state"]),
+		Command::new("selection_save", "Save selected files as a named set").with_args(),
+		Command::new("selection_load", "Select the files of a saved set").with_flags(&["cd"]),
+		Command::new("visual_mode", "Enter visual mode").with_flags(&["unset"]),
+		Command::new("open", "Open selected files").with_flags(&[
+			"interactive",
+			"which",
+			"hovered",
+			"line",
+			"column",
+			"spread",
+			"separate",
+		]),
+		Command::new("yank", "Yank selected files").with_flags(&["cut"]),
+		Command::new("unyank", "Cancel the yank status"),
+		Command::new("recents", "Pick a recently opened file").with_flags(&["open", "clear"]),
+		Command::new("log", "Show the commands run and file operations done").with_flags(&["clear"]),
+		Command::new("paste", "Paste yanked files, or the clipboard as a new file").with_flags(&[
+			"force",
+			"follow",
+			"preserve-timestamps",
+			"no-preserve",
+			"archive",
+			"from-clipboard",
+			"into-hovered",
+		]),
+		Command::new("duplicate", "Copy selected files in place under a new name")
+			.with_flags(&["suffix", "follow"]),
+		Command::new("copy_to", "Copy selected files to a picked directory").with_path(),
+		Command::new("move_to", "Move selected files to a picked directory").with_path(),
+		Command::new("link", "Symlink yanked files").with_flags(&["relative", "force"]),
+		Command::new("hardlink", "Hardlink yanked files").with_flags(&["force", "follow"]),
+		Command::new("remove", "Trash selected files").with_flags(&["force", "permanently"]),
+		Command::new("restore", "Restore the files trashed last"),
+		Command::new("create", "Create a file (ends with / for directories)").with_flags(&["force"]),
+		Command::new("rename", "Rename selected file(s)")
+			.with_flags(&["hovered", "force", "empty", "cursor"]),
+		Command::new("copy", "Copy the path or name of selected files, a command on them, or an image")
+			.with_flags(&["column", "compound", "each"])
+			.with_args(),
+		Command::new("calculate_size", "Calculate the size of selected directories"),
+		Command::new("shell", "Run a shell command")
+			.with_flags(&["block", "orphan", "confirm", "interactive", "cursor"])
+			.with_args(),
+		Command::new("run_file", "Run the hovered file if it's executable")
+			.with_flags(&["block", "preview"]),
+		Command::new("terminal", "Open a shell in the current directory").with_args(),
+		Command::new("tree", "Copy the directory tree, or show it live in the preview")
+			.with_flags(&["depth", "hidden", "ignore", "preview"]),
+		Command::new("layout", "Cycle through the layouts, or maximize the current pane")
+			.with_flags(&["maximize"])
+			.with_args(),
+		Command::new("hidden", "Toggle the visibility of hidden files").with_args(),
+		Command::new("focus", "Switch the focus between the file list and the preview").with_args(),
+		Command::new("extensions", "Toggle the visibility of file extensions").with_args(),
+		Command::new("collapse", "Toggle going through single directories on enter").with_args(),
+		Command::new("readme", "Toggle previewing directories as their README").with_args(),
+		Command::new("dir_tree", "Toggle previewing directories as a tree").with_args(),
+		Command::new("stats", "Toggle the line, word and byte counts of text previews").with_args(),
+		Command::new("linemode", "Set the linemode, or how times and sizes are shown")
+			.with_flags(&["time", "size"])
+			.with_args(),
+		Command::new("search", "Search files"),
+		Command::new("search_contents", "Search file contents using ripgrep"),
+		Command::new("filter", "Filter files").with_flags(&["smart", "insensitive"]),
+		Command::new("tag", "Add, remove, select or filter by tags on the selected files").with_args(),
+		Command::new("find", "Find next file")
+			.with_flags(&["previous", "smart", "insensitive"])
+			.with_args(),
+		Command::new("find_arrow", "Go to the next found file").with_flags(&["previous"]),
+		Command::new("jump", "Jump to the next entry matching the last find pattern")
+			.with_flags(&["next", "prev"]),
+		Command::new(
+			"jump_labels",
+			"Label the visible files, and jump to the one whose label is typed",
+		),
+		Command::new("sort", "Sort files")
+			.with_flags(&["reverse", "dir-first", "dir-last", "dir-mixed", "translit"])
+			.with_args(),
+		Command::new("tab_create", "Create a new tab")
+			.with_flags(&["current", "hovered", "stay"])
+			.with_path(),
+		Command::new("tab_close", "Close a tab").with_args(),
+		Command::new("tab_switch", "Switch to a tab").with_flags(&["relative"]).with_args(),
+		Command::new("tab_swap", "Swap the current tab with another one").with_args(),
+		Command::new("tasks_show", "Show the task manager"),
+		Command::new("help", "Open help"),
+		Command::new("plugin", "Run a plugin, reloading it first if asked")
+			.with_flags(&["sync", "args", "reload"])
+			.with_args()
+			.with_pairs(),
+	]
+}
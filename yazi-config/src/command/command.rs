@@ -0,0 +1,57 @@
+use yazi_shared::fuzzy_score;
+
+#[derive(Clone, Debug)]
+pub struct Command {
+	pub name:  String,
+	pub desc:  String,
+	pub flags: Vec<String>,
+	// Whether this command takes positional arguments
+	pub args:  bool,
+	// Whether the positional arguments of this command are paths
+	pub path:  bool,
+	// Whether this command takes named arguments, in the form of `key=value`
+	pub pairs: bool,
+}
+
+impl Command {
+	pub fn new(name: impl Into<String>, desc: impl Into<String>) -> Self {
+		Self {
+			name:  name.into(),
+			desc:  desc.into(),
+			flags: vec![],
+			args:  false,
+			path:  false,
+			pairs: false,
+		}
+	}
+
+	#[inline]
+	pub fn with_flags(mut self, flags: &[&str]) -> Self {
+		self.flags = flags.iter().map(|&f| f.to_owned()).collect();
+		self
+	}
+
+	#[inline]
+	pub fn with_args(mut self) -> Self {
+		self.args = true;
+		self
+	}
+
+	#[inline]
+	pub fn with_path(mut self) -> Self {
+		self.args = true;
+		self.path = true;
+		self
+	}
+
+	#[inline]
+	pub fn with_pairs(mut self) -> Self {
+		self.pairs = true;
+		self
+	}
+
+	// The best fuzzy score of `kw` among the name and the description
+	pub fn fuzzy_score(&self, kw: &str) -> Option<i64> {
+		fuzzy_score(kw, &self.name).max(fuzzy_score(kw, &self.desc))
+	}
+}
@@ -0,0 +1,68 @@
+use std::sync::RwLock;
+
+use anyhow::{bail, Result};
+
+use super::{builtin, Command};
+
+pub struct Commands {
+	inner: RwLock<Vec<Command>>,
+}
+
+impl Default for Commands {
+	fn default() -> Self { Self { inner: RwLock::new(builtin::commands()) } }
+}
+
+impl Commands {
+	pub fn register(&self, cmd: Command) -> Result<()> {
+		let mut inner = self.inner.write().unwrap();
+		if inner.iter().any(|c| c.name == cmd.name) {
+			bail!("command `{}` already exists", cmd.name);
+		}
+
+		inner.push(cmd);
+		Ok(())
+	}
+
+	#[inline]
+	pub fn get(&self, name: &str) -> Option<Command> {
+		self.inner.read().unwrap().iter().find(|c| c.name == name).cloned()
+	}
+
+	#[inline]
+	pub fn contains(&self, name: &str) -> bool {
+		self.inner.read().unwrap().iter().any(|c| c.name == name)
+	}
+
+	#[inline]
+	pub fn names(&self) -> Vec<String> {
+		self.inner.read().unwrap().iter().map(|c| c.name.clone()).collect()
+	}
+
+	#[inline]
+	pub fn all(&self) -> Vec<Command> { self.inner.read().unwrap().clone() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_register() {
+		let commands = Commands::default();
+		assert!(commands.register(Command::new("cd", "")).is_err());
+		assert!(commands.register(Command::new("my-plugin", "Run my plugin")).is_ok());
+		assert!(commands.register(Command::new("my-plugin", "")).is_err());
+		assert_eq!(commands.get("my-plugin").unwrap().desc, "Run my plugin");
+	}
+
+	#[test]
+	fn test_args() {
+		let commands = Commands::default();
+		for name in ["arrow", "hidden", "sort", "linemode", "cd"] {
+			assert!(commands.get(name).unwrap().args, "{name}");
+		}
+		for name in ["suspend", "unyank", "tasks_show"] {
+			assert!(!commands.get(name).unwrap().args, "{name}");
+		}
+	}
+}
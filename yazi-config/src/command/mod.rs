@@ -0,0 +1,6 @@
+mod builtin;
+mod command;
+mod commands;
+
+pub use command::*;
+pub use commands::*;
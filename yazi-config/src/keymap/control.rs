@@ -1,10 +1,10 @@
-use std::{borrow::Cow, collections::VecDeque, sync::OnceLock};
+use std::{borrow::Cow, sync::OnceLock};
 
 use regex::Regex;
 use serde::Deserialize;
-use yazi_shared::event::Cmd;
+use yazi_shared::{event::{Cmd, CmdSeq}, fuzzy_score};
 
-use super::Key;
+use super::{Key, KeyMode};
 
 static RE: OnceLock<Regex> = OnceLock::new();
 
@@ -15,16 +15,34 @@ pub struct Control {
 	#[serde(deserialize_with = "super::deserialize_run")]
 	pub run:  Vec<Cmd>,
 	pub desc: Option<String>,
+	#[serde(default)]
+	pub mode: Option<KeyMode>,
+
+	// Whether to go on with the rest of `run` after a command fails
+	#[serde(default)]
+	pub continue_on_error: bool,
 }
 
 impl Control {
 	#[inline]
-	pub fn to_seq(&self) -> VecDeque<Cmd> { self.run.iter().map(|c| c.shallow_clone()).collect() }
+	pub fn to_seq(&self) -> CmdSeq {
+		CmdSeq::new(
+			self.run.iter().map(|c| c.shallow_clone()).collect::<Vec<_>>(),
+			self.continue_on_error,
+		)
+	}
 }
 
 impl Control {
 	pub fn on(&self) -> String { self.on.iter().map(ToString::to_string).collect() }
 
+	pub fn on_with_mode(&self) -> String {
+		match self.mode {
+			Some(mode) => format!("{} ({mode})", self.on()),
+			None => self.on(),
+		}
+	}
+
 	pub fn run(&self) -> String {
 		RE.get_or_init(|| Regex::new(r"\s+").unwrap())
 			.replace_all(&self.run.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("; "), " ")
@@ -37,11 +55,9 @@ impl Control {
 
 	pub fn desc_or_run(&self) -> Cow<str> { self.desc().unwrap_or_else(|| self.run().into()) }
 
-	#[inline]
-	pub fn contains(&self, s: &str) -> bool {
-		let s = s.to_lowercase();
-		self.desc().map(|d| d.to_lowercase().contains(&s)) == Some(true)
-			|| self.run().to_lowercase().contains(&s)
-			|| self.on().to_lowercase().contains(&s)
+	// The best fuzzy score of `kw` among the keys, the command and the description
+	pub fn fuzzy_score(&self, kw: &str) -> Option<i64> {
+		let desc = self.desc().and_then(|d| fuzzy_score(kw, &d));
+		desc.max(fuzzy_score(kw, &self.run())).max(fuzzy_score(kw, &self.on_with_mode()))
 	}
 }
@@ -1,6 +1,6 @@
-use std::{collections::VecDeque, ops::Deref};
+use std::ops::Deref;
 
-use yazi_shared::event::Cmd;
+use yazi_shared::event::CmdSeq;
 
 use super::Control;
 
@@ -34,9 +34,9 @@ impl Default for ControlCow {
 }
 
 impl ControlCow {
-	pub fn into_seq(self) -> VecDeque<Cmd> {
+	pub fn into_seq(self) -> CmdSeq {
 		match self {
-			Self::Owned(c) => c.run.into(),
+			Self::Owned(c) => CmdSeq::new(c.run, c.continue_on_error),
 			Self::Borrowed(c) => c.to_seq(),
 		}
 	}
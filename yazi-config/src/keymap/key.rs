@@ -1,8 +1,11 @@
 use std::{fmt::{Display, Write}, str::FromStr};
 
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+const NAMES: &str = "`F1` to `F24`, or one of `Space`, `Backspace`, `Enter`, `Left`, `Right`, \
+	`Up`, `Down`, `Home`, `End`, `PageUp`, `PageDown`, `Tab`, `BackTab`, `Delete`, `Insert` or `Esc`";
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Key {
 	pub code:   KeyCode,
@@ -77,63 +80,58 @@ impl FromStr for Key {
 			match next.to_ascii_lowercase().as_str() {
 				"s-" => key.shift = true,
 				"c-" => key.ctrl = true,
-				"a-" => key.alt = true,
+				"a-" | "m-" => key.alt = true,
 				"d-" => key.super_ = true,
 
-				"space" => key.code = KeyCode::Char(' '),
-				"backspace" => key.code = KeyCode::Backspace,
-				"enter" => key.code = KeyCode::Enter,
-				"left" => key.code = KeyCode::Left,
-				"right" => key.code = KeyCode::Right,
-				"up" => key.code = KeyCode::Up,
-				"down" => key.code = KeyCode::Down,
-				"home" => key.code = KeyCode::Home,
-				"end" => key.code = KeyCode::End,
-				"pageup" => key.code = KeyCode::PageUp,
-				"pagedown" => key.code = KeyCode::PageDown,
-				"tab" => key.code = KeyCode::Tab,
-				"backtab" => key.code = KeyCode::BackTab,
-				"delete" => key.code = KeyCode::Delete,
-				"insert" => key.code = KeyCode::Insert,
-				"f1" => key.code = KeyCode::F(1),
-				"f2" => key.code = KeyCode::F(2),
-				"f3" => key.code = KeyCode::F(3),
-				"f4" => key.code = KeyCode::F(4),
-				"f5" => key.code = KeyCode::F(5),
-				"f6" => key.code = KeyCode::F(6),
-				"f7" => key.code = KeyCode::F(7),
-				"f8" => key.code = KeyCode::F(8),
-				"f9" => key.code = KeyCode::F(9),
-				"f10" => key.code = KeyCode::F(10),
-				"f11" => key.code = KeyCode::F(11),
-				"f12" => key.code = KeyCode::F(12),
-				"f13" => key.code = KeyCode::F(13),
-				"f14" => key.code = KeyCode::F(14),
-				"f15" => key.code = KeyCode::F(15),
-				"f16" => key.code = KeyCode::F(16),
-				"f17" => key.code = KeyCode::F(17),
-				"f18" => key.code = KeyCode::F(18),
-				"f19" => key.code = KeyCode::F(19),
-				"esc" => key.code = KeyCode::Esc,
-
-				_ => match next {
-					s if it.peek().is_none() => {
-						let c = s.chars().next().unwrap();
-						key.shift |= c.is_ascii_uppercase();
-						key.code = KeyCode::Char(if key.shift { c.to_ascii_uppercase() } else { c });
-					}
-					s => bail!("unknown key: {s}"),
-				},
+				_ if it.peek().is_some() => {
+					bail!("unknown modifier `{next}` in `{s}`, expected one of `S-`, `C-`, `A-` or `D-`")
+				}
+				name => {
+					key.code = Self::parse_code(next, name).ok_or_else(|| {
+						anyhow!("unknown key `{next}` in `{s}`, expected a single character, {NAMES}")
+					})?
+				}
 			}
 		}
 
-		if key.code == KeyCode::Null {
-			bail!("empty key")
+		match key.code {
+			KeyCode::Null => bail!("empty key"),
+			// Terminals report Shift+Tab as `BackTab`, without the Shift
+			KeyCode::Tab if key.shift => (key.code, key.shift) = (KeyCode::BackTab, false),
+			KeyCode::Char(c) => {
+				key.shift |= c.is_ascii_uppercase();
+				key.code = KeyCode::Char(if key.shift { c.to_ascii_uppercase() } else { c });
+			}
+			_ => {}
 		}
 		Ok(key)
 	}
 }
 
+impl Key {
+	fn parse_code(raw: &str, name: &str) -> Option<KeyCode> {
+		Some(match name {
+			"space" => KeyCode::Char(' '),
+			"backspace" => KeyCode::Backspace,
+			"enter" => KeyCode::Enter,
+			"left" => KeyCode::Left,
+			"right" => KeyCode::Right,
+			"up" => KeyCode::Up,
+			"down" => KeyCode::Down,
+			"home" => KeyCode::Home,
+			"end" => KeyCode::End,
+			"pageup" => KeyCode::PageUp,
+			"pagedown" => KeyCode::PageDown,
+			"tab" => KeyCode::Tab,
+			"backtab" => KeyCode::BackTab,
+			"delete" => KeyCode::Delete,
+			"insert" => KeyCode::Insert,
+			"esc" => KeyCode::Esc,
+			_ if raw.chars().count() == 1 => KeyCode::Char(raw.chars().next()?),
+			_ => KeyCode::F(name.strip_prefix('f')?.parse().ok().filter(|n| (1..=24).contains(n))?),
+		})
+	}
+}
 impl Display for Key {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		if let Some(c) = self.plain() {
@@ -154,6 +152,7 @@ impl Display for Key {
 			write!(f, "S-")?;
 		}
 
+		let f_key;
 		let code = match self.code {
 			KeyCode::Backspace => "Backspace",
 			KeyCode::Enter => "Enter",
@@ -169,25 +168,10 @@ impl Display for Key {
 			KeyCode::BackTab => "BackTab",
 			KeyCode::Delete => "Delete",
 			KeyCode::Insert => "Insert",
-			KeyCode::F(1) => "F1",
-			KeyCode::F(2) => "F2",
-			KeyCode::F(3) => "F3",
-			KeyCode::F(4) => "F4",
-			KeyCode::F(5) => "F5",
-			KeyCode::F(6) => "F6",
-			KeyCode::F(7) => "F7",
-			KeyCode::F(8) => "F8",
-			KeyCode::F(9) => "F9",
-			KeyCode::F(10) => "F10",
-			KeyCode::F(11) => "F11",
-			KeyCode::F(12) => "F12",
-			KeyCode::F(13) => "F13",
-			KeyCode::F(14) => "F14",
-			KeyCode::F(15) => "F15",
-			KeyCode::F(16) => "F16",
-			KeyCode::F(17) => "F17",
-			KeyCode::F(18) => "F18",
-			KeyCode::F(19) => "F19",
+			KeyCode::F(n) => {
+				f_key = format!("F{n}");
+				&f_key
+			}
 			KeyCode::Esc => "Esc",
 
 			KeyCode::Char(' ') => "Space",
@@ -201,3 +185,51 @@ impl Display for Key {
 		write!(f, "{}>", code)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn key(code: KeyCode, shift: bool, ctrl: bool, alt: bool) -> Key {
+		Key { code, shift, ctrl, alt, super_: false }
+	}
+
+	#[test]
+	fn test_parse() {
+		let parse = |s: &str| Key::from_str(s).unwrap();
+		assert_eq!(parse("<C-S-Up>"), key(KeyCode::Up, true, true, false));
+		assert_eq!(parse("<F12>"), key(KeyCode::F(12), false, false, false));
+		assert_eq!(parse("<A-f24>"), key(KeyCode::F(24), false, false, true));
+		assert_eq!(parse("<S-Tab>"), key(KeyCode::BackTab, false, false, false));
+		assert_eq!(parse("<C-Home>"), key(KeyCode::Home, false, true, false));
+		assert_eq!(parse("<S-PageUp>"), key(KeyCode::PageUp, true, false, false));
+		assert_eq!(parse("<M-Insert>"), key(KeyCode::Insert, false, false, true));
+		assert_eq!(parse("<C-S-Delete>"), key(KeyCode::Delete, true, true, false));
+		assert_eq!(parse("<C-->"), key(KeyCode::Char('-'), false, true, false));
+		assert_eq!(parse("<S-a>"), key(KeyCode::Char('A'), true, false, false));
+		assert!(parse("<D-Space>").super_);
+
+		for s in ["<C-S-Up>", "<F12>", "<A-F24>", "<C-Home>", "<S-PageUp>", "<A-.>"] {
+			assert_eq!(parse(s).to_string(), s);
+		}
+	}
+
+	#[test]
+	fn test_parse_error() {
+		for s in ["<F25>", "<F0>", "<C-Foo>", "<X-a>", "<C->", "<>"] {
+			assert!(Key::from_str(s).is_err(), "{s}");
+		}
+
+		let e = Key::from_str("<C-Foo>").unwrap_err().to_string();
+		assert!(e.starts_with("unknown key `Foo` in `<C-Foo>`"), "{e}");
+	}
+
+	#[test]
+	fn test_from_event() {
+		let event = |code, m| Key::from(KeyEvent::new(code, m));
+		let parse = |s: &str| Key::from_str(s).unwrap();
+		assert_eq!(event(KeyCode::Up, KeyModifiers::CONTROL | KeyModifiers::SHIFT), parse("<C-S-Up>"));
+		assert_eq!(event(KeyCode::BackTab, KeyModifiers::SHIFT), parse("<S-Tab>"));
+		assert_eq!(event(KeyCode::F(5), KeyModifiers::NONE), parse("<F5>"));
+	}
+}
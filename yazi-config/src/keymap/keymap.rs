@@ -3,7 +3,7 @@ use std::str::FromStr;
 use serde::{Deserialize, Deserializer};
 use yazi_shared::Layer;
 
-use super::Control;
+use super::{Control, KeyMode};
 use crate::Preset;
 
 #[derive(Debug)]
@@ -32,6 +32,36 @@ impl Keymap {
 			Layer::Which => unreachable!(),
 		}
 	}
+
+	// The bindings of `layer` that apply in `mode`: the ones scoped to it come
+	// first, then falls through to the base ones, so a scoped binding shadows a
+	// base binding with the same keys.
+	#[inline]
+	pub fn get_in(&self, layer: Layer, mode: KeyMode) -> impl Iterator<Item = &Control> {
+		Self::scoped(self.get(layer), mode)
+	}
+
+	// The bindings scoped to a mode their layer is never in
+	pub fn misplaced(&self) -> impl Iterator<Item = (Layer, &Control)> {
+		let layers = [
+			Layer::Manager,
+			Layer::Tasks,
+			Layer::Select,
+			Layer::Input,
+			Layer::Confirm,
+			Layer::Help,
+			Layer::Completion,
+		];
+		layers.into_iter().flat_map(move |layer| {
+			let misplaced = move |c: &&Control| c.mode.is_some_and(|m| !m.applies_to(layer));
+			self.get(layer).iter().filter(misplaced).map(move |c| (layer, c))
+		})
+	}
+
+	fn scoped(ctrls: &[Control], mode: KeyMode) -> impl Iterator<Item = &Control> {
+		let base = ctrls.iter().filter(|c| c.mode.is_none());
+		ctrls.iter().filter(move |c| c.mode == Some(mode)).chain(base)
+	}
 }
 
 impl FromStr for Keymap {
@@ -92,3 +122,77 @@ impl<'de> Deserialize<'de> for Keymap {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::keymap::Key;
+
+	fn resolve(ctrls: &[Control], mode: KeyMode, key: &str) -> Option<String> {
+		let key = Key::from_str(key).unwrap();
+		Keymap::scoped(ctrls, mode).find(|c| c.on == [key]).map(|c| c.run())
+	}
+
+	#[test]
+	fn test_scoped() {
+		#[derive(Deserialize)]
+		struct Shadow {
+			keymap: Vec<Control>,
+		}
+
+		let ctrls = toml::from_str::<Shadow>(
+			r#"
+			keymap = [
+				{ on = "y", run = "yank --cut", mode = "visual" },
+				{ on = "y", run = "yank" },
+				{ on = "d", run = "remove", mode = "visual" },
+				{ on = "p", run = "paste", mode = "normal" },
+			]
+			"#,
+		)
+		.unwrap()
+		.keymap;
+
+		assert_eq!(resolve(&ctrls, KeyMode::Visual, "y").as_deref(), Some("yank --cut"));
+		assert_eq!(resolve(&ctrls, KeyMode::Normal, "y").as_deref(), Some("yank"));
+
+		assert_eq!(resolve(&ctrls, KeyMode::Visual, "d").as_deref(), Some("remove"));
+		assert_eq!(resolve(&ctrls, KeyMode::Normal, "d"), None);
+
+		assert_eq!(resolve(&ctrls, KeyMode::Normal, "p").as_deref(), Some("paste"));
+		assert_eq!(resolve(&ctrls, KeyMode::Visual, "p"), None);
+	}
+
+	#[test]
+	fn test_misplaced() {
+		let keymap = Keymap::from_str(include_str!("../../preset/keymap.toml")).unwrap();
+		assert_eq!(keymap.misplaced().count(), 0);
+
+		let toml = include_str!("../../preset/keymap.toml").replacen(
+			"[tasks]\n",
+			"[tasks]\nprepend_keymap = [ { on = \"v\", run = \"close\", mode = \"visual\" } ]\n",
+			1,
+		);
+		let keymap = Keymap::from_str(&toml).unwrap();
+		let misplaced: Vec<_> = keymap.misplaced().map(|(l, c)| (l, c.on_with_mode())).collect();
+		assert_eq!(misplaced, [(Layer::Tasks, "v (visual)".to_owned())]);
+	}
+
+	#[test]
+	fn test_preview() {
+		let keymap = Keymap::from_str(include_str!("../../preset/keymap.toml")).unwrap();
+		let resolve = |mode, key| resolve(&keymap.manager, mode, key);
+
+		// Only the keys bound for it go to the preview, the rest still work the list
+		assert_eq!(resolve(KeyMode::Preview, "j").as_deref(), Some("seek 1"));
+		assert_eq!(resolve(KeyMode::Normal, "j").as_deref(), Some("arrow 1"));
+		let space = resolve(KeyMode::Preview, "<Space>");
+		assert_eq!(space.as_deref(), Some("select --state=none; arrow 1"));
+		assert_eq!(resolve(KeyMode::Preview, "<Esc>").as_deref(), Some("focus list"));
+
+		assert_eq!(resolve(KeyMode::Preview, "<C-d>").as_deref(), Some("seek 50%"));
+		assert_eq!(resolve(KeyMode::Preview, "<PageUp>").as_deref(), Some("seek -100%"));
+		assert_eq!(resolve(KeyMode::Preview, "G").as_deref(), Some("seek bottom"));
+		assert_eq!(resolve(KeyMode::Normal, "G").as_deref(), Some("arrow 99999999"));
+	}
+}
@@ -3,9 +3,11 @@ mod cow;
 mod deserializers;
 mod key;
 mod keymap;
+mod mode;
 
 pub use control::*;
 pub use cow::*;
 use deserializers::*;
 pub use key::*;
 pub use keymap::*;
+pub use mode::*;
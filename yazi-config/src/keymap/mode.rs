@@ -0,0 +1,42 @@
+use std::fmt::{self, Display};
+
+use serde::Deserialize;
+use yazi_shared::Layer;
+
+// The mode a binding is scoped to with e.g. `mode = "visual"`. Bindings without
+// a mode make up the base layer, which applies in every mode.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyMode {
+	// Not in visual mode in the manager, or not inserting in the input
+	Normal,
+	// Visual selection mode in the manager
+	Visual,
+	// Insert mode in the input
+	Insert,
+	// The preview has the focus in the manager
+	Preview,
+}
+
+impl KeyMode {
+	// Whether `layer` is ever in this mode, as bindings scoped to a mode their
+	// layer is never in would never fire
+	pub fn applies_to(self, layer: Layer) -> bool {
+		match self {
+			Self::Normal => true,
+			Self::Visual | Self::Preview => layer == Layer::Manager,
+			Self::Insert => matches!(layer, Layer::Input | Layer::Completion),
+		}
+	}
+}
+
+impl Display for KeyMode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Normal => "normal",
+			Self::Visual => "visual",
+			Self::Insert => "insert",
+			Self::Preview => "preview",
+		})
+	}
+}
@@ -4,6 +4,8 @@ use std::str::FromStr;
 
 use yazi_shared::{RoCell, Xdg};
 
+pub mod check;
+pub mod command;
 pub mod keymap;
 mod layout;
 mod log;
@@ -15,7 +17,7 @@ pub mod popup;
 mod preset;
 pub mod preview;
 mod priority;
-mod tasks;
+pub mod tasks;
 pub mod theme;
 pub mod which;
 
@@ -26,6 +28,7 @@ pub use priority::*;
 
 pub static LAYOUT: RoCell<arc_swap::ArcSwap<Layout>> = RoCell::new();
 
+pub static COMMANDS: RoCell<command::Commands> = RoCell::new();
 pub static KEYMAP: RoCell<keymap::Keymap> = RoCell::new();
 pub static LOG: RoCell<log::Log> = RoCell::new();
 pub static MANAGER: RoCell<manager::Manager> = RoCell::new();
@@ -41,11 +44,14 @@ pub static WHICH: RoCell<which::Which> = RoCell::new();
 
 pub fn init() -> anyhow::Result<()> {
 	let config_dir = Xdg::config_dir();
-	let yazi_toml = &Preset::yazi(&config_dir)?;
-	let keymap_toml = &Preset::keymap(&config_dir)?;
-	let theme_toml = &Preset::theme(&config_dir)?;
+	let dirs = Preset::dirs(&std::env::current_dir().unwrap_or_default(), &config_dir);
+
+	let yazi_toml = &Preset::yazi(&dirs)?;
+	let keymap_toml = &Preset::keymap(&dirs)?;
+	let theme_toml = &Preset::theme(&dirs)?;
 
 	LAYOUT.with(<_>::default);
+	COMMANDS.with(<_>::default);
 
 	KEYMAP.init(<_>::from_str(keymap_toml)?);
 	LOG.init(<_>::from_str(yazi_toml)?);
@@ -60,6 +66,14 @@ pub fn init() -> anyhow::Result<()> {
 	SELECT.init(<_>::from_str(yazi_toml)?);
 	WHICH.init(<_>::from_str(yazi_toml)?);
 
+	for (layer, c) in KEYMAP.misplaced() {
+		eprintln!(
+			"WARNING: The key `{}` in your keymap.toml never fires, as `[{layer}]` is never in {} mode.",
+			c.on(),
+			c.mode.unwrap()
+		);
+	}
+
 	// TODO: Remove in v0.3.2
 	for c in &KEYMAP.manager {
 		for r in &c.run {
@@ -84,3 +98,10 @@ Please replace e.g. `shell` with `shell --interactive`, `shell "my-template"` wi
 
 	Ok(())
 }
+
+// Sets up what parsing the config needs, for tests on it
+#[cfg(test)]
+pub(crate) fn init_tests() {
+	static INIT: std::sync::Once = std::sync::Once::new();
+	INIT.call_once(yazi_shared::init);
+}
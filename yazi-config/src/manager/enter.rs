@@ -0,0 +1,51 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+// What `enter` does when the hovered file isn't a directory
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub enum EnterFile {
+	// Nothing, only directories are entered
+	None,
+	// Open it with the default opener, as `open` does
+	Open,
+	// Pick an opener for it, as `open --interactive` does
+	Interactive,
+}
+
+impl FromStr for EnterFile {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"none" => Self::None,
+			"open" => Self::Open,
+			"interactive" => Self::Interactive,
+			_ => bail!("Invalid `enter_file` value: {s}"),
+		})
+	}
+}
+
+impl TryFrom<String> for EnterFile {
+	type Error = anyhow::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> { Self::from_str(&value) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::manager::Manager;
+
+	#[test]
+	fn test_enter_file() {
+		// Files are left alone unless asked for
+		let preset = Manager::from_str(include_str!("../../preset/yazi.toml")).unwrap();
+		assert_eq!(preset.enter_file, EnterFile::None);
+
+		assert_eq!(EnterFile::from_str("interactive").unwrap(), EnterFile::Interactive);
+		assert!(EnterFile::from_str("edit").is_err());
+	}
+}
@@ -1,13 +1,14 @@
-use std::str::FromStr;
+use std::{path::{Path, PathBuf}, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use super::{ManagerRatio, MouseEvents, SortBy};
+use super::{EnterFile, ManagerRatio, MouseEvents, RemoveRule, SortBy};
 
 #[derive(Debug, Deserialize, Serialize, Validate)]
 pub struct Manager {
-	pub ratio: ManagerRatio,
+	pub ratio:   ManagerRatio,
+	pub layouts: Vec<ManagerRatio>,
 
 	// Sorting
 	pub sort_by:        SortBy,
@@ -18,12 +19,56 @@ pub struct Manager {
 
 	// Display
 	#[validate(length(min = 1, max = 20, message = "must be between 1 and 20 characters"))]
-	pub linemode:     String,
-	pub show_hidden:  bool,
-	pub show_symlink: bool,
-	pub scrolloff:    u8,
-	pub mouse_events: MouseEvents,
-	pub title_format: String,
+	pub linemode:        String,
+	pub time_format:     String,
+	pub size_format:     String,
+	pub size_precision:  u8,
+	pub show_hidden:     bool,
+	pub show_extensions: bool,
+	pub show_symlink:    bool,
+	pub show_disk_space: bool,
+	pub scrolloff:       u8,
+	pub mouse_events:    MouseEvents,
+	pub title_format:    String,
+	pub recents_size:    usize,
+	pub line_separator:  String,
+
+	// Polling
+	pub poll_interval: u16,
+	pub poll_paths:    Vec<PathBuf>,
+
+	// Removing
+	pub remove_permanently: bool,
+	#[serde(skip_serializing)]
+	pub remove_rules:       Vec<RemoveRule>,
+	pub protected_paths:    Vec<PathBuf>,
+
+	// Clipboard
+	pub copy_image_max: u32,
+
+	// Duplicating
+	pub duplicate_suffix: String,
+
+	// Tabs
+	#[validate(range(min = 1, max = 99))]
+	pub max_tabs: usize,
+
+	// Entering
+	pub enter_file:     EnterFile,
+	pub collapse_dirs:  bool,
+	#[validate(range(min = 1, max = 64))]
+	pub collapse_depth: usize,
+
+	// Action log
+	pub action_log_size:    usize,
+	pub action_log_persist: bool,
+}
+
+impl Manager {
+	#[inline]
+	pub fn removes_permanently(&self, path: &Path) -> bool {
+		RemoveRule::permanently(&self.remove_rules, path, self.remove_permanently)
+	}
 }
 
 impl FromStr for Manager {
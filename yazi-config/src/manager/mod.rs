@@ -1,9 +1,13 @@
+mod enter;
 mod manager;
 mod mouse;
 mod ratio;
+mod remove;
 mod sorting;
 
+pub use enter::*;
 pub use manager::*;
 pub use mouse::*;
 pub use ratio::*;
+pub use remove::*;
 pub use sorting::*;
@@ -21,11 +21,30 @@ impl TryFrom<Vec<u16>> for ManagerRatio {
 			bail!("at least one layout ratio must be non-zero: {:?}", ratio);
 		}
 
-		Ok(Self {
-			parent:  ratio[0],
-			current: ratio[1],
-			preview: ratio[2],
-			all:     ratio[0] + ratio[1] + ratio[2],
-		})
+		let Some(all) = ratio[0].checked_add(ratio[1]).and_then(|n| n.checked_add(ratio[2])) else {
+			bail!("layout ratio is too large: {:?}", ratio);
+		};
+
+		Ok(Self { parent: ratio[0], current: ratio[1], preview: ratio[2], all })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_try_from() {
+		let ratio = ManagerRatio::try_from(vec![1, 4, 3]).unwrap();
+		assert_eq!(ratio, ManagerRatio { parent: 1, current: 4, preview: 3, all: 8 });
+
+		// Hidden panes are fine, as long as there's something to show
+		let ratio = ManagerRatio::try_from(vec![0, 0, 1]).unwrap();
+		assert_eq!(ratio, ManagerRatio { parent: 0, current: 0, preview: 1, all: 1 });
+
+		assert!(ManagerRatio::try_from(vec![0, 0, 0]).is_err());
+		assert!(ManagerRatio::try_from(vec![1, 4]).is_err());
+		assert!(ManagerRatio::try_from(vec![1, 4, 3, 1]).is_err());
+		assert!(ManagerRatio::try_from(vec![u16::MAX, 1, 0]).is_err());
 	}
 }
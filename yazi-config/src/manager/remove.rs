@@ -0,0 +1,53 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::Pattern;
+
+#[derive(Debug, Deserialize)]
+pub struct RemoveRule {
+	name:        Pattern,
+	permanently: bool,
+}
+
+impl RemoveRule {
+	// The first rule matching `path` decides, otherwise `default` does
+	pub(super) fn permanently(rules: &[Self], path: &Path, default: bool) -> bool {
+		rules.iter().find(|r| r.name.match_any(path)).map_or(default, |r| r.permanently)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_permanently() {
+		#[derive(Deserialize)]
+		struct Outer {
+			rules: Vec<RemoveRule>,
+		}
+
+		let Outer { rules } = toml::from_str(
+			r#"
+			rules = [
+				{ name = "/home/*/code/docs/**", permanently = false },
+				{ name = "/home/*/code/**", permanently = true },
+				{ name = "*.tmp", permanently = true },
+			]
+			"#,
+		)
+		.unwrap();
+
+		let of = |p: &str, default| RemoveRule::permanently(&rules, Path::new(p), default);
+		assert!(of("/home/a/code/yazi", false));
+		assert!(of("/home/a/code/yazi/src/main.rs", false));
+		assert!(!of("/home/a/code/docs/a.md", true));
+		assert!(of("/home/a/Documents/a.tmp", false));
+
+		// Files no rule matches fall back to the default
+		assert!(!of("/home/a/Documents/a.md", false));
+		assert!(of("/home/a/Documents/a.md", true));
+		assert!(RemoveRule::permanently(&[], Path::new("/a"), true));
+	}
+}
@@ -3,7 +3,7 @@ use std::{fmt::Display, str::FromStr};
 use anyhow::bail;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(try_from = "String")]
 pub enum SortBy {
 	#[default]
@@ -15,6 +15,8 @@ pub enum SortBy {
 	Natural,
 	Size,
 	Random,
+	// A sort mode registered by a plugin with `Sorter:register()`, given as `plugin:<name>`
+	Plugin(String),
 }
 
 impl FromStr for SortBy {
@@ -30,7 +32,10 @@ impl FromStr for SortBy {
 			"natural" => Self::Natural,
 			"size" => Self::Size,
 			"random" => Self::Random,
-			_ => bail!("invalid sort_by value: {s}"),
+			_ => match s.strip_prefix("plugin:") {
+				Some(name) if !name.is_empty() => Self::Plugin(name.to_owned()),
+				_ => bail!("invalid sort_by value: {s}"),
+			},
 		})
 	}
 }
@@ -52,6 +57,36 @@ impl Display for SortBy {
 			Self::Natural => "natural",
 			Self::Size => "size",
 			Self::Random => "random",
+			Self::Plugin(name) => return write!(f, "plugin:{name}"),
 		})
 	}
 }
+
+// Where directories go, regardless of the sort order
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortDirs {
+	First,
+	Last,
+	#[default]
+	Mixed,
+}
+
+impl From<bool> for SortDirs {
+	fn from(first: bool) -> Self { if first { Self::First } else { Self::Mixed } }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_sort_by() {
+		assert_eq!("natural".parse::<SortBy>().unwrap(), SortBy::Natural);
+		assert_eq!("plugin:version".parse::<SortBy>().unwrap(), SortBy::Plugin("version".to_owned()));
+		assert_eq!(SortBy::Plugin("version".to_owned()).to_string(), "plugin:version");
+
+		assert!("".parse::<SortBy>().is_err());
+		assert!("alphabetic".parse::<SortBy>().is_err());
+		assert!("plugin:".parse::<SortBy>().is_err());
+	}
+}
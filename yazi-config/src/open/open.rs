@@ -11,6 +11,9 @@ use crate::{open::OpenRule, Preset};
 pub struct Open {
 	rules:   Vec<OpenRule>,
 	openers: HashMap<String, IndexSet<Opener>>,
+
+	// Whether the `which` menu lists the opener last picked for a mimetype first
+	pub remember: bool,
 }
 
 impl Open {
@@ -38,6 +41,11 @@ impl Open {
 		})
 	}
 
+	#[inline]
+	pub fn named(&self, name: &str) -> Option<&IndexSet<Opener>> {
+		self.openers.get(name).filter(|o| !o.is_empty())
+	}
+
 	#[inline]
 	pub fn block_opener<P, M>(&self, path: P, mime: M) -> Option<&Opener>
 	where
@@ -77,6 +85,8 @@ impl<'de> Deserialize<'de> for Open {
 			prepend_rules: Vec<OpenRule>,
 			#[serde(default)]
 			append_rules:  Vec<OpenRule>,
+			#[serde(default)]
+			remember:      bool,
 		}
 
 		let mut outer = Outer::deserialize(deserializer)?;
@@ -95,6 +105,6 @@ impl<'de> Deserialize<'de> for Open {
 			.map(|(k, v)| (k, v.into_iter().filter_map(|o| o.take()).collect::<IndexSet<_>>()))
 			.collect();
 
-		Ok(Self { rules: outer.open.rules, openers })
+		Ok(Self { rules: outer.open.rules, openers, remember: outer.open.remember })
 	}
 }
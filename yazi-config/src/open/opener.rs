@@ -1,13 +1,22 @@
+use std::{borrow::Cow, sync::OnceLock};
+
+use regex::{Captures, Regex};
 use serde::{Deserialize, Deserializer};
 
+static GROUP: OnceLock<Regex> = OnceLock::new();
+static BARE: OnceLock<Regex> = OnceLock::new();
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Opener {
-	pub run:    String,
-	pub block:  bool,
-	pub orphan: bool,
-	pub desc:   String,
-	pub for_:   Option<String>,
-	pub spread: bool,
+	pub run:      String,
+	pub block:    bool,
+	pub orphan:   bool,
+	pub desc:     String,
+	pub for_:     Option<String>,
+	pub spread:   bool,
+	// Whether `run` was typed into a prompt, which may hold anything such as a
+	// password, so it's kept out of the task name and the action log
+	pub prompted: bool,
 }
 
 impl Opener {
@@ -23,6 +32,40 @@ impl Opener {
 		}
 		Some(self)
 	}
+
+	// Override whether all files are passed to one process, or each to its own
+	pub fn with_spread(opener: Cow<'static, Self>, spread: Option<bool>) -> Cow<'static, Self> {
+		match spread {
+			Some(spread) if spread != opener.spread => Cow::Owned(Self { spread, ..opener.into_owned() }),
+			_ => opener,
+		}
+	}
+
+	// Fill in the `{line}` and `{column}` placeholders of `run`. A placeholder
+	// can be wrapped in an optional group like `{+{line}}`, which is dropped as a
+	// whole when the value is unknown.
+	pub fn with_position(
+		opener: Cow<'static, Self>,
+		line: Option<usize>,
+		column: Option<usize>,
+	) -> Cow<'static, Self> {
+		if !opener.run.contains("{line}") && !opener.run.contains("{column}") {
+			return opener;
+		}
+
+		let value = |name: &str| if name == "line" { line } else { column };
+		let group = GROUP.get_or_init(|| Regex::new(r"\{([^{}]*)\{(line|column)\}([^{}]*)\}").unwrap());
+		let bare = BARE.get_or_init(|| Regex::new(r"\{(line|column)\}").unwrap());
+
+		let run = group.replace_all(&opener.run, |caps: &Captures| {
+			value(&caps[2]).map(|v| format!("{}{v}{}", &caps[1], &caps[3])).unwrap_or_default()
+		});
+		let run = bare.replace_all(&run, |caps: &Captures| {
+			value(&caps[1]).map(|v| v.to_string()).unwrap_or_default()
+		});
+
+		Cow::Owned(Self { run: run.into_owned(), ..opener.into_owned() })
+	}
 }
 
 impl<'de> Deserialize<'de> for Opener {
@@ -52,6 +95,45 @@ impl<'de> Deserialize<'de> for Opener {
 		let desc = shadow.desc.unwrap_or_else(|| run.split_whitespace().next().unwrap().to_string());
 
 		let spread = run.contains("$@") || run.contains("%*") || run.contains("$*");
-		Ok(Self { run, block: shadow.block, orphan: shadow.orphan, desc, for_: shadow.for_, spread })
+		Ok(Self {
+			run,
+			block: shadow.block,
+			orphan: shadow.orphan,
+			desc,
+			for_: shadow.for_,
+			spread,
+			prompted: false,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn run(run: &str, line: Option<usize>, column: Option<usize>) -> String {
+		let opener = Opener {
+			run:      run.to_owned(),
+			block:    false,
+			orphan:   false,
+			desc:     String::new(),
+			for_:     None,
+			spread:   false,
+			prompted: false,
+		};
+		Opener::with_position(Cow::Owned(opener), line, column).into_owned().run
+	}
+
+	#[test]
+	fn test_with_position() {
+		assert_eq!(run(r#"${EDITOR:-vi} {+{line}} "$@""#, Some(42), None), r#"${EDITOR:-vi} +42 "$@""#);
+		assert_eq!(run(r#"${EDITOR:-vi} {+{line}} "$@""#, None, None), r#"${EDITOR:-vi}  "$@""#);
+
+		let code = r#"code -g "$1"{:{line}}{:{column}}"#;
+		assert_eq!(run(code, Some(3), Some(7)), r#"code -g "$1":3:7"#);
+		assert_eq!(run(code, Some(3), None), r#"code -g "$1":3"#);
+
+		assert_eq!(run("hx --line {line} {column}", Some(1), None), "hx --line 1 ");
+		assert_eq!(run(r#"xdg-open "$1""#, Some(1), Some(2)), r#"xdg-open "$1""#);
 	}
 }
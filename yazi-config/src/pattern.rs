@@ -22,6 +22,11 @@ impl Pattern {
 		is_dir == self.is_dir && (self.is_star || self.inner.is_match(path))
 	}
 
+	#[inline]
+	pub fn match_any(&self, path: impl AsRef<Path>) -> bool {
+		self.is_star || self.inner.is_match(path)
+	}
+
 	#[inline]
 	pub fn any_file(&self) -> bool { self.is_star && !self.is_dir }
 
@@ -9,3 +9,21 @@ pub use preloader::*;
 pub use previewer::*;
 
 pub const MAX_PREWORKERS: u8 = 32;
+
+// Plugins that come with Yazi, passed to `$m` as string literals, so that the
+// loader can embed them and the config checker can recognize them.
+#[macro_export]
+macro_rules! preset_plugins {
+	($m:ident) => {
+		$m!(
+			"archive", "clock", "code", "dds", "empty", "extract", "file", "folder", "font", "fzf",
+			"gzip", "image", "json", "magick", "mime", "noop", "pdf", "session", "video", "zoxide"
+		)
+	};
+}
+
+macro_rules! names {
+	($($name:literal),*) => { &[$($name),*] };
+}
+
+pub const PRESET_PLUGINS: &[&str] = preset_plugins!(names);
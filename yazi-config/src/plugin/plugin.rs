@@ -44,6 +44,17 @@ impl Plugin {
 		})
 	}
 
+	/// Matching previewers in the order they are defined, the first one wins
+	/// unless it declines the file, in which case the next one is tried.
+	pub fn previewers<'a: 'b, 'b>(
+		&'a self,
+		path: &'b Path,
+		mime: &'b str,
+	) -> impl Iterator<Item = &'a Previewer> + 'b {
+		self.previewers.iter().filter(move |&p| p.matches(path, mime))
+	}
+
+	#[inline]
 	pub fn previewer(&self, path: &Path, mime: &str) -> Option<&Previewer> {
 		self.previewers.iter().find(|&p| p.matches(path, mime))
 	}
@@ -108,3 +119,34 @@ impl FromStr for Plugin {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn chain(plugin: &Plugin, path: &str, mime: &str) -> Vec<String> {
+		plugin.previewers(Path::new(path), mime).map(|p| p.run.name.clone()).collect()
+	}
+
+	#[test]
+	fn test_previewers_fallthrough() {
+		let plugin: Plugin = r#"
+			[plugin]
+			fetchers = []
+			preloaders = []
+			previewers = [
+				{ name = "*.md", run = "glow" },
+				{ mime = "text/*", run = "code" },
+				{ name = "*", run = "file" },
+			]
+			prepend_previewers = [{ mime = "text/markdown", run = "markdown" }]
+		"#
+		.parse()
+		.unwrap();
+
+		assert_eq!(chain(&plugin, "a.md", "text/markdown"), ["markdown", "glow", "code", "file"]);
+		assert_eq!(chain(&plugin, "a.txt", "text/plain"), ["code", "file"]);
+		assert_eq!(chain(&plugin, "a.bin", "application/octet-stream"), ["file"]);
+		assert_eq!(plugin.previewer(Path::new("a.md"), "text/plain").unwrap().run.name, "glow");
+	}
+}
@@ -3,7 +3,7 @@ use std::path::Path;
 use serde::Deserialize;
 use yazi_shared::{event::Cmd, MIME_DIR};
 
-use crate::Pattern;
+use crate::{preview::PreviewWrap, Pattern, PREVIEW};
 
 #[derive(Debug, Deserialize)]
 pub struct Previewer {
@@ -12,6 +12,7 @@ pub struct Previewer {
 	pub run:  Cmd,
 	#[serde(default)]
 	pub sync: bool,
+	pub wrap: Option<PreviewWrap>,
 }
 
 impl Previewer {
@@ -21,6 +22,10 @@ impl Previewer {
 			|| self.name.as_ref().is_some_and(|p| p.match_path(path, mime == MIME_DIR))
 	}
 
+	// Whether long lines are wrapped, as set for this previewer or else globally
+	#[inline]
+	pub fn wraps(&self) -> bool { self.wrap.unwrap_or(PREVIEW.wrap) == PreviewWrap::Yes }
+
 	#[inline]
 	pub fn any_file(&self) -> bool { self.name.as_ref().is_some_and(|p| p.any_file()) }
 
@@ -7,12 +7,18 @@ use super::{Offset, Origin};
 #[derive(Deserialize)]
 pub struct Input {
 	pub cursor_blink: bool,
+	pub history_size: usize,
 
 	// cd
 	pub cd_title:  String,
 	pub cd_origin: Origin,
 	pub cd_offset: Offset,
 
+	// fuzzy
+	pub fuzzy_title:  String,
+	pub fuzzy_origin: Origin,
+	pub fuzzy_offset: Offset,
+
 	// create
 	pub create_title:  String,
 	pub create_origin: Origin,
@@ -38,10 +44,40 @@ pub struct Input {
 	pub search_origin: Origin,
 	pub search_offset: Offset,
 
+	// search_contents
+	pub contents_title:  String,
+	pub contents_origin: Origin,
+	pub contents_offset: Offset,
+
 	// shell
 	pub shell_title:  [String; 2],
 	pub shell_origin: Origin,
 	pub shell_offset: Offset,
+
+	// goto
+	pub goto_title:  String,
+	pub goto_origin: Origin,
+	pub goto_offset: Offset,
+
+	// copy_to
+	pub copy_to_title:  [String; 2],
+	pub copy_to_origin: Origin,
+	pub copy_to_offset: Offset,
+
+	// tag
+	pub tag_title:  String,
+	pub tag_origin: Origin,
+	pub tag_offset: Offset,
+
+	// command
+	pub command_title:  String,
+	pub command_origin: Origin,
+	pub command_offset: Offset,
+
+	// palette
+	pub palette_title:  String,
+	pub palette_origin: Origin,
+	pub palette_offset: Offset,
 }
 
 impl Input {
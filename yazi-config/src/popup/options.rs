@@ -12,7 +12,13 @@ pub struct InputCfg {
 	pub position:   Position,
 	pub realtime:   bool,
 	pub completion: bool,
+	// Whether to complete right away when shown, rather than after the first change
+	pub eager:      bool,
 	pub highlight:  bool,
+	pub history:    Option<&'static str>,
+	// Whether the value goes into the history only once `remember`ed, rather than
+	// right on submit, for the ones that may still fail after that
+	pub deferred:   bool,
 }
 
 #[derive(Default)]
@@ -36,6 +42,27 @@ impl InputCfg {
 			title: INPUT.cd_title.to_owned(),
 			position: Position::new(INPUT.cd_origin, INPUT.cd_offset),
 			completion: true,
+			history: Some("cd"),
+			..Default::default()
+		}
+	}
+
+	pub fn fuzzy() -> Self {
+		Self {
+			title: INPUT.fuzzy_title.to_owned(),
+			position: Position::new(INPUT.fuzzy_origin, INPUT.fuzzy_offset),
+			completion: true,
+			eager: true,
+			..Default::default()
+		}
+	}
+
+	pub fn copy_to(cut: bool) -> Self {
+		Self {
+			title: INPUT.copy_to_title[cut as usize].to_owned(),
+			position: Position::new(INPUT.copy_to_origin, INPUT.copy_to_offset),
+			completion: true,
+			eager: true,
 			..Default::default()
 		}
 	}
@@ -44,6 +71,7 @@ impl InputCfg {
 		Self {
 			title: INPUT.create_title.to_owned(),
 			position: Position::new(INPUT.create_origin, INPUT.create_offset),
+			history: Some("create"),
 			..Default::default()
 		}
 	}
@@ -52,6 +80,7 @@ impl InputCfg {
 		Self {
 			title: INPUT.rename_title.to_owned(),
 			position: Position::new(INPUT.rename_origin, INPUT.rename_offset),
+			history: Some("rename"),
 			..Default::default()
 		}
 	}
@@ -61,6 +90,7 @@ impl InputCfg {
 			title: INPUT.filter_title.to_owned(),
 			position: Position::new(INPUT.filter_origin, INPUT.filter_offset),
 			realtime: true,
+			history: Some("filter"),
 			..Default::default()
 		}
 	}
@@ -70,6 +100,7 @@ impl InputCfg {
 			title: INPUT.find_title[prev as usize].to_owned(),
 			position: Position::new(INPUT.find_origin, INPUT.find_offset),
 			realtime: true,
+			history: Some("find"),
 			..Default::default()
 		}
 	}
@@ -78,6 +109,16 @@ impl InputCfg {
 		Self {
 			title: INPUT.search_title.replace("{n}", name),
 			position: Position::new(INPUT.search_origin, INPUT.search_offset),
+			history: Some("search"),
+			..Default::default()
+		}
+	}
+
+	pub fn search_contents() -> Self {
+		Self {
+			title: INPUT.contents_title.to_owned(),
+			position: Position::new(INPUT.contents_origin, INPUT.contents_offset),
+			history: Some("search"),
 			..Default::default()
 		}
 	}
@@ -87,6 +128,47 @@ impl InputCfg {
 			title: INPUT.shell_title[block as usize].to_owned(),
 			position: Position::new(INPUT.shell_origin, INPUT.shell_offset),
 			highlight: true,
+			history: Some("shell"),
+			deferred: true,
+			..Default::default()
+		}
+	}
+
+	pub fn goto() -> Self {
+		Self {
+			title: INPUT.goto_title.to_owned(),
+			position: Position::new(INPUT.goto_origin, INPUT.goto_offset),
+			completion: true,
+			history: Some("goto"),
+			..Default::default()
+		}
+	}
+
+	pub fn tag() -> Self {
+		Self {
+			title: INPUT.tag_title.to_owned(),
+			position: Position::new(INPUT.tag_origin, INPUT.tag_offset),
+			history: Some("tag"),
+			..Default::default()
+		}
+	}
+
+	pub fn palette() -> Self {
+		Self {
+			title: INPUT.palette_title.to_owned(),
+			position: Position::new(INPUT.palette_origin, INPUT.palette_offset),
+			completion: true,
+			eager: true,
+			..Default::default()
+		}
+	}
+
+	pub fn command() -> Self {
+		Self {
+			title: INPUT.command_title.to_owned(),
+			position: Position::new(INPUT.command_origin, INPUT.command_offset),
+			completion: true,
+			history: Some("command"),
 			..Default::default()
 		}
 	}
@@ -178,4 +260,36 @@ impl SelectCfg {
 			}),
 		}
 	}
+
+	pub fn recents(items: Vec<String>) -> Self {
+		Self {
+			title: SELECT.recents_title.to_owned(),
+			items,
+			position: Position::new(SELECT.recents_origin, SELECT.recents_offset),
+		}
+	}
+
+	pub fn log(items: Vec<String>) -> Self {
+		Self {
+			title: SELECT.log_title.to_owned(),
+			items,
+			position: Position::new(SELECT.log_origin, SELECT.log_offset),
+		}
+	}
+
+	pub fn mounts(items: Vec<String>) -> Self {
+		Self {
+			title: SELECT.mounts_title.to_owned(),
+			items,
+			position: Position::new(SELECT.mounts_origin, SELECT.mounts_offset),
+		}
+	}
+
+	pub fn matches(subject: &str) -> Self {
+		Self {
+			title: SELECT.matches_title.replace("{s}", subject),
+			position: Position::new(SELECT.matches_origin, SELECT.matches_offset),
+			..Default::default()
+		}
+	}
 }
@@ -10,6 +10,26 @@ pub struct Select {
 	pub open_title:  String,
 	pub open_origin: Origin,
 	pub open_offset: Offset,
+
+	// recents
+	pub recents_title:  String,
+	pub recents_origin: Origin,
+	pub recents_offset: Offset,
+
+	// log
+	pub log_title:  String,
+	pub log_origin: Origin,
+	pub log_offset: Offset,
+
+	// mounts
+	pub mounts_title:  String,
+	pub mounts_origin: Origin,
+	pub mounts_offset: Offset,
+
+	// matches
+	pub matches_title:  String,
+	pub matches_origin: Origin,
+	pub matches_offset: Offset,
 }
 
 impl Select {
@@ -1,23 +1,61 @@
-use std::{borrow::Cow, mem, path::{Path, PathBuf}};
+use std::{borrow::Cow, env, fs::canonicalize, mem, path::{Path, PathBuf}};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use toml::{Table, Value};
+use tracing::debug;
+use yazi_shared::fs::expand_path;
 
 use crate::theme::Flavor;
 
 pub(crate) struct Preset;
 
+// What an unset variable in `${VAR}` becomes, which is the `[env] unset` of
+// the user's yazi.toml
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum Unset {
+	#[default]
+	Empty,
+	Error,
+	Keep,
+}
+
 impl Preset {
-	pub(crate) fn yazi(p: &Path) -> Result<Cow<str>> {
-		Self::merge_path(p.join("yazi.toml"), include_str!("../preset/yazi.toml"))
+	// `dirs` go from the lowest precedence to the highest, and the first one is
+	// the user's config directory, as given by `Self::dirs()`
+	pub(crate) fn yazi(dirs: &[PathBuf]) -> Result<Cow<str>> {
+		let unset = Self::unset_env(&dirs[0])?;
+		let mut s =
+			Self::merge_path(dirs[0].join("yazi.toml"), include_str!("../preset/yazi.toml"), unset)?;
+		for p in dirs[1..].iter().map(|d| d.join("yazi.toml")) {
+			let Some(over) = Self::read(&p, unset)? else { continue };
+			s = Self::merge_str(&over, &s).with_context(|| anyhow!("Loading {p:?}"))?;
+		}
+		Ok(s)
 	}
 
-	pub(crate) fn keymap(p: &Path) -> Result<Cow<str>> {
-		Self::merge_path(p.join("keymap.toml"), include_str!("../preset/keymap.toml"))
+	pub(crate) fn keymap(dirs: &[PathBuf]) -> Result<Cow<str>> {
+		let unset = Self::unset_env(&dirs[0])?;
+		let mut s =
+			Self::merge_path(dirs[0].join("keymap.toml"), include_str!("../preset/keymap.toml"), unset)?;
+		for p in dirs[1..].iter().map(|d| d.join("keymap.toml")) {
+			let Some(over) = Self::read(&p, unset)? else { continue };
+			s = Self::layer_keymap(&s, &over, &p).with_context(|| anyhow!("Loading {p:?}"))?;
+		}
+		Ok(s)
+	}
+
+	pub(crate) fn theme(dirs: &[PathBuf]) -> Result<Cow<str>> {
+		let unset = Self::unset_env(&dirs[0])?;
+		let mut s = Self::theme_of(&dirs[0], unset)?;
+		for p in dirs[1..].iter().map(|d| d.join("theme.toml")) {
+			let Some(over) = Self::read(&p, unset)? else { continue };
+			s = Self::merge_str(&over, &s).with_context(|| anyhow!("Loading {p:?}"))?;
+		}
+		Ok(s)
 	}
 
-	pub(crate) fn theme(p: &Path) -> Result<Cow<str>> {
-		let Ok(user) = std::fs::read_to_string(p.join("theme.toml")) else {
+	fn theme_of(p: &Path, unset: Unset) -> Result<Cow<'static, str>> {
+		let Some(user) = Self::read(&p.join("theme.toml"), unset)? else {
 			return Ok(include_str!("../preset/theme.toml").into());
 		};
 		let Some(use_) = Flavor::parse_use(&user) else {
@@ -31,6 +69,47 @@ impl Preset {
 		Self::merge_str(&user, &Self::merge_str(&flavor, include_str!("../preset/theme.toml"))?)
 	}
 
+	// The directories the config files are read from, in the order they're
+	// layered: the user's config directory, the `[project] layers` of its
+	// yazi.toml, then the `.yazi` directories of the trusted projects `cwd` is in
+	pub(crate) fn dirs(cwd: &Path, config_dir: &Path) -> Vec<PathBuf> {
+		let user = Self::user_table(config_dir).unwrap_or_default();
+		let list = |key| {
+			let v = user.get("project").and_then(|p| p.get(key)).and_then(Value::as_array);
+			v.into_iter().flatten().filter_map(Value::as_str).map(expand_path).collect::<Vec<_>>()
+		};
+
+		let mut dirs = vec![config_dir.to_owned()];
+		for dir in list("layers").into_iter().chain(Self::project_dirs(cwd, &list("trusted"))) {
+			if !dirs.contains(&dir) {
+				dirs.push(dir);
+			}
+		}
+		dirs
+	}
+
+	// The `.yazi` directories from the root down to `cwd`, of the projects that
+	// are `trusted`, since their keymap could run any command
+	fn project_dirs(cwd: &Path, trusted: &[PathBuf]) -> Vec<PathBuf> {
+		let trusted: Vec<_> = trusted.iter().filter_map(|t| canonicalize(t).ok()).collect();
+		let mut dirs: Vec<_> = cwd
+			.ancestors()
+			.map(|p| p.join(".yazi"))
+			.filter(|p| p.is_dir())
+			.filter(|dir| {
+				let Some(root) = dir.parent().and_then(|p| canonicalize(p).ok()) else { return false };
+				if trusted.contains(&root) {
+					return true;
+				}
+				debug!("Skipping {dir:?}, as {root:?} isn't a trusted project");
+				false
+			})
+			.collect();
+
+		dirs.reverse();
+		dirs
+	}
+
 	#[inline]
 	pub(crate) fn mix<T>(a: &mut Vec<T>, b: Vec<T>, c: Vec<T>) {
 		*a = b.into_iter().chain(mem::take(a)).chain(c).collect();
@@ -45,8 +124,8 @@ impl Preset {
 	}
 
 	#[inline]
-	fn merge_path(user: PathBuf, base: &str) -> Result<Cow<str>> {
-		let s = std::fs::read_to_string(&user).unwrap_or_default();
+	fn merge_path(user: PathBuf, base: &str, unset: Unset) -> Result<Cow<'_, str>> {
+		let s = Self::read(&user, unset)?.unwrap_or_default();
 		if s.is_empty() {
 			return Ok(base.into());
 		}
@@ -54,6 +133,101 @@ impl Preset {
 		Self::merge_str(&s, base).with_context(|| anyhow!("Loading {user:?}"))
 	}
 
+	// Reads a user's config file, with each `${VAR}` in its string values replaced
+	// by the environment variable, or `None` if it doesn't exist. The `run`s are
+	// left alone, as they're shell commands that expand their own variables.
+	fn read(p: &Path, unset: Unset) -> Result<Option<String>> {
+		let Ok(s) = std::fs::read_to_string(p) else { return Ok(None) };
+		if !s.contains('$') {
+			return Ok(Some(s));
+		}
+
+		let mut t: Table = s.parse().with_context(|| anyhow!("Loading {p:?}"))?;
+		Self::interpolate_table(&mut t, unset).with_context(|| anyhow!("Loading {p:?}"))?;
+		Ok(Some(t.to_string()))
+	}
+
+	fn unset_env(dir: &Path) -> Result<Unset> {
+		let Some(t) = Self::user_table(dir) else { return Ok(Unset::default()) };
+		Ok(match t.get("env").and_then(|e| e.get("unset")) {
+			None => Unset::default(),
+			Some(Value::String(s)) if s == "empty" => Unset::Empty,
+			Some(Value::String(s)) if s == "error" => Unset::Error,
+			Some(Value::String(s)) if s == "keep" => Unset::Keep,
+			Some(v) => bail!(
+				"Loading {:?}: `[env] unset` must be \"empty\", \"error\" or \"keep\", not {v}",
+				dir.join("yazi.toml")
+			),
+		})
+	}
+
+	// The user's yazi.toml as it is, for the settings needed before it's loaded
+	fn user_table(dir: &Path) -> Option<Table> {
+		std::fs::read_to_string(dir.join("yazi.toml")).ok()?.parse().ok()
+	}
+
+	fn interpolate_value(v: &mut Value, unset: Unset) -> Result<()> {
+		match v {
+			Value::String(s) => {
+				if let Cow::Owned(new) = Self::interpolate(s, unset)? {
+					*s = new;
+				}
+			}
+			Value::Array(a) => a.iter_mut().try_for_each(|v| Self::interpolate_value(v, unset))?,
+			Value::Table(t) => Self::interpolate_table(t, unset)?,
+			_ => {}
+		}
+		Ok(())
+	}
+
+	#[inline]
+	fn interpolate_table(t: &mut Table, unset: Unset) -> Result<()> {
+		t.iter_mut()
+			.filter(|(k, _)| *k != "run")
+			.try_for_each(|(_, v)| Self::interpolate_value(v, unset))
+	}
+
+	// Replaces `${VAR}` with the environment variable, where `VAR` starts with a
+	// letter or `_`, and `$$` with `$`. Any other `$` is left as it is, so that a
+	// shell can still expand it.
+	fn interpolate(s: &str, unset: Unset) -> Result<Cow<'_, str>> {
+		if !s.contains('$') {
+			return Ok(s.into());
+		}
+
+		let (mut out, mut rest) = (String::with_capacity(s.len()), s);
+		while let Some(i) = rest.find('$') {
+			out.push_str(&rest[..i]);
+			rest = &rest[i..];
+			if let Some(r) = rest.strip_prefix("$$") {
+				out.push('$');
+				rest = r;
+				continue;
+			}
+
+			let var = rest.strip_prefix("${").and_then(|r| r.split_once('}')).filter(|(name, _)| {
+				name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+					&& name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+			});
+			let Some((name, r)) = var else {
+				out.push('$');
+				rest = &rest[1..];
+				continue;
+			};
+
+			match env::var(name) {
+				Ok(value) => out.push_str(&value),
+				Err(_) if unset == Unset::Error => bail!("Environment variable `{name}` is not set"),
+				Err(_) if unset == Unset::Keep => out.push_str(&rest[..rest.len() - r.len()]),
+				Err(_) => {}
+			}
+			rest = r;
+		}
+
+		out.push_str(rest);
+		Ok(out.into())
+	}
+
 	fn merge(a: &mut Table, b: Table, max: u8) {
 		for (k, v) in b {
 			let Some(a) = a.get_mut(&k) else {
@@ -74,4 +248,223 @@ impl Preset {
 			*a = v;
 		}
 	}
+
+	// Layers the keymap `over` on top of `base`, so its bindings take precedence
+	// over the ones with the same keys in `base`, which are logged as conflicts
+	fn layer_keymap(base: &str, over: &str, path: &Path) -> Result<Cow<'static, str>> {
+		let (mut base, over): (Table, Table) = (base.parse()?, over.parse()?);
+		let take = |t: &mut Table, k: &str| match t.remove(k) {
+			Some(Value::Array(a)) => a,
+			_ => vec![],
+		};
+
+		for (layer, v) in over {
+			let Value::Table(mut over) = v else { continue };
+			let Some(Value::Table(base)) = base.get_mut(&layer) else { continue };
+
+			let mut prepend = take(&mut over, "prepend_keymap");
+			prepend.extend(take(&mut over, "keymap"));
+			let old_prepend = take(base, "prepend_keymap");
+			let mut append = take(base, "append_keymap");
+
+			let existing: Vec<_> = [&old_prepend, &append]
+				.into_iter()
+				.flatten()
+				.chain(base.get("keymap").and_then(Value::as_array).into_iter().flatten())
+				.map(Self::binding)
+				.collect();
+			for key in prepend.iter().map(Self::binding).filter(|k| existing.contains(k)) {
+				debug!("`{}` in [{layer}] of {path:?} overrides an earlier binding", key.0);
+			}
+
+			prepend.extend(old_prepend);
+			append.extend(take(&mut over, "append_keymap"));
+			base.insert("prepend_keymap".to_owned(), Value::Array(prepend));
+			base.insert("append_keymap".to_owned(), Value::Array(append));
+		}
+
+		Ok(base.to_string().into())
+	}
+
+	// The keys and mode of a binding, which identify it when being layered
+	fn binding(v: &Value) -> (Value, Option<&Value>) {
+		let on = match v.get("on") {
+			Some(Value::String(s)) => Value::Array(vec![Value::String(s.clone())]),
+			Some(on) => on.clone(),
+			None => Value::Array(vec![]),
+		};
+		(on, v.get("mode"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+
+	use super::*;
+	use crate::keymap::{Key, Keymap};
+
+	#[test]
+	fn test_layer_keymap() {
+		let base = Preset::merge_str(
+			r#"
+			[manager]
+			prepend_keymap = [ { on = "j", run = "arrow 2" }, { on = "k", run = "arrow -2" } ]
+			"#,
+			include_str!("../preset/keymap.toml"),
+		)
+		.unwrap();
+
+		let over = r#"
+			[manager]
+			keymap = [ { on = "j", run = "arrow 5" } ]
+			append_keymap = [ { on = [ "g", "z" ], run = "cd ~/z" } ]
+		"#;
+		let keymap =
+			Keymap::from_str(&Preset::layer_keymap(&base, over, Path::new("")).unwrap()).unwrap();
+
+		let run = |on: &[&str]| {
+			let on: Vec<_> = on.iter().map(|s| Key::from_str(s).unwrap()).collect();
+			keymap.manager.iter().find(|c| c.on == on).map(|c| c.run())
+		};
+		assert_eq!(run(&["j"]).as_deref(), Some("arrow 5"));
+		assert_eq!(run(&["k"]).as_deref(), Some("arrow -2"));
+		assert_eq!(run(&["l"]).as_deref(), Some("enter"));
+		assert_eq!(run(&["g", "z"]).as_deref(), Some("cd ~/z"));
+	}
+
+	#[test]
+	fn test_interpolate() {
+		let path = env::var("PATH").unwrap();
+
+		let expand = |s| Preset::interpolate(s, Unset::Empty).unwrap();
+		assert_eq!(expand("${PATH}/bin"), format!("{path}/bin"));
+		assert_eq!(expand("a${YAZI_TEST_UNSET}b"), "ab");
+		assert_eq!(expand(r#"$EDITOR "$@""#), r#"$EDITOR "$@""#);
+		assert_eq!(expand("$${PATH} costs $$5"), "${PATH} costs $5");
+		assert_eq!(expand("${} ${A-B} ${1} ${unterminated"), "${} ${A-B} ${1} ${unterminated");
+		assert!(matches!(expand("no variables"), Cow::Borrowed(_)));
+
+		let keep = Preset::interpolate("a${YAZI_TEST_UNSET}b", Unset::Keep).unwrap();
+		assert_eq!(keep, "a${YAZI_TEST_UNSET}b");
+
+		assert!(Preset::interpolate("${PATH}", Unset::Error).is_ok());
+		let e = Preset::interpolate("${YAZI_TEST_UNSET}", Unset::Error).unwrap_err();
+		assert_eq!(e.to_string(), "Environment variable `YAZI_TEST_UNSET` is not set");
+	}
+
+	#[test]
+	fn test_interpolate_run() {
+		let tmp = tempfile::tempdir().unwrap();
+		let run = r#"for f in "$@"; do echo "${f}" "${YAZI_TEST_RUN_UNSET}" $$; done"#;
+		let yazi = format!(
+			"[env]\nunset = 'empty'\n[opener]\nedit = [ {{ run = '{run}', desc = 'a${{YAZI_TEST_RUN_UNSET}}' }} ]"
+		);
+		let keymap = format!("[manager]\nprepend_keymap = [ {{ on = 'x', run = 'shell -- {run}' }} ]");
+		std::fs::write(tmp.path().join("yazi.toml"), yazi).unwrap();
+		std::fs::write(tmp.path().join("keymap.toml"), keymap).unwrap();
+
+		// The shell commands are kept as they are, while the other strings are expanded
+		let dirs = [tmp.path().to_owned()];
+		let yazi: Table = Preset::yazi(&dirs).unwrap().parse().unwrap();
+		assert_eq!(yazi["opener"]["edit"][0]["run"].as_str(), Some(run));
+		assert_eq!(yazi["opener"]["edit"][0]["desc"].as_str(), Some("a"));
+
+		let keymap: Table = Preset::keymap(&dirs).unwrap().parse().unwrap();
+		let shell = keymap["manager"]["prepend_keymap"][0]["run"].as_str();
+		assert_eq!(shell, Some(format!("shell -- {run}").as_str()));
+	}
+
+	#[test]
+	fn test_unset_env() {
+		let tmp = tempfile::tempdir().unwrap();
+		let unset = |toml: &str| {
+			std::fs::write(tmp.path().join("yazi.toml"), toml).unwrap();
+			Preset::unset_env(tmp.path())
+		};
+
+		assert_eq!(unset("").unwrap(), Unset::Empty);
+		assert_eq!(unset("[env]\nunset = 'keep'").unwrap(), Unset::Keep);
+		assert_eq!(unset("[env]\nunset = 'error'").unwrap(), Unset::Error);
+
+		let e = unset("[env]\nunset = 'ignore'").unwrap_err().to_string();
+		assert!(
+			e.ends_with(r#"`[env] unset` must be "empty", "error" or "keep", not "ignore""#),
+			"{e}"
+		);
+		assert!(Preset::yazi(&[tmp.path().to_owned()]).is_err());
+	}
+
+	#[test]
+	fn test_dirs() {
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		std::fs::create_dir_all(root.join("a/.yazi")).unwrap();
+		std::fs::create_dir_all(root.join("a/b/.yazi")).unwrap();
+		std::fs::create_dir_all(root.join("a/b/c/d")).unwrap();
+		std::fs::write(root.join("a/b/c/.yazi"), "").unwrap();
+
+		let config = root.join("config");
+		std::fs::create_dir_all(&config).unwrap();
+		let user = |layers: &[&str], trusted: &[&str]| {
+			let list = |v: &[&str]| -> Vec<_> {
+				v.iter().map(|d| root.join(d).to_string_lossy().into_owned()).collect()
+			};
+			let (layers, trusted) = (list(layers), list(trusted));
+			let toml = format!("[project]\nlayers = {layers:?}\ntrusted = {trusted:?}");
+			std::fs::write(config.join("yazi.toml"), toml).unwrap();
+		};
+		let dirs = |cwd: &str| -> Vec<_> {
+			let dirs = Preset::dirs(&root.join(cwd), &config);
+			dirs.iter().map(|d| d.strip_prefix(&root).unwrap().to_string_lossy().into_owned()).collect()
+		};
+
+		// Nothing is trusted to begin with
+		assert_eq!(dirs("a/b/c/d"), ["config"]);
+
+		user(&[], &["a"]);
+		assert_eq!(dirs("a"), ["config", "a/.yazi"]);
+		assert_eq!(dirs("a/b/c/d"), ["config", "a/.yazi"], "the nearest one isn't trusted");
+
+		user(&["x", "config", "y"], &["a/b", "a/b/c", "a"]);
+		assert_eq!(dirs("a/b/c/d"), ["config", "x", "y", "a/.yazi", "a/b/.yazi"]);
+		assert_eq!(dirs("a"), ["config", "x", "y", "a/.yazi"]);
+	}
+
+	#[test]
+	fn test_layers() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dirs: Vec<_> = (0..3).map(|i| tmp.path().join(i.to_string())).collect();
+		for (i, dir) in dirs.iter().enumerate() {
+			std::fs::create_dir(dir).unwrap();
+			let keymap = format!(
+				r#"
+				[manager]
+				prepend_keymap = [ {{ on = "{i}", run = "arrow {i}" }}, {{ on = "j", run = "arrow {i}" }} ]
+				"#
+			);
+			std::fs::write(dir.join("keymap.toml"), keymap).unwrap();
+			std::fs::write(dir.join("theme.toml"), format!("[status]\nseparator_open = '{i}'")).unwrap();
+			std::fs::write(dir.join("yazi.toml"), format!("[manager]\nscrolloff = {i}")).unwrap();
+		}
+		std::fs::write(dirs[2].join("yazi.toml"), "[manager]\nshow_hidden = true").unwrap();
+
+		let keymap = Keymap::from_str(&Preset::keymap(&dirs).unwrap()).unwrap();
+		let run = |on: &str| {
+			let on = vec![Key::from_str(on).unwrap()];
+			keymap.manager.iter().find(|c| c.on == on).map(|c| c.run())
+		};
+		assert_eq!(run("j").as_deref(), Some("arrow 2"));
+		assert_eq!(run("0").as_deref(), Some("arrow 0"));
+		assert_eq!(run("1").as_deref(), Some("arrow 1"));
+		assert_eq!(run("k").as_deref(), Some("arrow -1"));
+
+		let theme: Table = Preset::theme(&dirs).unwrap().parse().unwrap();
+		assert_eq!(theme["status"]["separator_open"].as_str(), Some("2"));
+
+		let yazi: Table = Preset::yazi(&dirs).unwrap().parse().unwrap();
+		assert_eq!(yazi["manager"]["scrolloff"].as_integer(), Some(1));
+		assert_eq!(yazi["manager"]["show_hidden"].as_bool(), Some(true));
+		assert_eq!(yazi["manager"]["sort_by"].as_str(), Some("alphabetical"));
+	}
 }
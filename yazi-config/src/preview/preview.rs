@@ -6,7 +6,7 @@ use validator::Validate;
 use yazi_shared::fs::expand_path;
 
 use super::PreviewWrap;
-use crate::Xdg;
+use crate::{Pattern, Xdg};
 
 #[rustfmt::skip]
 const TABS: &[&str] = &["", " ", "  ", "   ", "    ", "     ", "      ", "       ", "        ", "         ", "          ", "           ", "            ", "             ", "              ", "               ", "                "];
@@ -27,6 +27,21 @@ pub struct Preview {
 
 	pub ueberzug_scale:  f32,
 	pub ueberzug_offset: (f32, f32, f32, f32),
+
+	pub worker_nice: u8,
+	pub cpu_limit:   u8,
+	pub eager_peek:  bool,
+
+	pub text_stats:     bool,
+	pub text_stats_max: u64,
+
+	pub dir_readme:       bool,
+	#[serde(skip_serializing)]
+	pub dir_readme_names: Vec<Pattern>,
+
+	pub dir_tree:       bool,
+	pub dir_tree_depth: u8,
+	pub dir_tree_max:   usize,
 }
 
 impl Preview {
@@ -72,6 +87,24 @@ impl FromStr for Preview {
 
 			ueberzug_scale:  f32,
 			ueberzug_offset: (f32, f32, f32, f32),
+
+			#[validate(range(min = 0, max = 19))]
+			worker_nice: u8,
+			#[validate(range(min = 10, max = 100))]
+			cpu_limit:   u8,
+			eager_peek:  bool,
+
+			text_stats:     bool,
+			text_stats_max: u64,
+
+			dir_readme:       bool,
+			dir_readme_names: Vec<Pattern>,
+
+			dir_tree:       bool,
+			#[validate(range(min = 1, max = 8))]
+			dir_tree_depth: u8,
+			#[validate(range(min = 1, message = "Cannot be less than 1"))]
+			dir_tree_max:   usize,
 		}
 
 		let preview = toml::from_str::<Outer>(s)?.preview;
@@ -96,6 +129,20 @@ impl FromStr for Preview {
 
 			ueberzug_scale: preview.ueberzug_scale,
 			ueberzug_offset: preview.ueberzug_offset,
+
+			worker_nice: preview.worker_nice,
+			cpu_limit: preview.cpu_limit,
+			eager_peek: preview.eager_peek,
+
+			text_stats: preview.text_stats,
+			text_stats_max: preview.text_stats_max,
+
+			dir_readme: preview.dir_readme,
+			dir_readme_names: preview.dir_readme_names,
+
+			dir_tree: preview.dir_tree,
+			dir_tree_depth: preview.dir_tree_depth,
+			dir_tree_max: preview.dir_tree_max,
 		})
 	}
 }
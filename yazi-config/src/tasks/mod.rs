@@ -1,3 +1,5 @@
 mod tasks;
+mod trash;
 
 pub use tasks::*;
+pub use trash::*;
@@ -1,8 +1,11 @@
 use std::str::FromStr;
 
+use anyhow::bail;
 use serde::Deserialize;
 use validator::Validate;
 
+use super::TrashBackend;
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct Tasks {
 	#[validate(range(min = 1, message = "Cannot be less than 1"))]
@@ -16,6 +19,11 @@ pub struct Tasks {
 	pub image_bound: [u16; 2],
 
 	pub suppress_preload: bool,
+
+	pub preserve_timestamps: bool,
+
+	pub trash:     TrashBackend,
+	pub trash_run: String,
 }
 
 impl FromStr for Tasks {
@@ -29,6 +37,9 @@ impl FromStr for Tasks {
 
 		let tasks = toml::from_str::<Outer>(s)?.tasks;
 		tasks.validate()?;
+		if tasks.trash == TrashBackend::Command && tasks.trash_run.trim().is_empty() {
+			bail!("`trash_run` cannot be empty when `trash` is \"command\"");
+		}
 
 		Ok(tasks)
 	}
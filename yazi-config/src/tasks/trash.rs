@@ -0,0 +1,51 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub enum TrashBackend {
+	Native,
+	TrashCli,
+	Gio,
+	Command,
+}
+
+impl TrashBackend {
+	#[inline]
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Native => "native",
+			Self::TrashCli => "trash-cli",
+			Self::Gio => "gio",
+			Self::Command => "command",
+		}
+	}
+
+	// `trash-cli` and `gio` both follow the freedesktop spec, so what they trash
+	// can be found and put back the same way the native one does. There's no
+	// telling where a custom command puts the files.
+	#[inline]
+	pub fn restorable(self) -> bool { self != Self::Command }
+}
+
+impl FromStr for TrashBackend {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"native" => Self::Native,
+			"trash-cli" => Self::TrashCli,
+			"gio" => Self::Gio,
+			"command" => Self::Command,
+			_ => bail!("Invalid `trash` value: {s}"),
+		})
+	}
+}
+
+impl TryFrom<String> for TrashBackend {
+	type Error = anyhow::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> { Self::from_str(&value) }
+}
@@ -74,6 +74,9 @@ pub struct Manager {
 	count_cut:      Style,
 	count_selected: Style,
 
+	// Tag
+	tag: Style,
+
 	// Border
 	pub border_symbol: String,
 	pub border_style:  Style,
@@ -104,6 +107,9 @@ struct Status {
 	pub permissions_w: Style,
 	pub permissions_x: Style,
 	pub permissions_s: Style,
+
+	// Disk space
+	pub disk_space: Style,
 }
 
 #[derive(Deserialize, Serialize)]
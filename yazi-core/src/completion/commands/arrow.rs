@@ -11,7 +11,7 @@ impl From<Cmd> for Opt {
 }
 
 impl Completion {
-	fn next(&mut self, step: usize) {
+	pub(super) fn next(&mut self, step: usize) {
 		let len = self.cands.len();
 		if len == 0 {
 			return;
@@ -28,7 +28,7 @@ impl Completion {
 		render!(old != self.cursor);
 	}
 
-	fn prev(&mut self, step: usize) {
+	pub(super) fn prev(&mut self, step: usize) {
 		let old = self.cursor;
 		self.cursor = self.cursor.saturating_sub(step);
 
@@ -19,10 +19,12 @@ impl Completion {
 		let opt = opt.into() as Opt;
 
 		if let Some(s) = self.selected().filter(|_| opt.submit) {
-			InputProxy::complete(s, self.ticket);
+			InputProxy::complete(&format!("{}{s}", self.prefix), self.ticket, self.whole);
 		}
 
 		self.caches.clear();
+		self.prefix.clear();
+		self.cycling = false;
 		self.visible = false;
 		render!();
 	}
@@ -0,0 +1,38 @@
+use yazi_proxy::InputProxy;
+use yazi_shared::{event::{Cmd, Data}, render};
+
+use crate::completion::Completion;
+
+pub struct Opt {
+	step: isize,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self { Self { step: c.first().and_then(Data::as_isize).unwrap_or(1) } }
+}
+
+impl Completion {
+	pub fn cycle(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		let len = self.cands.len();
+		if len == 0 {
+			return;
+		}
+
+		// The first cycle submits the current candidate, the following ones move on
+		if self.cycling {
+			let cursor = (self.cursor as isize + opt.step).rem_euclid(len as isize) as usize;
+			if cursor > self.cursor {
+				self.next(cursor - self.cursor);
+			} else {
+				self.prev(self.cursor - cursor);
+			}
+		}
+
+		self.cycling = true;
+		if let Some(s) = self.selected() {
+			InputProxy::complete(&format!("{}{s}", self.prefix), self.ticket, self.whole);
+		}
+		render!();
+	}
+}
@@ -1,4 +1,5 @@
 mod arrow;
 mod close;
+mod cycle;
 mod show;
 mod trigger;
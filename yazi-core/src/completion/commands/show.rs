@@ -1,4 +1,4 @@
-use std::{mem, ops::ControlFlow};
+use std::{mem, ops::ControlFlow, sync::Arc};
 
 use yazi_shared::{event::{Cmd, Data}, render};
 
@@ -7,19 +7,21 @@ use crate::completion::Completion;
 const LIMIT: usize = 30;
 
 pub struct Opt {
-	cache:      Vec<String>,
+	cache:      Option<Arc<Vec<String>>>,
 	cache_name: String,
 	word:       String,
 	ticket:     usize,
+	whole:      bool,
 }
 
 impl From<Cmd> for Opt {
 	fn from(mut c: Cmd) -> Self {
 		Self {
-			cache:      c.take_any("cache").unwrap_or_default(),
+			cache:      c.take_any("cache"),
 			cache_name: c.take_str("cache-name").unwrap_or_default(),
 			word:       c.take_str("word").unwrap_or_default(),
 			ticket:     c.get("ticket").and_then(Data::as_usize).unwrap_or(0),
+			whole:      c.bool("whole"),
 		}
 	}
 }
@@ -62,14 +64,15 @@ impl Completion {
 			return;
 		}
 
-		if !opt.cache.is_empty() {
-			self.caches.insert(opt.cache_name.to_owned(), opt.cache);
+		if let Some(cache) = opt.cache {
+			self.caches.insert(opt.cache_name.to_owned(), cache);
 		}
 		let Some(cache) = self.caches.get(&opt.cache_name) else {
 			return;
 		};
 
 		self.ticket = opt.ticket;
+		self.whole = opt.whole;
 		self.cands = Self::match_candidates(&opt.word, cache);
 		if self.cands.is_empty() {
 			return render!(mem::replace(&mut self.visible, false));
@@ -81,3 +84,23 @@ impl Completion {
 		render!();
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use yazi_config::command::Commands;
+
+	use super::*;
+
+	#[test]
+	fn test_command_names() {
+		let names = Commands::default().names();
+
+		let cands = Completion::match_candidates("tab_", &names);
+		assert_eq!(cands, ["tab_create", "tab_close", "tab_switch", "tab_swap"]);
+
+		let cands = Completion::match_candidates("find", &names);
+		assert_eq!(cands, ["find_arrow"]);
+
+		assert!(Completion::match_candidates("zzz", &names).is_empty());
+	}
+}
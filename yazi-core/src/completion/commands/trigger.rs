@@ -1,7 +1,8 @@
-use std::{borrow::Cow, mem, path::{MAIN_SEPARATOR, MAIN_SEPARATOR_STR}};
+use std::{mem, path::{MAIN_SEPARATOR, MAIN_SEPARATOR_STR}, sync::Arc};
 
 use tokio::fs;
-use yazi_shared::{emit, event::{Cmd, Data}, render, Layer};
+use yazi_config::COMMANDS;
+use yazi_shared::{emit, event::{Cmd, Data}, fs::expand_str, render, Layer};
 
 use crate::completion::Completion;
 
@@ -12,15 +13,19 @@ const SEPARATOR: [char; 2] = ['/', '\\'];
 const SEPARATOR: char = std::path::MAIN_SEPARATOR;
 
 pub struct Opt {
-	word:   String,
-	ticket: usize,
+	word:    String,
+	ticket:  usize,
+	command: bool,
+	cached:  bool,
 }
 
 impl From<Cmd> for Opt {
 	fn from(mut c: Cmd) -> Self {
 		Self {
-			word:   c.take_first_str().unwrap_or_default(),
-			ticket: c.get("ticket").and_then(Data::as_usize).unwrap_or(0),
+			word:    c.take_first_str().unwrap_or_default(),
+			ticket:  c.get("ticket").and_then(Data::as_usize).unwrap_or(0),
+			command: c.bool("command"),
+			cached:  c.bool("cached"),
 		}
 	}
 }
@@ -33,13 +38,62 @@ impl Completion {
 		}
 
 		self.ticket = opt.ticket;
-		let Some((parent, child)) = Self::split_path(&opt.word) else {
+		if opt.cached {
+			// The candidates are sent along with `show` by the caller, e.g. the fuzzy finder
+			self.prefix.clear();
+			return;
+		}
+		if opt.command {
+			return self.trigger_command(&opt.word);
+		}
+
+		self.prefix.clear();
+		self.trigger_path(&opt.word);
+	}
+
+	fn trigger_command(&mut self, before: &str) {
+		let (head, word) = match before.rsplit_once(' ') {
+			Some((head, word)) => (head.trim_start(), word),
+			None => ("", before.trim_start()),
+		};
+
+		// Skip the trigger caused by the candidate we just submitted via cycling
+		if self.cycling && self.visible && self.selected().is_some_and(|s| word.ends_with(s.as_str())) {
+			return;
+		}
+
+		self.cycling = false;
+		self.prefix = Self::command_prefix(before, word);
+
+		let name = head.split(' ').next().unwrap_or_default();
+		let (cache_name, cache) = if head.is_empty() {
+			("\0names".to_owned(), COMMANDS.names())
+		} else if word.starts_with('-') {
+			let flags = COMMANDS.get(name).map(|c| c.flags).unwrap_or_default();
+			(format!("\0flags:{name}"), flags.into_iter().map(|f| format!("--{f}")).collect())
+		} else if COMMANDS.get(name).is_some_and(|c| c.path) {
+			return self.trigger_path(word);
+		} else {
+			return self.close(false);
+		};
+
+		self.show(
+			Cmd::new("show")
+				.with_any("cache", Arc::new(cache))
+				.with("cache-name", cache_name)
+				.with("word", word)
+				.with("ticket", self.ticket),
+		);
+	}
+
+	fn trigger_path(&mut self, word: &str) {
+		let Some((parent, child)) = Self::split_path(word) else {
 			return self.close(false);
 		};
 
 		if self.caches.contains_key(&parent) {
 			return self.show(
-				Cmd::new("show").with("cache-name", parent).with("word", child).with("ticket", opt.ticket),
+				Cmd::new("show").with("cache-name", parent).with("word", child).with("ticket", self.ticket),
 			);
 		}
 
@@ -60,7 +114,7 @@ impl Completion {
 			if !cache.is_empty() {
 				emit!(Call(
 					Cmd::new("show")
-						.with_any("cache", cache)
+						.with_any("cache", Arc::new(cache))
 						.with("cache-name", parent)
 						.with("word", child)
 						.with("ticket", ticket),
@@ -75,24 +129,23 @@ impl Completion {
 	}
 
 	fn split_path(s: &str) -> Option<(String, String)> {
-		if s == "~" {
-			return None; // We don't autocomplete a `~`, but `~/`
+		if s.starts_with('~') && !s.contains(SEPARATOR) {
+			return None; // We don't autocomplete a `~` or `~user`, but `~/` or `~user/`
 		}
 
-		let s = if let Some(rest) = s.strip_prefix("~") {
-			Cow::Owned(format!(
-				"{}{rest}",
-				dirs::home_dir().unwrap_or_default().to_string_lossy().trim_end_matches(SEPARATOR),
-			))
-		} else {
-			Cow::Borrowed(s)
-		};
-
 		Some(match s.rsplit_once(SEPARATOR) {
-			Some((p, c)) => (format!("{p}{}", MAIN_SEPARATOR), c.to_owned()),
-			None => (".".to_owned(), s.into_owned()),
+			Some((p, c)) => (format!("{}{}", expand_str(p), MAIN_SEPARATOR), c.to_owned()),
+			None => (".".to_owned(), s.to_owned()),
 		})
 	}
+
+	// The input replaces everything after the last separator with the submitted
+	// text, so keep the part between that separator and the completed word.
+	fn command_prefix(before: &str, word: &str) -> String {
+		let start = before.rfind(SEPARATOR).map_or(0, |i| i + 1);
+		let end = before.len() - word.len();
+		if start <= end { before[start..end].to_owned() } else { String::new() }
+	}
 }
 
 #[cfg(test)]
@@ -103,6 +156,16 @@ mod tests {
 		matches!(Completion::split_path(s), Some((p, c)) if p == parent && c == child)
 	}
 
+	#[cfg(unix)]
+	#[test]
+	fn test_command_prefix() {
+		assert_eq!(Completion::command_prefix("ta", "ta"), "");
+		assert_eq!(Completion::command_prefix("sort --re", "--re"), "sort ");
+		assert_eq!(Completion::command_prefix("cd Doc", "Doc"), "cd ");
+		assert_eq!(Completion::command_prefix("cd ~/Doc", "~/Doc"), "");
+		assert_eq!(Completion::command_prefix("cd /tmp --in", "--in"), "tmp ");
+	}
+
 	#[cfg(unix)]
 	#[test]
 	fn test_split() {
@@ -113,6 +176,15 @@ mod tests {
 		assert!(compare("/foo", "/", "foo"));
 		assert!(compare("/foo/", "/foo/", ""));
 		assert!(compare("/foo/bar", "/foo/", "bar"));
+
+		let home = dirs::home_dir().unwrap();
+		let home = home.to_string_lossy();
+		assert!(Completion::split_path("~").is_none());
+		assert!(compare("~/", &format!("{home}/"), ""));
+		assert!(compare("~/foo", &format!("{home}/"), "foo"));
+
+		assert!(compare("${HOME}/foo", &format!("{home}/"), "foo"));
+		assert!(compare("$HOME/", &format!("{home}/"), ""));
 	}
 
 	#[cfg(windows)]
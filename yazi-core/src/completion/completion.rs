@@ -1,12 +1,21 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 #[derive(Default)]
 pub struct Completion {
-	pub(super) caches: HashMap<String, Vec<String>>,
+	// Shared with whoever sent them, as they're sent again on each keystroke
+	pub(super) caches: HashMap<String, Arc<Vec<String>>>,
 	pub(super) cands:  Vec<String>,
 	pub(super) offset: usize,
 	pub cursor:        usize,
 
+	// Text to put before the selected candidate when submitting, used by the
+	// command prompt where only the last word is completed
+	pub(super) prefix:  String,
+	pub(super) cycling: bool,
+	// Whether the candidates replace the whole input, rather than its last path
+	// component, used by the fuzzy finder whose candidates are relative paths
+	pub(super) whole:   bool,
+
 	pub(super) ticket: usize,
 	pub visible:       bool,
 }
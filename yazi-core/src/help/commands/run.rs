@@ -0,0 +1,16 @@
+use yazi_shared::{emit, event::Cmd};
+
+use crate::help::Help;
+
+impl Help {
+	// Hides the help, and runs the hovered binding in the layer it belongs to
+	pub fn run(&mut self, _: Cmd) {
+		let Some(ctrl) = self.bindings.get(self.cursor).copied() else {
+			return;
+		};
+
+		let layer = self.layer;
+		self.toggle(layer);
+		emit!(Seq(ctrl.to_seq(), layer));
+	}
+}
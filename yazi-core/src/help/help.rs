@@ -1,3 +1,5 @@
+use std::cmp::Reverse;
+
 use crossterm::event::KeyCode;
 use unicode_width::UnicodeWidthStr;
 use yazi_adapter::Dimension;
@@ -69,14 +71,23 @@ impl Help {
 
 		if kw.is_empty() {
 			self.keyword = String::new();
-			self.bindings = KEYMAP.get(self.layer).iter().collect();
+			self.bindings = Self::rank(KEYMAP.get(self.layer), "");
 		} else if self.keyword != kw {
 			self.keyword = kw.to_owned();
-			self.bindings = KEYMAP.get(self.layer).iter().filter(|&c| c.contains(kw)).collect();
+			self.bindings = Self::rank(KEYMAP.get(self.layer), kw);
 		}
 
 		self.arrow(0);
 	}
+
+	// The bindings that fuzzy match the keyword, grouped by their mode with the
+	// unscoped ones first, and the best matches first within a group
+	fn rank<'a>(bindings: &'a [Control], kw: &str) -> Vec<&'a Control> {
+		let mut scored: Vec<_> =
+			bindings.iter().filter_map(|c| c.fuzzy_score(kw).map(|s| (s, c))).collect();
+		scored.sort_by_key(|&(s, c)| (c.mode.map_or(0, |m| m as u8 + 1), Reverse(s)));
+		scored.into_iter().map(|(_, c)| c).collect()
+	}
 }
 
 impl Help {
@@ -113,3 +124,38 @@ impl Help {
 	#[inline]
 	pub fn rel_cursor(&self) -> usize { self.cursor - self.offset }
 }
+
+#[cfg(test)]
+mod tests {
+	use yazi_config::keymap::KeyMode;
+	use yazi_shared::event::Cmd;
+
+	use super::*;
+
+	#[test]
+	fn test_rank() {
+		let control = |on: &str, run: &str, desc: &str, mode| Control {
+			on: vec![on.parse().unwrap()],
+			run: vec![Cmd::new(run)],
+			desc: Some(desc.to_owned()),
+			mode,
+			..Default::default()
+		};
+		let bindings = [
+			control("x", "tab_close", "Close the tab", Some(KeyMode::Preview)),
+			control("t", "tab_create", "Create a tab", None),
+			control("T", "tasks_show", "Show the tasks", None),
+			control("w", "tab_swap", "Swap the tab", None),
+		];
+		let runs = |kw| Help::rank(&bindings, kw).into_iter().map(|c| c.run()).collect::<Vec<_>>();
+
+		// Unscoped ones come first, in their own order when there's no keyword
+		assert_eq!(runs(""), ["tab_create", "tasks_show", "tab_swap", "tab_close"]);
+
+		// Then the best matches within each group
+		assert_eq!(runs("tsw"), ["tab_swap", "tasks_show"]);
+		assert_eq!(runs("tc"), ["tab_create", "tab_close"]);
+		assert_eq!(runs("close"), ["tab_close"]);
+		assert!(runs("zzz").is_empty());
+	}
+}
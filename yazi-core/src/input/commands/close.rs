@@ -24,6 +24,12 @@ impl Input {
 
 		if let Some(cb) = self.callback.take() {
 			let value = self.snap_mut().value.clone();
+			if let Some(kind) = self.history.filter(|_| opt.submit && !self.deferred) {
+				if let Some(h) = self.histories.get_mut(kind) {
+					h.push(value.clone()).then(|| h.save(kind));
+				}
+			}
+
 			_ = cb.send(if opt.submit { Ok(value) } else { Err(InputError::Canceled(value)) });
 		}
 
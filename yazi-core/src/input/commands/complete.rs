@@ -13,6 +13,7 @@ const SEPARATOR: char = std::path::MAIN_SEPARATOR;
 pub struct Opt {
 	word:   String,
 	ticket: usize,
+	whole:  bool,
 }
 
 impl From<Cmd> for Opt {
@@ -20,6 +21,7 @@ impl From<Cmd> for Opt {
 		Self {
 			word:   c.take_first_str().unwrap_or_default(),
 			ticket: c.get("ticket").and_then(Data::as_usize).unwrap_or(0),
+			whole:  c.bool("whole"),
 		}
 	}
 }
@@ -32,7 +34,9 @@ impl Input {
 		}
 
 		let [before, after] = self.partition();
-		let new = if let Some((prefix, _)) = before.rsplit_once(SEPARATOR) {
+		let new = if opt.whole {
+			format!("{}{after}", opt.word)
+		} else if let Some((prefix, _)) = before.rsplit_once(SEPARATOR) {
 			format!("{prefix}/{}{after}", opt.word).replace(SEPARATOR, MAIN_SEPARATOR_STR)
 		} else {
 			format!("{}{after}", opt.word).replace(SEPARATOR, MAIN_SEPARATOR_STR)
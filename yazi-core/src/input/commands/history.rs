@@ -0,0 +1,27 @@
+use yazi_shared::{event::{Cmd, Data}, render};
+
+use crate::input::Input;
+
+pub struct Opt {
+	step: isize,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self { Self { step: c.first().and_then(Data::as_isize).unwrap_or(0) } }
+}
+
+impl Input {
+	pub fn history(&mut self, opt: impl Into<Opt>) {
+		let Some(kind) = self.history else { return };
+		let Some(history) = self.histories.get_mut(kind) else { return };
+
+		let Some(value) = history.step(opt.into().step, &self.snaps.current().value) else {
+			return;
+		};
+
+		let limit = self.limit();
+		self.snaps.reset(value, limit);
+		self.flush_value();
+		render!();
+	}
+}
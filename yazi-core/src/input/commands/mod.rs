@@ -5,11 +5,13 @@ mod complete;
 mod delete;
 mod escape;
 mod forward;
+mod history;
 mod insert;
 mod kill;
 mod move_;
 mod paste;
 mod redo;
+mod remember;
 mod show;
 mod type_;
 mod undo;
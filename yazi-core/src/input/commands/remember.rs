@@ -0,0 +1,29 @@
+use yazi_shared::event::Cmd;
+
+use crate::input::Input;
+
+pub struct Opt {
+	kind:  String,
+	value: String,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			kind:  c.take_str("kind").unwrap_or_default(),
+			value: c.take_first_str().unwrap_or_default(),
+		}
+	}
+}
+
+impl Input {
+	// Adds a value submitted to a `deferred` input to its history
+	pub fn remember(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		let Some((&kind, h)) = self.histories.iter_mut().find(|(&k, _)| k == opt.kind) else {
+			return;
+		};
+
+		h.push(opt.value).then(|| h.save(kind));
+	}
+}
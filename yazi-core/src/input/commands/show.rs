@@ -2,7 +2,7 @@ use tokio::sync::mpsc;
 use yazi_config::popup::InputCfg;
 use yazi_shared::{event::Cmd, render, InputError};
 
-use crate::input::Input;
+use crate::input::{Input, InputHistory};
 
 pub struct Opt {
 	cfg: InputCfg,
@@ -34,6 +34,13 @@ impl Input {
 		// Shell
 		self.highlight = opt.cfg.highlight;
 
+		// History
+		self.history = opt.cfg.history;
+		self.deferred = opt.cfg.deferred;
+		if let Some(kind) = self.history {
+			self.histories.entry(kind).or_insert_with(|| InputHistory::load(kind)).reset();
+		}
+
 		// Reset snaps
 		self.snaps.reset(opt.cfg.value, self.limit());
 
@@ -43,6 +50,10 @@ impl Input {
 			self.move_(0);
 		}
 
+		if opt.cfg.eager {
+			self.flush_value();
+		}
+
 		render!();
 	}
 }
@@ -0,0 +1,132 @@
+use std::{collections::VecDeque, io, path::Path};
+
+use yazi_boot::BOOT;
+use yazi_config::INPUT;
+use yazi_shared::fs::Saver;
+
+static SAVER: Saver = Saver::new();
+
+#[derive(Default)]
+pub(super) struct InputHistory {
+	items:  VecDeque<String>,
+	cap:    usize,
+	cursor: usize,
+	draft:  String,
+}
+
+impl InputHistory {
+	pub(super) fn new(items: impl IntoIterator<Item = String>, cap: usize) -> Self {
+		let mut me = Self { cap, ..Default::default() };
+		items.into_iter().for_each(|s| _ = me.push(s));
+		me.reset();
+		me
+	}
+
+	pub(super) fn load(kind: &str) -> Self {
+		let s = std::fs::read_to_string(BOOT.state_dir.join("history").join(kind)).unwrap_or_default();
+		Self::new(s.lines().map(ToOwned::to_owned), INPUT.history_size)
+	}
+
+	#[inline]
+	pub(super) fn reset(&mut self) {
+		self.cursor = self.items.len();
+		self.draft.clear();
+	}
+
+	// Consecutive duplicates are collapsed, and the oldest entries are dropped
+	// once the history grows beyond its cap.
+	pub(super) fn push(&mut self, value: String) -> bool {
+		if value.is_empty() || value.contains('\n') || self.cap == 0 {
+			return false;
+		}
+		if self.items.back() == Some(&value) {
+			return false;
+		}
+
+		self.items.push_back(value);
+		while self.items.len() > self.cap {
+			self.items.pop_front();
+		}
+
+		self.reset();
+		true
+	}
+
+	pub(super) fn step(&mut self, step: isize, current: &str) -> Option<String> {
+		let len = self.items.len();
+		let new = if step < 0 {
+			self.cursor.saturating_sub(step.unsigned_abs())
+		} else {
+			len.min(self.cursor + step as usize)
+		};
+
+		if new == self.cursor {
+			return None;
+		} else if self.cursor == len {
+			self.draft = current.to_owned();
+		}
+
+		self.cursor = new;
+		Some(self.items.get(new).unwrap_or(&self.draft).clone())
+	}
+
+	fn dump(&self) -> String {
+		let mut s = self.items.iter().fold(String::new(), |mut s, item| {
+			s.push_str(item);
+			s.push('\n');
+			s
+		});
+		s.shrink_to_fit();
+		s
+	}
+
+	#[inline]
+	pub(super) fn flush(&self, dir: &Path, kind: &str) -> io::Result<()> {
+		SAVER.flush(&dir.join("history").join(kind), &self.dump())
+	}
+
+	#[inline]
+	pub(super) fn save(&self, kind: &str) {
+		SAVER.save(BOOT.state_dir.join("history").join(kind), self.dump())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn items(h: &InputHistory) -> Vec<&str> { h.items.iter().map(String::as_str).collect() }
+
+	#[test]
+	fn test_dedup() {
+		let mut h = InputHistory::new(vec![], 10);
+
+		assert!(h.push("a".to_owned()));
+		assert!(!h.push("a".to_owned()));
+		assert!(h.push("b".to_owned()));
+		assert!(h.push("a".to_owned()));
+		assert!(!h.push(String::new()));
+		assert_eq!(items(&h), ["a", "b", "a"]);
+	}
+
+	#[test]
+	fn test_cap() {
+		let h = InputHistory::new(["1", "2", "3", "4", "5"].map(ToOwned::to_owned), 3);
+		assert_eq!(items(&h), ["3", "4", "5"]);
+
+		let h = InputHistory::new(["1", "2"].map(ToOwned::to_owned), 0);
+		assert!(items(&h).is_empty());
+	}
+
+	#[test]
+	fn test_step() {
+		let mut h = InputHistory::new(["a", "b"].map(ToOwned::to_owned), 10);
+
+		assert_eq!(h.step(1, "draft"), None);
+		assert_eq!(h.step(-1, "draft").as_deref(), Some("b"));
+		assert_eq!(h.step(-1, "b").as_deref(), Some("a"));
+		assert_eq!(h.step(-1, "a"), None);
+		assert_eq!(h.step(1, "a").as_deref(), Some("b"));
+		assert_eq!(h.step(1, "b").as_deref(), Some("draft"));
+	}
+}
@@ -1,4 +1,4 @@
-use std::ops::Range;
+use std::{collections::HashMap, io, ops::Range, path::Path};
 
 use tokio::sync::mpsc::UnboundedSender;
 use unicode_width::UnicodeWidthStr;
@@ -6,7 +6,7 @@ use yazi_config::{popup::Position, INPUT};
 use yazi_plugin::CLIPBOARD;
 use yazi_shared::{render, InputError};
 
-use super::{mode::InputMode, op::InputOp, InputSnap, InputSnaps};
+use super::{mode::InputMode, op::InputOp, InputHistory, InputSnap, InputSnaps};
 
 #[derive(Default)]
 pub struct Input {
@@ -24,9 +24,19 @@ pub struct Input {
 
 	// Shell
 	pub highlight: bool,
+
+	// History
+	pub(super) history:   Option<&'static str>,
+	pub(super) deferred:  bool,
+	pub(super) histories: HashMap<&'static str, InputHistory>,
 }
 
 impl Input {
+	// Saves the input histories into `dir` right away, before the process exits
+	pub fn flush_histories(&self, dir: &Path) -> io::Result<()> {
+		self.histories.iter().try_for_each(|(kind, h)| h.flush(dir, kind))
+	}
+
 	#[inline]
 	pub(super) fn limit(&self) -> usize {
 		self.position.offset.width.saturating_sub(INPUT.border()) as usize
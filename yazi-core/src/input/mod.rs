@@ -1,10 +1,12 @@
 mod commands;
+mod history;
 mod input;
 mod mode;
 mod op;
 mod snap;
 mod snaps;
 
+use history::*;
 pub use input::*;
 pub use mode::*;
 use op::*;
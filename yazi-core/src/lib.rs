@@ -20,4 +20,32 @@ pub mod which;
 pub fn init() {
 	manager::WATCHED.with(<_>::default);
 	manager::LINKED.with(<_>::default);
+	tab::Tab::init_peek();
+
+	let tags = yazi_fs::Tags::load(yazi_boot::BOOT.state_dir.join("tags"));
+	yazi_fs::TAGS.init(parking_lot::RwLock::new(tags));
+}
+
+pub fn serve() { tab::Tab::serve_peek(); }
+
+// Sets up the bits of config that tabs and tasks need, for tests on them
+#[cfg(test)]
+pub(crate) fn init_tests() {
+	static INIT: std::sync::Once = std::sync::Once::new();
+	INIT.call_once(|| {
+		yazi_shared::init();
+		yazi_boot::ARGS.with(<_>::default);
+		yazi_boot::BOOT.with(<_>::default);
+		yazi_dds::init();
+		yazi_proxy::init();
+		yazi_fs::TAGS.with(<_>::default);
+		tab::Tab::init_peek();
+
+		let toml = include_str!("../../yazi-config/preset/yazi.toml");
+		yazi_config::LAYOUT.with(<_>::default);
+		yazi_config::MANAGER.init(toml.parse().unwrap());
+		yazi_config::PREVIEW.init(toml.parse().unwrap());
+		yazi_config::TASKS.init(toml.parse().unwrap());
+		yazi_config::COMMANDS.with(<_>::default);
+	});
 }
@@ -0,0 +1,152 @@
+use std::{collections::VecDeque, io, path::Path, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use yazi_boot::BOOT;
+use yazi_config::{COMMANDS, MANAGER};
+use yazi_shared::{event::Cmd, fs::write_atomic, Layer};
+
+// Commands run too often to tell anything about what was done
+const NOISY: &[&str] = &["arrow", "seek", "escape", "log", "peek", "reveal"];
+
+#[derive(Default)]
+pub struct ActionLog {
+	entries: VecDeque<Action>,
+	cap:     usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Action {
+	pub time: SystemTime,
+	pub op:   bool,
+	pub text: String,
+}
+
+impl ActionLog {
+	pub(super) fn new(cap: usize) -> Self { Self { cap, ..Default::default() } }
+
+	pub(super) fn load() -> Self {
+		let mut me = Self::new(MANAGER.action_log_size);
+		if !MANAGER.action_log_persist {
+			return me;
+		}
+
+		let s = std::fs::read_to_string(BOOT.state_dir.join("log")).unwrap_or_default();
+		for line in s.lines() {
+			let mut it = line.splitn(3, '\t');
+			let (Some(secs), Some(kind), Some(text)) = (it.next(), it.next(), it.next()) else {
+				continue;
+			};
+			let Ok(secs) = secs.parse() else { continue };
+			me.push(Action {
+				time: UNIX_EPOCH + Duration::from_secs(secs),
+				op:   kind == "op",
+				text: text.to_owned(),
+			});
+		}
+		me
+	}
+
+	// Only the commands bound to keys in the manager and tasks are recorded, so
+	// what's typed into a prompt, like a password, never ends up in here
+	pub fn record_cmd(&mut self, cmd: &Cmd, layer: Layer) {
+		if matches!(layer, Layer::Manager | Layer::Tasks) && !NOISY.contains(&cmd.name.as_str()) {
+			self.push(Action { time: SystemTime::now(), op: false, text: cmd.to_string() });
+		}
+	}
+
+	// The commands emitted by others, such as the palette, the command line and
+	// plugins, are recorded by name only, since their arguments may have been
+	// typed into a prompt. The internal ones that aren't commands are left out.
+	pub fn record_call(&mut self, cmd: &Cmd, layer: Layer) {
+		if !matches!(layer, Layer::Manager | Layer::Tasks) || NOISY.contains(&cmd.name.as_str()) {
+			return;
+		}
+		if COMMANDS.contains(&cmd.name) {
+			self.push(Action { time: SystemTime::now(), op: false, text: cmd.name.clone() });
+		}
+	}
+
+	#[inline]
+	pub(super) fn record_op(&mut self, text: String) {
+		self.push(Action { time: SystemTime::now(), op: true, text });
+	}
+
+	fn push(&mut self, action: Action) {
+		if self.cap == 0 {
+			return;
+		}
+		if self.entries.len() >= self.cap {
+			self.entries.pop_front();
+		}
+		self.entries.push_back(action);
+	}
+
+	#[inline]
+	pub(super) fn clear(&mut self) { self.entries.clear(); }
+
+	#[inline]
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Action> { self.entries.iter() }
+
+	// Saves them into `dir` if they're to be kept, as the process is about to exit
+	pub fn flush(&self, dir: &Path) -> io::Result<()> {
+		if !MANAGER.action_log_persist {
+			return Ok(());
+		}
+
+		let s = self.entries.iter().fold(String::new(), |mut s, a| {
+			let secs = a.time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+			let text = a.text.replace(['\t', '\n'], " ");
+			s.push_str(&format!("{secs}\t{}\t{text}\n", if a.op { "op" } else { "cmd" }));
+			s
+		});
+		write_atomic(&dir.join("log"), &s)
+	}
+}
+
+impl Action {
+	pub fn ago(&self) -> String {
+		let secs = SystemTime::now().duration_since(self.time).map(|d| d.as_secs()).unwrap_or(0);
+		match secs {
+			0..=59 => format!("{secs}s ago"),
+			60..=3599 => format!("{}m ago", secs / 60),
+			3600..=86399 => format!("{}h ago", secs / 3600),
+			_ => format!("{}d ago", secs / 86400),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn texts(log: &ActionLog) -> Vec<(bool, &str)> {
+		log.iter().map(|a| (a.op, a.text.as_str())).collect()
+	}
+
+	#[test]
+	fn test_record() {
+		let mut log = ActionLog::new(3);
+		log.record_cmd(&Cmd::args("cd", &["/a"]), Layer::Manager);
+		log.record_cmd(&Cmd::new("arrow"), Layer::Manager);
+		log.record_cmd(&Cmd::args("insert", &["secret"]), Layer::Input);
+		log.record_op("Copy /a/x to /b/x".to_owned());
+		log.record_cmd(&Cmd::new("cancel"), Layer::Tasks);
+		assert_eq!(texts(&log), [(false, "cd /a"), (true, "Copy /a/x to /b/x"), (false, "cancel")]);
+
+		// The oldest ones make room for the new
+		log.record_op("Trash /a/y".to_owned());
+		assert_eq!(texts(&log), [(true, "Copy /a/x to /b/x"), (false, "cancel"), (true, "Trash /a/y")]);
+
+		log.clear();
+		assert!(texts(&log).is_empty());
+
+		// A command emitted from a prompt keeps what's typed out
+		crate::init_tests();
+		log.record_call(&Cmd::args("shell", &["echo hunter2"]), Layer::Manager);
+		log.record_call(&Cmd::args("update_files", &["/a"]), Layer::Manager);
+		log.record_call(&Cmd::args("peek", &["1"]), Layer::Manager);
+		assert_eq!(texts(&log), [(false, "shell")]);
+
+		log.clear();
+		assert!(ActionLog::new(0).iter().next().is_none());
+	}
+}
@@ -5,6 +5,7 @@ use scopeguard::defer;
 use tokio::{fs::{self, OpenOptions}, io::{stdin, AsyncReadExt, AsyncWriteExt}};
 use yazi_config::{OPEN, PREVIEW};
 use yazi_dds::Pubsub;
+use yazi_fs::Tags;
 use yazi_proxy::{AppProxy, TasksProxy, HIDER, WATCHER};
 use yazi_shared::{fs::{max_common_root, maybe_exists, paths_to_same_file, File, FilesOp, Url}, terminal_clear};
 
@@ -97,6 +98,7 @@ impl Manager {
 
 		if !succeeded.is_empty() {
 			Pubsub::pub_from_bulk(succeeded.iter().map(|(u, f)| (u, &f.url)).collect());
+			Tags::follow(succeeded.iter().map(|(u, f)| (u, &f.url)));
 			FilesOp::Upserting(cwd, succeeded).emit();
 		}
 		drop(permit);
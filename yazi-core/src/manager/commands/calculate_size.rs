@@ -0,0 +1,20 @@
+use std::collections::HashSet;
+
+use yazi_shared::{event::Cmd, render};
+
+use crate::{manager::Manager, tasks::Tasks};
+
+impl Manager {
+	pub fn calculate_size(&mut self, _: Cmd, tasks: &Tasks) {
+		let targets: HashSet<_> = self.selected_or_hovered(false).collect();
+		let files = &self.current().files;
+
+		tasks.prework_size(
+			files
+				.iter()
+				.filter(|f| f.is_dir() && targets.contains(&f.url) && !files.sizes.contains_key(&f.url))
+				.map(|f| &f.url),
+		);
+		render!();
+	}
+}
@@ -0,0 +1,55 @@
+use std::{str::FromStr, time::Duration};
+
+use tokio::pin;
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+use yazi_config::{popup::InputCfg, COMMANDS};
+use yazi_proxy::{AppProxy, CompletionProxy, InputProxy};
+use yazi_shared::{emit, event::{Cmd, Outcome}, Debounce, InputError, Layer};
+
+use crate::manager::Manager;
+
+pub struct Opt {
+	value: String,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { value: c.take_first_str().unwrap_or_default() } }
+}
+
+impl Manager {
+	pub fn command(&self, opt: impl Into<Opt>) -> Outcome {
+		let opt = opt.into() as Opt;
+		tokio::spawn(async move {
+			let rx = InputProxy::show(InputCfg::command().with_value(opt.value));
+
+			let rx = Debounce::new(UnboundedReceiverStream::new(rx), Duration::from_millis(50));
+			pin!(rx);
+
+			while let Some(result) = rx.next().await {
+				match result {
+					Ok(s) if s.trim().is_empty() => {
+						emit!(Call(Cmd::new("palette"), Layer::Manager));
+						break;
+					}
+					Ok(s) => {
+						match Cmd::from_str(&s) {
+							Ok(cmd) if COMMANDS.get(&cmd.name).is_some() => {
+								emit!(Call(cmd, Layer::Manager));
+							}
+							Ok(cmd) => {
+								AppProxy::notify_warn("Command", format!("Unknown command `{}`", cmd.name))
+							}
+							Err(e) => AppProxy::notify_warn("Command", e),
+						}
+						break;
+					}
+					Err(InputError::Completed(before, ticket)) => {
+						CompletionProxy::trigger_command(&before, ticket);
+					}
+					_ => break,
+				}
+			}
+		});
+		Outcome::Prompted
+	}
+}
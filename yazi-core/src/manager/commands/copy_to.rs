@@ -0,0 +1,154 @@
+use std::{collections::HashSet, path::MAIN_SEPARATOR, sync::Arc, time::Duration};
+
+use tokio::{fs, pin};
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+use yazi_config::{popup::InputCfg, TASKS};
+use yazi_proxy::{AppProxy, CompletionProxy, InputProxy};
+use yazi_shared::{emit, event::{Cmd, Data, Outcome}, fs::{expand_path, Url}, Debounce, InputError, Layer};
+
+use crate::{manager::Manager, tasks::Tasks};
+
+pub struct Opt {
+	dest:    Option<Url>,
+	targets: Option<Vec<Url>>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		let mut dest = c.take_first().and_then(Data::into_url);
+		if let Some(u) = dest.as_mut().filter(|u| u.is_regular()) {
+			u.set_path(expand_path(&u));
+		}
+		Self { dest, targets: c.take_any("targets") }
+	}
+}
+
+impl Manager {
+	#[inline]
+	pub fn copy_to(&mut self, opt: impl Into<Opt>, tasks: &Tasks) -> Outcome {
+		self.transfer_to(opt.into(), false, tasks)
+	}
+
+	#[inline]
+	pub fn move_to(&mut self, opt: impl Into<Opt>, tasks: &Tasks) -> Outcome {
+		self.transfer_to(opt.into(), true, tasks)
+	}
+
+	fn transfer_to(&mut self, opt: Opt, cut: bool, tasks: &Tasks) -> Outcome {
+		let targets = opt.targets.unwrap_or_else(|| self.selected_or_hovered(false).cloned().collect());
+		if targets.is_empty() {
+			return Outcome::Done;
+		}
+
+		let Some(dest) = opt.dest else {
+			return self.transfer_pick(targets, cut);
+		};
+
+		if cut {
+			Self::move_checked(targets, dest, false);
+		} else {
+			Self::transfer(tasks, &targets.iter().collect::<Vec<_>>(), &dest, false, false);
+		}
+		Outcome::Done
+	}
+
+	// Unless `force` is set, taken names get a suffix, the same as `paste`
+	pub(super) fn transfer(tasks: &Tasks, src: &[&Url], dest: &Url, cut: bool, force: bool) {
+		if cut {
+			tasks.file_cut(src, dest, force);
+		} else {
+			tasks.file_copy(src, dest, force, false, TASKS.preserve_timestamps, false);
+		}
+	}
+
+	fn transfer_pick(&self, targets: Vec<Url>, cut: bool) -> Outcome {
+		let cands = Arc::new(self.transfer_candidates());
+		tokio::spawn(async move {
+			let rx = InputProxy::show(InputCfg::copy_to(cut));
+			let rx = Debounce::new(UnboundedReceiverStream::new(rx), Duration::from_millis(50));
+			pin!(rx);
+
+			while let Some(result) = rx.next().await {
+				match result {
+					Ok(s) => return Self::transfer_go(&s, targets, cut).await,
+					Err(InputError::Completed(before, ticket)) => {
+						CompletionProxy::show(cands.clone(), "\0copy_to", &before, ticket, true);
+					}
+					_ => break,
+				}
+			}
+		});
+		Outcome::Prompted
+	}
+
+	// Relative paths are taken from the current directory, the same as `goto`, and
+	// a directory that doesn't exist is created if it ends with a separator
+	async fn transfer_go(s: &str, targets: Vec<Url>, cut: bool) {
+		if s.is_empty() {
+			return;
+		}
+
+		let title = if cut { "Move" } else { "Copy" };
+		let dest = Url::from(expand_path(s));
+		match fs::metadata(&dest).await {
+			Ok(m) if m.is_dir() => {}
+			Ok(_) => {
+				return AppProxy::notify_warn(title, format!("`{}` is not a directory", dest.display()));
+			}
+			Err(_) if s.ends_with(['/', MAIN_SEPARATOR]) => {
+				if let Err(e) = fs::create_dir_all(&dest).await {
+					return AppProxy::notify_error(title, format!("Cannot create `{}`: {e}", dest.display()));
+				}
+			}
+			Err(_) => {
+				return AppProxy::notify_warn(
+					title,
+					format!("`{}` doesn't exist, end it with `/` to create it", dest.display()),
+				);
+			}
+		}
+
+		let name = if cut { "move_to" } else { "copy_to" };
+		emit!(Call(Cmd::args(name, &[dest]).with_any("targets", targets), Layer::Manager));
+	}
+
+	// The directories of the recently opened files, the ones open in the other
+	// tabs, and the subdirectories of the current one
+	fn transfer_candidates(&self) -> Vec<String> {
+		let recents = self.recents.iter().filter_map(|u| u.parent_url());
+		let tabs = self.tabs.iter().map(|t| t.current.cwd.clone()).filter(|u| u.is_regular());
+		let subdirs = self.current().files.iter().filter(|f| f.is_dir()).map(|f| f.url());
+
+		let mut seen = HashSet::from([self.cwd().clone()]);
+		recents
+			.chain(tabs)
+			.chain(subdirs)
+			.filter(|u| seen.insert(u.clone()))
+			.map(|u| format!("{}{MAIN_SEPARATOR}", u.display()))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_transfer() {
+		crate::init_tests();
+		let tasks = Tasks::serve();
+		let (a, b, dest) = (Url::from("/src/a"), Url::from("/src/b"), Url::from("/dest"));
+
+		Manager::transfer(&tasks, &[&a], &dest, false, false);
+		Manager::transfer(&tasks, &[&b], &dest, true, false);
+		tasks.shutdown();
+
+		let ongoing = tasks.ongoing().lock();
+		let mut names: Vec<_> = ongoing.values().map(|t| t.name.as_str()).collect();
+		names.sort_unstable();
+		assert_eq!(names, [
+			format!("Copy {:?} to {:?}", a, dest.join("a")),
+			format!("Cut {:?} to {:?}", b, dest.join("b")),
+		]);
+	}
+}
@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 
-use anyhow::Result;
 use tokio::fs;
 use yazi_config::popup::{ConfirmCfg, InputCfg};
-use yazi_proxy::{ConfirmProxy, InputProxy, TabProxy, WATCHER};
-use yazi_shared::{event::Cmd, fs::{maybe_exists, ok_or_not_found, symlink_realpath, File, FilesOp, Url}};
+use yazi_fs::FsError;
+use yazi_proxy::{AppProxy, ConfirmProxy, InputProxy, TabProxy, WATCHER};
+use yazi_shared::{event::{Cmd, Outcome}, fs::{maybe_exists, ok_or_not_found, symlink_realpath, File, FilesOp, Url}};
 
 use crate::manager::Manager;
 
@@ -18,44 +18,52 @@ impl From<Cmd> for Opt {
 }
 
 impl Manager {
-	pub fn create(&self, opt: impl Into<Opt>) {
+	pub fn create(&self, opt: impl Into<Opt>) -> Outcome {
 		let opt = opt.into() as Opt;
 		let cwd = self.cwd().to_owned();
 		tokio::spawn(async move {
 			let mut result = InputProxy::show(InputCfg::create());
-			let Some(Ok(name)) = result.recv().await else {
-				return Ok(());
-			};
-			if name.is_empty() {
-				return Ok(());
+			if let Some(Ok(name)) = result.recv().await {
+				Self::create_named(cwd, &name, opt).await;
 			}
+		});
+		Outcome::Prompted
+	}
 
-			let new = cwd.join(&name);
-			if !opt.force
-				&& maybe_exists(&new).await
-				&& !ConfirmProxy::show(ConfirmCfg::overwrite(&new)).await
-			{
-				return Ok(());
-			}
+	// `name` is taken as it is, without expanding `~` or variables
+	async fn create_named(cwd: Url, name: &str, opt: Opt) {
+		if name.is_empty() {
+			return;
+		}
 
-			Self::create_do(new, opt.dir || name.ends_with('/') || name.ends_with('\\')).await
-		});
+		let new = cwd.join(name);
+		if !opt.force
+			&& maybe_exists(&new).await
+			&& !ConfirmProxy::show(ConfirmCfg::overwrite(&new)).await
+		{
+			return;
+		}
+
+		let dir = opt.dir || name.ends_with('/') || name.ends_with('\\');
+		if let Err(e) = Self::create_do(new, dir).await {
+			AppProxy::notify_error("Create", e.to_string());
+		}
 	}
 
-	async fn create_do(new: Url, dir: bool) -> Result<()> {
+	async fn create_do(new: Url, dir: bool) -> Result<(), FsError> {
 		let Some(parent) = new.parent_url() else { return Ok(()) };
 		let _permit = WATCHER.acquire().await.unwrap();
 
 		if dir {
-			fs::create_dir_all(&new).await?;
+			fs::create_dir_all(&new).await.map_err(|e| FsError::new(&new, e))?;
 		} else if let Ok(real) = symlink_realpath(&new).await {
-			ok_or_not_found(fs::remove_file(&new).await)?;
+			ok_or_not_found(fs::remove_file(&new).await).map_err(|e| FsError::new(&new, e))?;
 			FilesOp::Deleting(parent.clone(), vec![Url::from(real)]).emit();
-			fs::File::create(&new).await?;
+			fs::File::create(&new).await.map_err(|e| FsError::new(&new, e))?;
 		} else {
 			fs::create_dir_all(&parent).await.ok();
-			ok_or_not_found(fs::remove_file(&new).await)?;
-			fs::File::create(&new).await?;
+			ok_or_not_found(fs::remove_file(&new).await).map_err(|e| FsError::new(&new, e))?;
+			fs::File::create(&new).await.map_err(|e| FsError::new(&new, e))?;
 		}
 
 		if let Ok(f) = File::from(new.clone()).await {
@@ -65,3 +73,22 @@ impl Manager {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_create_named() {
+		crate::init_tests();
+
+		let tmp = tempfile::tempdir().unwrap();
+		let cwd = Url::from(tmp.path());
+		let opt = || Opt { dir: false, force: true };
+		Manager::create_named(cwd.clone(), "${HOME}/a", opt()).await;
+		Manager::create_named(cwd.clone(), "~/b$c/", opt()).await;
+
+		assert!(tmp.path().join("${HOME}/a").is_file());
+		assert!(tmp.path().join("~/b$c").is_dir());
+	}
+}
@@ -0,0 +1,23 @@
+use yazi_config::MANAGER;
+use yazi_shared::event::Cmd;
+
+use crate::{manager::Manager, tasks::Tasks};
+
+pub struct Opt {
+	suffix: Option<String>,
+	follow: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { suffix: c.take_str("suffix"), follow: c.bool("follow") } }
+}
+
+impl Manager {
+	pub fn duplicate(&mut self, opt: impl Into<Opt>, tasks: &Tasks) {
+		let opt = opt.into() as Opt;
+		let src = self.selected_or_hovered(false).cloned().collect();
+		let suffix = opt.suffix.unwrap_or_else(|| MANAGER.duplicate_suffix.clone());
+
+		tasks.file_duplicate(src, suffix, opt.follow);
+	}
+}
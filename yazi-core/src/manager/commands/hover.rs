@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use yazi_dds::Pubsub;
 use yazi_shared::{event::{Cmd, Data}, fs::Url, render};
 
-use crate::manager::Manager;
+use crate::manager::{Manager, Panes, Tabs};
 
 pub struct Opt {
 	url: Option<Url>,
@@ -38,19 +38,63 @@ impl Manager {
 		self.peek(false);
 
 		// Refresh watcher
-		let mut to_watch = HashSet::with_capacity(3 * self.tabs.len());
-		for tab in self.tabs.iter() {
-			to_watch.insert(&tab.current.cwd);
-			if let Some(ref p) = tab.parent {
-				to_watch.insert(&p.cwd);
-			}
-			if let Some(h) = tab.current.hovered().filter(|&h| h.is_dir()) {
-				to_watch.insert(&h.url);
-			}
-		}
-		self.watcher.watch(to_watch);
+		self.watcher.watch(watched(&self.tabs, &self.panes));
+		self.watcher.focus(self.cwd());
 
 		// Publish through DDS
 		Pubsub::pub_from_hover(self.active().idx, self.hovered().map(|h| &h.url));
 	}
 }
+
+// The cwd of each tab, along with its parent and hovered directory as long as
+// their panes are shown
+fn watched<'a>(tabs: &'a Tabs, panes: &Panes) -> HashSet<&'a Url> {
+	let mut to_watch = HashSet::with_capacity(3 * tabs.len());
+	for tab in tabs.iter() {
+		to_watch.insert(&tab.current.cwd);
+		if let Some(p) = tab.parent.as_ref().filter(|_| panes.show_parent()) {
+			to_watch.insert(&p.cwd);
+		}
+		if let Some(h) = tab.current.hovered().filter(|&h| h.is_dir() && panes.show_preview()) {
+			to_watch.insert(&h.url);
+		}
+	}
+	to_watch
+}
+
+#[cfg(test)]
+mod tests {
+	use yazi_fs::Folder;
+	use yazi_shared::fs::{Cha, ChaKind, File, FilesOp};
+
+	use super::*;
+	use crate::tab::Tab;
+
+	#[test]
+	fn test_watched() {
+		crate::init_tests();
+		let (parent, cwd) = (Url::from("/yazi-test/p"), Url::from("/yazi-test/p/c"));
+		let sub = cwd.join("sub");
+
+		let mut tab = Tab::default();
+		(tab.current, tab.parent) = (Folder::from(&cwd), Some(Folder::from(&parent)));
+		let dir = Cha::default().with_kind(ChaKind::DIR);
+		let files = vec![File { url: sub.clone(), cha: dir, ..Default::default() }];
+		tab.current.update(FilesOp::Full(cwd.clone(), files, dir));
+		let tabs = Tabs { cursor: 0, items: vec![tab] };
+
+		let mut panes = Panes::default();
+		let sorted = |panes: &Panes| {
+			let mut v: Vec<_> = watched(&tabs, panes).into_iter().cloned().collect();
+			v.sort_unstable();
+			v
+		};
+		assert_eq!(sorted(&panes), [parent.clone(), cwd.clone(), sub.clone()]);
+
+		// `[0, 4, 3]` hides the parent pane, and maximizing hides the preview as well
+		panes.cycle(1);
+		assert_eq!(sorted(&panes), [cwd.clone(), sub]);
+		panes.toggle_maximized();
+		assert_eq!(sorted(&panes), [cwd]);
+	}
+}
@@ -0,0 +1,32 @@
+use yazi_proxy::ManagerProxy;
+use yazi_shared::{event::{Cmd, Data}, render};
+
+use crate::manager::Manager;
+
+pub struct Opt {
+	step:     isize,
+	maximize: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self {
+		Self { step: c.first().and_then(Data::as_isize).unwrap_or(1), maximize: c.bool("maximize") }
+	}
+}
+
+impl Manager {
+	pub fn layout(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		if opt.maximize {
+			self.panes.toggle_maximized();
+		} else if !self.panes.cycle(opt.step) {
+			return;
+		}
+
+		// The preview area has changed, or is gone, so drop the current one
+		// and let `refresh` read the panes that have just become visible.
+		self.active_mut().preview.reset();
+		ManagerProxy::refresh();
+		render!();
+	}
+}
@@ -0,0 +1,34 @@
+use yazi_config::popup::SelectCfg;
+use yazi_proxy::{AppProxy, SelectProxy};
+use yazi_shared::event::Cmd;
+
+use crate::manager::Manager;
+
+pub struct Opt {
+	clear: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self { Self { clear: c.bool("clear") } }
+}
+
+impl Manager {
+	pub fn log(&mut self, opt: impl Into<Opt>) {
+		if (opt.into() as Opt).clear {
+			return self.log.clear();
+		}
+
+		// The latest ones come first
+		let items: Vec<_> = self
+			.log
+			.iter()
+			.rev()
+			.map(|a| format!("{:>8}  {}  {}", a.ago(), if a.op { "✓" } else { "›" }, a.text))
+			.collect();
+		if items.is_empty() {
+			return AppProxy::notify_warn("Log", "Nothing has been done yet");
+		}
+
+		tokio::spawn(SelectProxy::show(SelectCfg::log(items)));
+	}
+}
@@ -1,16 +1,26 @@
 mod bulk_rename;
+mod calculate_size;
 mod close;
+mod command;
+mod copy_to;
 mod create;
+mod duplicate;
 mod hardlink;
 mod hover;
+mod layout;
 mod link;
+mod log;
+mod move_do;
 mod open;
+mod palette;
 mod paste;
 mod peek;
 mod quit;
+mod recents;
 mod refresh;
 mod remove;
 mod rename;
+mod restore;
 mod seek;
 mod suspend;
 mod tab_close;
@@ -18,9 +28,12 @@ mod tab_create;
 mod tab_swap;
 mod tab_switch;
 mod unyank;
+mod update_disk;
 mod update_files;
+mod update_log;
 mod update_mimetype;
 mod update_paged;
+mod update_recents;
 mod update_task;
 mod update_yanked;
 mod yank;
@@ -0,0 +1,50 @@
+use yazi_proxy::{AppProxy, ManagerProxy};
+use yazi_shared::{event::Cmd, fs::Url};
+
+use crate::{manager::{Manager, Protected}, tasks::Tasks};
+
+pub struct Opt {
+	targets: Vec<Url>,
+	dest:    Url,
+	force:   bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			targets: c.take_any("targets").unwrap_or_default(),
+			dest:    c.take_any("dest").unwrap_or_default(),
+			force:   c.bool("force"),
+		}
+	}
+}
+
+impl Manager {
+	// Moves `targets` into `dest` once they're known not to be protected, which
+	// takes resolving their paths, so it's done off the main thread
+	pub(super) fn move_checked(targets: Vec<Url>, dest: Url, force: bool) {
+		tokio::spawn(async move {
+			match Protected::find(&dest, &targets).await {
+				Some(u) => AppProxy::notify_warn("Move", format!("`{}` is protected", u.display())),
+				None => ManagerProxy::move_do(targets, dest, force),
+			}
+		});
+	}
+
+	pub fn move_do(&mut self, opt: impl Into<Opt>, tasks: &Tasks) {
+		let opt = opt.into() as Opt;
+		if opt.targets.is_empty() {
+			return;
+		}
+
+		let src: Vec<_> = opt.targets.iter().collect();
+		self.tabs.iter_mut().for_each(|t| _ = t.selected.remove_many(&src, false));
+
+		for u in &opt.targets {
+			self.yanked.remove(u);
+		}
+		self.yanked.catchup_revision(false);
+
+		Self::transfer(tasks, &src, &opt.dest, true, opt.force);
+	}
+}
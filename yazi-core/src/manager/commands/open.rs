@@ -1,24 +1,48 @@
-use std::{borrow::Cow, ffi::OsString};
+use std::{borrow::Cow, collections::BTreeMap, ffi::OsString, str::FromStr};
 
-use tracing::error;
+use parking_lot::{const_mutex, Mutex};
+use tokio::sync::mpsc;
 use yazi_boot::ARGS;
-use yazi_config::{popup::SelectCfg, OPEN};
+use yazi_config::{keymap::{Control, Key}, open::Opener, popup::SelectCfg, OPEN};
 use yazi_fs::Folder;
-use yazi_plugin::isolate;
-use yazi_proxy::{options::OpenDoOpt, ManagerProxy, TasksProxy};
-use yazi_shared::{emit, event::{Cmd, EventQuit}, fs::{File, Url}, MIME_DIR};
+use yazi_plugin::{pick_opener, LUA};
+use yazi_proxy::{options::OpenDoOpt, AppProxy, ManagerProxy, SelectProxy, TasksProxy};
+use yazi_shared::{emit, event::{Cmd, Data, EventQuit}, fs::{File, Url}, Layer, MIME_DIR};
 
 use crate::{manager::Manager, tasks::Tasks};
 
+// Keys of the openers in the `which` menu, in order
+const WHICH_KEYS: &str = "123456789abcdefghijklmnopqrstuvwxyz";
+
+// The opener last picked from the `which` menu for each mimetype
+static PICKED: Mutex<BTreeMap<String, &'static Opener>> = const_mutex(BTreeMap::new());
+
 #[derive(Clone, Copy)]
 pub struct Opt {
 	interactive: bool,
+	which:       bool,
 	hovered:     bool,
+	line:        Option<usize>,
+	column:      Option<usize>,
+	spread:      Option<bool>,
 }
 
 impl From<Cmd> for Opt {
 	fn from(c: Cmd) -> Self {
-		Self { interactive: c.bool("interactive"), hovered: c.bool("hovered") }
+		Self {
+			interactive: c.bool("interactive") || c.bool("which"),
+			which:       c.bool("which"),
+			hovered:     c.bool("hovered"),
+			line:        c.get("line").and_then(Data::as_usize),
+			column:      c.get("column").and_then(Data::as_usize),
+			spread:      if c.bool("spread") {
+				Some(true)
+			} else if c.bool("separate") {
+				Some(false)
+			} else {
+				None
+			},
+		}
 	}
 }
 
@@ -51,24 +75,36 @@ impl Manager {
 		}
 
 		if todo.is_empty() {
-			return self
-				.open_do(OpenDoOpt { hovered, targets: done, interactive: opt.interactive }, tasks);
+			return self.open_do(
+				OpenDoOpt {
+					hovered,
+					targets: done,
+					interactive: opt.interactive,
+					which: opt.which,
+					line: opt.line,
+					column: opt.column,
+					spread: opt.spread,
+				},
+				tasks,
+			);
 		}
 
+		// Opens with whatever has been detected if the detection is cancelled
+		let rx = tasks.fetch_mimetype(todo);
 		tokio::spawn(async move {
-			let mut files = Vec::with_capacity(todo.len());
-			for u in todo {
-				if let Ok(f) = File::from(u).await {
-					files.push(f);
-				}
+			if let Ok(files) = rx.await {
+				done.extend(files.into_iter().map(|f| (f.url, String::new())));
 			}
 
-			done.extend(files.iter().map(|f| (f.url(), String::new())));
-			if let Err(e) = isolate::fetch("mime", files).await {
-				error!("Fetch `mime` failed in opening: {e}");
-			}
-
-			ManagerProxy::open_do(OpenDoOpt { hovered, targets: done, interactive: opt.interactive });
+			ManagerProxy::open_do(OpenDoOpt {
+				hovered,
+				targets: done,
+				interactive: opt.interactive,
+				which: opt.which,
+				line: opt.line,
+				column: opt.column,
+				spread: opt.spread,
+			});
 		});
 	}
 
@@ -85,7 +121,9 @@ impl Manager {
 		if targets.is_empty() {
 			return;
 		} else if !opt.interactive {
-			return tasks.process_from_files(opt.hovered, targets);
+			let targets =
+				targets.into_iter().filter_map(|(u, m)| self.opener_of(&u, &m).map(|o| (u, o))).collect();
+			return tasks.process_from_files(opt.hovered, targets, (opt.line, opt.column), opt.spread);
 		}
 
 		let openers: Vec<_> = OPEN.common_openers(&targets);
@@ -93,18 +131,65 @@ impl Manager {
 			return;
 		}
 
+		let (line, column, spread, which) = (opt.line, opt.column, opt.spread, opt.which);
+		let mime = targets[0].1.clone();
 		let urls = [opt.hovered].into_iter().chain(targets.into_iter().map(|(u, _)| u)).collect();
 		tokio::spawn(async move {
-			let result = yazi_proxy::SelectProxy::show(SelectCfg::open(
-				openers.iter().map(|o| o.desc.clone()).collect(),
-			));
-			if let Ok(choice) = result.await {
-				TasksProxy::open_with(urls, Cow::Borrowed(openers[choice]));
+			let choice = if which {
+				Self::open_which(&openers, &mime).await
+			} else {
+				SelectProxy::show(SelectCfg::open(openers.iter().map(|o| o.desc.clone()).collect()))
+					.await
+					.ok()
+			};
+
+			if let Some(choice) = choice {
+				if which && OPEN.remember {
+					PICKED.lock().insert(mime, openers[choice]);
+				}
+				let opener = Opener::with_position(Cow::Borrowed(openers[choice]), line, column);
+				let opener = Opener::with_spread(opener, spread);
+				TasksProxy::open_with_recents(urls, opener);
 			}
 		});
 	}
 
-	fn guess_folder(&self, url: &Url) -> bool {
+	// Picks one of `openers` by a single key, or none if cancelled
+	async fn open_which(openers: &[&'static Opener], mime: &str) -> Option<usize> {
+		let last = if OPEN.remember { PICKED.lock().get(mime).copied() } else { None };
+		let (tx, mut rx) = mpsc::channel(1);
+		let cands = which_cands(openers, last, &tx);
+
+		drop(tx);
+		emit!(Call(
+			Cmd::new("show").with("layer", Layer::Which).with_any("candidates", cands),
+			Layer::Which
+		));
+		rx.recv().await
+	}
+
+	// `Opener:pick()` is asked first, then the `[open]` rules are matched
+	fn opener_of(&self, url: &Url, mime: &str) -> Option<&'static Opener> {
+		if let Some(name) = self.file_of(url).and_then(|f| pick_opener(&LUA, f, mime)) {
+			if let Some(opener) = OPEN.named(&name).and_then(|o| o.first()) {
+				return Some(opener);
+			}
+			let s = format!("`Opener:pick()` gave `{name}` for `{}`, not an opener", url.display());
+			AppProxy::notify_warn("Open", s);
+		}
+		OPEN.openers(url, mime).and_then(|o| o.first().copied())
+	}
+
+	fn file_of(&self, url: &Url) -> Option<&File> {
+		let p = url.parent_url()?;
+		[Some(self.current()), self.parent(), self.hovered_folder(), self.active().history.get(&p)]
+			.into_iter()
+			.flatten()
+			.filter(|folder| folder.cwd == p)
+			.find_map(|folder| folder.files.iter().find(|f| f.url == *url))
+	}
+
+	pub(super) fn guess_folder(&self, url: &Url) -> bool {
 		let Some(p) = url.parent_url() else {
 			return true;
 		};
@@ -136,3 +221,70 @@ impl Manager {
 		true
 	}
 }
+
+// The candidates of the `which` menu for `openers`, with the `last` one first
+fn which_cands(
+	openers: &[&Opener],
+	last: Option<&Opener>,
+	tx: &mpsc::Sender<usize>,
+) -> Vec<Control> {
+	let mut order: Vec<_> = (0..openers.len()).collect();
+	if let Some(i) = last.and_then(|l| openers.iter().position(|&o| o == l)) {
+		order.remove(i);
+		order.insert(0, i);
+	}
+
+	order
+		.into_iter()
+		.zip(WHICH_KEYS.chars())
+		.map(|(i, key)| Control {
+			on: vec![Key::from_str(&key.to_string()).unwrap()],
+			run: vec![Cmd::args("callback", &[i]).with_any("tx", tx.clone())],
+			desc: Some(openers[i].desc.clone()),
+			..Default::default()
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use yazi_config::open::Open;
+
+	use super::*;
+
+	#[test]
+	fn test_which_cands() {
+		let open = Open::from_str(
+			r#"
+			[opener]
+			edit = [ { run = "vim", desc = "Edit" } ]
+			play = [ { run = "mpv", desc = "Play" } ]
+			show = [ { run = "feh", desc = "Show" } ]
+
+			[open]
+			rules = [
+				{ name = "*.gif", use = [ "show", "play", "edit" ] },
+				{ mime = "video/*", use = [ "play", "edit" ] },
+			]
+			"#,
+		)
+		.unwrap();
+
+		let openers = open.common_openers(&[("a.gif", "image/gif"), ("b.mp4", "video/mp4")]);
+		assert_eq!(openers.iter().map(|o| &o.desc).collect::<Vec<_>>(), ["Play", "Edit"]);
+
+		let (tx, _rx) = mpsc::channel(1);
+		let menu = |last: Option<&Opener>| {
+			which_cands(&openers, last, &tx)
+				.into_iter()
+				.map(|c| {
+					let idx = c.run[0].first().and_then(Data::as_usize).unwrap();
+					format!("{} {} {idx}", c.on[0], c.desc.unwrap())
+				})
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(menu(None), ["1 Play 0", "2 Edit 1"]);
+		assert_eq!(menu(Some(openers[1])), ["1 Edit 1", "2 Play 0"]);
+	}
+}
@@ -0,0 +1,124 @@
+use std::{cmp::Reverse, sync::Arc, time::Duration};
+
+use tokio::pin;
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+use yazi_config::{command::Command, keymap::Control, popup::InputCfg, COMMANDS, KEYMAP};
+use yazi_proxy::{CompletionProxy, InputProxy};
+use yazi_shared::{emit, event::{Cmd, Outcome}, Debounce, InputError, Layer};
+
+use crate::manager::Manager;
+
+impl Manager {
+	pub fn palette(&self, _: Cmd) -> Outcome {
+		tokio::spawn(async move {
+			let commands = COMMANDS.all();
+			let labels = Self::palette_labels(&commands, &KEYMAP.manager);
+
+			let rx = InputProxy::show(InputCfg::palette());
+			let rx = Debounce::new(UnboundedReceiverStream::new(rx), Duration::from_millis(50));
+			pin!(rx);
+
+			while let Some(result) = rx.next().await {
+				match result {
+					Ok(s) => {
+						let name = s.split_whitespace().next().unwrap_or_default();
+						if let Some(c) = commands.iter().find(|c| c.name == name) {
+							Self::palette_run(c);
+						}
+						break;
+					}
+					// Already ranked, so they're shown as they are with an empty word
+					Err(InputError::Completed(before, ticket)) => {
+						let cands = Arc::new(Self::palette_rank(&commands, &labels, &before));
+						CompletionProxy::show(cands, "\0palette", "", ticket, true);
+					}
+					_ => break,
+				}
+			}
+		});
+		Outcome::Prompted
+	}
+
+	// Commands that take arguments go to the command prompt to fill them in
+	fn palette_run(c: &Command) {
+		if c.flags.is_empty() && !c.args {
+			emit!(Call(Cmd::new(&c.name), Layer::Manager));
+		} else {
+			emit!(Call(Cmd::args("command", &[format!("{} ", c.name)]), Layer::Manager));
+		}
+	}
+
+	// The labels of the commands that fuzzy match the keyword, the best first
+	fn palette_rank(commands: &[Command], labels: &[String], kw: &str) -> Vec<String> {
+		let mut scored: Vec<_> = commands
+			.iter()
+			.zip(labels)
+			.filter_map(|(c, l)| c.fuzzy_score(kw).map(|s| (s, l)))
+			.collect();
+		scored.sort_by_key(|&(s, _)| Reverse(s));
+		scored.into_iter().map(|(_, l)| l.clone()).collect()
+	}
+
+	// Each label has the name of a command, its description, and its keys
+	fn palette_labels(commands: &[Command], keymap: &[Control]) -> Vec<String> {
+		let width = commands.iter().map(|c| c.name.len()).max().unwrap_or(0);
+		commands
+			.iter()
+			.map(|c| {
+				let keys: Vec<_> = keymap
+					.iter()
+					.filter(|ctrl| ctrl.run.first().is_some_and(|r| r.name == c.name))
+					.map(|ctrl| ctrl.on())
+					.take(3)
+					.collect();
+
+				let mut label = format!("{:width$}  {}", c.name, c.desc);
+				if !keys.is_empty() {
+					label.push_str(&format!("  ({})", keys.join(", ")));
+				}
+				label
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+
+	use yazi_config::keymap::Key;
+
+	use super::*;
+
+	#[test]
+	fn test_palette_labels() {
+		let commands =
+			[Command::new("cd", "Change the current directory"), Command::new("quit", "Exit")];
+		let keymap = [("g", "cd ~"), ("q", "quit"), ("Q", "quit --no-cwd-file"), ("w", "tasks_show")]
+			.map(|(on, run)| Control {
+				on: vec![Key::from_str(on).unwrap()],
+				run: vec![Cmd::from_str(run).unwrap()],
+				..Default::default()
+			});
+
+		assert_eq!(Manager::palette_labels(&commands, &keymap), [
+			"cd    Change the current directory  (g)",
+			"quit  Exit  (q, Q)",
+		]);
+	}
+
+	#[test]
+	fn test_palette_rank() {
+		let commands = [
+			Command::new("tab_close", "Close a tab"),
+			Command::new("tab_create", "Create a new tab"),
+			Command::new("quit", "Exit the process"),
+		];
+		let labels = Manager::palette_labels(&commands, &[]);
+		let rank = |kw| Manager::palette_rank(&commands, &labels, kw);
+
+		assert_eq!(rank("tbcr")[0], labels[1]);
+		assert_eq!(rank("exit"), [labels[2].clone()]);
+		assert!(rank("zzz").is_empty());
+	}
+}
@@ -1,28 +1,156 @@
-use yazi_shared::event::Cmd;
+use std::{collections::HashMap, time::{SystemTime, UNIX_EPOCH}};
+
+use tokio::fs;
+use yazi_config::{popup::InputCfg, TASKS};
+use yazi_fs::FsError;
+use yazi_plugin::CLIPBOARD;
+use yazi_proxy::{AppProxy, InputProxy, TabProxy, WATCHER};
+use yazi_shared::{event::Cmd, fs::{unique_name, File, FilesOp, Url}};
 
 use crate::{manager::Manager, tasks::Tasks};
 
 pub struct Opt {
-	force:  bool,
-	follow: bool,
+	force:          bool,
+	follow:         bool,
+	times:          bool,
+	archive:        bool,
+	from_clipboard: bool,
+	into_hovered:   bool,
 }
 
 impl From<Cmd> for Opt {
-	fn from(c: Cmd) -> Self { Self { force: c.bool("force"), follow: c.bool("follow") } }
+	fn from(c: Cmd) -> Self {
+		Self {
+			force:          c.bool("force"),
+			// Archiving copies symlinks as they are, and keeps the timestamps
+			follow:         c.bool("follow") && !c.bool("archive"),
+			times:          match (c.bool("preserve-timestamps"), c.bool("no-preserve")) {
+				(true, _) => true,
+				(_, true) => false,
+				_ => TASKS.preserve_timestamps,
+			} || c.bool("archive"),
+			archive:        c.bool("archive"),
+			from_clipboard: c.bool("from-clipboard"),
+			into_hovered:   c.bool("into-hovered"),
+		}
+	}
 }
 
 impl Manager {
 	pub fn paste(&mut self, opt: impl Into<Opt>, tasks: &Tasks) {
 		let opt = opt.into() as Opt;
-		let (src, dest) = (self.yanked.iter().collect::<Vec<_>>(), self.cwd());
+		let dest = paste_dest(self.cwd(), self.hovered(), opt.into_hovered);
+		if opt.from_clipboard {
+			return Self::paste_clipboard(dest.clone(), opt.force);
+		}
 
 		if self.yanked.cut {
-			tasks.file_cut(&src, dest, opt.force);
-
-			self.tabs.iter_mut().for_each(|t| _ = t.selected.remove_many(&src, false));
-			self.unyank(());
+			Self::move_checked(self.yanked.iter().cloned().collect(), dest.clone(), opt.force);
 		} else {
-			tasks.file_copy(&src, dest, opt.force, opt.follow);
+			let src = self.yanked.iter().collect::<Vec<_>>();
+			tasks.file_copy(&src, dest, opt.force, opt.follow, opt.times, opt.archive);
 		}
 	}
+
+	// Saves the image on the system clipboard, or its text if it holds no image,
+	// as a new file in `cwd`. Unless `force` is set, a taken name gets a suffix.
+	fn paste_clipboard(cwd: Url, force: bool) {
+		tokio::spawn(async move {
+			let (data, ext) = match CLIPBOARD.get_image().await {
+				Some(image) => image,
+				None => (CLIPBOARD.get().await.into_encoded_bytes(), "txt"),
+			};
+			if data.is_empty() {
+				return AppProxy::notify_warn("Paste", "Nothing to paste from the clipboard");
+			}
+
+			let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+			let stem = format!("clipboard_{}", timestamp(secs));
+			let cfg = InputCfg::create().with_value(format!("{stem}.{ext}"));
+
+			let mut result = InputProxy::show(cfg.with_cursor(Some(stem.len())));
+			let Some(Ok(name)) = result.recv().await else {
+				return;
+			};
+			if name.is_empty() {
+				return;
+			}
+
+			let mut new = cwd.join(&name);
+			if !force {
+				new = unique_name(new).await;
+			}
+			if let Err(e) = Self::paste_write(new, data).await {
+				AppProxy::notify_error("Paste", e.to_string());
+			}
+		});
+	}
+
+	async fn paste_write(new: Url, data: Vec<u8>) -> Result<(), FsError> {
+		let Some(parent) = new.parent_url() else { return Ok(()) };
+		let _permit = WATCHER.acquire().await.unwrap();
+
+		fs::write(&new, data).await.map_err(|e| FsError::new(&new, e))?;
+		if let Ok(f) = File::from(new.clone()).await {
+			FilesOp::Upserting(parent, HashMap::from_iter([(f.url(), f)])).emit();
+			TabProxy::reveal(&new)
+		}
+		Ok(())
+	}
+}
+
+// The hovered directory with `--into-hovered`, or `cwd` otherwise, or if a
+// file is hovered
+fn paste_dest<'a>(cwd: &'a Url, hovered: Option<&'a File>, into_hovered: bool) -> &'a Url {
+	match hovered {
+		Some(f) if into_hovered && f.is_dir() => &f.url,
+		_ => cwd,
+	}
+}
+
+// Formats seconds since the epoch as `YYYYMMDD_HHMMSS` in UTC
+fn timestamp(secs: u64) -> String {
+	let (days, rest) = ((secs / 86400) as i64, secs % 86400);
+
+	// Howard Hinnant's `civil_from_days`
+	let z = days + 719468;
+	let era = z.div_euclid(146097);
+	let doe = z.rem_euclid(146097);
+	let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let d = doy - (153 * mp + 2) / 5 + 1;
+	let m = if mp < 10 { mp + 3 } else { mp - 9 };
+	let y = yoe + era * 400 + (m <= 2) as i64;
+
+	format!("{y:04}{m:02}{d:02}_{:02}{:02}{:02}", rest / 3600, rest / 60 % 60, rest % 60)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_timestamp() {
+		assert_eq!(timestamp(0), "19700101_000000");
+		assert_eq!(timestamp(951782400), "20000229_000000");
+		assert_eq!(timestamp(1791979384), "20261014_120304");
+	}
+
+	#[tokio::test]
+	async fn test_paste_dest() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		std::fs::create_dir_all(dir.join("sub")).unwrap();
+		std::fs::write(dir.join("file"), "").unwrap();
+
+		let cwd = Url::from(dir);
+		let sub = File::from(cwd.join("sub")).await.unwrap();
+		let file = File::from(cwd.join("file")).await.unwrap();
+
+		assert_eq!(paste_dest(&cwd, Some(&sub), true), &sub.url);
+		assert_eq!(paste_dest(&cwd, Some(&sub), false), &cwd);
+		assert_eq!(paste_dest(&cwd, Some(&file), true), &cwd);
+		assert_eq!(paste_dest(&cwd, None, true), &cwd);
+	}
 }
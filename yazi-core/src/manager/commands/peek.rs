@@ -1,4 +1,6 @@
-use yazi_shared::{event::{Cmd, Data}, fs::Url, render};
+use tracing::error;
+use yazi_plugin::isolate;
+use yazi_shared::{event::{Cmd, Data}, fs::{File, Url}, render};
 
 use crate::manager::Manager;
 
@@ -8,6 +10,8 @@ pub struct Opt {
 	force:       bool,
 	only_if:     Option<Url>,
 	upper_bound: bool,
+	rule:        Option<usize>,
+	eager:       bool,
 }
 
 impl From<Cmd> for Opt {
@@ -17,6 +21,8 @@ impl From<Cmd> for Opt {
 			force:       c.bool("force"),
 			only_if:     c.take("only-if").and_then(Data::into_url),
 			upper_bound: c.bool("upper-bound"),
+			rule:        c.get("rule").and_then(Data::as_usize),
+			eager:       c.bool("eager"),
 		}
 	}
 }
@@ -26,18 +32,20 @@ impl From<bool> for Opt {
 
 impl Manager {
 	pub fn peek(&mut self, opt: impl Into<Opt>) {
-		let Some(hovered) = self.hovered().cloned() else {
+		let Some(hovered) = self.hovered().cloned().filter(|_| self.panes.show_preview()) else {
 			return render!(self.active_mut().preview.reset());
 		};
 
 		let folder = self.active().hovered_folder().map(|f| (f.offset, f.cha));
 		if !self.active().preview.same_url(&hovered.url) {
 			self.active_mut().preview.skip = folder.map(|f| f.0).unwrap_or_default();
+			self.active_mut().preview.rule = 0;
 			render!(self.active_mut().preview.reset());
 		}
 
 		let opt = opt.into() as Opt;
-		if matches!(opt.only_if, Some(ref u) if *u != hovered.url) {
+		let readme = self.active().preview.readme.as_ref();
+		if matches!(opt.only_if, Some(ref u) if *u != hovered.url && Some(u) != readme) {
 			return;
 		}
 
@@ -50,8 +58,16 @@ impl Manager {
 			}
 		}
 
+		if let Some(rule) = opt.rule {
+			self.active_mut().preview.rule = rule;
+		}
+
+		let stats = self.active().conf.text_stats;
+		self.active_mut().preview.stats = stats;
+
 		if hovered.is_dir() {
-			self.active_mut().preview.go_folder(hovered, folder.map(|f| f.1), opt.force);
+			let view = self.active().conf.folder_view();
+			self.active_mut().preview.go_folder(hovered, folder.map(|f| f.1), opt.force, view);
 			return;
 		}
 
@@ -59,6 +75,18 @@ impl Manager {
 		if !mime.is_empty() {
 			// Wait till mimetype is resolved to avoid flickering
 			self.active_mut().preview.go(hovered, &mime, opt.force);
+		} else if opt.eager {
+			Self::peek_mime(hovered);
 		}
 	}
+
+	// Detects the mimetype of the hovered file alone, rather than along with the
+	// rest of the page, so that it's previewed as soon as possible
+	fn peek_mime(hovered: File) {
+		tokio::spawn(async move {
+			if let Err(e) = isolate::fetch("mime", vec![hovered]).await {
+				error!("Fetch `mime` failed in peeking: {e}");
+			}
+		});
+	}
 }
@@ -10,22 +10,24 @@ use crate::{manager::Manager, tasks::Tasks};
 #[derive(Default)]
 pub struct Opt {
 	no_cwd_file: bool,
+	force:       bool,
 }
 impl From<()> for Opt {
 	fn from(_: ()) -> Self { Self::default() }
 }
 impl From<Cmd> for Opt {
-	fn from(c: Cmd) -> Self { Self { no_cwd_file: c.bool("no-cwd-file") } }
+	fn from(c: Cmd) -> Self { Self { no_cwd_file: c.bool("no-cwd-file"), force: c.bool("force") } }
 }
 
 impl Manager {
 	pub fn quit(&self, opt: impl Into<Opt>, tasks: &Tasks) {
-		let opt = EventQuit { no_cwd_file: opt.into().no_cwd_file, ..Default::default() };
+		let opt = opt.into() as Opt;
+		let opt = EventQuit { no_cwd_file: opt.no_cwd_file, force: opt.force, ..Default::default() };
 
 		let ongoing = tasks.ongoing().clone();
 		let left: Vec<String> = ongoing.lock().values().take(11).map(|t| t.name.clone()).collect();
 
-		if left.is_empty() {
+		if left.is_empty() || opt.force {
 			emit!(Quit(opt));
 			return;
 		}
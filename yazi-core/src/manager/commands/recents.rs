@@ -0,0 +1,43 @@
+use yazi_config::popup::SelectCfg;
+use yazi_proxy::{AppProxy, ManagerProxy, SelectProxy, TabProxy};
+use yazi_shared::event::Cmd;
+
+use crate::manager::Manager;
+
+pub struct Opt {
+	open:  bool,
+	clear: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self { Self { open: c.bool("open"), clear: c.bool("clear") } }
+}
+
+impl Manager {
+	pub fn recents(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		if opt.clear {
+			if self.recents.clear() {
+				self.recents.save();
+			}
+			return;
+		}
+
+		let urls: Vec<_> = self.recents.iter().cloned().collect();
+		if urls.is_empty() {
+			return AppProxy::notify_warn("Recents", "No recently opened files");
+		}
+
+		tokio::spawn(async move {
+			let items = urls.iter().map(ToString::to_string).collect();
+			let Ok(choice) = SelectProxy::show(SelectCfg::recents(items)).await else {
+				return;
+			};
+
+			TabProxy::reveal(&urls[choice]);
+			if opt.open {
+				ManagerProxy::open_hovered(false);
+			}
+		});
+	}
+}
@@ -2,9 +2,10 @@ use std::{env, path::MAIN_SEPARATOR};
 
 use crossterm::{execute, terminal::SetTitle};
 use yazi_config::MANAGER;
+use yazi_fs::Folder;
 use yazi_shared::event::Cmd;
 
-use crate::{manager::Manager, tasks::Tasks};
+use crate::{manager::{Manager, Panes}, tab::Tab, tasks::Tasks};
 
 impl Manager {
 	pub fn refresh(&mut self, _: Cmd, tasks: &Tasks) {
@@ -16,12 +17,9 @@ impl Manager {
 		}
 
 		self.active_mut().apply_files_attrs();
+		self.disk.watch(self.cwd());
 
-		if let Some(p) = self.parent() {
-			self.watcher.trigger_dirs(&[self.current(), p]);
-		} else {
-			self.watcher.trigger_dirs(&[self.current()]);
-		}
+		self.watcher.trigger_dirs(&triggered(self.active(), &self.panes));
 
 		self.hover(None);
 		self.update_paged((), tasks);
@@ -40,3 +38,39 @@ impl Manager {
 		MANAGER.title_format.replace("{cwd}", &cwd)
 	}
 }
+
+// The folders of `tab` to read, where a hidden parent pane is left out
+fn triggered<'a>(tab: &'a Tab, panes: &Panes) -> Vec<&'a Folder> {
+	let parent = tab.parent.as_ref().filter(|_| panes.show_parent());
+	[Some(&tab.current), parent].into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use yazi_shared::fs::Url;
+
+	use super::*;
+
+	#[test]
+	fn test_triggered() {
+		crate::init_tests();
+		let (parent, cwd) = (Url::from("/yazi-test/p"), Url::from("/yazi-test/p/c"));
+
+		let mut tab = Tab::default();
+		(tab.current, tab.parent) = (Folder::from(&cwd), Some(Folder::from(&parent)));
+
+		let mut panes = Panes::default();
+		let cwds = |tab: &Tab, panes: &Panes| -> Vec<Url> {
+			triggered(tab, panes).into_iter().map(|f| f.cwd.clone()).collect()
+		};
+		assert_eq!(cwds(&tab, &panes), [cwd.clone(), parent]);
+
+		panes.cycle(1);
+		assert_eq!(cwds(&tab, &panes), vec![cwd.clone()]);
+
+		// The filesystem root has no parent
+		panes.cycle(-1);
+		tab.parent = None;
+		assert_eq!(cwds(&tab, &panes), [cwd]);
+	}
+}
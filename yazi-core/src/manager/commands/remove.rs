@@ -1,8 +1,8 @@
-use yazi_config::popup::ConfirmCfg;
-use yazi_proxy::{ConfirmProxy, ManagerProxy};
+use yazi_config::{popup::ConfirmCfg, MANAGER, TASKS};
+use yazi_proxy::{AppProxy, ConfirmProxy, ManagerProxy};
 use yazi_shared::{event::Cmd, fs::Url};
 
-use crate::{manager::Manager, tasks::Tasks};
+use crate::{manager::{Manager, Protected}, tasks::Tasks};
 
 pub struct Opt {
 	force:       bool,
@@ -23,7 +23,7 @@ impl From<Cmd> for Opt {
 }
 
 impl Manager {
-	pub fn remove(&mut self, opt: impl Into<Opt>, tasks: &Tasks) {
+	pub fn remove(&mut self, opt: impl Into<Opt>) {
 		if !self.active_mut().try_escape_visual() {
 			return;
 		}
@@ -31,26 +31,28 @@ impl Manager {
 			return;
 		};
 
-		let mut opt = opt.into() as Opt;
-		opt.targets = if opt.hovered {
+		let opt = opt.into() as Opt;
+		let targets = if opt.hovered {
 			vec![hovered.clone()]
 		} else {
 			self.selected_or_hovered(true).cloned().collect()
 		};
 
-		if opt.force {
-			return self.remove_do(opt, tasks);
-		}
-
+		let (cwd, Opt { force, permanently, .. }) = (self.cwd().clone(), opt);
 		tokio::spawn(async move {
-			let result = ConfirmProxy::show(if opt.permanently {
-				ConfirmCfg::delete(&opt.targets)
-			} else {
-				ConfirmCfg::trash(&opt.targets)
-			});
+			if let Some(u) = Protected::find(&cwd, &targets).await {
+				return AppProxy::notify_warn("Remove", format!("`{}` is protected", u.display()));
+			}
 
-			if result.await {
-				ManagerProxy::remove_do(opt.targets, opt.permanently);
+			// Without `--permanently`, each file goes the way `remove_rules` says
+			let (delete, trash): (Vec<_>, Vec<_>) =
+				targets.into_iter().partition(|u| permanently || MANAGER.removes_permanently(u));
+
+			if !delete.is_empty() && (force || ConfirmProxy::show(ConfirmCfg::delete(&delete)).await) {
+				ManagerProxy::remove_do(delete, true);
+			}
+			if !trash.is_empty() && (force || ConfirmProxy::show(ConfirmCfg::trash(&trash)).await) {
+				ManagerProxy::remove_do(trash, false);
 			}
 		});
 	}
@@ -67,6 +69,9 @@ impl Manager {
 		}
 
 		self.yanked.catchup_revision(false);
+		if !opt.permanently && !opt.targets.is_empty() {
+			self.trashed = Some((opt.targets.clone(), TASKS.trash));
+		}
 		tasks.file_remove(opt.targets, opt.permanently);
 	}
 }
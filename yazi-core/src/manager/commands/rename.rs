@@ -1,11 +1,12 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ffi::OsString, io, path::Path};
 
 use anyhow::Result;
 use tokio::fs;
 use yazi_config::popup::{ConfirmCfg, InputCfg};
 use yazi_dds::Pubsub;
+use yazi_fs::Tags;
 use yazi_proxy::{ConfirmProxy, InputProxy, TabProxy, WATCHER};
-use yazi_shared::{event::Cmd, fs::{maybe_exists, ok_or_not_found, paths_to_same_file, symlink_realpath, File, FilesOp, Url}};
+use yazi_shared::{event::{Cmd, Outcome}, fs::{maybe_exists, ok_or_not_found, paths_to_same_file, symlink_realpath, File, FilesOp, Url}, timestamp_us};
 
 use crate::manager::Manager;
 
@@ -28,17 +29,18 @@ impl From<Cmd> for Opt {
 }
 
 impl Manager {
-	pub fn rename(&mut self, opt: impl Into<Opt>) {
+	pub fn rename(&mut self, opt: impl Into<Opt>) -> Outcome {
 		if !self.active_mut().try_escape_visual() {
-			return;
+			return Outcome::Done;
 		}
 		let Some(hovered) = self.hovered().map(|h| h.url()) else {
-			return;
+			return Outcome::Done;
 		};
 
 		let opt = opt.into() as Opt;
 		if !opt.hovered && !self.active().selected.is_empty() {
-			return self.bulk_rename();
+			self.bulk_rename();
+			return Outcome::Done;
 		}
 
 		let name = Self::empty_url_part(&hovered, &opt.empty);
@@ -56,21 +58,29 @@ impl Manager {
 		let tab = self.tabs.cursor;
 		tokio::spawn(async move {
 			let mut result = InputProxy::show(InputCfg::rename().with_value(name).with_cursor(cursor));
-			let Some(Ok(name)) = result.recv().await else {
-				return;
-			};
-
-			if name.is_empty() {
-				return;
-			}
-
-			let new = Url::from(hovered.parent().unwrap().join(name));
-			if opt.force || !maybe_exists(&new).await || paths_to_same_file(&hovered, &new).await {
-				Self::rename_do(tab, hovered, new).await.ok();
-			} else if ConfirmProxy::show(ConfirmCfg::overwrite(&new)).await {
-				Self::rename_do(tab, hovered, new).await.ok();
+			if let Some(Ok(name)) = result.recv().await {
+				Self::rename_named(tab, hovered, &name, opt.force).await;
 			}
 		});
+		Outcome::Prompted
+	}
+
+	// `name` is taken as it is, without expanding `~` or variables
+	async fn rename_named(tab: usize, hovered: Url, name: &str, force: bool) {
+		if name.is_empty() {
+			return;
+		}
+
+		let new = Url::from(hovered.parent().unwrap().join(name));
+		if new == hovered {
+			return;
+		}
+		if force
+			|| !clobbers(&hovered, &new).await
+			|| ConfirmProxy::show(ConfirmCfg::overwrite(&new)).await
+		{
+			Self::rename_do(tab, hovered, new).await.ok();
+		}
 	}
 
 	async fn rename_do(tab: usize, old: Url, new: Url) -> Result<()> {
@@ -78,14 +88,19 @@ impl Manager {
 		let Some(p_new) = new.parent_url() else { return Ok(()) };
 		let _permit = WATCHER.acquire().await.unwrap();
 
-		let overwritten = symlink_realpath(&new).await;
-		fs::rename(&old, &new).await?;
+		if paths_to_same_file(&old, &new).await {
+			rename_case(&old, &new).await?;
+		} else {
+			let overwritten = symlink_realpath(&new).await;
+			fs::rename(&old, &new).await?;
 
-		if let Ok(o) = overwritten {
-			ok_or_not_found(fs::rename(&o, &new).await)?;
-			FilesOp::Deleting(p_new.clone(), vec![Url::from(o)]).emit();
+			if let Ok(o) = overwritten {
+				ok_or_not_found(fs::rename(&o, &new).await)?;
+				FilesOp::Deleting(p_new.clone(), vec![Url::from(o)]).emit();
+			}
 		}
 		Pubsub::pub_from_rename(tab, &old, &new);
+		Tags::follow([(&old, &new)]);
 
 		let file = File::from(new.clone()).await?;
 		FilesOp::Deleting(p_old, vec![old]).emit();
@@ -107,3 +122,72 @@ impl Manager {
 		}
 	}
 }
+
+// Whether renaming `old` to `new` would replace another file. A case-only
+// rename on a case-insensitive filesystem points to `old` itself, so it doesn't.
+async fn clobbers(old: &Path, new: &Path) -> bool {
+	maybe_exists(new).await && !paths_to_same_file(old, new).await
+}
+
+// Renames `old` to `new` in two steps through a temporary name, because
+// some case-insensitive filesystems ignore a rename that only changes the case
+async fn rename_case(old: &Path, new: &Path) -> io::Result<()> {
+	let mut tmp = OsString::from(new);
+	tmp.push(format!(".{}.tmp", timestamp_us()));
+
+	fs::rename(old, &tmp).await?;
+	if let Err(e) = fs::rename(&tmp, new).await {
+		fs::rename(&tmp, old).await.ok();
+		return Err(e);
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_clobbers() {
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		fs::create_dir_all(&root).await.unwrap();
+		fs::write(root.join("a"), "").await.unwrap();
+		fs::write(root.join("b"), "").await.unwrap();
+
+		assert!(clobbers(&root.join("a"), &root.join("b")).await);
+		assert!(!clobbers(&root.join("a"), &root.join("c")).await);
+		assert!(!clobbers(&root.join("a"), &root.join("a")).await);
+	}
+
+	#[tokio::test]
+	async fn test_rename_named() {
+		crate::init_tests();
+
+		let tmp = tempfile::tempdir().unwrap();
+		fs::write(tmp.path().join("a"), "").await.unwrap();
+
+		let old = Url::from(tmp.path().join("a"));
+		Manager::rename_named(0, old, "a$HOME.txt", true).await;
+		assert!(tmp.path().join("a$HOME.txt").is_file());
+		assert!(!tmp.path().join("a").exists());
+	}
+
+	#[tokio::test]
+	async fn test_rename_case() {
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		fs::create_dir_all(&root).await.unwrap();
+		fs::write(root.join("readme.md"), "hi").await.unwrap();
+
+		rename_case(&root.join("readme.md"), &root.join("README.md")).await.unwrap();
+
+		let mut names = vec![];
+		let mut it = fs::read_dir(&root).await.unwrap();
+		while let Some(entry) = it.next_entry().await.unwrap() {
+			names.push(entry.file_name());
+		}
+		assert_eq!(names, ["README.md"]);
+		assert_eq!(fs::read_to_string(root.join("README.md")).await.unwrap(), "hi");
+	}
+}
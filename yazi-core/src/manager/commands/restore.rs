@@ -0,0 +1,22 @@
+use yazi_proxy::AppProxy;
+use yazi_shared::event::Cmd;
+
+use crate::{manager::Manager, tasks::Tasks};
+
+impl Manager {
+	// Only the files of the last `remove` are kept track of, along with the
+	// backend they were trashed with
+	pub fn restore(&mut self, _: Cmd, tasks: &Tasks) {
+		let Some((targets, backend)) = self.trashed.take() else {
+			return AppProxy::notify_warn("Restore", "Nothing to restore");
+		};
+
+		if !backend.restorable() {
+			return AppProxy::notify_warn(
+				"Restore",
+				format!("Files trashed with the `{}` backend cannot be restored", backend.as_str()),
+			);
+		}
+		tasks.file_restore(targets, backend);
+	}
+}
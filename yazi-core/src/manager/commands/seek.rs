@@ -1,16 +1,54 @@
-use yazi_config::PLUGIN;
+use yazi_config::{LAYOUT, PLUGIN};
+use yazi_fs::Step;
 use yazi_plugin::isolate;
 use yazi_shared::{event::{Cmd, Data}, render, MIME_DIR};
 
 use crate::manager::Manager;
 
-#[derive(Debug)]
+// Past the end of any preview, which the previewers bring back to their end
+const BOTTOM: usize = i32::MAX as usize;
+
+enum Motion {
+	By(Step),
+	Top,
+	Bottom,
+}
+
 pub struct Opt {
-	units: i16,
+	motion: Motion,
+	count:  usize,
 }
 
 impl From<Cmd> for Opt {
-	fn from(c: Cmd) -> Self { Self { units: c.first().and_then(Data::as_i16).unwrap_or(0) } }
+	fn from(mut c: Cmd) -> Self {
+		let motion = match c.take_first() {
+			Some(Data::Integer(i)) => Motion::By(Step::from(i as isize)),
+			Some(Data::String(s)) if s == "top" => Motion::Top,
+			Some(Data::String(s)) if s == "bottom" => Motion::Bottom,
+			Some(Data::String(s)) => Motion::By(s.parse().unwrap_or_default()),
+			_ => Motion::By(Step::default()),
+		};
+
+		Self { motion, count: c.get("count").and_then(Data::as_usize).unwrap_or(1) }
+	}
+}
+
+impl Opt {
+	// The lines to seek by, where a percentage is of the preview's height, and
+	// `count` is the number typed before the key
+	fn lines(&self, height: u16) -> isize {
+		let Motion::By(step) = self.motion else { return 0 };
+		step.fixed(height as usize).saturating_mul(self.count.max(1) as isize)
+	}
+
+	// The lines in tenths of the preview's height, as `seek()` of the previewers
+	// has always been given them, and at least one for any move
+	fn units(lines: isize, height: u16) -> isize {
+		match lines * 10 / height.max(1) as isize {
+			0 => lines.signum(),
+			n => n,
+		}
+	}
 }
 
 impl Manager {
@@ -19,6 +57,25 @@ impl Manager {
 			return render!(self.active_mut().preview.reset());
 		};
 
+		// Every previewer starts at 0, and brings a skip past its end back to its
+		// last page, so the top and bottom are the same for all of them
+		let opt = opt.into() as Opt;
+		match opt.motion {
+			Motion::Top => return self.peek(Cmd::args("peek", &[0])),
+			Motion::Bottom => return self.peek(Cmd::args("peek", &[BOTTOM])),
+			Motion::By(_) => {}
+		}
+
+		let height = LAYOUT.load().preview.height;
+		let lines = opt.lines(height);
+
+		// A README previewed in place of the directory scrolls like code does,
+		// instead of by the directory's file count
+		if hovered.is_dir() && self.active().preview.readme.is_some() {
+			let skip = self.active().preview.skip.saturating_add_signed(lines);
+			return self.peek(Cmd::args("peek", &[skip]));
+		}
+
 		let mime = if hovered.is_dir() {
 			MIME_DIR
 		} else if let Some(s) = self.mimetype.get(&hovered.url) {
@@ -27,11 +84,44 @@ impl Manager {
 			return render!(self.active_mut().preview.reset());
 		};
 
-		let Some(previewer) = PLUGIN.previewer(&hovered.url, mime) else {
+		let rule = self.active().preview.rule;
+		let Some(previewer) = PLUGIN.previewers(&hovered.url, mime).nth(rule) else {
 			return render!(self.active_mut().preview.reset());
 		};
 
-		let opt = opt.into() as Opt;
-		isolate::seek_sync(&previewer.run, hovered.clone(), opt.units);
+		isolate::seek_sync(&previewer.run, hovered.clone(), Opt::units(lines, height), lines);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_lines() {
+		let lines = |s: &str, count: Option<usize>| {
+			let mut c = Cmd::args("seek", &[s]);
+			if let Some(n) = count {
+				c = c.with("count", n);
+			}
+			Opt::from(c).lines(40)
+		};
+
+		assert_eq!(lines("1", None), 1);
+		assert_eq!(lines("-1", Some(5)), -5);
+		assert_eq!(lines("50%", None), 20);
+		assert_eq!(lines("-100%", Some(2)), -80);
+		assert_eq!(lines("0", Some(3)), 0);
+		assert_eq!(lines("top", Some(3)), 0);
+		assert!(matches!(Opt::from(Cmd::args("seek", &["bottom"])).motion, Motion::Bottom));
+	}
+
+	#[test]
+	fn test_units() {
+		assert_eq!(Opt::units(20, 40), 5);
+		assert_eq!(Opt::units(-80, 40), -20);
+		assert_eq!(Opt::units(1, 40), 1);
+		assert_eq!(Opt::units(-1, 40), -1);
+		assert_eq!(Opt::units(0, 40), 0);
 	}
 }
@@ -1,27 +1,28 @@
 use yazi_boot::BOOT;
+use yazi_config::MANAGER;
 use yazi_proxy::AppProxy;
 use yazi_shared::{event::{Cmd, Data}, fs::Url, render};
 
 use crate::{manager::Tabs, tab::Tab};
 
-const MAX_TABS: usize = 9;
-
 pub struct Opt {
 	url:     Url,
 	current: bool,
+	hovered: bool,
+	stay:    bool,
 }
 
 impl From<Cmd> for Opt {
 	fn from(mut c: Cmd) -> Self {
-		if c.bool("current") {
-			Self { url: Default::default(), current: true }
+		let (current, hovered, stay) = (c.bool("current"), c.bool("hovered"), c.bool("stay"));
+		if current || hovered {
+			Self { url: Default::default(), current, hovered, stay }
 		} else {
 			Self {
-				url:     c
-					.take_first()
-					.and_then(Data::into_url)
-					.unwrap_or_else(|| Url::from(&BOOT.cwds[0])),
-				current: false,
+				url: c.take_first().and_then(Data::into_url).unwrap_or_else(|| Url::from(&BOOT.cwds[0])),
+				current,
+				hovered,
+				stay,
 			}
 		}
 	}
@@ -29,29 +30,67 @@ impl From<Cmd> for Opt {
 
 impl Tabs {
 	pub fn create(&mut self, opt: impl Into<Opt>) {
-		if self.items.len() >= MAX_TABS {
-			AppProxy::notify_warn("Too many tabs", "You can only open up to 9 tabs at the same time.");
+		if self.items.len() >= MANAGER.max_tabs {
+			AppProxy::notify_warn(
+				"Too many tabs",
+				format!("You can only open up to {} tabs at the same time.", MANAGER.max_tabs),
+			);
 			return;
 		}
 
 		let opt = opt.into() as Opt;
 		let mut tab = Tab::default();
 
-		if !opt.current {
+		if !opt.current && !opt.hovered {
 			tab.cd(opt.url);
-		} else if let Some(h) = self.active().current.hovered() {
-			tab.conf = self.active().conf.clone();
-			tab.apply_files_attrs();
-			tab.reveal(h.url.to_owned());
 		} else {
 			tab.conf = self.active().conf.clone();
 			tab.apply_files_attrs();
-			tab.cd(self.active().current.cwd.clone());
+
+			// A hovered directory is entered, and a file is hovered in the new tab as well
+			match self.active().current.hovered() {
+				Some(h) if opt.hovered && h.is_dir() => _ = tab.cd(h.url.to_owned()),
+				Some(h) => tab.reveal(h.url.to_owned()),
+				None => _ = tab.cd(self.active().current.cwd.clone()),
+			}
 		}
 
 		self.items.insert(self.cursor + 1, tab);
-		self.set_idx(self.cursor + 1);
+		if !opt.stay {
+			self.set_idx(self.cursor + 1);
+		}
 		self.reorder();
 		render!();
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use yazi_shared::fs::{Cha, ChaKind, File, FilesOp};
+
+	use super::*;
+
+	#[test]
+	fn test_create_hovered() {
+		crate::init_tests();
+		let mut tabs = Tabs { cursor: 0, items: vec![Tab::default()] };
+		tabs.reorder();
+
+		let tmp = tempfile::tempdir().unwrap();
+		let cwd = Url::from(tmp.path());
+		tabs.active_mut().cd(cwd.clone());
+		let (sub, file) = (cwd.join("sub"), cwd.join("file"));
+		let dir = Cha::default().with_kind(ChaKind::DIR);
+		let files = vec![File { url: sub.clone(), cha: dir, ..Default::default() }];
+		tabs.active_mut().current.update(FilesOp::Full(cwd.clone(), files, dir));
+
+		tabs.create(Cmd::new("tab_create").with_bool("hovered", true).with_bool("stay", true));
+		assert_eq!(tabs.cursor, 0);
+		assert_eq!(tabs[1].current.cwd, sub);
+
+		let op = FilesOp::Full(cwd.clone(), vec![File { url: file, ..Default::default() }], dir);
+		tabs.active_mut().current.update(op);
+		tabs.create(Cmd::new("tab_create").with_bool("hovered", true).with_bool("stay", true));
+		assert_eq!(tabs[1].current.cwd, cwd);
+	}
+}
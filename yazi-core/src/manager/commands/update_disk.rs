@@ -0,0 +1,21 @@
+use yazi_shared::{event::Cmd, fs::DiskSpace, render};
+
+use crate::manager::Manager;
+
+pub struct Opt {
+	space: Option<DiskSpace>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { space: c.take_any("space") } }
+}
+
+impl Manager {
+	pub fn update_disk(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		if self.disk.space != opt.space {
+			self.disk.space = opt.space;
+			render!();
+		}
+	}
+}
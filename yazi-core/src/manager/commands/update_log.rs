@@ -0,0 +1,16 @@
+use yazi_shared::event::Cmd;
+
+use crate::manager::Manager;
+
+pub struct Opt {
+	text: String,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { text: c.take_first_str().unwrap_or_default() } }
+}
+
+impl Manager {
+	#[inline]
+	pub fn update_log(&mut self, opt: impl Into<Opt>) { self.log.record_op(opt.into().text); }
+}
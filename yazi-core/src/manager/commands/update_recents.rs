@@ -0,0 +1,31 @@
+use yazi_shared::{event::Cmd, fs::Url, MIME_DIR};
+
+use crate::manager::Manager;
+
+pub struct Opt {
+	urls: Vec<Url>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { urls: c.take_any("urls").unwrap_or_default() } }
+}
+impl From<Vec<Url>> for Opt {
+	fn from(urls: Vec<Url>) -> Self { Self { urls } }
+}
+
+impl Manager {
+	pub fn update_recents(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+
+		let mut changed = false;
+		for url in opt.urls {
+			if self.mimetype.get(&url).is_some_and(|m| m == MIME_DIR) || self.guess_folder(&url) {
+				continue;
+			}
+			changed |= self.recents.push(url);
+		}
+		if changed {
+			self.recents.save();
+		}
+	}
+}
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use tokio::{sync::watch, task::spawn_blocking, time};
+use yazi_config::MANAGER;
+use yazi_proxy::ManagerProxy;
+use yazi_shared::fs::{disk_space, DiskSpace, Url};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Default)]
+pub struct Disk {
+	tx:        Option<watch::Sender<Url>>,
+	pub space: Option<DiskSpace>,
+}
+
+impl Disk {
+	pub(super) fn serve() -> Self {
+		if !MANAGER.show_disk_space {
+			return Self::default();
+		}
+
+		let (tx, rx) = watch::channel(Url::default());
+		tokio::spawn(Self::poll(rx));
+		Self { tx: Some(tx), space: None }
+	}
+
+	pub(super) fn watch(&self, cwd: &Url) {
+		if let Some(tx) = &self.tx {
+			tx.send_if_modified(|u| {
+				if u == cwd {
+					return false;
+				}
+				*u = cwd.clone();
+				true
+			});
+		}
+	}
+
+	// Queried on every cwd change, so entering another filesystem is picked up
+	// right away, and periodically to follow the usage of the current one.
+	async fn poll(mut rx: watch::Receiver<Url>) {
+		let mut interval = time::interval(POLL_INTERVAL);
+		let mut last = None;
+		loop {
+			tokio::select! {
+				r = rx.changed() => if r.is_err() { break },
+				_ = interval.tick() => {},
+			}
+
+			let cwd = rx.borrow_and_update().clone();
+			if !cwd.is_regular() {
+				continue;
+			}
+
+			let space = spawn_blocking(move || disk_space(&cwd)).await.ok().and_then(|r| r.ok());
+			if space != last {
+				last = space;
+				ManagerProxy::update_disk(space);
+			}
+		}
+	}
+}
@@ -2,29 +2,39 @@ use std::collections::HashMap;
 
 use ratatui::layout::Rect;
 use yazi_adapter::Dimension;
-use yazi_config::popup::{Origin, Position};
+use yazi_config::{popup::{Origin, Position}, tasks::TrashBackend};
 use yazi_fs::Folder;
 use yazi_shared::fs::{File, Url};
 
-use super::{Tabs, Watcher, Yanked};
+use super::{ActionLog, Disk, Panes, Recents, Tabs, Watcher, Yanked};
 use crate::tab::Tab;
 
 pub struct Manager {
-	pub tabs:   Tabs,
-	pub yanked: Yanked,
+	pub tabs:    Tabs,
+	pub yanked:  Yanked,
+	pub recents: Recents,
+	pub log:     ActionLog,
+	pub disk:    Disk,
+	pub panes:   Panes,
 
 	pub(super) watcher: Watcher,
 	pub mimetype:       HashMap<Url, String>,
+	pub(super) trashed: Option<(Vec<Url>, TrashBackend)>,
 }
 
 impl Manager {
 	pub fn make() -> Self {
 		Self {
-			tabs:   Tabs::make(),
-			yanked: Default::default(),
+			tabs:    Tabs::make(),
+			yanked:  Default::default(),
+			recents: Recents::load(),
+			log:     ActionLog::load(),
+			disk:    Disk::serve(),
+			panes:   Default::default(),
 
 			watcher:  Watcher::serve(),
 			mimetype: Default::default(),
+			trashed:  None,
 		}
 	}
 
@@ -1,12 +1,22 @@
+mod action_log;
 mod commands;
+mod disk;
 mod linked;
 mod manager;
+mod panes;
+mod protected;
+mod recents;
 mod tabs;
 mod watcher;
 mod yanked;
 
+pub use action_log::*;
+pub use disk::*;
 pub use linked::*;
 pub use manager::*;
+pub use panes::*;
+pub use protected::*;
+pub use recents::*;
 pub use tabs::*;
 pub use watcher::*;
 pub use yanked::*;
@@ -0,0 +1,92 @@
+use yazi_config::{manager::ManagerRatio, MANAGER};
+
+#[derive(Default)]
+pub struct Panes {
+	cursor:        usize,
+	pub maximized: bool,
+}
+
+impl Panes {
+	// The first preset is always `ratio`, followed by those in `layouts`
+	#[inline]
+	fn presets() -> usize { 1 + MANAGER.layouts.len() }
+
+	pub fn ratio(&self) -> ManagerRatio {
+		if self.maximized {
+			ManagerRatio { parent: 0, current: 1, preview: 0, all: 1 }
+		} else if self.cursor == 0 {
+			MANAGER.ratio
+		} else {
+			MANAGER.layouts[self.cursor - 1]
+		}
+	}
+
+	pub(super) fn cycle(&mut self, step: isize) -> bool {
+		let old = self.ratio();
+		self.maximized = false;
+		self.cursor = (self.cursor as isize + step).rem_euclid(Self::presets() as isize) as usize;
+		self.ratio() != old
+	}
+
+	#[inline]
+	pub(super) fn toggle_maximized(&mut self) { self.maximized = !self.maximized; }
+
+	#[inline]
+	pub fn show_parent(&self) -> bool { self.ratio().parent > 0 }
+
+	#[inline]
+	pub fn show_preview(&self) -> bool { self.ratio().preview > 0 }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn ratio(parent: u16, current: u16, preview: u16) -> ManagerRatio {
+		ManagerRatio { parent, current, preview, all: parent + current + preview }
+	}
+
+	#[test]
+	fn test_cycle() {
+		crate::init_tests();
+		let mut panes = Panes::default();
+		assert_eq!(panes.ratio(), ratio(1, 4, 3));
+
+		// Through `layouts` and back round to `ratio`
+		assert!(panes.cycle(1));
+		assert_eq!(panes.ratio(), ratio(0, 4, 3));
+		assert!(panes.cycle(2));
+		assert_eq!(panes.ratio(), ratio(0, 0, 1));
+		assert!(!panes.show_parent() && panes.show_preview());
+		assert!(panes.cycle(1));
+		assert_eq!(panes.ratio(), ratio(1, 4, 3));
+
+		// Backwards, and by more than a full round
+		assert!(panes.cycle(-1));
+		assert_eq!(panes.ratio(), ratio(0, 0, 1));
+		assert!(panes.cycle(-6));
+		assert_eq!(panes.ratio(), ratio(0, 4, 3));
+		assert!(!panes.cycle(4));
+		assert!(!panes.cycle(0));
+	}
+
+	#[test]
+	fn test_maximized() {
+		crate::init_tests();
+		let mut panes = Panes::default();
+		panes.cycle(2);
+
+		panes.toggle_maximized();
+		assert_eq!(panes.ratio(), ratio(0, 1, 0));
+		assert!(!panes.show_parent() && !panes.show_preview());
+
+		// Cycling leaves it, moving on from the preset it was maximized from
+		assert!(panes.cycle(1));
+		assert!(!panes.maximized);
+		assert_eq!(panes.ratio(), ratio(0, 0, 1));
+
+		panes.toggle_maximized();
+		panes.toggle_maximized();
+		assert_eq!(panes.ratio(), ratio(0, 0, 1));
+	}
+}
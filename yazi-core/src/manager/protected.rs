@@ -0,0 +1,118 @@
+use std::{collections::HashMap, io, path::{Path, PathBuf}};
+
+use tokio::{fs, task::spawn_blocking};
+use yazi_config::MANAGER;
+use yazi_shared::fs::{expand_path, mount_roots, Url};
+
+// Paths that are never removed or moved: those in `protected_paths`, the
+// current directory, and the mount points.
+pub struct Protected;
+
+impl Protected {
+	// The first of `targets` that's protected, if any
+	pub async fn find(cwd: &Path, targets: &[Url]) -> Option<Url> {
+		let mut paths: Vec<_> = MANAGER.protected_paths.iter().map(expand_path).collect();
+		paths.push(cwd.to_owned());
+
+		let mounts = spawn_blocking(mount_roots).await.ok().and_then(Result::ok);
+		Self::find_in(paths, mounts.unwrap_or_default(), targets).await
+	}
+
+	// The targets are resolved and the protected paths looked up among them,
+	// rather than the other way round, so that what's resolved is bound by the
+	// targets and not by the number of mount points. Those are real paths
+	// already, so unlike `paths`, they're taken as they are.
+	async fn find_in(paths: Vec<PathBuf>, mounts: Vec<PathBuf>, targets: &[Url]) -> Option<Url> {
+		let mut reals = HashMap::with_capacity(targets.len());
+		for (i, u) in targets.iter().enumerate() {
+			if let Ok(real) = Self::resolve(u).await {
+				reals.entry(real).or_insert(i);
+			}
+		}
+		if reals.is_empty() {
+			return None;
+		}
+
+		let mut first: Option<usize> = None;
+		for p in &paths {
+			let real = fs::canonicalize(p).await.unwrap_or_else(|_| p.clone());
+			first = first.into_iter().chain(reals.get(&real).copied()).min();
+		}
+
+		let mounts = mounts.iter().filter_map(|p| reals.get(p).copied());
+		first.into_iter().chain(mounts).min().map(|i| targets[i].clone())
+	}
+
+	// Symlinks in the parent directories, and `..`, are resolved, so a path can't
+	// sneak past. A symlink itself is left as is, since that's what gets removed.
+	async fn resolve(path: &Path) -> io::Result<PathBuf> {
+		match (path.parent(), path.file_name()) {
+			(Some(p), Some(n)) => Ok(fs::canonicalize(p).await?.join(n)),
+			_ => fs::canonicalize(path).await,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use super::*;
+
+	async fn find(paths: &[&Path], mounts: &[&Path], targets: &[&Path]) -> Option<PathBuf> {
+		let paths = paths.iter().map(|&p| p.to_owned()).collect();
+		let mounts = mounts.iter().map(|&p| p.to_owned()).collect();
+		let targets: Vec<_> = targets.iter().map(|&p| Url::from(p)).collect();
+		Protected::find_in(paths, mounts, &targets).await.map(|u| u.to_path_buf())
+	}
+
+	#[tokio::test]
+	async fn test_home() {
+		let home = dirs::home_dir().unwrap();
+		assert!(find(&[&home], &[], &[&home]).await.is_some());
+		assert!(find(&[&home], &[], &[&home.join(".")]).await.is_some());
+		assert!(find(&[&home], &[], &[&home.join("a")]).await.is_none());
+		assert!(find(&[&home], &[], &[Path::new("/")]).await.is_none());
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_root() {
+		let root = Path::new("/");
+		assert!(find(&[root], &[], &[root]).await.is_some());
+		assert!(find(&[root], &[], &[Path::new("/tmp/..")]).await.is_some());
+		assert!(find(&[root], &[], &[Path::new("/tmp")]).await.is_none());
+
+		// A path reached through a symlinked directory
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		std::os::unix::fs::symlink("/", dir.join("root")).unwrap();
+
+		assert!(find(&[root], &[], &[&dir.join("root/tmp/..")]).await.is_some());
+		assert!(find(&[root], &[], &[&dir.join("root")]).await.is_none());
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_first() {
+		let tmp = tempfile::tempdir().unwrap();
+		let (a, b, c) = (tmp.path().join("a"), tmp.path().join("b"), tmp.path().join("c"));
+		for p in [&a, &b, &c] {
+			fs::create_dir(p).unwrap();
+		}
+		let real = |p: &Path| p.canonicalize().unwrap();
+
+		// The first protected one in the order of the targets, whatever made it so
+		let (ra, rc) = (real(&a), real(&c));
+		assert_eq!(find(&[&c], &[&ra], &[&b, &c, &a]).await, Some(c.clone()));
+		assert_eq!(find(&[&a], &[&rc], &[&b, &c, &a]).await, Some(c.clone()));
+		assert_eq!(find(&[], &[&ra], &[&b, &a]).await, Some(a.clone()));
+
+		// Mount points aren't resolved, since they're real paths already
+		let link = tmp.path().join("link");
+		std::os::unix::fs::symlink(&b, &link).unwrap();
+		assert_eq!(find(&[], &[&link], &[&b]).await, None);
+		assert_eq!(find(&[&link], &[], &[&b]).await, Some(b));
+		assert_eq!(find(&[&rc], &[], &[]).await, None);
+	}
+}
@@ -0,0 +1,111 @@
+use std::{collections::VecDeque, io, path::{Path, PathBuf}};
+
+use yazi_boot::BOOT;
+use yazi_config::MANAGER;
+use yazi_shared::fs::{Saver, Url};
+
+static SAVER: Saver = Saver::new();
+
+#[derive(Default)]
+pub struct Recents {
+	items: VecDeque<Url>,
+	cap:   usize,
+}
+
+impl Recents {
+	pub(super) fn new(items: impl IntoIterator<Item = Url>, cap: usize) -> Self {
+		let mut me = Self { cap, ..Default::default() };
+		for url in items {
+			if me.items.len() < cap && !me.items.contains(&url) {
+				me.items.push_back(url);
+			}
+		}
+		me
+	}
+
+	// Entries whose files no longer exist are pruned on load
+	pub(super) fn load() -> Self {
+		let s = std::fs::read_to_string(BOOT.state_dir.join("recents")).unwrap_or_default();
+		let items =
+			s.lines().map(PathBuf::from).filter(|p| p.symlink_metadata().is_ok()).map(Url::from);
+
+		Self::new(items, MANAGER.recents_size)
+	}
+
+	// The most recent one goes to the front, and a repeated one is moved there
+	pub(super) fn push(&mut self, url: Url) -> bool {
+		if !url.is_regular() || self.cap == 0 || self.items.front() == Some(&url) {
+			return false;
+		}
+
+		self.items.retain(|u| *u != url);
+		self.items.push_front(url);
+		self.items.truncate(self.cap);
+		true
+	}
+
+	#[inline]
+	pub(super) fn clear(&mut self) -> bool {
+		let b = !self.items.is_empty();
+		self.items.clear();
+		b
+	}
+
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item = &Url> { self.items.iter() }
+
+	fn dump(&self) -> String {
+		self.items.iter().fold(String::new(), |mut s, u| {
+			s.push_str(&u.to_string_lossy());
+			s.push('\n');
+			s
+		})
+	}
+
+	// Saves them right away into `dir`, as the process is about to exit
+	#[inline]
+	pub fn flush(&self, dir: &Path) -> io::Result<()> {
+		SAVER.flush(&dir.join("recents"), &self.dump())
+	}
+
+	#[inline]
+	pub(super) fn save(&self) { SAVER.save(BOOT.state_dir.join("recents"), self.dump()) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn items(r: &Recents) -> Vec<String> { r.iter().map(|u| u.to_string()).collect() }
+
+	#[test]
+	fn test_push() {
+		let mut r = Recents::new([], 3);
+
+		assert!(r.push(Url::from("/a")));
+		assert!(!r.push(Url::from("/a")));
+		assert!(r.push(Url::from("/b")));
+		assert!(r.push(Url::from("/a")));
+		assert_eq!(items(&r), ["/a", "/b"]);
+
+		assert!(r.push(Url::from("/c")));
+		assert!(r.push(Url::from("/d")));
+		assert_eq!(items(&r), ["/d", "/c", "/a"]);
+	}
+
+	#[test]
+	fn test_flush() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+
+		let r = Recents::new(["/a", "/b"].map(Url::from), 3);
+		r.flush(dir).unwrap();
+		assert_eq!(std::fs::read_to_string(dir.join("recents")).unwrap(), "/a\n/b\n");
+	}
+
+	#[test]
+	fn test_new() {
+		let r = Recents::new(["/a", "/b", "/a", "/c"].map(Url::from), 2);
+		assert_eq!(items(&r), ["/a", "/b"]);
+	}
+}
@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use yazi_boot::BOOT;
 use yazi_dds::Pubsub;
-use yazi_proxy::ManagerProxy;
+use yazi_proxy::{AppProxy, ManagerProxy};
 use yazi_shared::fs::Url;
 
 use crate::tab::Tab;
@@ -26,6 +26,10 @@ impl Tabs {
 				tab.reveal(Url::from(BOOT.cwds[i].join(file)));
 			}
 		}
+
+		for warning in &BOOT.warnings {
+			AppProxy::notify_warn("Startup", warning);
+		}
 		tabs
 	}
 
@@ -91,3 +95,31 @@ impl Deref for Tabs {
 impl DerefMut for Tabs {
 	fn deref_mut(&mut self) -> &mut Self::Target { &mut self.items }
 }
+
+#[cfg(test)]
+mod tests {
+	use yazi_shared::event::Cmd;
+
+	use super::*;
+
+	#[test]
+	fn test_filter_per_tab() {
+		crate::init_tests();
+
+		let mut tabs = Tabs { cursor: 0, items: vec![Tab::default(), Tab::default()] };
+		tabs.reorder();
+
+		let filter = |query: &str| Cmd::args("filter_do", &[query]);
+		let query = |tab: &Tab| tab.current.files.filter().map(|f| f.to_string());
+		tabs.active_mut().filter_do(filter("a"));
+
+		// A filter typed into the prompt of the first tab, after switching away from it
+		tabs.cursor = 1;
+		assert_eq!(query(tabs.active()), None);
+		tabs.active_or_mut(Some(0)).filter_do(filter("ab"));
+		assert_eq!(query(tabs.active()), None);
+
+		tabs.cursor = 0;
+		assert_eq!(query(tabs.active()).as_deref(), Some("ab"));
+	}
+}
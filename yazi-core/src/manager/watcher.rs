@@ -3,13 +3,14 @@ use std::{collections::{HashMap, HashSet}, time::Duration};
 use anyhow::Result;
 use notify_fork::{RecommendedWatcher, RecursiveMode, Watcher as _Watcher};
 use parking_lot::RwLock;
-use tokio::{fs, pin, sync::{mpsc::{self, UnboundedReceiver}, watch}};
+use tokio::{fs, pin, select, sync::{mpsc::{self, UnboundedReceiver}, watch}, task::spawn_blocking, time::sleep};
 use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use tracing::error;
+use yazi_config::MANAGER;
 use yazi_fs::{Files, Folder};
 use yazi_plugin::isolate;
 use yazi_proxy::WATCHER;
-use yazi_shared::{fs::{symlink_realname, Cha, File, FilesOp, Url}, RoCell};
+use yazi_shared::{fs::{expand_path, is_remote, symlink_realname, Cha, File, FilesOp, Url}, RoCell};
 
 use super::Linked;
 
@@ -17,14 +18,16 @@ pub(crate) static WATCHED: RoCell<RwLock<HashSet<Url>>> = RoCell::new();
 pub static LINKED: RoCell<RwLock<Linked>> = RoCell::new();
 
 pub struct Watcher {
-	in_tx:  watch::Sender<HashSet<Url>>,
-	out_tx: mpsc::UnboundedSender<Url>,
+	in_tx:   watch::Sender<HashSet<Url>>,
+	out_tx:  mpsc::UnboundedSender<Url>,
+	poll_tx: watch::Sender<Option<Url>>,
 }
 
 impl Watcher {
 	pub(super) fn serve() -> Self {
 		let (in_tx, in_rx) = watch::channel(Default::default());
 		let (out_tx, out_rx) = mpsc::unbounded_channel();
+		let (poll_tx, poll_rx) = watch::channel(None);
 
 		let out_tx_ = out_tx.clone();
 		let watcher = RecommendedWatcher::new(
@@ -42,7 +45,8 @@ impl Watcher {
 
 		tokio::spawn(Self::fan_in(in_rx, watcher.unwrap()));
 		tokio::spawn(Self::fan_out(out_rx));
-		Self { in_tx, out_tx }
+		tokio::spawn(Self::poll(poll_rx));
+		Self { in_tx, out_tx, poll_tx }
 	}
 
 	pub(super) fn watch(&mut self, mut new: HashSet<&Url>) {
@@ -50,6 +54,17 @@ impl Watcher {
 		self.in_tx.send(new.into_iter().cloned().collect()).ok();
 	}
 
+	// Only the focused directory is polled
+	pub(super) fn focus(&self, cwd: &Url) {
+		self.poll_tx.send_if_modified(|old| {
+			if old.as_ref() == Some(cwd) {
+				return false;
+			}
+			*old = Some(cwd.clone());
+			true
+		});
+	}
+
 	pub(super) fn push_file(&self, url: Url) {
 		if url.parent_url().is_some_and(|p| WATCHED.read().contains(&p)) {
 			self.out_tx.send(url).ok();
@@ -153,6 +168,62 @@ impl Watcher {
 		}
 	}
 
+	// Directories on network filesystems are re-read at an interval, which is
+	// doubled each time nothing has changed, up to 8 times as long.
+	async fn poll(mut rx: watch::Receiver<Option<Url>>) {
+		loop {
+			let cwd = rx.borrow_and_update().clone();
+			if let Some(cwd) = cwd.filter(|u| u.is_regular() && MANAGER.poll_interval > 0) {
+				let path = cwd.to_path_buf();
+				let polled = MANAGER.poll_paths.iter().any(|p| path.starts_with(expand_path(p)))
+					|| spawn_blocking(move || is_remote(&path)).await.unwrap_or(false);
+
+				if polled {
+					Self::poll_dir(&cwd, &mut rx).await;
+					continue;
+				}
+			}
+
+			if rx.changed().await.is_err() {
+				break;
+			}
+		}
+	}
+
+	async fn poll_dir(cwd: &Url, rx: &mut watch::Receiver<Option<Url>>) {
+		let snapshot =
+			|files: Vec<File>| -> HashMap<_, _> { files.into_iter().map(|f| (f.url(), f)).collect() };
+
+		let base = Duration::from_secs(MANAGER.poll_interval as u64);
+		let (mut last, mut wait) = (HashMap::new(), base);
+		if let Ok(files) = Files::from_dir_bulk(cwd).await {
+			last = snapshot(files);
+		}
+
+		loop {
+			select! {
+				_ = rx.changed() => return,
+				_ = sleep(wait) => {},
+			}
+
+			let Ok(files) = Files::from_dir_bulk(cwd).await else { continue };
+			let now = snapshot(files);
+			let (deleted, upserted) = poll_diff(&last, &now);
+
+			let idle = deleted.is_empty() && upserted.is_empty();
+			wait = if idle { (wait * 2).min(base * 8) } else { base };
+			last = now;
+
+			let _permit = WATCHER.acquire().await.unwrap();
+			if !deleted.is_empty() {
+				FilesOp::Deleting(cwd.clone(), deleted).emit();
+			}
+			if !upserted.is_empty() {
+				FilesOp::Upserting(cwd.clone(), upserted).emit();
+			}
+		}
+	}
+
 	async fn sync_linked() {
 		let mut new = WATCHED.read().clone();
 
@@ -176,3 +247,41 @@ impl Watcher {
 		go(old).await;
 	}
 }
+
+// The files gone from `old`, and those added or changed in `new`
+fn poll_diff(old: &HashMap<Url, File>, new: &HashMap<Url, File>) -> (Vec<Url>, HashMap<Url, File>) {
+	let deleted = old.keys().filter(|&u| !new.contains_key(u)).cloned().collect();
+	let upserted = new
+		.iter()
+		.filter(|&(u, f)| !old.get(u).is_some_and(|o| o.cha.hits(f.cha)))
+		.map(|(u, f)| (u.clone(), f.clone()))
+		.collect();
+	(deleted, upserted)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_poll_diff() {
+		let file = |name: &str, len| {
+			let mut f = File::from_dummy(Url::from(format!("/tmp/{name}")), None);
+			f.cha.len = len;
+			(f.url(), f)
+		};
+
+		let old = HashMap::from_iter([file("a", 1), file("b", 2), file("c", 3)]);
+		let new = HashMap::from_iter([file("a", 1), file("b", 5), file("d", 4)]);
+
+		let (deleted, upserted) = poll_diff(&old, &new);
+		assert_eq!(deleted, [Url::from("/tmp/c")]);
+
+		let mut upserted: Vec<_> = upserted.into_keys().collect();
+		upserted.sort_unstable();
+		assert_eq!(upserted, [Url::from("/tmp/b"), Url::from("/tmp/d")]);
+
+		let (deleted, upserted) = poll_diff(&old, &old);
+		assert!(deleted.is_empty() && upserted.is_empty());
+	}
+}
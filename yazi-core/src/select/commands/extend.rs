@@ -0,0 +1,23 @@
+use yazi_shared::{event::Cmd, render};
+
+use crate::select::Select;
+
+pub struct Opt {
+	items: Vec<String>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { items: c.take_any("items").unwrap_or_default() } }
+}
+
+impl Select {
+	pub fn extend(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		if !self.visible || opt.items.is_empty() {
+			return;
+		}
+
+		self.items.extend(opt.items);
+		render!();
+	}
+}
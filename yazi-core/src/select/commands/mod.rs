@@ -1,3 +1,4 @@
 mod arrow;
 mod close;
+mod extend;
 mod show;
@@ -31,6 +31,12 @@ impl<T: Eq> Backstack<T> {
 		}
 	}
 
+	pub fn replace(&mut self, item: T) {
+		if let Some(cur) = self.stack.get_mut(self.cursor) {
+			*cur = item;
+		}
+	}
+
 	pub fn shift_backward(&mut self) -> Option<&T> {
 		if self.cursor > 0 {
 			self.cursor -= 1;
@@ -79,5 +85,9 @@ mod tests {
 		assert_eq!(bs.stack[bs.cursor], 4);
 		assert_eq!(bs.shift_forward(), None);
 		assert_eq!(bs.shift_backward(), Some(&2));
+
+		bs.replace(5);
+		assert_eq!(bs.shift_forward(), Some(&4));
+		assert_eq!(bs.shift_backward(), Some(&5));
 	}
 }
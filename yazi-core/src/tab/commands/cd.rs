@@ -1,47 +1,84 @@
-use std::{mem, time::Duration};
+use std::{mem, path::{Path, PathBuf}, time::Duration};
 
-use tokio::{fs, pin};
+use tokio::{fs, pin, sync::mpsc};
 use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
-use yazi_config::popup::InputCfg;
+use yazi_config::{popup::InputCfg, MANAGER, PREVIEW};
 use yazi_dds::Pubsub;
-use yazi_proxy::{CompletionProxy, InputProxy, ManagerProxy, TabProxy};
-use yazi_shared::{event::{Cmd, Data}, fs::{expand_path, Url}, render, Debounce, InputError};
+use yazi_proxy::{AppProxy, CompletionProxy, InputProxy, ManagerProxy, TabProxy};
+use yazi_shared::{emit, event::{Cmd, Data, Outcome}, fs::{expand_path, symlink_realpath, unset_var, Url}, render, Debounce, InputError, Layer, RoCell};
 
 use crate::tab::Tab;
 
+// Entering directories within this long of each other only previews the last
+const PEEK_DEBOUNCE: Duration = Duration::from_millis(50);
+
+static PEEK_TX: RoCell<mpsc::UnboundedSender<Option<Url>>> = RoCell::new();
+static PEEK_RX: RoCell<mpsc::UnboundedReceiver<Option<Url>>> = RoCell::new();
+
 pub struct Opt {
 	target:      Url,
 	interactive: bool,
+	fix_case:    bool,
+	replace:     Option<Url>,
+	collapse:    bool,
+	collapsed:   Option<Url>,
 }
 
 impl From<Cmd> for Opt {
 	fn from(mut c: Cmd) -> Self {
-		let mut target = c.take_first().and_then(Data::into_url).unwrap_or_default();
-		if target.is_regular() {
-			target.set_path(expand_path(&target))
-		}
+		// Only typed paths are expanded, URLs are taken as they are
+		let target = match c.take_first() {
+			Some(Data::String(s)) => {
+				let mut target = Url::from(s);
+				if target.is_regular() {
+					target.set_path(expand_path(&target))
+				}
+				target
+			}
+			d => d.and_then(Data::into_url).unwrap_or_default(),
+		};
 
-		Self { target, interactive: c.bool("interactive") }
+		let replace = c.take("replace").and_then(Data::into_url);
+		Self {
+			fix_case: target.is_regular() && replace.is_none(),
+			target,
+			interactive: c.bool("interactive"),
+			replace,
+			collapse: c.bool("collapse"),
+			collapsed: c.take("collapsed").and_then(Data::into_url),
+		}
 	}
 }
 impl From<Url> for Opt {
-	fn from(target: Url) -> Self { Self { target, interactive: false } }
+	fn from(target: Url) -> Self { (target, false).into() }
+}
+impl From<(Url, bool)> for Opt {
+	fn from((target, collapse): (Url, bool)) -> Self {
+		Self { target, interactive: false, fix_case: false, replace: None, collapse, collapsed: None }
+	}
 }
 
 impl Tab {
-	pub fn cd(&mut self, opt: impl Into<Opt>) {
+	pub fn cd(&mut self, opt: impl Into<Opt>) -> Outcome {
 		if !self.try_escape_visual() {
-			return;
+			return Outcome::Done;
 		}
 
 		let opt = opt.into() as Opt;
 		if opt.interactive {
 			return self.cd_interactive();
+		} else if let Some(stale) = opt.replace {
+			self.cd_replace(stale, opt.target);
+			return Outcome::Done;
+		} else if opt.collapse && opt.target.is_regular() {
+			self.cd_collapse(opt.target);
+			return Outcome::Done;
 		}
 
 		if self.current.cwd == opt.target {
-			return;
+			return Outcome::Done;
 		}
+		self.collapsed = opt.collapsed.map(|top| (top, opt.target.clone()));
 
 		// Take parent to history
 		if let Some(rep) = self.parent.take() {
@@ -65,12 +102,97 @@ impl Tab {
 			self.backstack.push(opt.target.clone());
 		}
 
+		if opt.fix_case {
+			Self::cd_fix_case(opt.target);
+		}
+
+		if PREVIEW.eager_peek {
+			self.peek_eagerly(&PEEK_TX);
+		}
+
+		Pubsub::pub_from_cd(self.idx, &self.current.cwd);
+		ManagerProxy::refresh();
+		render!();
+		Outcome::Done
+	}
+
+	// Previews the entry hovered in the new directory, which is the one hovered
+	// last time if it's been entered before, or the first one once it's loaded
+	#[inline]
+	fn peek_eagerly(&self, tx: &mpsc::UnboundedSender<Option<Url>>) {
+		tx.send(self.current.hovered().map(|h| h.url())).ok();
+	}
+
+	pub(crate) fn init_peek() {
+		let (tx, rx) = mpsc::unbounded_channel();
+		PEEK_TX.init(tx);
+		PEEK_RX.init(rx);
+	}
+
+	pub(crate) fn serve_peek() {
+		tokio::spawn(Self::debounce_peek(PEEK_RX.drop(), |cmd| emit!(Call(cmd, Layer::Manager))));
+	}
+
+	// The directories entered in a row only have the last one previewed
+	async fn debounce_peek(rx: mpsc::UnboundedReceiver<Option<Url>>, emit: impl Fn(Cmd)) {
+		let rx = Debounce::new(UnboundedReceiverStream::new(rx), PEEK_DEBOUNCE);
+		pin!(rx);
+		while let Some(hovered) = rx.next().await {
+			let mut cmd = Cmd::new("peek").with_bool("eager", true);
+			if let Some(url) = hovered {
+				cmd.args.insert("only-if".to_owned(), Data::Url(url));
+			}
+			emit(cmd);
+		}
+	}
+
+	// Typed paths may differ in case from what's on disk on case-insensitive
+	// filesystems, so only the final component is looked up to keep it cheap.
+	// It's a no-op on case-sensitive ones, as such a path would not exist.
+	fn cd_fix_case(target: Url) {
+		tokio::spawn(async move {
+			let Ok(real) = symlink_realpath(&target).await else { return };
+			if real != *target {
+				let mut cmd = Cmd::new("cd");
+				cmd.args.insert("0".to_owned(), Data::Url(Url::from(real)));
+				cmd.args.insert("replace".to_owned(), Data::Url(target));
+				emit!(Call(cmd, Layer::Manager));
+			}
+		});
+	}
+
+	// Goes on into the directories that hold nothing but a single subdirectory,
+	// and remembers where it started, so that `leave` gets back above all of them
+	fn cd_collapse(&self, target: Url) {
+		let (idx, hidden) = (self.idx, self.conf.show_hidden);
+		tokio::spawn(async move {
+			let dir = target.clone();
+			let walk = move || collapse(&dir, MANAGER.collapse_depth, hidden);
+			let Ok(bottom) = tokio::task::spawn_blocking(walk).await else { return };
+
+			let mut cmd = Cmd::new("cd").with("tab", idx);
+			if bottom != *target {
+				cmd.args.insert("collapsed".to_owned(), Data::Url(target));
+			}
+			cmd.args.insert("0".to_owned(), Data::Url(Url::from(bottom)));
+			emit!(Call(cmd, Layer::Manager));
+		});
+	}
+
+	fn cd_replace(&mut self, stale: Url, target: Url) {
+		if self.current.cwd != stale {
+			return;
+		}
+
+		self.current = self.history_new(&target);
+		self.backstack.replace(target);
+
 		Pubsub::pub_from_cd(self.idx, &self.current.cwd);
 		ManagerProxy::refresh();
 		render!();
 	}
 
-	fn cd_interactive(&mut self) {
+	fn cd_interactive(&mut self) -> Outcome {
 		tokio::spawn(async move {
 			let rx = InputProxy::show(InputCfg::cd());
 
@@ -80,6 +202,10 @@ impl Tab {
 			while let Some(result) = rx.next().await {
 				match result {
 					Ok(s) => {
+						if let Some(name) = unset_var(&s) {
+							AppProxy::notify_warn("Cd", format!("Environment variable `{name}` is not set"));
+						}
+
 						let u = Url::from(expand_path(s));
 						let Ok(meta) = fs::metadata(&u).await else {
 							return;
@@ -98,5 +224,79 @@ impl Tab {
 				}
 			}
 		});
+		Outcome::Prompted
+	}
+}
+
+// The deepest directory reached from `dir` by going into the only entry of each
+// one, as long as that is a directory, and at most `depth` levels down
+fn collapse(dir: &Path, depth: usize, hidden: bool) -> PathBuf {
+	let mut dir = dir.to_owned();
+	for _ in 0..depth {
+		let Ok(it) = std::fs::read_dir(&dir) else { break };
+		let mut it =
+			it.flatten().filter(|e| hidden || !e.file_name().as_encoded_bytes().starts_with(b"."));
+
+		let (Some(only), None) = (it.next(), it.next()) else { break };
+		if !only.path().is_dir() {
+			break;
+		}
+		dir = only.path();
+	}
+	dir
+}
+
+#[cfg(test)]
+mod tests {
+	use yazi_shared::fs::{File, FilesOp};
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_peek_eagerly() {
+		crate::init_tests();
+		let ((tx, rx), (cmd_tx, mut cmd_rx)) = (mpsc::unbounded_channel(), mpsc::unbounded_channel());
+		let served = tokio::spawn(Tab::debounce_peek(rx, move |c| _ = cmd_tx.send(c)));
+		let mut tab = Tab::default();
+
+		let (a, b) = (Url::from("/yazi-test-peek/a"), Url::from("/yazi-test-peek/b"));
+		let files = ["x", "y", "z"].map(|n| File { url: a.join(n), ..Default::default() });
+		tab.cd(a.clone());
+		tab.current.update(FilesOp::Full(a.clone(), files.to_vec(), Default::default()));
+		tab.current.repos(Some(&a.join("y")));
+
+		// Only the last of the directories entered in a row is previewed, at the
+		// entry hovered there before
+		for url in [b, a.clone()] {
+			tab.cd(url);
+			tab.peek_eagerly(&tx);
+		}
+		drop(tx);
+		served.await.unwrap();
+
+		let mut cmd = cmd_rx.recv().await.unwrap();
+		assert!(cmd.bool("eager") && !cmd.bool("force"));
+		assert_eq!(cmd.take("only-if").and_then(Data::into_url), Some(a.join("y")));
+		assert!(cmd_rx.recv().await.is_none());
+	}
+
+	#[test]
+	fn test_collapse() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		std::fs::create_dir_all(dir.join("a/b/c/d")).unwrap();
+		std::fs::write(dir.join("a/b/c/file"), "").unwrap();
+		std::fs::write(dir.join("a/b/.hidden"), "").unwrap();
+		std::fs::create_dir_all(dir.join("x/y")).unwrap();
+		std::fs::write(dir.join("x/y/file"), "").unwrap();
+
+		// Stops at `c`, which holds more than one entry
+		assert_eq!(collapse(&dir.join("a"), 8, false), dir.join("a/b/c"));
+		assert_eq!(collapse(&dir.join("a"), 1, false), dir.join("a/b"));
+		assert_eq!(collapse(&dir.join("a"), 8, true), dir.join("a/b"));
+
+		// A single file isn't gone into
+		assert_eq!(collapse(&dir.join("x"), 8, false), dir.join("x/y"));
+		assert_eq!(collapse(&dir.join("gone"), 8, false), dir.join("gone"));
 	}
 }
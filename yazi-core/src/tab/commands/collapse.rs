@@ -0,0 +1,13 @@
+use yazi_shared::event::Cmd;
+
+use crate::tab::Tab;
+
+impl Tab {
+	pub fn collapse(&mut self, mut c: Cmd) {
+		self.conf.collapse_dirs = match c.take_first_str().as_deref() {
+			Some("on") => true,
+			Some("off") => false,
+			_ => !self.conf.collapse_dirs,
+		};
+	}
+}
@@ -1,16 +1,31 @@
 use std::ffi::{OsStr, OsString};
 
+use yazi_adapter::Image;
+use yazi_config::MANAGER;
 use yazi_plugin::CLIPBOARD;
-use yazi_shared::event::Cmd;
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Cmd, fs::Url, shell::escape_os_str};
 
 use crate::tab::Tab;
 
 pub struct Opt {
-	type_: String,
+	type_:    String,
+	column:   bool,
+	compound: bool,
+	template: String,
+	each:     bool,
 }
 
 impl From<Cmd> for Opt {
-	fn from(mut c: Cmd) -> Self { Self { type_: c.take_first_str().unwrap_or_default() } }
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			type_:    c.take_first_str().unwrap_or_default(),
+			column:   c.bool("column"),
+			compound: c.bool("compound"),
+			template: c.take_str("1").unwrap_or_default(),
+			each:     c.bool("each"),
+		}
+	}
 }
 
 impl Tab {
@@ -18,16 +33,27 @@ impl Tab {
 		let opt = opt.into() as Opt;
 		if !self.try_escape_visual() {
 			return;
+		} else if opt.type_ == "image" {
+			return self.copy_image();
+		} else if opt.type_ == "command" {
+			return self.copy_command(&opt.template, opt.each);
 		}
 
 		let mut s = OsString::new();
 		let mut it = self.selected_or_hovered(true).peekable();
 		while let Some(u) = it.next() {
+			let (mut with_line, name) = (OsString::new(), u.file_name().unwrap_or_default());
 			s.push(match opt.type_.as_str() {
 				"path" => u.as_os_str(),
 				"dirname" => u.parent().map_or(OsStr::new(""), |p| p.as_os_str()),
-				"filename" => u.file_name().unwrap_or(OsStr::new("")),
-				"name_without_ext" => u.file_stem().unwrap_or(OsStr::new("")),
+				"filename" => name,
+				"stem" | "name_without_ext" => split_ext(name, opt.compound).0,
+				"extension" => split_ext(name, opt.compound).1.unwrap_or_default(),
+				"path_with_line" => {
+					with_line.push(u.as_os_str());
+					with_line.push(self.position_of(u, opt.column));
+					&with_line
+				}
 				_ => return,
 			});
 			if it.peek().is_some() {
@@ -37,4 +63,169 @@ impl Tab {
 
 		futures::executor::block_on(CLIPBOARD.set(s));
 	}
+
+	fn copy_command(&self, template: &str, each: bool) {
+		if template.is_empty() {
+			return AppProxy::notify_warn("Copy command", "No command template given");
+		}
+
+		let paths: Vec<_> = self.selected_or_hovered(true).map(|u| u.as_os_str()).collect();
+		if !paths.is_empty() {
+			futures::executor::block_on(CLIPBOARD.set(fill_command(template, &paths, each)));
+		}
+	}
+
+	fn copy_image(&self) {
+		let Some(url) = self.current.hovered().filter(|h| !h.is_dir()).map(|h| h.url()) else {
+			return;
+		};
+
+		tokio::spawn(async move {
+			let data = match Image::clipboard(&url, MANAGER.copy_image_max).await {
+				Ok(data) => data,
+				Err(e) => {
+					return AppProxy::notify_warn(
+						"Copy image",
+						format!("Only images can be copied as image data, `{}`: {e}", url.display()),
+					);
+				}
+			};
+			if !CLIPBOARD.set_image(data).await {
+				AppProxy::notify_warn("Copy image", "No clipboard tool could take the image");
+			}
+		});
+	}
+
+	fn position_of(&self, url: &Url, column: bool) -> String {
+		match self.position {
+			Some((ref u, line, col)) if u == url => {
+				position(&MANAGER.line_separator, line, Some(col).filter(|_| column))
+			}
+			_ => String::new(),
+		}
+	}
+}
+
+// `:12` or `:12:5` for a file at line 12 and column 5, with the `line_separator`
+// going before the line only, so it's ` +12:5` for editors like Kakoune.
+fn position(sep: &str, line: usize, column: Option<usize>) -> String {
+	match column {
+		Some(col) => format!("{sep}{line}:{col}"),
+		None => format!("{sep}{line}"),
+	}
+}
+
+// Replaces each `{}` in `template` with the quoted `paths`, or appends them if
+// there's none. They go into one command, or one per line with `each`.
+fn fill_command(template: &str, paths: &[&OsStr], each: bool) -> OsString {
+	let fill = |out: &mut OsString, args: &[&OsStr]| {
+		let mut list = OsString::new();
+		for (i, &arg) in args.iter().enumerate() {
+			if i > 0 {
+				list.push(" ");
+			}
+			list.push(escape_os_str(arg));
+		}
+
+		let mut parts = template.split("{}");
+		out.push(parts.next().unwrap_or_default());
+		if !template.contains("{}") {
+			out.push(" ");
+			out.push(&list);
+		}
+		for part in parts {
+			out.push(&list);
+			out.push(part);
+		}
+	};
+
+	let mut out = OsString::new();
+	if !each {
+		fill(&mut out, paths);
+		return out;
+	}
+	for (i, &path) in paths.iter().enumerate() {
+		if i > 0 {
+			out.push("\n");
+		}
+		fill(&mut out, &[path]);
+	}
+	out
+}
+
+// The stem and extension of `name`, split at its last dot, or its first one if
+// `compound`, so `x.tar.gz` has either `gz` or `tar.gz`. A leading dot belongs
+// to the stem, so `.bashrc` has no extension.
+fn split_ext(name: &OsStr, compound: bool) -> (&OsStr, Option<&OsStr>) {
+	let b = name.as_encoded_bytes();
+	let dot = if compound {
+		b.iter().skip(1).position(|&c| c == b'.').map(|i| i + 1)
+	} else {
+		b.iter().rposition(|&c| c == b'.').filter(|&i| i > 0)
+	};
+
+	let Some(i) = dot else { return (name, None) };
+	// SAFETY: both halves are split at an ASCII character
+	unsafe {
+		(
+			OsStr::from_encoded_bytes_unchecked(&b[..i]),
+			Some(OsStr::from_encoded_bytes_unchecked(&b[i + 1..])),
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn split(name: &str, compound: bool) -> (&str, Option<&str>) {
+		let (stem, ext) = split_ext(OsStr::new(name), compound);
+		(stem.to_str().unwrap(), ext.map(|e| e.to_str().unwrap()))
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_fill_command() {
+		let paths = ["/tmp/a b", "/tmp/it's \"quoted\"", "/tmp/plain"].map(OsStr::new);
+		let fill = |template, each| fill_command(template, &paths, each).into_string().unwrap();
+
+		let s = fill("tar czf out.tgz {}", false);
+		assert_eq!(s, r#"tar czf out.tgz '/tmp/a b' '/tmp/it'\''s "quoted"' /tmp/plain"#);
+		let args = yazi_shared::shell::split_unix(&s).unwrap();
+		assert_eq!(args[3..], paths.map(|p| p.to_str().unwrap()));
+
+		assert_eq!(fill("ls -l", false), fill("ls -l {}", false));
+		assert_eq!(fill("cp {} {}.bak", true).lines().collect::<Vec<_>>(), [
+			"cp '/tmp/a b' '/tmp/a b'.bak",
+			r#"cp '/tmp/it'\''s "quoted"' '/tmp/it'\''s "quoted"'.bak"#,
+			"cp /tmp/plain /tmp/plain.bak",
+		]);
+	}
+
+	#[test]
+	fn test_position() {
+		assert_eq!(position(":", 12, None), ":12");
+		assert_eq!(position(":", 12, Some(5)), ":12:5");
+		assert_eq!(position(" +", 12, None), " +12");
+		assert_eq!(position(" +", 12, Some(5)), " +12:5");
+	}
+
+	#[test]
+	fn test_split_ext() {
+		assert_eq!(split("a.txt", false), ("a", Some("txt")));
+		assert_eq!(split("a.txt", true), ("a", Some("txt")));
+		assert_eq!(split("noext", false), ("noext", None));
+
+		assert_eq!(split("x.tar.gz", false), ("x.tar", Some("gz")));
+		assert_eq!(split("x.tar.gz", true), ("x", Some("tar.gz")));
+	}
+
+	#[test]
+	fn test_split_ext_dotfiles() {
+		assert_eq!(split(".bashrc", false), (".bashrc", None));
+		assert_eq!(split(".bashrc", true), (".bashrc", None));
+
+		assert_eq!(split(".config.bak", false), (".config", Some("bak")));
+		assert_eq!(split(".local.tar.gz", true), (".local", Some("tar.gz")));
+	}
 }
@@ -0,0 +1,18 @@
+use yazi_proxy::ManagerProxy;
+use yazi_shared::event::Cmd;
+
+use crate::tab::Tab;
+
+impl Tab {
+	pub fn dir_tree(&mut self, mut c: Cmd) {
+		let state = match c.take_first_str().as_deref() {
+			Some("show") => true,
+			Some("hide") => false,
+			_ => !self.conf.dir_tree,
+		};
+
+		if self.conf.patch(|new| new.dir_tree = state) {
+			ManagerProxy::peek(true);
+		}
+	}
+}
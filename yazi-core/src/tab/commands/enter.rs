@@ -1,9 +1,73 @@
-use yazi_shared::event::Cmd;
+use yazi_config::{manager::EnterFile, MANAGER};
+use yazi_proxy::ManagerProxy;
+use yazi_shared::{event::Cmd, fs::{File, Url}};
 
 use crate::tab::Tab;
 
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+	Cd(Url),
+	Open(bool),
+}
+
 impl Tab {
 	pub fn enter(&mut self, _: Cmd) {
-		self.current.hovered().filter(|h| h.is_dir()).map(|h| h.url()).map(|u| self.cd(u));
+		match action(self.current.hovered(), MANAGER.enter_file) {
+			Some(Action::Cd(url)) => _ = self.cd((url, self.conf.collapse_dirs)),
+			Some(Action::Open(interactive)) => ManagerProxy::open_hovered(interactive),
+			None => {}
+		}
+	}
+}
+
+// The `cha` of a symlink is that of its target, so a link to a directory is
+// entered, and a broken one is left alone
+fn action(hovered: Option<&File>, file: EnterFile) -> Option<Action> {
+	let h = hovered?;
+	if h.is_dir() {
+		return Some(Action::Cd(h.url()));
+	} else if h.cha.is_orphan() {
+		return None;
+	}
+
+	match file {
+		EnterFile::None => None,
+		EnterFile::Open => Some(Action::Open(false)),
+		EnterFile::Interactive => Some(Action::Open(true)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_action() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		std::fs::create_dir_all(dir.join("sub")).unwrap();
+		std::fs::write(dir.join("file"), "").unwrap();
+
+		let file = |name: &str| File::from(Url::from(dir.join(name)));
+		let (sub, f) = (file("sub").await.unwrap(), file("file").await.unwrap());
+		assert_eq!(action(Some(&sub), EnterFile::Open), Some(Action::Cd(sub.url())));
+		assert_eq!(action(Some(&f), EnterFile::Open), Some(Action::Open(false)));
+		assert_eq!(action(Some(&f), EnterFile::Interactive), Some(Action::Open(true)));
+		assert_eq!(action(Some(&f), EnterFile::None), None);
+		assert_eq!(action(None, EnterFile::Open), None);
+
+		#[cfg(unix)]
+		{
+			std::os::unix::fs::symlink(dir.join("sub"), dir.join("link-sub")).unwrap();
+			std::os::unix::fs::symlink(dir.join("file"), dir.join("link-file")).unwrap();
+			std::os::unix::fs::symlink(dir.join("gone"), dir.join("link-gone")).unwrap();
+
+			let link = file("link-sub").await.unwrap();
+			assert_eq!(action(Some(&link), EnterFile::None), Some(Action::Cd(link.url())));
+			let link = file("link-file").await.unwrap();
+			assert_eq!(action(Some(&link), EnterFile::Open), Some(Action::Open(false)));
+			let link = file("link-gone").await.unwrap();
+			assert_eq!(action(Some(&link), EnterFile::Open), None);
+		}
 	}
 }
@@ -1,6 +1,7 @@
 use bitflags::bitflags;
+use yazi_dds::Pubsub;
 use yazi_proxy::{AppProxy, ManagerProxy};
-use yazi_shared::{event::Cmd, render, render_and};
+use yazi_shared::{event::Cmd, fs::Url, render, render_and};
 
 use crate::tab::Tab;
 
@@ -85,6 +86,7 @@ impl Tab {
 		}
 
 		self.selected.clear();
+		Pubsub::pub_from_select(self.idx, self.selected.keys());
 		if self.current.hovered().is_some_and(|h| h.is_dir()) {
 			ManagerProxy::peek(true);
 		}
@@ -93,11 +95,28 @@ impl Tab {
 
 	pub fn escape_search(&mut self) -> bool {
 		let b = self.current.cwd.is_search();
+		let hovered = self.current.hovered().filter(|_| b).map(|h| h.url());
 		self.search_stop();
 
+		// Stay on the hovered result, or the directory it was found in
+		if let Some(target) = hovered.and_then(|u| self.search_origin(u)) {
+			self.current.hover(&target);
+			ManagerProxy::hover(Some(target), self.idx);
+		}
 		render_and!(b)
 	}
 
+	// The entry of the current directory that contains `url`
+	fn search_origin(&self, mut url: Url) -> Option<Url> {
+		while let Some(parent) = url.parent_url() {
+			if parent == self.current.cwd {
+				return Some(url);
+			}
+			url = parent;
+		}
+		None
+	}
+
 	pub fn try_escape_visual(&mut self) -> bool {
 		let select = self.mode.is_select();
 		let Some((_, indices)) = self.mode.take_visual() else {
@@ -109,16 +128,63 @@ impl Tab {
 			indices.into_iter().filter_map(|i| self.current.files.get(i)).map(|f| &f.url).collect();
 
 		let same = !self.current.cwd.is_search();
-		if !select {
+		let len = self.selected.len();
+		let b = if !select {
 			self.selected.remove_many(&urls, same);
-		} else if self.selected.add_many(&urls, same) != urls.len() {
+			true
+		} else {
+			self.selected.add_many(&urls, same) == urls.len()
+		};
+
+		if self.selected.len() != len {
+			Pubsub::pub_from_select(self.idx, self.selected.keys());
+		}
+		if !b {
 			AppProxy::notify_warn(
 				"Escape visual mode",
 				"Some files cannot be selected, due to path nesting conflict.",
 			);
-			return false;
 		}
+		b
+	}
+}
 
-		true
+#[cfg(test)]
+mod tests {
+	use yazi_fs::{Folder, FilterCase};
+	use yazi_shared::fs::{Cha, File, FilesOp};
+
+	use super::*;
+	use crate::tab::{Finder, Mode};
+
+	#[test]
+	fn test_escape_all() {
+		crate::init_tests();
+		let files = |v: &[&str]| v.iter().map(|&p| File::from_dummy(Url::from(p), None)).collect();
+
+		let mut tab = Tab::default();
+		let cwd = Url::from("/d");
+		let mut regular = Folder::from(&cwd);
+		regular.update(FilesOp::Full(cwd.clone(), files(&["/d/a", "/d/b"]), Cha::dummy()));
+		tab.history.insert(cwd.clone(), regular);
+
+		let search = cwd.into_search("y".to_owned());
+		tab.current = Folder::from(&search);
+		tab.current.update(FilesOp::Full(search, files(&["/d/a/x", "/d/b/y"]), Cha::dummy()));
+		tab.filter_do(Cmd::args("filter_do", &["y"]));
+		tab.finder = Some(Finder::new("y", FilterCase::Smart).unwrap());
+		tab.mode = Mode::Unset(0, [0].into());
+		assert_eq!(tab.current.hovered().unwrap().url, Url::from("/d/b/y"));
+
+		tab.escape(Cmd::new("escape").with_bool("all", true));
+		assert!(tab.finder.is_none());
+		assert!(!tab.mode.is_visual());
+		assert!(tab.current.files.filter().is_none());
+		assert_eq!(tab.current.cwd, Url::from("/d"));
+		assert_eq!(tab.current.hovered().unwrap().url, Url::from("/d/b"));
+
+		// Nothing left to clear
+		tab.escape(Cmd::new("escape").with_bool("all", true));
+		assert_eq!(tab.current.hovered().unwrap().url, Url::from("/d/b"));
 	}
 }
@@ -0,0 +1,15 @@
+use yazi_shared::{event::Cmd, render};
+
+use crate::tab::Tab;
+
+impl Tab {
+	pub fn extensions(&mut self, mut c: Cmd) {
+		let state = match c.take_first_str().as_deref() {
+			Some("show") => true,
+			Some("hide") => false,
+			_ => !self.conf.show_extensions,
+		};
+
+		render!(self.conf.patch(|new| new.show_extensions = state));
+	}
+}
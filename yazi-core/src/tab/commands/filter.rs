@@ -5,7 +5,7 @@ use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use yazi_config::popup::InputCfg;
 use yazi_fs::{Filter, FilterCase};
 use yazi_proxy::{InputProxy, ManagerProxy};
-use yazi_shared::{emit, event::Cmd, render, Debounce, InputError, Layer};
+use yazi_shared::{emit, event::{Cmd, Outcome}, render, Debounce, InputError, Layer};
 
 use crate::tab::Tab;
 
@@ -13,6 +13,7 @@ use crate::tab::Tab;
 pub struct Opt {
 	pub query: String,
 	pub case:  FilterCase,
+	pub tag:   bool,
 	pub done:  bool,
 }
 
@@ -21,14 +22,15 @@ impl From<Cmd> for Opt {
 		Self {
 			query: c.take_first_str().unwrap_or_default(),
 			case:  FilterCase::from(&c),
+			tag:   c.bool("tag"),
 			done:  c.bool("done"),
 		}
 	}
 }
 
 impl Tab {
-	pub fn filter(&mut self, opt: impl Into<Opt>) {
-		let opt = opt.into() as Opt;
+	pub fn filter(&mut self, opt: impl Into<Opt>) -> Outcome {
+		let (opt, idx) = (opt.into() as Opt, self.idx);
 		tokio::spawn(async move {
 			let rx = InputProxy::show(InputCfg::filter());
 
@@ -43,11 +45,13 @@ impl Tab {
 					Cmd::args("filter_do", &[s])
 						.with_bool("smart", opt.case == FilterCase::Smart)
 						.with_bool("insensitive", opt.case == FilterCase::Insensitive)
-						.with_bool("done", done),
+						.with_bool("done", done)
+						.with("tab", idx),
 					Layer::Manager
 				));
 			}
 		});
+		Outcome::Prompted
 	}
 
 	pub fn filter_do(&mut self, opt: impl Into<Opt>) {
@@ -55,6 +59,8 @@ impl Tab {
 
 		let filter = if opt.query.is_empty() {
 			None
+		} else if opt.tag {
+			Some(Filter::tag(&opt.query))
 		} else if let Ok(f) = Filter::new(&opt.query, opt.case) {
 			Some(f)
 		} else {
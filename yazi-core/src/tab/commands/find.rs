@@ -5,7 +5,7 @@ use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use yazi_config::popup::InputCfg;
 use yazi_fs::FilterCase;
 use yazi_proxy::InputProxy;
-use yazi_shared::{emit, event::Cmd, render, Debounce, InputError, Layer};
+use yazi_shared::{emit, event::{Cmd, Outcome}, render, Debounce, InputError, Layer};
 
 use crate::tab::{Finder, Tab};
 
@@ -29,9 +29,13 @@ impl From<Cmd> for ArrowOpt {
 	fn from(c: Cmd) -> Self { Self { prev: c.bool("previous") } }
 }
 
+impl From<bool> for ArrowOpt {
+	fn from(prev: bool) -> Self { Self { prev } }
+}
+
 impl Tab {
-	pub fn find(&mut self, opt: impl Into<Opt>) {
-		let opt = opt.into() as Opt;
+	pub fn find(&mut self, opt: impl Into<Opt>) -> Outcome {
+		let (opt, idx) = (opt.into() as Opt, self.idx);
 		tokio::spawn(async move {
 			let rx = InputProxy::show(InputCfg::find(opt.prev));
 
@@ -43,11 +47,13 @@ impl Tab {
 					Cmd::args("find_do", &[s])
 						.with_bool("previous", opt.prev)
 						.with_bool("smart", opt.case == FilterCase::Smart)
-						.with_bool("insensitive", opt.case == FilterCase::Insensitive),
+						.with_bool("insensitive", opt.case == FilterCase::Insensitive)
+						.with("tab", idx),
 					Layer::Manager
 				));
 			}
 		});
+		Outcome::Prompted
 	}
 
 	pub fn find_do(&mut self, opt: impl Into<Opt>) {
@@ -77,6 +83,7 @@ impl Tab {
 			self.arrow(step);
 		}
 
+		self.jump = Some(query);
 		self.finder = Some(finder);
 		render!();
 	}
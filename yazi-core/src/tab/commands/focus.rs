@@ -0,0 +1,19 @@
+use yazi_shared::{event::Cmd, render};
+
+use crate::tab::Tab;
+
+impl Tab {
+	pub fn focus(&mut self, mut c: Cmd) {
+		let focused = match c.take_first_str().as_deref() {
+			Some("preview") => true,
+			Some("list") => false,
+			_ => !self.preview.focused,
+		};
+
+		if self.preview.focused != focused {
+			self.preview.focused = focused;
+			self.preview.count = 0;
+			render!();
+		}
+	}
+}
@@ -0,0 +1,222 @@
+use std::{cmp::Reverse, fs, path::{Path, PathBuf, MAIN_SEPARATOR_STR}, sync::Arc, time::Duration};
+
+use tokio::{pin, select, sync::mpsc, task::spawn_blocking};
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+use yazi_config::popup::InputCfg;
+use yazi_proxy::{CompletionProxy, InputProxy, TabProxy};
+use yazi_shared::{event::{Cmd, Data, Outcome}, fs::Url, fuzzy_score, Debounce, InputError};
+
+use crate::tab::{tree::Ignore, Tab};
+
+// Stop walking once this many entries have been found
+const MAX_ENTRIES: usize = 10000;
+
+// Number of entries sent to the picker at a time
+const CHUNK_SIZE: usize = 500;
+
+// Number of the best matches shown as candidates
+const LIMIT: usize = 30;
+
+pub struct Opt {
+	depth:  usize,
+	hidden: Option<bool>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self {
+		Self {
+			depth:  c.get("depth").and_then(Data::as_usize).unwrap_or(5),
+			hidden: c.get("hidden").and_then(Data::as_bool),
+		}
+	}
+}
+
+impl Tab {
+	pub fn fuzzy(&mut self, opt: impl Into<Opt>) -> Outcome {
+		let opt = opt.into() as Opt;
+		let cwd = self.current.cwd.to_path_buf();
+		let hidden = opt.hidden.unwrap_or(self.conf.show_hidden);
+
+		tokio::spawn(async move {
+			let rx = InputProxy::show(InputCfg::fuzzy());
+			let rx = Debounce::new(UnboundedReceiverStream::new(rx), Duration::from_millis(50));
+			pin!(rx);
+
+			// Dropping `walked` when the picker is closed stops the walk
+			let (tx, mut walked) = mpsc::unbounded_channel();
+			let root = cwd.clone();
+			spawn_blocking(move || Walker::new(root, hidden, tx).walk(opt.depth));
+
+			let (mut entries, mut word, mut ticket) = (vec![], String::new(), None);
+			loop {
+				select! {
+					Some(chunk) = walked.recv() => entries.extend(chunk),
+					result = rx.next() => match result {
+						Some(Ok(s)) => return Self::fuzzy_go(cwd.join(s)).await,
+						Some(Err(InputError::Completed(before, t))) => {
+							CompletionProxy::trigger_cached(t);
+							(word, ticket) = (before, Some(t));
+						}
+						_ => break,
+					},
+				}
+
+				// Already ranked, so they're shown as they are with an empty word
+				if let Some(ticket) = ticket {
+					let cands = Arc::new(Self::fuzzy_rank(&word, &entries));
+					CompletionProxy::show(cands, "\0fuzzy", "", ticket, true);
+				}
+			}
+		});
+		Outcome::Prompted
+	}
+
+	// The entries that fuzzy match the word, the best matches first, and the
+	// shallower ones first among the equally good, as they were walked
+	fn fuzzy_rank(word: &str, entries: &[String]) -> Vec<String> {
+		let mut scored: Vec<_> =
+			entries.iter().filter_map(|e| fuzzy_score(word, e).map(|s| (s, e))).collect();
+		scored.sort_by_key(|&(s, _)| Reverse(s));
+		scored.into_iter().take(LIMIT).map(|(_, e)| e.clone()).collect()
+	}
+
+	async fn fuzzy_go(path: PathBuf) {
+		let url = Url::from(path);
+		match tokio::fs::metadata(&url).await {
+			Ok(m) if m.is_dir() => TabProxy::cd(&url),
+			Ok(_) => TabProxy::reveal(&url),
+			Err(_) => {}
+		}
+	}
+}
+
+struct Walker {
+	root:   PathBuf,
+	hidden: bool,
+	tx:     mpsc::UnboundedSender<Vec<String>>,
+	chunk:  Vec<String>,
+	left:   usize,
+	ignore: Vec<Ignore>,
+}
+
+impl Walker {
+	fn new(root: PathBuf, hidden: bool, tx: mpsc::UnboundedSender<Vec<String>>) -> Self {
+		Self { root, hidden, tx, chunk: vec![], left: MAX_ENTRIES, ignore: vec![] }
+	}
+
+	fn walk(mut self, depth: usize) {
+		let root = self.root.clone();
+		if self.visit(&root, depth) {
+			self.tx.send(self.chunk).ok();
+		}
+	}
+
+	// Lists the entries of a directory before descending into its subdirectories,
+	// so the shallow ones show up first. Returns `false` once the walk should stop.
+	fn visit(&mut self, dir: &Path, depth: usize) -> bool {
+		let Ok(it) = fs::read_dir(dir) else { return true };
+		let ignore = Ignore::load(dir).map(|i| self.ignore.push(i)).is_some();
+
+		let mut dirs = vec![];
+		for entry in it.flatten() {
+			let name = entry.file_name();
+			if name == ".git" || (!self.hidden && name.as_encoded_bytes().starts_with(b".")) {
+				continue;
+			}
+
+			let path = entry.path();
+			let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+			if self.ignore.iter().any(|i| i.matches(&path, is_dir)) {
+				continue;
+			}
+
+			let Ok(rel) = path.strip_prefix(&self.root) else { continue };
+			self.chunk.push(format!(
+				"{}{}",
+				rel.to_string_lossy(),
+				if is_dir { MAIN_SEPARATOR_STR } else { "" }
+			));
+
+			self.left -= 1;
+			if self.left == 0 {
+				// Send what's left of the last chunk before stopping
+				self.tx.send(std::mem::take(&mut self.chunk)).ok();
+				return false;
+			}
+			if !self.flush() {
+				return false;
+			}
+			if is_dir && depth > 1 {
+				dirs.push(path);
+			}
+		}
+
+		for dir in dirs {
+			if !self.visit(&dir, depth - 1) {
+				return false;
+			}
+		}
+
+		if ignore {
+			self.ignore.pop();
+		}
+		true
+	}
+
+	fn flush(&mut self) -> bool {
+		if self.chunk.len() < CHUNK_SIZE {
+			return true;
+		}
+		self.tx.send(std::mem::take(&mut self.chunk)).is_ok()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_walk() {
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		fs::create_dir_all(root.join("a/b")).unwrap();
+		fs::create_dir_all(root.join("target")).unwrap();
+		fs::create_dir_all(root.join(".git")).unwrap();
+		fs::write(root.join(".gitignore"), "/target/\n*.log\n").unwrap();
+		fs::write(root.join("a/b/c.txt"), "").unwrap();
+		fs::write(root.join("a/d.log"), "").unwrap();
+		fs::write(root.join(".env"), "").unwrap();
+
+		let walk = |depth, hidden| {
+			let (tx, mut rx) = mpsc::unbounded_channel();
+			Walker::new(root.to_owned(), hidden, tx).walk(depth);
+
+			let mut found: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).flatten().collect();
+			found.sort_unstable();
+			found
+		};
+
+		let sep = MAIN_SEPARATOR_STR;
+		let c = ["a", "b", "c.txt"].join(sep);
+		assert_eq!(walk(5, false), [format!("a{sep}"), format!("a{sep}b{sep}"), c]);
+		assert_eq!(walk(1, true), [".env", ".gitignore", &format!("a{sep}")]);
+
+		// The last partial chunk is still sent once the limit is hit
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let mut walker = Walker::new(root.to_owned(), false, tx);
+		walker.left = 2;
+		walker.walk(5);
+		assert_eq!(std::iter::from_fn(|| rx.try_recv().ok()).flatten().count(), 2);
+	}
+
+	#[test]
+	fn test_rank() {
+		let entries = ["abc.txt", "tab_close.rs", "src/tab/bar.rs", "tab_create.rs"].map(String::from);
+
+		let ranked = Tab::fuzzy_rank("tbcr", &entries);
+		assert_eq!(ranked, ["tab_create.rs", "tab_close.rs"]);
+
+		assert_eq!(Tab::fuzzy_rank("", &entries), entries);
+		assert!(Tab::fuzzy_rank("zzz", &entries).is_empty());
+	}
+}
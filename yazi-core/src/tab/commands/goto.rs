@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use tokio::{fs, pin};
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+use yazi_config::popup::InputCfg;
+use yazi_proxy::{AppProxy, CompletionProxy, InputProxy, TabProxy};
+use yazi_shared::{event::{Cmd, Outcome}, fs::{expand_path, Url}, Debounce, InputError};
+
+use crate::tab::Tab;
+
+impl Tab {
+	pub fn goto(&mut self, _: Cmd) -> Outcome {
+		if !self.try_escape_visual() {
+			return Outcome::Done;
+		}
+
+		tokio::spawn(async move {
+			let rx = InputProxy::show(InputCfg::goto());
+
+			let rx = Debounce::new(UnboundedReceiverStream::new(rx), Duration::from_millis(50));
+			pin!(rx);
+
+			while let Some(result) = rx.next().await {
+				match result {
+					Ok(s) if s.is_empty() => break,
+					Ok(s) => {
+						let u = Url::from(expand_path(&s));
+						match fs::metadata(&u).await {
+							Ok(meta) if meta.is_dir() => TabProxy::cd(&u),
+							Ok(_) => TabProxy::reveal(&u),
+							Err(e) => AppProxy::notify_warn("Goto", format!("Cannot go to `{s}`: {e}")),
+						}
+						break;
+					}
+					Err(InputError::Completed(before, ticket)) => {
+						CompletionProxy::trigger(&before, ticket);
+					}
+					_ => break,
+				}
+			}
+		});
+		Outcome::Prompted
+	}
+}
@@ -0,0 +1,37 @@
+use yazi_fs::FilterCase;
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Cmd, render};
+
+use crate::tab::{Finder, Tab};
+
+pub struct Opt {
+	prev: bool,
+}
+
+impl From<Cmd> for Opt {
+	// `--previous` as well, to go with `find_arrow`
+	fn from(c: Cmd) -> Self { Self { prev: c.bool("prev") || c.bool("previous") } }
+}
+
+impl Tab {
+	pub fn jump(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		if self.finder.is_none() {
+			let Some(query) = &self.jump else {
+				return AppProxy::notify_warn(
+					"Jump",
+					"No pattern to jump to, use `find` to enter one first",
+				);
+			};
+
+			let Ok(finder) = Finder::new(query, FilterCase::Smart) else {
+				return;
+			};
+
+			self.finder = Some(finder);
+			render!();
+		}
+
+		self.find_arrow(opt.prev);
+	}
+}
@@ -0,0 +1,46 @@
+use crossterm::event::KeyCode;
+use yazi_config::{keymap::Key, LAYOUT};
+use yazi_shared::{event::Cmd, render, render_and};
+
+use crate::tab::{Labels, Tab};
+
+impl Tab {
+	pub fn jump_labels(&mut self, _: Cmd) {
+		let (offset, len) = (self.current.offset, self.current.files.len());
+		let count = (LAYOUT.load().current.height as usize).min(len.saturating_sub(offset));
+		if count == 0 {
+			return;
+		}
+
+		self.labels = Some(Labels::new(offset, count));
+		render!();
+	}
+
+	// Takes the keys while the labels are shown: moves the cursor once a label is
+	// typed in full, and hides them on `<Esc>` or a key that matches no label.
+	pub fn jump_labels_type(&mut self, key: &Key) -> bool {
+		let Some(labels) = &mut self.labels else {
+			return false;
+		};
+
+		match key.plain() {
+			Some(c) if key.code != KeyCode::Esc => labels.typed.push(c),
+			_ => {
+				self.labels = None;
+				return render_and!(true);
+			}
+		}
+
+		// Labels are prefix-free, so a label typed in full is the only one left
+		let first = labels.matches().next().map(|(l, idx)| (*l == labels.typed).then_some(*idx));
+		match first {
+			None => self.labels = None,
+			Some(None) => {}
+			Some(Some(idx)) => {
+				self.labels = None;
+				self.arrow(idx as isize - self.current.cursor as isize);
+			}
+		}
+		render_and!(true)
+	}
+}
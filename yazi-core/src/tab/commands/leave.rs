@@ -12,6 +12,11 @@ impl From<Cmd> for Opt {
 
 impl Tab {
 	pub fn leave(&mut self, _: impl Into<Opt>) {
+		// A collapsed chain of directories is left all at once
+		if let Some((top, _)) = self.collapsed.take().filter(|(_, b)| *b == self.current.cwd) {
+			return self.reveal(top);
+		}
+
 		self
 			.current
 			.hovered()
@@ -5,12 +5,17 @@ use crate::tab::Tab;
 impl Tab {
 	pub fn linemode(&mut self, mut c: Cmd) {
 		render!(self.conf.patch(|new| {
-			let Some(mode) = c.take_first_str() else {
-				return;
-			};
-			if !mode.is_empty() && mode.len() <= 20 {
+			if let Some(mode) = c.take_first_str().filter(|m| !m.is_empty() && m.len() <= 20) {
 				new.linemode = mode;
 			}
+			// `relative`, a `strftime` format, or empty for the default
+			if let Some(format) = c.take_str("time") {
+				new.time_format = format;
+			}
+			// `binary`, `decimal`, or `bytes`
+			if let Some(format) = c.take_str("size") {
+				new.size_format = format;
+			}
 		}));
 	}
 }
@@ -1,20 +1,39 @@
 mod arrow;
 mod back;
 mod cd;
+mod collapse;
 mod copy;
+mod dir_tree;
 mod enter;
 mod escape;
+mod extensions;
 mod filter;
 mod find;
+mod focus;
 mod forward;
+mod fuzzy;
+mod goto;
 mod hidden;
+mod jump;
+mod jump_labels;
 mod leave;
 mod linemode;
+mod mounts;
 mod preview;
+mod readme;
 mod reveal;
+mod reveal_in_gui;
+mod run_file;
 mod search;
+mod search_contents;
 mod select;
 mod select_all;
+mod selection_load;
+mod selection_save;
 mod shell;
 mod sort;
+mod stats;
+mod tag;
+mod terminal;
+mod tree;
 mod visual_mode;
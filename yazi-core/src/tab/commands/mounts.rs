@@ -0,0 +1,127 @@
+use std::path::Path;
+
+use tokio::{fs, task::spawn_blocking};
+use yazi_config::popup::SelectCfg;
+use yazi_plugin::external::{self, DriveOp};
+use yazi_proxy::{AppProxy, SelectProxy, TabProxy};
+use yazi_shared::{emit, event::Cmd, fs::{mounts, Mount, Url}, readable_size, Layer};
+
+use crate::tab::Tab;
+
+pub struct Opt {
+	op: Option<DriveOp>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self {
+		Self {
+			op: if c.bool("mount") {
+				Some(DriveOp::Mount)
+			} else if c.bool("unmount") {
+				Some(DriveOp::Unmount)
+			} else if c.bool("eject") {
+				Some(DriveOp::Eject)
+			} else {
+				None
+			},
+		}
+	}
+}
+
+impl Tab {
+	pub fn mounts(&mut self, opt: impl Into<Opt>) {
+		if !self.try_escape_visual() {
+			return;
+		}
+
+		let opt = opt.into() as Opt;
+		tokio::spawn(async move {
+			match opt.op {
+				None => Self::mounts_cd().await,
+				Some(DriveOp::Mount) => Self::mounts_mount().await,
+				Some(op) => Self::mounts_unmount(op).await,
+			}
+		});
+	}
+
+	async fn mounts_cd() {
+		let Some(mounts) = Self::mounts_list().await else { return };
+		if mounts.is_empty() {
+			return AppProxy::notify_warn("Mounts", "No mounted filesystems found");
+		}
+
+		let items = mounts.iter().map(Self::mounts_item).collect();
+		let Ok(choice) = SelectProxy::show(SelectCfg::mounts(items)).await else {
+			return;
+		};
+
+		let url = Url::from(&mounts[choice].path);
+		match fs::metadata(&url).await {
+			Ok(m) if m.is_dir() => TabProxy::cd(&url),
+			_ => AppProxy::notify_warn("Mounts", format!("`{}` is no longer available", url.display())),
+		}
+	}
+
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	async fn mounts_mount() {
+		let devices = match spawn_blocking(yazi_shared::fs::unmounted_devices).await {
+			Ok(Ok(d)) => d,
+			Ok(Err(e)) => return AppProxy::notify_error("Mounts", format!("Cannot list devices: {e}")),
+			Err(_) => return,
+		};
+		if devices.is_empty() {
+			return AppProxy::notify_warn("Mounts", "No unmounted removable drives found");
+		}
+
+		let items = devices.iter().map(|d| d.display().to_string()).collect();
+		if let Ok(choice) = SelectProxy::show(SelectCfg::mounts(items)).await {
+			Self::mounts_run(DriveOp::Mount, &devices[choice]).await;
+		}
+	}
+
+	#[cfg(not(any(target_os = "linux", target_os = "android")))]
+	async fn mounts_mount() {
+		AppProxy::notify_warn("Mounts", "Mounting drives is not supported on this platform");
+	}
+
+	// Only mounted removable drives can be unmounted or ejected
+	async fn mounts_unmount(op: DriveOp) {
+		let Some(mut mounts) = Self::mounts_list().await else { return };
+		mounts.retain(|m| m.removable && m.device.is_some());
+		if mounts.is_empty() {
+			return AppProxy::notify_warn("Mounts", "No removable drives are mounted");
+		}
+
+		let items = mounts.iter().map(Self::mounts_item).collect();
+		if let Ok(choice) = SelectProxy::show(SelectCfg::mounts(items)).await {
+			Self::mounts_run(op, mounts[choice].device.as_ref().unwrap()).await;
+		}
+	}
+
+	async fn mounts_list() -> Option<Vec<Mount>> {
+		match spawn_blocking(mounts).await {
+			Ok(Ok(m)) => Some(m),
+			Ok(Err(e)) => {
+				AppProxy::notify_error("Mounts", format!("Cannot list mounts: {e}"));
+				None
+			}
+			Err(_) => None,
+		}
+	}
+
+	fn mounts_item(m: &Mount) -> String {
+		let (free, total) = (readable_size(m.space.free), readable_size(m.space.total));
+		match &m.device {
+			Some(d) => format!("{}  {}  ({free} free / {total})", m.path.display(), d.display()),
+			None => format!("{}  ({free} free / {total})", m.path.display()),
+		}
+	}
+
+	// Reopens the picker on success, so it reflects the new state of the drives
+	async fn mounts_run(op: DriveOp, device: &Path) {
+		match external::drive(op, device).await {
+			Ok(()) => emit!(Call(Cmd::new("mounts"), Layer::Manager)),
+			Err(e) => AppProxy::notify_error("Mounts", format!("`{}`: {e}", device.display())),
+		}
+	}
+}
@@ -17,19 +17,65 @@ impl TryFrom<Cmd> for Opt {
 
 impl Tab {
 	pub fn preview(&mut self, opt: impl TryInto<Opt>) {
-		let Some(hovered) = self.current.hovered().map(|h| &h.url) else {
+		let Some(hovered) = self.current.hovered() else {
 			return render!(self.preview.reset());
 		};
 
-		let Ok(opt) = opt.try_into() else {
+		let Ok(mut opt) = opt.try_into() else {
 			return;
 		};
 
-		if opt.lock.url != *hovered {
-			return;
+		// The README of a directory is previewed in place of it
+		let mut readme = None;
+		if opt.lock.url != hovered.url {
+			if !self.conf.dir_readme || opt.lock.url.parent_url().as_ref() != Some(&hovered.url) {
+				return;
+			}
+			readme = Some(std::mem::replace(&mut opt.lock.url, hovered.url()));
+			opt.lock.cha = hovered.cha;
 		}
 
+		self.preview.readme = readme;
 		self.preview.lock = Some(opt.lock);
 		render!();
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use yazi_fs::Folder;
+	use yazi_shared::fs::{Cha, File, FilesOp, Url};
+
+	use super::*;
+
+	#[test]
+	fn test_preview_readme() {
+		crate::init_tests();
+		let lock = |p: &str| PreviewLock {
+			url:    Url::from(p),
+			cha:    Cha::dummy(),
+			skip:   0,
+			window: Default::default(),
+			data:   vec![],
+		};
+
+		let mut tab = Tab::default();
+		tab.conf.dir_readme = true;
+		let cwd = Url::from("/d");
+		tab.current = Folder::from(&cwd);
+		let files = vec![File::from_dummy(Url::from("/d/x"), None)];
+		tab.current.update(FilesOp::Full(cwd, files, Cha::dummy()));
+
+		tab.preview(Cmd::new("preview").with_any("lock", lock("/d/x/README.md")));
+		assert_eq!(tab.preview.readme, Some(Url::from("/d/x/README.md")));
+		assert_eq!(tab.preview.lock.as_ref().unwrap().url, Url::from("/d/x"));
+
+		// Some other file's preview that has come too late is dropped
+		tab.preview(Cmd::new("preview").with_any("lock", lock("/e/README.md")));
+		assert!(tab.preview.readme.is_some());
+
+		// Listed for want of a README
+		tab.preview(Cmd::new("preview").with_any("lock", lock("/d/x")));
+		assert_eq!(tab.preview.readme, None);
+	}
+}
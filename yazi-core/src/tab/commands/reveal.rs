@@ -4,7 +4,8 @@ use yazi_shared::{event::{Cmd, Data}, fs::{expand_path, File, FilesOp, Url}};
 use crate::tab::Tab;
 
 pub struct Opt {
-	target: Url,
+	target:   Url,
+	position: Option<(usize, usize)>,
 }
 
 impl From<Cmd> for Opt {
@@ -14,11 +15,13 @@ impl From<Cmd> for Opt {
 			target.set_path(expand_path(&target))
 		}
 
-		Self { target }
+		let line = c.get("line").and_then(Data::as_usize);
+		let column = c.get("column").and_then(Data::as_usize);
+		Self { target, position: line.map(|l| (l, column.unwrap_or(1))) }
 	}
 }
 impl From<Url> for Opt {
-	fn from(target: Url) -> Self { Self { target } }
+	fn from(target: Url) -> Self { Self { target, position: None } }
 }
 
 impl Tab {
@@ -30,6 +33,7 @@ impl Tab {
 		};
 
 		self.cd(parent.clone());
+		self.position = opt.position.map(|(l, c)| (opt.target.clone(), l, c));
 		FilesOp::Creating(parent, vec![File::from_dummy(opt.target.clone(), None)]).emit();
 		ManagerProxy::hover(Some(opt.target), self.idx);
 	}
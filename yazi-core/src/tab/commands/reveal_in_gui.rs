@@ -0,0 +1,17 @@
+use yazi_plugin::external;
+use yazi_proxy::AppProxy;
+use yazi_shared::event::Cmd;
+
+use crate::tab::Tab;
+
+impl Tab {
+	pub fn reveal_in_gui(&mut self, _: Cmd) {
+		let url = self.current.hovered().map_or_else(|| self.current.cwd.clone(), |h| h.url());
+
+		tokio::spawn(async move {
+			if let Err(e) = external::reveal(&url).await {
+				AppProxy::notify_warn("Reveal", format!("`{}`: {e}", url.display()));
+			}
+		});
+	}
+}
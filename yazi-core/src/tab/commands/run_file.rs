@@ -0,0 +1,174 @@
+use std::{borrow::Cow, ffi::OsString, path::Path, process::Stdio, time::Duration};
+
+use tokio::{process::Command, time::timeout};
+use yazi_config::{open::Opener, LAYOUT};
+use yazi_plugin::{bindings::Window, elements::Paragraph, utils::PreviewLock};
+use yazi_proxy::{AppProxy, TasksProxy};
+use yazi_shared::{emit, event::Cmd, fs::{Cha, File}, shell::escape_native, Layer};
+
+use crate::tab::Tab;
+
+pub struct Opt {
+	args:    Vec<String>,
+	block:   bool,
+	preview: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			args:    (0..).map_while(|i| c.take_str(&i.to_string())).collect(),
+			block:   c.bool("block"),
+			preview: c.bool("preview"),
+		}
+	}
+}
+
+impl Tab {
+	pub fn run_file(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		let Some(hovered) = self.current.hovered().cloned() else {
+			return;
+		};
+
+		if !is_executable(&hovered.url, hovered.cha) {
+			return AppProxy::notify_warn(
+				"Run",
+				format!("`{}` is not executable, try `chmod +x` on it", hovered.url.display()),
+			);
+		}
+
+		if opt.preview {
+			return Self::run_file_preview(hovered, opt.args);
+		}
+
+		let args = opt.args.iter().map(|s| escape_native(s)).collect::<Vec<_>>().join(" ");
+		TasksProxy::open_with(
+			vec![hovered.url.clone()],
+			Cow::Owned(Opener {
+				run:      format!("{} {args}", template(&hovered.url)).trim_end().to_owned(),
+				block:    opt.block,
+				orphan:   false,
+				desc:     Default::default(),
+				for_:     None,
+				spread:   true,
+				prompted: false,
+			}),
+		);
+	}
+
+	// Runs the file in the background from its directory, and shows what it
+	// prints in the preview. It's killed if it doesn't finish within `TIMEOUT`.
+	fn run_file_preview(hovered: File, args: Vec<String>) {
+		const TIMEOUT: Duration = Duration::from_secs(10);
+
+		tokio::spawn(async move {
+			let (program, mut argv) = program(&hovered.url);
+			argv.extend(args.into_iter().map(OsString::from));
+
+			let mut cmd = Command::new(program);
+			if let Some(parent) = hovered.url.parent() {
+				cmd.current_dir(parent);
+			}
+			let output = cmd.args(argv).stdin(Stdio::null()).kill_on_drop(true).output();
+
+			let text = match timeout(TIMEOUT, output).await {
+				Err(_) => {
+					let s = format!("`{}` didn't finish within {TIMEOUT:?}", hovered.url.display());
+					return AppProxy::notify_warn("Run", s);
+				}
+				Ok(Ok(o)) => {
+					let mut s = String::from_utf8_lossy(&o.stdout).into_owned();
+					s.push_str(&String::from_utf8_lossy(&o.stderr));
+					if !o.status.success() {
+						s.push_str(&format!("\n[{}]", o.status));
+					}
+					s
+				}
+				Ok(Err(e)) => return AppProxy::notify_error("Run", e),
+			};
+
+			let lock = PreviewLock {
+				url:    hovered.url(),
+				cha:    hovered.cha,
+				skip:   0,
+				window: Window::default(),
+				data:   vec![Box::new(Paragraph {
+					area: LAYOUT.load().preview,
+					text: text.into(),
+					..Default::default()
+				})],
+			};
+			emit!(Call(Cmd::new("preview").with_any("lock", lock), Layer::Manager));
+		});
+	}
+}
+
+// The shell template that runs the file, which is passed as the first argument
+#[cfg(unix)]
+fn template(_: &Path) -> &'static str { r#""$0""# }
+
+#[cfg(windows)]
+fn template(path: &Path) -> &'static str {
+	if ext(path).as_deref() == Some("ps1") {
+		"powershell -NoProfile -ExecutionPolicy Bypass -File %0"
+	} else {
+		"%0"
+	}
+}
+
+#[cfg(unix)]
+fn program(path: &Path) -> (OsString, Vec<OsString>) { (path.into(), vec![]) }
+
+#[cfg(windows)]
+fn program(path: &Path) -> (OsString, Vec<OsString>) {
+	let args = |a: &[&str]| a.iter().map(OsString::from).chain([path.into()]).collect();
+	match ext(path).as_deref() {
+		Some("ps1") => {
+			("powershell".into(), args(&["-NoProfile", "-ExecutionPolicy", "Bypass", "-File"]))
+		}
+		Some("bat" | "cmd") => ("cmd".into(), args(&["/C"])),
+		_ => (path.into(), vec![]),
+	}
+}
+
+#[cfg(unix)]
+fn is_executable(_: &Path, cha: Cha) -> bool { !cha.is_dir() && cha.perm & 0o111 != 0 }
+
+#[cfg(windows)]
+fn is_executable(path: &Path, cha: Cha) -> bool {
+	!cha.is_dir() && matches!(ext(path).as_deref(), Some("exe" | "com" | "bat" | "cmd" | "ps1"))
+}
+
+#[cfg(windows)]
+fn ext(path: &Path) -> Option<String> {
+	path.extension().map(|e| e.to_string_lossy().to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[cfg(unix)]
+	#[test]
+	fn test_is_executable() {
+		use yazi_shared::fs::ChaKind;
+
+		let cha = |kind, perm| Cha { kind, perm, ..Default::default() };
+		let path = Path::new("/bin/a");
+
+		assert!(is_executable(path, cha(ChaKind::empty(), 0o755)));
+		assert!(is_executable(path, cha(ChaKind::empty(), 0o610)));
+		assert!(!is_executable(path, cha(ChaKind::empty(), 0o644)));
+		assert!(!is_executable(path, cha(ChaKind::DIR, 0o755)));
+	}
+
+	#[cfg(windows)]
+	#[test]
+	fn test_is_executable() {
+		let cha = Cha::default();
+		assert!(is_executable(Path::new("C:\\a.EXE"), cha));
+		assert!(is_executable(Path::new("C:\\a.ps1"), cha));
+		assert!(!is_executable(Path::new("C:\\a.txt"), cha));
+	}
+}
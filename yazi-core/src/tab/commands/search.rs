@@ -6,33 +6,37 @@ use tracing::error;
 use yazi_config::popup::InputCfg;
 use yazi_plugin::external;
 use yazi_proxy::{options::{SearchOpt, SearchOptVia}, AppProxy, InputProxy, ManagerProxy, TabProxy};
-use yazi_shared::fs::{Cha, FilesOp};
+use yazi_shared::{event::Outcome, fs::{Cha, FilesOp}};
 
 use crate::tab::Tab;
 
 impl Tab {
-	pub fn search(&mut self, opt: impl TryInto<SearchOpt>) {
+	pub fn search(&mut self, opt: impl TryInto<SearchOpt>) -> Outcome {
 		let Ok(mut opt) = opt.try_into() else {
-			return AppProxy::notify_error("Invalid `search` option", "Failed to parse search option");
+			AppProxy::notify_error("Invalid `search` option", "Failed to parse search option");
+			return Outcome::Done;
 		};
 
 		if opt.via == SearchOptVia::None {
-			return self.search_stop();
+			self.search_stop();
+			return Outcome::Done;
 		}
 
 		if let Some(handle) = self.search.take() {
 			handle.abort();
 		}
 
+		let idx = self.idx;
 		tokio::spawn(async move {
 			let mut input =
 				InputProxy::show(InputCfg::search(&opt.via.to_string()).with_value(opt.subject));
 
 			if let Some(Ok(subject)) = input.recv().await {
 				opt.subject = subject;
-				TabProxy::search_do(opt);
+				TabProxy::search_do(opt, idx);
 			}
 		});
+		Outcome::Prompted
 	}
 
 	pub fn search_do(&mut self, opt: impl TryInto<SearchOpt>) {
@@ -45,7 +49,7 @@ impl Tab {
 		}
 
 		let mut cwd = self.current.cwd.clone();
-		let hidden = self.conf.show_hidden;
+		let (idx, hidden) = (self.idx, self.conf.show_hidden);
 
 		self.search = Some(tokio::spawn(async move {
 			cwd = cwd.into_search(opt.subject.clone());
@@ -68,7 +72,7 @@ impl Tab {
 			let rx = UnboundedReceiverStream::new(rx).chunks_timeout(1000, Duration::from_millis(300));
 			pin!(rx);
 
-			let ((), ticket) = (TabProxy::cd(&cwd), FilesOp::prepare(&cwd));
+			let ((), ticket) = (TabProxy::cd_in(&cwd, idx), FilesOp::prepare(&cwd));
 			while let Some(chunk) = rx.next().await {
 				FilesOp::Part(cwd.clone(), chunk, ticket).emit();
 			}
@@ -0,0 +1,70 @@
+use std::{io::{self, ErrorKind::NotFound}, time::Duration};
+
+use tokio::pin;
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+use yazi_config::popup::{InputCfg, SelectCfg};
+use yazi_plugin::external::{self, RgMatch, RgOpt};
+use yazi_proxy::{AppProxy, InputProxy, ManagerProxy, SelectProxy, TabProxy};
+use yazi_shared::event::Cmd;
+
+use crate::tab::Tab;
+
+impl Tab {
+	pub fn search_contents(&mut self, _: Cmd) {
+		let cwd = self.current.cwd.to_regular();
+		let hidden = self.conf.show_hidden;
+
+		tokio::spawn(async move {
+			let mut input = InputProxy::show(InputCfg::search_contents());
+			let Some(Ok(subject)) = input.recv().await else { return };
+			if subject.is_empty() {
+				return;
+			}
+
+			let opt = RgOpt { cwd, hidden, subject: subject.clone(), args: vec![] };
+			let rx = match external::rg_matches(opt) {
+				Ok(rx) => rx,
+				Err(e) if e.downcast_ref::<io::Error>().is_some_and(|e| e.kind() == NotFound) => {
+					return AppProxy::notify_error(
+						"Search contents",
+						"`rg` was not found, please install ripgrep to search file contents",
+					);
+				}
+				Err(e) => return AppProxy::notify_error("Search contents", e),
+			};
+
+			let rx = UnboundedReceiverStream::new(rx).chunks_timeout(100, Duration::from_millis(200));
+			let choice = SelectProxy::show(SelectCfg::matches(&subject));
+			pin!(rx, choice);
+
+			let (mut matches, mut done) = (Vec::<RgMatch>::new(), false);
+			loop {
+				tokio::select! {
+					result = &mut choice => {
+						if let Some(m) = result.ok().and_then(|i| matches.get(i)) {
+							TabProxy::reveal_at(&m.url, m.line, m.column);
+							ManagerProxy::open_at(m.line, m.column);
+						}
+						break;
+					}
+					chunk = rx.next(), if !done => {
+						let Some(chunk) = chunk else {
+							done = true;
+							if matches.is_empty() {
+								SelectProxy::close();
+								let content = format!("No matches found for `{subject}`");
+								AppProxy::notify_warn("Search contents", content);
+							}
+							continue;
+						};
+
+						SelectProxy::extend(
+							chunk.iter().map(|m| format!("{}:{}: {}", m.path, m.line, m.text)).collect(),
+						);
+						matches.extend(chunk);
+					}
+				}
+			}
+		});
+	}
+}
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+use yazi_dds::Pubsub;
 use yazi_proxy::AppProxy;
 use yazi_shared::{event::{Cmd, Data}, fs::Url, render, render_and};
 
@@ -31,12 +32,17 @@ impl<'a> Tab {
 			return;
 		};
 
+		let len = self.selected.len();
 		let b = match opt.state {
 			Some(true) => render_and!(self.selected.add(&url)),
 			Some(false) => render_and!(self.selected.remove(&url)) | true,
 			None => render_and!(self.selected.remove(&url) || self.selected.add(&url)),
 		};
 
+		if self.selected.len() != len {
+			Pubsub::pub_from_select(self.idx, self.selected.keys());
+		}
+
 		if !b {
 			AppProxy::notify_warn(
 				"Select one",
@@ -45,3 +51,46 @@ impl<'a> Tab {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use yazi_dds::{body::Body, Payload};
+	use yazi_shared::{event::Event, fs::{Cha, File, FilesOp}};
+
+	use super::*;
+
+	#[test]
+	fn test_pub_select() {
+		crate::init_tests();
+		let lua: &'static mlua::Lua = Box::leak(Box::default());
+		Pubsub::sub("test", "select", lua.create_function(|_, ()| Ok(())).unwrap());
+		let mut rx = Event::take();
+
+		// Told apart from the other tests by the index
+		let mut tab = Tab { idx: 115, ..Default::default() };
+		let cwd = Url::from("/d");
+		let files = ["/d/a", "/d/b"].map(|p| File::from_dummy(Url::from(p), None)).into();
+		tab.current.update(FilesOp::Full(cwd, files, Cha::dummy()));
+
+		tab.select(Cmd::new("select").with("state", true));
+		tab.arrow(1);
+		tab.select(Cmd::new("select"));
+		tab.select(Cmd::new("select"));
+		tab.select(Cmd::new("select").with("state", false));
+		tab.escape(Cmd::new("escape").with_bool("select", true));
+
+		let published: Vec<Vec<_>> = std::iter::from_fn(|| rx.try_recv().ok())
+			.filter_map(|e| match e {
+				Event::Call(mut c, _) if c.name == "accept_payload" => c.take_any::<Payload>("payload"),
+				_ => None,
+			})
+			.filter_map(|p| match p.body {
+				Body::Select(b) if b.tab == 115 => Some(b.urls.iter().map(ToString::to_string).collect()),
+				_ => None,
+			})
+			.collect();
+
+		// Deselecting what isn't selected changes nothing, so isn't published
+		assert_eq!(published, [vec!["/d/a"], vec!["/d/a", "/d/b"], vec!["/d/a"], vec![]]);
+	}
+}
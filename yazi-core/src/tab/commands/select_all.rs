@@ -1,3 +1,4 @@
+use yazi_dds::Pubsub;
 use yazi_proxy::AppProxy;
 use yazi_shared::{event::Cmd, render};
 
@@ -32,11 +33,16 @@ impl Tab {
 		};
 
 		let same = !self.current.cwd.is_search();
-		render!(self.selected.remove_many(&removal, same) > 0);
+		let removed = self.selected.remove_many(&removal, same);
+		render!(removed > 0);
 
 		let added = self.selected.add_many(&addition, same);
 		render!(added > 0);
 
+		if removed > 0 || added > 0 {
+			Pubsub::pub_from_select(self.idx, self.selected.keys());
+		}
+
 		if added != addition.len() {
 			AppProxy::notify_warn(
 				"Select all",
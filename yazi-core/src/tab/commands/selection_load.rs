@@ -0,0 +1,113 @@
+use std::{io, path::{Path, PathBuf}};
+
+use yazi_boot::BOOT;
+use yazi_dds::Pubsub;
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Cmd, fs::Url, render};
+
+use super::selection_save::{save_set, set_path};
+use crate::tab::Tab;
+
+pub struct Opt {
+	name: String,
+	cd:   bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self { name: c.take_first_str().unwrap_or_default(), cd: c.bool("cd") }
+	}
+}
+
+impl Tab {
+	pub fn selection_load(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		let Some(path) = set_path(&BOOT.state_dir, &opt.name) else {
+			return AppProxy::notify_warn("Load selection", format!("Invalid name `{}`", opt.name));
+		};
+
+		let (urls, stale) = match load_set(&path) {
+			Ok(v) => v,
+			Err(e) => {
+				let msg = format!("Cannot load `{}`: {e}", opt.name);
+				return AppProxy::notify_warn("Load selection", msg);
+			}
+		};
+
+		if opt.cd {
+			if let Some(u) = common_ancestor(&urls).filter(|u| *u != self.current.cwd) {
+				self.cd(u);
+			}
+		}
+
+		let added = self.selected.add_many(&urls.iter().collect::<Vec<_>>(), false);
+		if added > 0 {
+			render!();
+			Pubsub::pub_from_select(self.idx, self.selected.keys());
+		}
+		if stale > 0 {
+			AppProxy::notify_warn(
+				"Load selection",
+				format!("Selected {added} files, {stale} that no longer exist are removed from the set"),
+			);
+		}
+	}
+}
+
+// Reads the set saved at `path`, leaving out the files that no longer exist,
+// which are also pruned from the set. Returns them along with how many were.
+fn load_set(path: &Path) -> io::Result<(Vec<Url>, usize)> {
+	let s = std::fs::read_to_string(path)?;
+	let (urls, stale): (Vec<_>, Vec<_>) = s
+		.lines()
+		.filter(|l| !l.is_empty())
+		.map(PathBuf::from)
+		.partition(|p| p.symlink_metadata().is_ok());
+
+	let urls: Vec<_> = urls.into_iter().map(Url::from).collect();
+	if !stale.is_empty() {
+		save_set(path, urls.iter())?;
+	}
+	Ok((urls, stale.len()))
+}
+
+fn common_ancestor(urls: &[Url]) -> Option<Url> {
+	let mut parents = urls.iter().filter_map(|u| u.parent_url());
+	let mut ancestor = parents.next()?;
+	for p in parents {
+		while !p.starts_with(&ancestor) {
+			ancestor = ancestor.parent_url()?;
+		}
+	}
+	Some(ancestor)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use super::*;
+
+	#[test]
+	fn test_save_load() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		fs::create_dir_all(dir.join("files/sub")).unwrap();
+
+		let files = ["files/a", "files/sub/b", "files/gone"].map(|p| Url::from(dir.join(p)));
+		files.iter().for_each(|u| fs::write(u, "").unwrap());
+
+		let path = set_path(dir, "work").unwrap();
+		save_set(&path, files.iter()).unwrap();
+		fs::remove_file(&files[2]).unwrap();
+
+		let (urls, stale) = load_set(&path).unwrap();
+		assert_eq!(urls, &files[..2]);
+		assert_eq!(stale, 1);
+		assert_eq!(load_set(&path).unwrap(), (urls.clone(), 0));
+		assert_eq!(common_ancestor(&urls), Some(Url::from(dir.join("files"))));
+
+		assert_eq!(set_path(dir, "../x"), None);
+		assert_eq!(set_path(dir, ""), None);
+	}
+}
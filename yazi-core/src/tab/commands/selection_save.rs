@@ -0,0 +1,46 @@
+use std::{io, path::{Path, PathBuf}};
+
+use yazi_boot::BOOT;
+use yazi_proxy::AppProxy;
+use yazi_shared::{event::Cmd, fs::{write_atomic, Url}};
+
+use crate::tab::Tab;
+
+pub struct Opt {
+	name: String,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { name: c.take_first_str().unwrap_or_default() } }
+}
+
+impl Tab {
+	pub fn selection_save(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		let Some(path) = set_path(&BOOT.state_dir, &opt.name) else {
+			return AppProxy::notify_warn("Save selection", format!("Invalid name `{}`", opt.name));
+		};
+		if self.selected.is_empty() {
+			return AppProxy::notify_warn("Save selection", "No files selected");
+		}
+
+		if let Err(e) = save_set(&path, self.selected.keys()) {
+			AppProxy::notify_error("Save selection", format!("Cannot save `{}`: {e}", opt.name));
+		}
+	}
+}
+
+// Each set is a file in `selections` of the state directory, named after it
+pub(super) fn set_path(dir: &Path, name: &str) -> Option<PathBuf> {
+	let valid = !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\']);
+	valid.then(|| dir.join("selections").join(name))
+}
+
+pub(super) fn save_set<'a>(path: &Path, urls: impl Iterator<Item = &'a Url>) -> io::Result<()> {
+	let mut lines: Vec<_> = urls.map(|u| u.to_string_lossy().into_owned()).collect();
+	lines.sort_unstable();
+
+	let mut s = lines.join("\n");
+	s.push('\n');
+	write_atomic(path, &s)
+}
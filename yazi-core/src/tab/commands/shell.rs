@@ -3,7 +3,7 @@ use std::{borrow::Cow, fmt::Display};
 use anyhow::bail;
 use yazi_config::{open::Opener, popup::InputCfg};
 use yazi_proxy::{AppProxy, InputProxy, TasksProxy};
-use yazi_shared::event::{Cmd, Data};
+use yazi_shared::event::{Cmd, Data, Outcome};
 
 use crate::tab::Tab;
 
@@ -38,14 +38,17 @@ impl TryFrom<Cmd> for Opt {
 }
 
 impl Tab {
-	pub fn shell(&mut self, opt: impl TryInto<Opt, Error = impl Display>) {
+	pub fn shell(&mut self, opt: impl TryInto<Opt, Error = impl Display>) -> Outcome {
 		if !self.try_escape_visual() {
-			return;
+			return Outcome::Done;
 		}
 
 		let mut opt = match opt.try_into() {
 			Ok(o) => o as Opt,
-			Err(e) => return AppProxy::notify_warn("`shell` command", e),
+			Err(e) => {
+				AppProxy::notify_warn("`shell` command", e);
+				return Outcome::Done;
+			}
 		};
 
 		// TODO: Remove in v0.3.2
@@ -56,18 +59,19 @@ impl Tab {
 
 Please replace e.g. `shell` with `shell --interactive`, `shell "my-template"` with `shell "my-template" --interactive`, in your keymap.toml"#,
 			);
-			return;
+			return Outcome::Done;
 		} else if opt.interactive && opt.confirm {
 			AppProxy::notify_error(
 				"`shell` command",
 				"The `shell` command cannot specify both `--confirm` and `--interactive` at the same time.",
 			);
-			return;
+			return Outcome::Done;
 		}
 
 		let selected = self.hovered_and_selected(true).cloned().collect();
+		let prompted = !opt.confirm || opt.run.is_empty();
 		tokio::spawn(async move {
-			if !opt.confirm || opt.run.is_empty() {
+			if prompted {
 				let mut result =
 					InputProxy::show(InputCfg::shell(opt.block).with_value(opt.run).with_cursor(opt.cursor));
 				match result.recv().await {
@@ -76,17 +80,24 @@ Please replace e.g. `shell` with `shell --interactive`, `shell "my-template"` wi
 				}
 			}
 
-			TasksProxy::open_with(
+			let run = opt.run.clone();
+			let spawned = TasksProxy::open_with_spawned(
 				selected,
 				Cow::Owned(Opener {
-					run:    opt.run,
-					block:  opt.block,
+					run: opt.run,
+					block: opt.block,
 					orphan: opt.orphan,
-					desc:   Default::default(),
-					for_:   None,
+					desc: Default::default(),
+					for_: None,
 					spread: true,
+					prompted,
 				}),
 			);
+			// Left out of the history if it doesn't even start
+			if spawned.await && prompted {
+				InputProxy::remember("shell", &run);
+			}
 		});
+		if prompted { Outcome::Prompted } else { Outcome::Done }
 	}
 }
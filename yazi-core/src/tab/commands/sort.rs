@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use yazi_config::manager::SortBy;
+use yazi_config::manager::{SortBy, SortDirs};
 use yazi_proxy::ManagerProxy;
 use yazi_shared::event::Cmd;
 
@@ -14,7 +14,14 @@ impl Tab {
 		}
 
 		conf.sort_reverse = c.maybe_bool("reverse").unwrap_or(conf.sort_reverse);
-		conf.sort_dir_first = c.maybe_bool("dir-first").unwrap_or(conf.sort_dir_first);
+		if let Some(first) = c.maybe_bool("dir-first") {
+			conf.sort_dirs = first.into();
+		}
+		if c.bool("dir-last") {
+			conf.sort_dirs = SortDirs::Last;
+		} else if c.bool("dir-mixed") {
+			conf.sort_dirs = SortDirs::Mixed;
+		}
 		conf.sort_sensitive = c.maybe_bool("sensitive").unwrap_or(conf.sort_sensitive);
 		conf.sort_translit = c.maybe_bool("translit").unwrap_or(conf.sort_translit);
 
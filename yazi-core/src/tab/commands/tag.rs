@@ -0,0 +1,79 @@
+use yazi_config::popup::InputCfg;
+use yazi_dds::Pubsub;
+use yazi_fs::{Tags, TAGS};
+use yazi_proxy::{AppProxy, InputProxy};
+use yazi_shared::{emit, event::{Cmd, Outcome}, render, Layer};
+
+use super::filter::Opt as FilterOpt;
+use crate::tab::Tab;
+
+pub struct Opt {
+	action: String,
+	name:   Option<String>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self {
+		Self {
+			action: c.take_first_str().unwrap_or_default(),
+			name:   c.take_str("1").filter(|s| !s.is_empty()),
+		}
+	}
+}
+
+impl Tab {
+	pub fn tag(&mut self, opt: impl Into<Opt>) -> Outcome {
+		let opt = opt.into() as Opt;
+		if let Some(name) = opt.name.as_deref().filter(|&n| !Tags::valid(n)) {
+			AppProxy::notify_warn("Tag", format!("Invalid tag `{name}`"));
+			return Outcome::Done;
+		}
+
+		match (opt.action.as_str(), opt.name) {
+			("add" | "select" | "filter", None) => return Self::tag_prompt(opt.action, self.idx),
+			("add", Some(name)) => {
+				let mut tags = TAGS.write();
+				if tags.add(self.selected_or_hovered(false), &name) {
+					tags.save();
+					render!();
+				}
+			}
+			("remove", name) => {
+				let mut tags = TAGS.write();
+				if tags.remove(self.selected_or_hovered(false), name.as_deref()) {
+					tags.save();
+					render!();
+				}
+			}
+			("select", Some(name)) => self.tag_select(&name),
+			("filter", Some(name)) => {
+				self.filter_do(FilterOpt { query: name, tag: true, done: true, ..Default::default() })
+			}
+			_ => {}
+		}
+		Outcome::Done
+	}
+
+	fn tag_prompt(action: String, idx: usize) -> Outcome {
+		tokio::spawn(async move {
+			let mut rx = InputProxy::show(InputCfg::tag());
+			if let Some(Ok(name)) = rx.recv().await {
+				emit!(Call(Cmd::args("tag", &[action, name]).with("tab", idx), Layer::Manager));
+			}
+		});
+		Outcome::Prompted
+	}
+
+	fn tag_select(&mut self, name: &str) {
+		let tags = TAGS.read();
+		let urls: Vec<_> =
+			self.current.files.iter().map(|f| &f.url).filter(|u| tags.has(u, name)).collect();
+
+		let added = self.selected.add_many(&urls, !self.current.cwd.is_search());
+		render!(added > 0);
+
+		if added > 0 {
+			Pubsub::pub_from_select(self.idx, self.selected.keys());
+		}
+	}
+}
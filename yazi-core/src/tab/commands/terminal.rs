@@ -0,0 +1,47 @@
+use std::{borrow::Cow, env};
+
+use yazi_config::open::Opener;
+use yazi_proxy::TasksProxy;
+use yazi_shared::{event::Cmd, shell::escape_native};
+
+use crate::tab::Tab;
+
+pub struct Opt {
+	program: Option<String>,
+}
+
+impl From<Cmd> for Opt {
+	fn from(mut c: Cmd) -> Self { Self { program: c.take_first_str() } }
+}
+
+impl Tab {
+	// The process always follows the CWD of the active tab, and the terminal
+	// state is handed over to the child the same way as for `shell --block`.
+	pub fn terminal(&mut self, opt: impl Into<Opt>) {
+		if !self.try_escape_visual() {
+			return;
+		}
+
+		let opt = opt.into() as Opt;
+		let program = opt.program.filter(|s| !s.is_empty()).unwrap_or_else(|| {
+			#[cfg(unix)]
+			let shell = env::var("SHELL").ok().filter(|s| !s.is_empty()).unwrap_or("sh".to_owned());
+			#[cfg(windows)]
+			let shell = env::var("COMSPEC").ok().filter(|s| !s.is_empty()).unwrap_or("cmd.exe".to_owned());
+			escape_native(&shell).into_owned()
+		});
+
+		TasksProxy::open_with(
+			vec![],
+			Cow::Owned(Opener {
+				run:      program,
+				block:    true,
+				orphan:   false,
+				desc:     Default::default(),
+				for_:     None,
+				spread:   true,
+				prompted: false,
+			}),
+		);
+	}
+}
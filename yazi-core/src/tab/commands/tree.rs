@@ -0,0 +1,256 @@
+use std::{collections::HashSet, fs, path::{Path, PathBuf}, time::Duration};
+
+use notify_fork::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::{select, sync::mpsc, task::spawn_blocking, time::sleep};
+use tokio_util::sync::CancellationToken;
+use yazi_config::{LAYOUT, MANAGER};
+use yazi_plugin::{bindings::Window, elements::Paragraph, utils::PreviewLock, CLIPBOARD};
+use yazi_proxy::AppProxy;
+use yazi_shared::{emit, event::{Cmd, Data}, Layer};
+
+use crate::tab::{tree::draw, Tab};
+
+// Stop walking once this many entries have been drawn
+const MAX_ENTRIES: usize = 1000;
+
+// Poll instead of watching if the tree has more directories than this,
+// to leave some inotify watches for everything else
+const MAX_WATCHES: usize = 256;
+
+pub struct Opt {
+	depth:   usize,
+	hidden:  Option<bool>,
+	ignore:  bool,
+	preview: bool,
+}
+
+impl From<Cmd> for Opt {
+	fn from(c: Cmd) -> Self {
+		Self {
+			depth:   c.get("depth").and_then(Data::as_usize).unwrap_or(3),
+			hidden:  c.get("hidden").and_then(Data::as_bool),
+			ignore:  c.get("ignore").and_then(Data::as_bool).unwrap_or(true),
+			preview: c.bool("preview"),
+		}
+	}
+}
+
+impl Tab {
+	pub fn tree(&mut self, opt: impl Into<Opt>) {
+		let opt = opt.into() as Opt;
+		let cwd = self.current.cwd.to_path_buf();
+		let hidden = opt.hidden.unwrap_or(self.conf.show_hidden);
+
+		if !opt.preview {
+			tokio::spawn(async move {
+				if let Ok(text) =
+					spawn_blocking(move || draw(&cwd, opt.depth, hidden, opt.ignore, MAX_ENTRIES)).await
+				{
+					CLIPBOARD.set(&text).await;
+				}
+			});
+			return;
+		}
+
+		let Some(hovered) = self.current.hovered().cloned() else {
+			return AppProxy::notify_warn("Tree", "Nothing hovered to preview the tree in");
+		};
+
+		// The tree is redrawn until the preview moves on to something else
+		let ct = CancellationToken::new();
+		self.preview.hold(ct.clone());
+
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let interval = Duration::from_secs(MANAGER.poll_interval.max(1) as u64);
+		tokio::spawn(live(cwd, opt.depth, hidden, opt.ignore, interval, ct, tx));
+		tokio::spawn(async move {
+			while let Some(text) = rx.recv().await {
+				let lock = PreviewLock {
+					url:    hovered.url(),
+					cha:    hovered.cha,
+					skip:   0,
+					window: Window::default(),
+					data:   vec![Box::new(Paragraph {
+						area: LAYOUT.load().preview,
+						text: text.into(),
+						..Default::default()
+					})],
+				};
+				emit!(Call(Cmd::new("preview").with_any("lock", lock), Layer::Manager));
+			}
+		});
+	}
+}
+
+// Sends the tree of `root` to `tx`, and again each time it changes, until `ct`
+// is cancelled. Changes are watched for in every directory drawn, or polled
+// for every `interval` if there are too many of them.
+async fn live(
+	root: PathBuf,
+	depth: usize,
+	hidden: bool,
+	ignore: bool,
+	interval: Duration,
+	ct: CancellationToken,
+	tx: mpsc::UnboundedSender<String>,
+) {
+	let (changed_tx, mut changed) = mpsc::unbounded_channel();
+	let mut watcher = RecommendedWatcher::new(
+		move |res: Result<notify_fork::Event, notify_fork::Error>| {
+			if res.is_ok_and(|e| !e.kind.is_access()) {
+				changed_tx.send(()).ok();
+			}
+		},
+		Default::default(),
+	)
+	.ok();
+
+	let (mut watched, mut last) = (HashSet::new(), String::new());
+	loop {
+		let (root_, last_) = (root.to_owned(), last.clone());
+		let Ok((text, dirs)) = spawn_blocking(move || {
+			let text = draw(&root_, depth, hidden, ignore, MAX_ENTRIES);
+			let dirs = (text != last_).then(|| subdirs(&root_, depth, hidden, MAX_WATCHES));
+			(text, dirs)
+		})
+		.await
+		else {
+			return;
+		};
+
+		if let Some(dirs) = dirs {
+			if watcher.as_mut().is_some_and(|w| !rewatch(w, &mut watched, dirs)) {
+				watcher = None;
+				AppProxy::notify_warn(
+					"Tree",
+					format!("Too many directories to watch, polling every {}s", interval.as_secs()),
+				);
+			}
+			if tx.send(text.clone()).is_err() {
+				return;
+			}
+			last = text;
+		}
+
+		select! {
+			_ = ct.cancelled() => return,
+			Some(()) = changed.recv() => {
+				// Coalesce the events of a burst into a single redraw
+				sleep(Duration::from_millis(100)).await;
+				while changed.try_recv().is_ok() {}
+			},
+			_ = sleep(interval), if watcher.is_none() => {},
+		}
+	}
+}
+
+// Updates the watches to cover `dirs`, returning `false` if they can't
+fn rewatch(
+	watcher: &mut RecommendedWatcher,
+	watched: &mut HashSet<PathBuf>,
+	dirs: Option<Vec<PathBuf>>,
+) -> bool {
+	let Some(dirs) = dirs else { return false };
+	let dirs: HashSet<_> = dirs.into_iter().collect();
+
+	for dir in watched.difference(&dirs) {
+		watcher.unwatch(dir).ok();
+	}
+	for dir in dirs.difference(watched) {
+		if watcher.watch(dir, RecursiveMode::NonRecursive).is_err() {
+			return false;
+		}
+	}
+
+	*watched = dirs;
+	true
+}
+
+// The directories within `depth` levels of `root`, including itself,
+// or `None` if there are more than `max` of them
+fn subdirs(root: &Path, depth: usize, hidden: bool, max: usize) -> Option<Vec<PathBuf>> {
+	let (mut dirs, mut i) = (vec![root.to_owned()], 0);
+	let mut levels = vec![depth];
+
+	while i < dirs.len() {
+		if levels[i] > 1 {
+			for entry in fs::read_dir(&dirs[i]).into_iter().flatten().flatten() {
+				if !hidden && entry.file_name().as_encoded_bytes().starts_with(b".") {
+					continue;
+				}
+				if entry.file_type().is_ok_and(|t| t.is_dir()) {
+					dirs.push(entry.path());
+					levels.push(levels[i] - 1);
+				}
+			}
+		}
+		if dirs.len() > max {
+			return None;
+		}
+		i += 1;
+	}
+	Some(dirs)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_subdirs() {
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		fs::create_dir_all(root.join("a/b/c")).unwrap();
+		fs::create_dir_all(root.join(".git")).unwrap();
+
+		let mut dirs = subdirs(root, 3, false, 10).unwrap();
+		dirs.sort_unstable();
+		assert_eq!(dirs, [root.to_owned(), root.join("a"), root.join("a/b")]);
+
+		assert_eq!(subdirs(root, 1, true, 10).unwrap(), [root]);
+		assert_eq!(subdirs(root, 5, true, 3), None);
+	}
+
+	#[tokio::test]
+	async fn test_live() {
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		let name = root.file_name().unwrap().to_string_lossy();
+		fs::create_dir_all(root.join("a/b")).unwrap();
+
+		let (ct, (tx, mut rx)) = (CancellationToken::new(), mpsc::unbounded_channel());
+		tokio::spawn(live(root.to_owned(), 3, false, true, Duration::from_secs(60), ct.clone(), tx));
+		assert_eq!(rx.recv().await.unwrap(), format!("{name}\n└── a\n    └── b\n"));
+
+		fs::write(root.join("a/b/c.txt"), "").unwrap();
+		let text = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+		assert_eq!(text, format!("{name}\n└── a\n    └── b\n        └── c.txt\n"));
+
+		ct.cancel();
+		assert_eq!(rx.recv().await, None);
+	}
+
+	#[tokio::test]
+	async fn test_ignore() {
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		let name = root.file_name().unwrap().to_string_lossy();
+		fs::create_dir_all(root.join("target/debug")).unwrap();
+		fs::write(root.join(".gitignore"), "/target/\n").unwrap();
+		fs::write(root.join("a.txt"), "").unwrap();
+
+		let opt = |c: Cmd| Opt::from(c).ignore;
+		assert!(opt(Cmd::new("tree")));
+		assert!(!opt(Cmd::new("tree").with("ignore", "no")));
+
+		for (ignore, expected) in [
+			(true, format!("{name}\n└── a.txt\n")),
+			(false, format!("{name}\n├── target\n│   └── debug\n└── a.txt\n")),
+		] {
+			let (ct, (tx, mut rx)) = (CancellationToken::new(), mpsc::unbounded_channel());
+			tokio::spawn(live(root.to_owned(), 3, false, ignore, Duration::from_secs(60), ct.clone(), tx));
+			assert_eq!(rx.recv().await.unwrap(), expected);
+			ct.cancel();
+		}
+	}
+}
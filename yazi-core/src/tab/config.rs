@@ -1,33 +1,53 @@
-use yazi_config::{manager::SortBy, MANAGER};
+use yazi_config::{manager::{SortBy, SortDirs}, MANAGER, PREVIEW};
 use yazi_fs::FilesSorter;
 
+use super::FolderView;
+
 #[derive(Clone, PartialEq)]
 pub struct Config {
 	// Sorting
 	pub sort_by:        SortBy,
 	pub sort_sensitive: bool,
 	pub sort_reverse:   bool,
-	pub sort_dir_first: bool,
+	pub sort_dirs:      SortDirs,
 	pub sort_translit:  bool,
 
 	// Display
-	pub linemode:    String,
-	pub show_hidden: bool,
+	pub linemode:        String,
+	pub time_format:     String,
+	pub size_format:     String,
+	pub show_hidden:     bool,
+	pub show_extensions: bool,
+	pub dir_readme:      bool,
+	pub text_stats:      bool,
+	pub dir_tree:        bool,
+
+	// Entering
+	pub collapse_dirs: bool,
 }
 
 impl Default for Config {
 	fn default() -> Self {
 		Self {
 			// Sorting
-			sort_by:        MANAGER.sort_by,
+			sort_by:        MANAGER.sort_by.clone(),
 			sort_sensitive: MANAGER.sort_sensitive,
 			sort_reverse:   MANAGER.sort_reverse,
-			sort_dir_first: MANAGER.sort_dir_first,
+			sort_dirs:      MANAGER.sort_dir_first.into(),
 			sort_translit:  MANAGER.sort_translit,
 
 			// Display
-			linemode:    MANAGER.linemode.to_owned(),
-			show_hidden: MANAGER.show_hidden,
+			linemode:        MANAGER.linemode.to_owned(),
+			time_format:     MANAGER.time_format.to_owned(),
+			size_format:     MANAGER.size_format.to_owned(),
+			show_hidden:     MANAGER.show_hidden,
+			show_extensions: MANAGER.show_extensions,
+			dir_readme:      PREVIEW.dir_readme,
+			text_stats:      PREVIEW.text_stats,
+			dir_tree:        PREVIEW.dir_tree,
+
+			// Entering
+			collapse_dirs: MANAGER.collapse_dirs,
 		}
 	}
 }
@@ -39,13 +59,24 @@ impl Config {
 		*self != old
 	}
 
+	#[inline]
+	pub fn folder_view(&self) -> FolderView {
+		if self.dir_readme {
+			FolderView::Readme
+		} else if self.dir_tree {
+			FolderView::Tree { hidden: self.show_hidden }
+		} else {
+			FolderView::List
+		}
+	}
+
 	#[inline]
 	pub(super) fn sorter(&self) -> FilesSorter {
 		FilesSorter {
-			by:        self.sort_by,
+			by:        self.sort_by.clone(),
 			sensitive: self.sort_sensitive,
 			reverse:   self.sort_reverse,
-			dir_first: self.sort_dir_first,
+			dirs:      self.sort_dirs,
 			translit:  self.sort_translit,
 		}
 	}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 use yazi_fs::{Files, Filter, FilterCase};
-use yazi_shared::fs::Url;
+use yazi_shared::fs::{File, Url};
 
 pub struct Finder {
 	pub filter: Filter,
@@ -15,7 +15,7 @@ impl Finder {
 		Ok(Self { filter: Filter::new(s, case)?, matched: Default::default(), revision: 0 })
 	}
 
-	pub(super) fn prev(&self, files: &Files, cursor: usize, include: bool) -> Option<isize> {
+	pub(super) fn prev(&self, files: &[File], cursor: usize, include: bool) -> Option<isize> {
 		for i in !include as usize..files.len() {
 			let idx = (cursor + files.len() - i) % files.len();
 			if files[idx].name().is_some_and(|n| self.filter.matches(n)) {
@@ -25,7 +25,7 @@ impl Finder {
 		None
 	}
 
-	pub(super) fn next(&self, files: &Files, cursor: usize, include: bool) -> Option<isize> {
+	pub(super) fn next(&self, files: &[File], cursor: usize, include: bool) -> Option<isize> {
 		for i in !include as usize..files.len() {
 			let idx = (cursor + i) % files.len();
 			if files[idx].name().is_some_and(|n| self.filter.matches(n)) {
@@ -67,3 +67,46 @@ impl Finder {
 	#[inline]
 	pub fn matched_idx(&self, url: &Url) -> Option<u8> { self.matched.get(url).copied() }
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn files(names: &[&str]) -> Vec<File> {
+		names.iter().map(|n| File::from_dummy(Url::from(format!("/tmp/{n}")), None)).collect()
+	}
+
+	#[test]
+	fn test_next_wraps_around() {
+		let files = files(&["a.rs", "b.txt", "c.rs", "d.txt"]);
+		let finder = Finder::new(r"\.rs$", FilterCase::Smart).unwrap();
+
+		assert_eq!(finder.next(&files, 0, false), Some(2));
+		assert_eq!(finder.next(&files, 2, false), Some(-2));
+		assert_eq!(finder.next(&files, 3, false), Some(-3));
+		assert_eq!(finder.next(&files, 0, true), Some(0));
+	}
+
+	#[test]
+	fn test_prev_wraps_around() {
+		let files = files(&["a.rs", "b.txt", "c.rs", "d.txt"]);
+		let finder = Finder::new(r"\.rs$", FilterCase::Smart).unwrap();
+
+		assert_eq!(finder.prev(&files, 2, false), Some(-2));
+		assert_eq!(finder.prev(&files, 0, false), Some(2));
+		assert_eq!(finder.prev(&files, 1, false), Some(-1));
+	}
+
+	#[test]
+	fn test_smart_case() {
+		let files = files(&["Foo", "foo", "bar"]);
+
+		let finder = Finder::new("foo", FilterCase::Smart).unwrap();
+		assert_eq!(finder.next(&files, 0, false), Some(1));
+		assert_eq!(finder.next(&files, 1, false), Some(-1));
+
+		let finder = Finder::new("Foo", FilterCase::Smart).unwrap();
+		assert_eq!(finder.next(&files, 0, false), None);
+		assert_eq!(finder.next(&files, 1, false), Some(-1));
+	}
+}
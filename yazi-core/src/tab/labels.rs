@@ -0,0 +1,57 @@
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+// Labels drawn over the visible files by `jump_labels`
+#[derive(Default)]
+pub struct Labels {
+	pub cands: Vec<(String, usize)>,
+	pub typed: String,
+}
+
+impl Labels {
+	pub fn new(start: usize, count: usize) -> Self {
+		Self { cands: Self::generate(count).into_iter().zip(start..).collect(), typed: String::new() }
+	}
+
+	// The candidates still matching what has been typed
+	#[inline]
+	pub fn matches(&self) -> impl Iterator<Item = &(String, usize)> {
+		self.cands.iter().filter(|(l, _)| l.starts_with(&self.typed))
+	}
+
+	// Single letters as long as they suffice, otherwise letters from the end of the
+	// alphabet become the prefixes of two-letter labels, so no label is a prefix of
+	// another. At most 26 * 26 labels are made.
+	fn generate(count: usize) -> Vec<String> {
+		let n = ALPHABET.len();
+		let prefixes = if count <= n { 0 } else { (count - n).div_ceil(n - 1).min(n) };
+
+		let singles = ALPHABET[..n - prefixes].iter().map(|&c| (c as char).to_string());
+		let doubles = ALPHABET[n - prefixes..]
+			.iter()
+			.flat_map(|&p| ALPHABET.iter().map(move |&c| format!("{}{}", p as char, c as char)));
+
+		singles.chain(doubles).take(count).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_generate() {
+		assert!(Labels::generate(0).is_empty());
+		assert_eq!(Labels::generate(3), ["a", "b", "c"]);
+		assert_eq!(Labels::generate(26).last().unwrap(), "z");
+
+		let labels = Labels::generate(30);
+		assert_eq!(labels[..2], ["a", "b"]);
+		assert_eq!(labels[24..], ["y", "za", "zb", "zc", "zd", "ze"]);
+
+		let labels = Labels::generate(1000);
+		assert_eq!(labels.len(), 26 * 26);
+		for (i, a) in labels.iter().enumerate() {
+			assert!(labels.iter().skip(i + 1).all(|b| !b.starts_with(a.as_str())));
+		}
+	}
+}
@@ -2,14 +2,17 @@ mod backstack;
 mod commands;
 mod config;
 mod finder;
+mod labels;
 mod mode;
 mod preview;
 mod selected;
 mod tab;
+mod tree;
 
 pub use backstack::*;
 pub use config::*;
 pub use finder::*;
+pub use labels::*;
 pub use mode::*;
 pub use preview::*;
 pub use selected::*;
@@ -1,18 +1,38 @@
 use std::time::Duration;
 
-use tokio::{pin, task::JoinHandle};
+use tokio::{fs, pin, select, task::{spawn_blocking, JoinHandle}};
 use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use tokio_util::sync::CancellationToken;
 use yazi_adapter::ADAPTOR;
-use yazi_config::PLUGIN;
+use yazi_config::{plugin::Previewer, LAYOUT, PLUGIN, PREVIEW};
 use yazi_fs::Files;
-use yazi_plugin::{external::Highlighter, isolate, utils::PreviewLock};
-use yazi_shared::{fs::{Cha, File, FilesOp, Url}, MIME_DIR};
+use yazi_plugin::{bindings::Window, elements::Paragraph, external::{self, Highlighter}, isolate, utils::PreviewLock};
+use yazi_shared::{emit, event::Cmd, fs::{Cha, File, FilesOp, Url}, Layer, MIME_DIR};
+
+use super::tree::draw;
+
+// How a hovered directory is previewed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FolderView {
+	List,
+	Readme,
+	Tree { hidden: bool },
+}
 
 #[derive(Default)]
 pub struct Preview {
-	pub lock: Option<PreviewLock>,
-	pub skip: usize,
+	pub lock:    Option<PreviewLock>,
+	pub skip:    usize,
+	pub rule:    usize,
+	// Whether the keys bound with `mode = "preview"` take over
+	pub focused: bool,
+	// The number typed before a key in the focused preview, e.g. the 5 of `5j`
+	pub count:   usize,
+	// Whether text previews come with their line, word and byte counts
+	pub stats:   bool,
+	// The README previewed in place of the hovered directory, seeked by lines
+	// rather than through the directory's previewer
+	pub readme:  Option<Url>,
 
 	previewer_ct:  Option<CancellationToken>,
 	folder_loader: Option<JoinHandle<()>>,
@@ -24,22 +44,22 @@ impl Preview {
 			return;
 		}
 
-		let Some(previewer) = PLUGIN.previewer(&file.url, mime) else {
+		let Some(previewer) = PLUGIN.previewers(&file.url, mime).nth(self.rule) else {
 			self.reset();
 			return;
 		};
 
 		self.abort();
-		if previewer.sync {
-			isolate::peek_sync(&previewer.run, file, self.skip);
-		} else {
-			self.previewer_ct = Some(isolate::peek(&previewer.run, file, self.skip));
-		}
+		self.previewer_ct = Self::run(previewer, file, self.skip, self.rule, self.stats);
 	}
 
-	pub fn go_folder(&mut self, file: File, dir: Option<Cha>, force: bool) {
+	pub fn go_folder(&mut self, file: File, dir: Option<Cha>, force: bool, view: FolderView) {
 		let (cha, url) = (file.cha, file.url());
-		self.go(file, MIME_DIR, force);
+		match view {
+			FolderView::List => self.go(file, MIME_DIR, force),
+			FolderView::Readme => self.go_readme(file, force),
+			FolderView::Tree { hidden } => self.go_tree(file, force, hidden),
+		}
 
 		if self.content_unchanged(&url, cha) {
 			return;
@@ -64,16 +84,124 @@ impl Preview {
 		}));
 	}
 
+	// Previews the README of `dir`, or lists it if there is none
+	fn go_readme(&mut self, dir: File, force: bool) {
+		if !force && self.content_unchanged(&dir.url, dir.cha) {
+			return;
+		}
+
+		self.abort();
+		let (ct, skip, rule, stats) = (CancellationToken::new(), self.skip, self.rule, self.stats);
+		self.previewer_ct = Some(ct.clone());
+
+		tokio::spawn(async move {
+			let readme = select! {
+				_ = ct.cancelled() => return,
+				r = Self::find_readme(&dir.url) => r,
+			};
+
+			let (file, mime, rule) = match readme {
+				Some((file, mime)) => (file, mime, 0),
+				None => (dir, MIME_DIR.to_owned(), rule),
+			};
+
+			let Some(previewer) = PLUGIN.previewers(&file.url, &mime).nth(rule) else { return };
+			if let Some(inner) = Self::run(previewer, file, skip, rule, stats) {
+				ct.cancelled().await;
+				inner.cancel();
+			}
+		});
+	}
+
+	// Draws the tree of `dir` on a blocking thread, as it may take a while
+	fn go_tree(&mut self, dir: File, force: bool, hidden: bool) {
+		if !force && self.content_unchanged(&dir.url, dir.cha) {
+			return;
+		}
+
+		self.abort();
+		let (ct, skip) = (CancellationToken::new(), self.skip);
+		self.previewer_ct = Some(ct.clone());
+
+		let (depth, max) = (PREVIEW.dir_tree_depth as usize, PREVIEW.dir_tree_max);
+		tokio::spawn(async move {
+			let root = dir.url.to_path_buf();
+			let text = select! {
+				_ = ct.cancelled() => return,
+				t = spawn_blocking(move || draw(&root, depth, hidden, true, max)) => t,
+			};
+			let Ok(text) = text else { return };
+
+			let lock = PreviewLock {
+				url: dir.url(),
+				cha: dir.cha,
+				skip,
+				window: Window::default(),
+				data: vec![Box::new(Paragraph {
+					area: LAYOUT.load().preview,
+					text: text.into(),
+					..Default::default()
+				})],
+			};
+			emit!(Call(Cmd::new("preview").with_any("lock", lock), Layer::Manager));
+		});
+	}
+
+	async fn find_readme(dir: &Url) -> Option<(File, String)> {
+		let mut it = fs::read_dir(dir).await.ok()?;
+		let mut names = vec![];
+		while let Ok(Some(entry)) = it.next_entry().await {
+			names.push(entry.file_name());
+		}
+		// So that a pattern like `index.*` picks the same file whatever the read order
+		names.sort_unstable();
+
+		for pat in &PREVIEW.dir_readme_names {
+			for name in names.iter().filter(|&n| pat.match_path(n, false)) {
+				let Ok(file) = File::from(dir.join(name)).await else { continue };
+				if file.is_dir() {
+					continue;
+				}
+				let mime = external::mimetype(&file.url).await.unwrap_or_default();
+				return Some((file, mime));
+			}
+		}
+		None
+	}
+
+	fn run(
+		previewer: &Previewer,
+		file: File,
+		skip: usize,
+		rule: usize,
+		stats: bool,
+	) -> Option<CancellationToken> {
+		if previewer.sync {
+			isolate::peek_sync(previewer, file, skip, rule, stats);
+			None
+		} else {
+			Some(isolate::peek(previewer, file, skip, rule, stats))
+		}
+	}
+
 	#[inline]
 	pub fn abort(&mut self) {
 		self.previewer_ct.take().map(|ct| ct.cancel());
 		Highlighter::abort();
 	}
 
+	// Cancels `ct` along with the preview, when it moves on
+	#[inline]
+	pub fn hold(&mut self, ct: CancellationToken) {
+		self.abort();
+		self.previewer_ct = Some(ct);
+	}
+
 	#[inline]
 	pub fn reset(&mut self) -> bool {
 		self.abort();
 		ADAPTOR.image_hide().ok();
+		self.readme = None;
 		self.lock.take().is_some()
 	}
 
@@ -96,3 +224,35 @@ impl Preview {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_find_readme() {
+		crate::init_tests();
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		fs::create_dir_all(root.join("README")).unwrap();
+		for name in ["index.txt", "index.md", "index.html"] {
+			fs::write(root.join(name), "").unwrap();
+		}
+
+		let name =
+			|r: Option<(File, String)>| r.map(|(f, _)| f.name().unwrap().to_string_lossy().into_owned());
+		let dir = Url::from(root);
+
+		// A directory named like a README is passed over, and ties go by name
+		assert_eq!(name(Preview::find_readme(&dir).await).as_deref(), Some("index.html"));
+
+		fs::write(root.join("readme.md"), "").unwrap();
+		assert_eq!(name(Preview::find_readme(&dir).await).as_deref(), Some("readme.md"));
+
+		let empty = Url::from(root.join("empty"));
+		fs::create_dir(&empty).unwrap();
+		assert!(Preview::find_readme(&empty).await.is_none());
+	}
+}
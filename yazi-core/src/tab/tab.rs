@@ -8,7 +8,7 @@ use yazi_config::{popup::{Origin, Position}, LAYOUT};
 use yazi_fs::{Folder, FolderStage};
 use yazi_shared::{fs::Url, render};
 
-use super::{Backstack, Config, Finder, Mode, Preview};
+use super::{Backstack, Config, Finder, Labels, Mode, Preview};
 use crate::tab::Selected;
 
 #[derive(Default)]
@@ -22,9 +22,15 @@ pub struct Tab {
 	pub backstack: Backstack<Url>,
 	pub history:   HashMap<Url, Folder>,
 	pub selected:  Selected,
+	// The line and column of a file revealed from a content search
+	pub position:  Option<(Url, usize, usize)>,
+	// Where a `cd --collapse` started, and the directory it ended up in
+	pub collapsed: Option<(Url, Url)>,
 
 	pub preview:       Preview,
 	pub finder:        Option<Finder>,
+	pub labels:        Option<Labels>,
+	pub(super) jump:   Option<String>,
 	pub(super) search: Option<JoinHandle<Result<()>>>,
 }
 
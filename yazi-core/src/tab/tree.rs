@@ -0,0 +1,180 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use yazi_shared::natsort;
+
+// Draws `root` like `tree(1)` does, listing directories first. With `ignore`,
+// the entries ignored by the `.gitignore` and `.ignore` files on the way are
+// left out, and so is `.git`.
+pub(super) fn draw(root: &Path, depth: usize, hidden: bool, ignore: bool, max: usize) -> String {
+	let mut out = root.file_name().unwrap_or(root.as_os_str()).to_string_lossy().into_owned();
+	out.push('\n');
+
+	let mut drawer = Drawer { hidden, ignore: ignore.then(Vec::new), left: max, out };
+	drawer.walk(root, "", depth);
+	drawer.out
+}
+
+struct Drawer {
+	hidden: bool,
+	ignore: Option<Vec<Ignore>>,
+	left:   usize,
+	out:    String,
+}
+
+impl Drawer {
+	fn walk(&mut self, dir: &Path, prefix: &str, depth: usize) {
+		let Ok(it) = fs::read_dir(dir) else { return };
+		let pushed = match self.ignore.as_mut().zip(Ignore::load(dir)) {
+			Some((stack, i)) => {
+				stack.push(i);
+				true
+			}
+			None => false,
+		};
+
+		let mut entries: Vec<_> = it
+			.flatten()
+			.filter(|e| self.hidden || !e.file_name().as_encoded_bytes().starts_with(b"."))
+			.map(|e| (e.file_type().is_ok_and(|t| t.is_dir()), e))
+			.filter(|(is_dir, e)| !self.ignored(&e.path(), *is_dir))
+			.collect();
+
+		entries.sort_unstable_by(|(a_dir, a), (b_dir, b)| {
+			b_dir.cmp(a_dir).then_with(|| {
+				natsort(a.file_name().as_encoded_bytes(), b.file_name().as_encoded_bytes(), false)
+			})
+		});
+
+		let len = entries.len();
+		for (i, (is_dir, entry)) in entries.into_iter().enumerate() {
+			if self.left == 0 {
+				self.out.push_str(&format!("{prefix}└── … {} more\n", len - i));
+				break;
+			}
+			self.left -= 1;
+
+			let last = i + 1 == len;
+			let name = entry.file_name();
+			self.out.push_str(prefix);
+			self.out.push_str(if last { "└── " } else { "├── " });
+			self.out.push_str(&name.to_string_lossy());
+			self.out.push('\n');
+
+			if is_dir && depth > 1 {
+				let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+				self.walk(&entry.path(), &prefix, depth - 1);
+			}
+		}
+
+		if pushed {
+			self.ignore.as_mut().map(|s| s.pop());
+		}
+	}
+
+	fn ignored(&self, path: &Path, is_dir: bool) -> bool {
+		let Some(stack) = &self.ignore else { return false };
+		path.file_name().is_some_and(|n| n == ".git") || stack.iter().any(|i| i.matches(path, is_dir))
+	}
+}
+
+// The patterns of the `.gitignore` and `.ignore` files in a directory.
+// Negated patterns are not supported, and are skipped.
+pub(super) struct Ignore {
+	base: PathBuf,
+	any:  GlobSet,
+	dirs: GlobSet,
+}
+
+impl Ignore {
+	pub(super) fn load(dir: &Path) -> Option<Self> {
+		let (mut any, mut dirs) = (GlobSetBuilder::new(), GlobSetBuilder::new());
+		let mut empty = true;
+
+		for name in [".gitignore", ".ignore"] {
+			let Ok(s) = fs::read_to_string(dir.join(name)) else { continue };
+			for line in s.lines().map(str::trim_end) {
+				if line.is_empty() || line.starts_with(['#', '!']) {
+					continue;
+				}
+
+				// Patterns without a slash match at any level, the others are relative to `dir`
+				let pat = line.trim_end_matches('/');
+				let pat = match pat.strip_prefix('/') {
+					Some(p) => p.to_owned(),
+					None if pat.contains('/') => pat.to_owned(),
+					None => format!("**/{pat}"),
+				};
+
+				let Ok(glob) = GlobBuilder::new(&pat).literal_separator(true).build() else { continue };
+				if line.ends_with('/') {
+					dirs.add(glob);
+				} else {
+					any.add(glob);
+				}
+				empty = false;
+			}
+		}
+
+		if empty {
+			return None;
+		}
+		Some(Self { base: dir.to_owned(), any: any.build().ok()?, dirs: dirs.build().ok()? })
+	}
+
+	pub(super) fn matches(&self, path: &Path, is_dir: bool) -> bool {
+		let Ok(rel) = path.strip_prefix(&self.base) else { return false };
+		self.any.is_match(rel) || (is_dir && self.dirs.is_match(rel))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_draw() {
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		let name = root.file_name().unwrap().to_string_lossy();
+		fs::create_dir_all(root.join("b/c")).unwrap();
+		fs::create_dir_all(root.join(".git")).unwrap();
+		fs::write(root.join("a.txt"), "").unwrap();
+		fs::write(root.join("b/d.txt"), "").unwrap();
+		fs::write(root.join("b/c/e.txt"), "").unwrap();
+
+		assert_eq!(
+			draw(root, 3, false, false, 100),
+			format!("{name}\n├── b\n│   ├── c\n│   │   └── e.txt\n│   └── d.txt\n└── a.txt\n")
+		);
+		assert_eq!(draw(root, 1, true, false, 100), format!("{name}\n├── .git\n├── b\n└── a.txt\n"));
+		assert_eq!(draw(root, 1, false, false, 1), format!("{name}\n├── b\n└── … 1 more\n"));
+	}
+
+	#[test]
+	fn test_draw_ignore() {
+		let tmp = tempfile::tempdir().unwrap();
+		let root = tmp.path();
+		let name = root.file_name().unwrap().to_string_lossy();
+		fs::create_dir_all(root.join("src/deep/er")).unwrap();
+		fs::create_dir_all(root.join("target/debug")).unwrap();
+		fs::create_dir_all(root.join(".git")).unwrap();
+		fs::write(root.join(".gitignore"), "/target/\n*.log\n").unwrap();
+		fs::write(root.join("src/a.rs"), "").unwrap();
+		fs::write(root.join("src/b.log"), "").unwrap();
+		fs::write(root.join("src/deep/er/c.rs"), "").unwrap();
+
+		assert_eq!(
+			draw(root, 2, true, true, 100),
+			format!("{name}\n├── src\n│   ├── deep\n│   └── a.rs\n└── .gitignore\n")
+		);
+		assert_eq!(
+			draw(root, 2, false, false, 100),
+			format!("{name}\n├── src\n│   ├── deep\n│   ├── a.rs\n│   └── b.log\n└── target\n    └── debug\n")
+		);
+		assert_eq!(
+			draw(root, 3, false, true, 2),
+			format!("{name}\n└── src\n    ├── deep\n    │   └── … 1 more\n    └── … 1 more\n")
+		);
+	}
+}
@@ -1,7 +1,8 @@
 use std::collections::HashSet;
 
 use tracing::debug;
-use yazi_shared::fs::Url;
+use yazi_config::{tasks::TrashBackend, TASKS};
+use yazi_shared::fs::{duplicate_url, Url};
 
 use super::Tasks;
 
@@ -17,17 +18,36 @@ impl Tasks {
 		}
 	}
 
-	pub fn file_copy(&self, src: &[&Url], dest: &Url, force: bool, follow: bool) {
+	pub fn file_copy(
+		&self,
+		src: &[&Url],
+		dest: &Url,
+		force: bool,
+		follow: bool,
+		times: bool,
+		archive: bool,
+	) {
 		for &u in src {
 			let to = dest.join(u.file_name().unwrap());
 			if force && *u == to {
 				debug!("file_copy: same file, skipping {:?}", to);
 			} else {
-				self.scheduler.file_copy(u.clone(), to, force, follow);
+				self.scheduler.file_copy(u.clone(), to, force, follow, times, archive);
 			}
 		}
 	}
 
+	pub fn file_duplicate(&self, src: Vec<Url>, suffix: String, follow: bool) {
+		let scheduler = self.scheduler.clone();
+		tokio::spawn(async move {
+			for u in src {
+				if let Some(to) = duplicate_url(&u, &suffix).await {
+					scheduler.file_copy(u, to, false, follow, TASKS.preserve_timestamps, false);
+				}
+			}
+		});
+	}
+
 	pub fn file_link(&self, src: &HashSet<Url>, dest: &Url, relative: bool, force: bool) {
 		for u in src {
 			let to = dest.join(u.file_name().unwrap());
@@ -59,4 +79,9 @@ impl Tasks {
 			}
 		}
 	}
+
+	#[inline]
+	pub fn file_restore(&self, targets: Vec<Url>, backend: TrashBackend) {
+		self.scheduler.file_restore(targets, backend);
+	}
 }
@@ -1,15 +1,17 @@
+use std::collections::HashMap;
+
 use yazi_shared::event::Data;
 
 use super::Tasks;
 
 impl Tasks {
 	#[inline]
-	pub fn plugin_micro(&self, name: String, args: Vec<Data>) {
-		self.scheduler.plugin_micro(name, args);
+	pub fn plugin_micro(&self, name: String, args: Vec<Data>, pairs: HashMap<String, String>) {
+		self.scheduler.plugin_micro(name, args, pairs);
 	}
 
 	#[inline]
-	pub fn plugin_macro(&self, name: String, args: Vec<Data>) {
-		self.scheduler.plugin_macro(name, args);
+	pub fn plugin_macro(&self, name: String, args: Vec<Data>, pairs: HashMap<String, String>) {
+		self.scheduler.plugin_macro(name, args, pairs);
 	}
 }
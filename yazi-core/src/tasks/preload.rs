@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use tokio::sync::oneshot;
 use yazi_config::{manager::SortBy, plugin::MAX_PREWORKERS, PLUGIN};
 use yazi_fs::Files;
 use yazi_shared::{fs::{File, Url}, MIME_DIR};
@@ -36,6 +37,11 @@ impl Tasks {
 		}
 	}
 
+	#[inline]
+	pub fn fetch_mimetype(&self, targets: Vec<Url>) -> oneshot::Receiver<Vec<File>> {
+		self.scheduler.fetch_mimetype(targets)
+	}
+
 	pub fn preload_paged(&self, paged: &[File], mimetype: &HashMap<Url, String>) {
 		let mut loaded = self.scheduler.prework.loaded.lock();
 		for f in paged {
@@ -69,13 +75,20 @@ impl Tasks {
 			return;
 		}
 
+		self.prework_size(
+			targets.iter().filter(|f| f.is_dir() && !targets.sizes.contains_key(&f.url)).map(|f| &f.url),
+		);
+	}
+
+	#[inline]
+	pub fn is_sizing(&self, url: &Url) -> bool {
+		self.scheduler.prework.size_loading.read().contains(url)
+	}
+
+	pub fn prework_size<'a>(&self, targets: impl Iterator<Item = &'a Url>) {
 		let targets: Vec<_> = {
 			let loading = self.scheduler.prework.size_loading.read();
-			targets
-				.iter()
-				.filter(|f| f.is_dir() && !targets.sizes.contains_key(&f.url) && !loading.contains(&f.url))
-				.map(|f| &f.url)
-				.collect()
+			targets.filter(|&u| !loading.contains(u)).collect()
 		};
 		if targets.is_empty() {
 			return;
@@ -1,41 +1,156 @@
-use std::{borrow::Cow, collections::HashMap, ffi::OsString, mem};
+use std::{borrow::Cow, ffi::OsString, path::PathBuf};
 
-use yazi_config::{open::Opener, OPEN};
+use tokio::sync::oneshot;
+use yazi_config::open::Opener;
+use yazi_proxy::ManagerProxy;
 use yazi_shared::fs::Url;
 
 use super::Tasks;
 
 impl Tasks {
-	pub fn process_from_files(&self, hovered: Url, targets: Vec<(Url, String)>) {
-		let mut openers = HashMap::new();
-		for (url, mime) in targets {
-			if let Some(opener) = OPEN.openers(&url, mime).and_then(|o| o.first().copied()) {
-				openers.entry(opener).or_insert_with(|| vec![hovered.clone()]).push(url);
+	// Files are grouped by their opener, and the groups are started in the order
+	// their first file appears in `targets`. Blocking openers take over the
+	// terminal, so they run one after another in that order instead of racing.
+	// The files become recent ones as soon as their process has started.
+	pub fn process_from_files(
+		&self,
+		hovered: Url,
+		targets: Vec<(Url, &'static Opener)>,
+		(line, column): (Option<usize>, Option<usize>),
+		spread: Option<bool>,
+	) {
+		let mut blocking = vec![];
+		for (opener, args) in group(&hovered, targets) {
+			let opener = Opener::with_position(Cow::Borrowed(opener), line, column);
+			let opener = Opener::with_spread(opener, spread);
+			if opener.block {
+				let argv = invocations(args, opener.spread);
+				blocking.extend(argv.into_iter().map(|a| (opener.clone(), a)));
+			} else {
+				self.process_from_opener(opener, args, true, None);
 			}
 		}
-		for (opener, args) in openers {
-			self.process_from_opener(
-				Cow::Borrowed(opener),
-				args.into_iter().map(|u| u.into_os_string()).collect(),
-			);
-		}
-	}
 
-	pub fn process_from_opener(&self, opener: Cow<'static, Opener>, mut args: Vec<OsString>) {
-		if opener.spread {
-			self.scheduler.process_open(opener, args, None);
+		if blocking.is_empty() {
 			return;
 		}
-		if args.is_empty() {
-			return;
+
+		let scheduler = self.scheduler.clone();
+		tokio::spawn(async move {
+			for (opener, args) in blocking {
+				let (tx, rx) = oneshot::channel();
+				let spawned = record_recents(&args, None);
+				scheduler.process_open(opener, args, Some(tx), Some(spawned));
+				rx.await.ok();
+			}
+		});
+	}
+
+	pub fn process_from_opener(
+		&self,
+		opener: Cow<'static, Opener>,
+		args: Vec<OsString>,
+		recents: bool,
+		mut spawned: Option<oneshot::Sender<()>>,
+	) {
+		for args in invocations(args, opener.spread) {
+			let spawned =
+				if recents { Some(record_recents(&args, spawned.take())) } else { spawned.take() };
+			self.scheduler.process_open(opener.clone(), args, None, spawned);
 		}
-		if args.len() == 2 {
-			self.scheduler.process_open(opener, args, None);
-			return;
+	}
+}
+
+// Adds the files of `[hovered, file1, file2, ...]` to the recents once told
+// their process has started, and leaves them out if it never does. `then` is
+// told after that.
+fn record_recents(args: &[OsString], then: Option<oneshot::Sender<()>>) -> oneshot::Sender<()> {
+	let urls: Vec<_> = args.iter().skip(1).map(|a| Url::from(PathBuf::from(a))).collect();
+	let (tx, rx) = oneshot::channel();
+	tokio::spawn(async move {
+		if rx.await.is_ok() {
+			ManagerProxy::update_recents(urls);
+			then.map(|tx| tx.send(()));
 		}
-		let hovered = mem::take(&mut args[0]);
-		for target in args.into_iter().skip(1) {
-			self.scheduler.process_open(opener.clone(), vec![hovered.clone(), target], None);
+	});
+	tx
+}
+
+// The `[hovered, file1, file2, ...]` of each opener, in the order their first
+// file appears in `targets`
+fn group(
+	hovered: &Url,
+	targets: Vec<(Url, &'static Opener)>,
+) -> Vec<(&'static Opener, Vec<OsString>)> {
+	let mut groups: Vec<(&Opener, Vec<OsString>)> = vec![];
+	for (url, opener) in targets {
+		match groups.iter_mut().find(|(o, _)| *o == opener) {
+			Some((_, args)) => args.push(url.into_os_string()),
+			None => groups.push((opener, vec![hovered.as_os_str().to_owned(), url.into_os_string()])),
 		}
 	}
+	groups
+}
+
+// Builds the argv of each process from `[hovered, file1, file2, ...]`. A spread
+// opener gets them all at once, otherwise each file gets its own process.
+fn invocations(args: Vec<OsString>, spread: bool) -> Vec<Vec<OsString>> {
+	if spread || args.len() == 2 {
+		return vec![args];
+	}
+
+	let mut it = args.into_iter();
+	let Some(hovered) = it.next() else { return vec![] };
+	it.map(|target| vec![hovered.clone(), target]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_group() {
+		let leak = |run: &str| {
+			&*Box::leak(Box::new(Opener {
+				run:      run.to_owned(),
+				block:    false,
+				orphan:   false,
+				desc:     String::new(),
+				for_:     None,
+				spread:   true,
+				prompted: false,
+			}))
+		};
+		let (image, pdf) = (leak("image"), leak("pdf"));
+
+		let targets = ["a.png", "b.pdf", "c.png", "d.pdf", "e.png"].map(Url::from);
+		let targets = targets.into_iter().zip([image, pdf, image, pdf, image]).collect();
+		let groups: Vec<_> = group(&Url::from("h"), targets)
+			.into_iter()
+			.map(|(o, args)| {
+				(o.run.as_str(), args.into_iter().map(|a| a.into_string().unwrap()).collect())
+			})
+			.collect();
+
+		// Files keep their order, and so do the groups by their first file
+		assert_eq!(groups, [
+			("image", vec!["h".to_owned(), "a.png".to_owned(), "c.png".to_owned(), "e.png".to_owned()]),
+			("pdf", vec!["h".to_owned(), "b.pdf".to_owned(), "d.pdf".to_owned()]),
+		]);
+		assert!(group(&Url::from("h"), vec![]).is_empty());
+	}
+
+	#[test]
+	fn test_invocations() {
+		let args = || ["h", "a", "b c", "d"].map(OsString::from).to_vec();
+		let argv = |v: &[&str]| v.iter().map(OsString::from).collect::<Vec<_>>();
+
+		assert_eq!(invocations(args(), true), [argv(&["h", "a", "b c", "d"])]);
+		assert_eq!(invocations(args(), false), [
+			argv(&["h", "a"]),
+			argv(&["h", "b c"]),
+			argv(&["h", "d"])
+		]);
+		assert!(invocations(vec![], false).is_empty());
+	}
 }
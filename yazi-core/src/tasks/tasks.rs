@@ -52,6 +52,12 @@ impl Tasks {
 		self.handle.abort();
 	}
 
+	// Cancels the tasks, letting the steps already running finish for a while
+	pub async fn shutdown_gracefully(&self) {
+		self.scheduler.shutdown_gracefully(Duration::from_secs(3)).await;
+		self.handle.abort();
+	}
+
 	#[inline]
 	pub fn limit() -> usize {
 		(Dimension::available().rows * TASKS_PERCENT / 100).saturating_sub(TASKS_BORDER + TASKS_PADDING)
@@ -1,6 +1,6 @@
 use std::{collections::HashSet, str::FromStr};
 
-use yazi_config::{keymap::{Control, Key}, KEYMAP};
+use yazi_config::{keymap::{Control, Key, KeyMode}, KEYMAP};
 use yazi_shared::{event::Cmd, render, Layer};
 
 use crate::which::{Which, WhichSorter};
@@ -42,14 +42,13 @@ impl Which {
 		render!();
 	}
 
-	pub fn show_with(&mut self, key: &Key, layer: Layer) {
+	pub fn show_with(&mut self, key: &Key, layer: Layer, mode: KeyMode) {
 		let mut seen = HashSet::new();
 
 		self.layer = layer;
 		self.times = 1;
 		self.cands = KEYMAP
-			.get(layer)
-			.iter()
+			.get_in(layer, mode)
 			.filter(|c| c.on.len() > 1 && &c.on[0] == key)
 			.filter(|&c| seen.insert(&c.on))
 			.map(|c| c.into())
@@ -2,7 +2,7 @@ use anyhow::{bail, Result};
 use mlua::{ExternalResult, IntoLua, Lua, Value};
 use serde::Serialize;
 
-use super::{BodyBulk, BodyBye, BodyCd, BodyCustom, BodyDelete, BodyHey, BodyHi, BodyHover, BodyMove, BodyRename, BodyTab, BodyTrash, BodyYank};
+use super::{BodyBulk, BodyBye, BodyCd, BodyCustom, BodyDelete, BodyHey, BodyHi, BodyHover, BodyMove, BodyRename, BodySelect, BodyTab, BodyTrash, BodyYank};
 use crate::Payload;
 
 #[derive(Debug, Serialize)]
@@ -14,6 +14,7 @@ pub enum Body<'a> {
 	Cd(BodyCd<'a>),
 	Hover(BodyHover<'a>),
 	Tab(BodyTab),
+	Select(BodySelect<'a>),
 	Rename(BodyRename<'a>),
 	Bulk(BodyBulk<'a>),
 	Yank(BodyYank<'a>),
@@ -32,6 +33,7 @@ impl Body<'static> {
 			"cd" => Self::Cd(serde_json::from_str(body)?),
 			"hover" => Self::Hover(serde_json::from_str(body)?),
 			"tab" => Self::Tab(serde_json::from_str(body)?),
+			"select" => Self::Select(serde_json::from_str(body)?),
 			"rename" => Self::Rename(serde_json::from_str(body)?),
 			"bulk" => Self::Bulk(serde_json::from_str(body)?),
 			"@yank" => Self::Yank(serde_json::from_str(body)?),
@@ -56,6 +58,7 @@ impl Body<'static> {
 				| "cd"
 				| "hover"
 				| "tab"
+				| "select"
 				| "rename"
 				| "bulk"
 				| "@yank"
@@ -88,6 +91,7 @@ impl<'a> Body<'a> {
 			Self::Cd(_) => "cd",
 			Self::Hover(_) => "hover",
 			Self::Tab(_) => "tab",
+			Self::Select(_) => "select",
 			Self::Rename(_) => "rename",
 			Self::Bulk(_) => "bulk",
 			Self::Yank(_) => "@yank",
@@ -116,6 +120,7 @@ impl IntoLua<'_> for Body<'static> {
 			Self::Cd(b) => b.into_lua(lua),
 			Self::Hover(b) => b.into_lua(lua),
 			Self::Tab(b) => b.into_lua(lua),
+			Self::Select(b) => b.into_lua(lua),
 			Self::Rename(b) => b.into_lua(lua),
 			Self::Bulk(b) => b.into_lua(lua),
 			Self::Yank(b) => b.into_lua(lua),
@@ -11,6 +11,7 @@ mod hi;
 mod hover;
 mod move_;
 mod rename;
+mod select;
 mod tab;
 mod trash;
 mod yank;
@@ -26,6 +27,7 @@ pub use hi::*;
 pub use hover::*;
 pub use move_::*;
 pub use rename::*;
+pub use select::*;
 pub use tab::*;
 pub use trash::*;
 pub use yank::*;
@@ -0,0 +1,45 @@
+use std::borrow::Cow;
+
+use mlua::{IntoLua, Lua, Value};
+use serde::{Deserialize, Serialize};
+use yazi_shared::fs::Url;
+
+use super::Body;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BodySelect<'a> {
+	pub tab:  usize,
+	pub urls: Cow<'a, Vec<Url>>,
+}
+
+impl<'a> BodySelect<'a> {
+	#[inline]
+	pub fn borrowed(tab: usize, urls: &'a Vec<Url>) -> Body<'a> {
+		Self { tab, urls: Cow::Borrowed(urls) }.into()
+	}
+}
+
+impl BodySelect<'static> {
+	#[inline]
+	pub fn owned(tab: usize, urls: Vec<Url>) -> Body<'static> {
+		Self { tab, urls: Cow::Owned(urls) }.into()
+	}
+}
+
+impl<'a> From<BodySelect<'a>> for Body<'a> {
+	fn from(value: BodySelect<'a>) -> Self { Self::Select(value) }
+}
+
+impl IntoLua<'_> for BodySelect<'static> {
+	fn into_lua(self, lua: &Lua) -> mlua::Result<Value<'_>> {
+		let urls = lua.create_table_with_capacity(self.urls.len(), 0)?;
+		#[allow(clippy::unnecessary_to_owned)]
+		for (i, url) in self.urls.into_owned().into_iter().enumerate() {
+			urls.raw_set(i + 1, lua.create_any_userdata(url)?)?;
+		}
+
+		lua
+			.create_table_from([("tab", self.tab.into_lua(lua)?), ("urls", urls.into_lua(lua)?)])?
+			.into_lua(lua)
+	}
+}
@@ -83,6 +83,7 @@ impl Display for Payload<'_> {
 			Body::Cd(b) => serde_json::to_string(b),
 			Body::Hover(b) => serde_json::to_string(b),
 			Body::Tab(b) => serde_json::to_string(b),
+			Body::Select(b) => serde_json::to_string(b),
 			Body::Rename(b) => serde_json::to_string(b),
 			Body::Bulk(b) => serde_json::to_string(b),
 			Body::Yank(b) => serde_json::to_string(b),
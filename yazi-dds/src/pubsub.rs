@@ -5,7 +5,7 @@ use parking_lot::RwLock;
 use yazi_boot::BOOT;
 use yazi_shared::{fs::Url, RoCell};
 
-use crate::{body::{Body, BodyBulk, BodyCd, BodyDelete, BodyHi, BodyHover, BodyMove, BodyMoveItem, BodyRename, BodyTab, BodyTrash, BodyYank}, Client, ID, PEERS};
+use crate::{body::{Body, BodyBulk, BodyCd, BodyDelete, BodyHi, BodyHover, BodyMove, BodyMoveItem, BodyRename, BodySelect, BodyTab, BodyTrash, BodyYank}, Client, ID, PEERS};
 
 pub static LOCAL: RoCell<RwLock<HashMap<String, HashMap<String, Function<'static>>>>> =
 	RoCell::new();
@@ -124,6 +124,28 @@ impl Pubsub {
 		}
 	}
 
+	pub fn pub_from_select<'a>(tab: usize, urls: impl Iterator<Item = &'a Url>) {
+		let (lua, remote, local) = (
+			LOCAL.read().contains_key("select"),
+			PEERS.read().values().any(|p| p.able("select")),
+			BOOT.local_events.contains("select"),
+		);
+		if !lua && !remote && !local {
+			return;
+		}
+
+		let urls: Vec<_> = urls.cloned().collect();
+		if remote {
+			Client::push(BodySelect::borrowed(tab, &urls));
+		}
+		if local {
+			BodySelect::borrowed(tab, &urls).with_receiver(*ID).flush();
+		}
+		if lua {
+			Self::pub_(BodySelect::owned(tab, urls));
+		}
+	}
+
 	pub fn pub_from_rename(tab: usize, from: &Url, to: &Url) {
 		if LOCAL.read().contains_key("rename") {
 			Self::pub_(BodyRename::dummy(tab, from, to));
@@ -1,10 +1,10 @@
-use std::{collections::VecDeque, sync::atomic::Ordering};
+use std::sync::atomic::Ordering;
 
 use anyhow::Result;
 use crossterm::event::KeyEvent;
 use yazi_config::keymap::Key;
 use yazi_core::input::InputMode;
-use yazi_shared::{emit, event::{Cmd, Event, NEED_RENDER}, Layer};
+use yazi_shared::{emit, event::{Cmd, CmdSeq, Event, Outcome, NEED_RENDER}, Layer};
 
 use crate::{lives::Lives, Ctx, Executor, Router, Signals, Term};
 
@@ -12,15 +12,25 @@ pub(crate) struct App {
 	pub(crate) cx:      Ctx,
 	pub(crate) term:    Option<Term>,
 	pub(crate) signals: Signals,
+
+	// The rest of a sequence, waiting for the prompt opened by its last command,
+	// and whether that has shown up yet
+	parked: Option<(CmdSeq, Layer, bool)>,
 }
 
 impl App {
+	// An app without a terminal, for tests on the way events are handled
+	#[cfg(test)]
+	pub(crate) fn dummy() -> Self {
+		Self { cx: Ctx::make(), term: None, signals: Signals::dummy(), parked: None }
+	}
+
 	pub(crate) async fn serve() -> Result<()> {
 		let term = Term::start()?;
 		let (mut rx, signals) = (Event::take(), Signals::start()?);
 
 		Lives::register()?;
-		let mut app = Self { cx: Ctx::make(), term: Some(term), signals };
+		let mut app = Self { cx: Ctx::make(), term: Some(term), signals, parked: None };
 		app.render();
 
 		let mut times = 0;
@@ -61,19 +71,42 @@ impl App {
 			Event::Paste(str) => self.dispatch_paste(str),
 			Event::Quit(opt) => self.quit(opt),
 		}
+
+		let prompting = self.cx.prompting();
+		match &mut self.parked {
+			Some((.., shown)) if prompting => *shown = true,
+			Some((.., true)) => {
+				let (seq, layer, _) = self.parked.take().unwrap();
+				emit!(Seq(seq, layer));
+			}
+			_ => {}
+		}
 		Ok(())
 	}
 
 	#[inline]
-	fn dispatch_call(&mut self, cmd: Cmd, layer: Layer) { Executor::new(self).execute(cmd, layer); }
+	fn dispatch_call(&mut self, cmd: Cmd, layer: Layer) {
+		self.cx.manager.log.record_call(&cmd, layer);
+		Executor::new(self).execute(cmd, layer);
+	}
 
-	#[inline]
-	fn dispatch_seq(&mut self, mut cmds: VecDeque<Cmd>, layer: Layer) {
-		if let Some(cmd) = cmds.pop_front() {
-			Executor::new(self).execute(cmd, layer);
+	fn dispatch_seq(&mut self, mut seq: CmdSeq, layer: Layer) {
+		// A sequence bound in the manager or tasks pauses while an interactive
+		// command earlier in it is prompting, or about to, and goes on once that
+		// is answered
+		if matches!(layer, Layer::Manager | Layer::Tasks) && self.cx.prompting() {
+			self.parked = Some((seq, layer, true));
+			return;
 		}
-		if !cmds.is_empty() {
-			emit!(Seq(cmds, layer));
+
+		let outcome = seq.step(|cmd| {
+			self.cx.manager.log.record_cmd(&cmd, layer);
+			Executor::new(self).execute(cmd, layer)
+		});
+		match outcome {
+			_ if seq.is_empty() => {}
+			Outcome::Prompted => self.parked = Some((seq, layer, false)),
+			_ => emit!(Seq(seq, layer)),
 		}
 	}
 
@@ -93,3 +126,88 @@ impl App {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use tokio::{sync::mpsc::UnboundedReceiver, time::timeout};
+
+	use super::*;
+
+	// Only the sequences and the prompt are dispatched, the rest needs a terminal
+	async fn dispatch_until(
+		app: &mut App,
+		rx: &mut UnboundedReceiver<Event>,
+		f: impl Fn(&App) -> bool,
+	) {
+		while !f(app) {
+			let event = timeout(Duration::from_secs(3), rx.recv()).await.unwrap().unwrap();
+			if let Event::Seq(..) | Event::Call(_, Layer::Input) = event {
+				app.dispatch(event).unwrap();
+			}
+		}
+	}
+
+	#[tokio::test]
+	async fn test_dispatch_seq() {
+		crate::init_tests();
+		let mut rx = crate::events().await;
+		let mut app = App::dummy();
+
+		// The prompt is opened from a task spawned by `create`, so it isn't shown yet
+		// when the rest of the sequence would be run, and that waits for it anyway
+		let seq = CmdSeq::new([Cmd::new("create"), Cmd::new("visual_mode")], false);
+		app.dispatch(Event::Seq(seq, Layer::Manager)).unwrap();
+		assert!(matches!(app.parked, Some((_, Layer::Manager, false))));
+		assert!(!app.cx.input.visible);
+
+		dispatch_until(&mut app, &mut rx, |app| app.cx.input.visible).await;
+		assert!(matches!(app.parked, Some((_, Layer::Manager, true))));
+		assert!(!app.cx.manager.active().mode.is_visual());
+
+		app.dispatch(Event::Call(Cmd::new("close"), Layer::Input)).unwrap();
+		dispatch_until(&mut app, &mut rx, |app| app.cx.manager.active().mode.is_visual()).await;
+		assert!(app.parked.is_none());
+	}
+
+	#[tokio::test]
+	async fn test_copy_to_picked() {
+		crate::init_tests();
+		let mut rx = crate::events().await;
+		let mut app = App::dummy();
+
+		let tmp = tempfile::tempdir_in(std::env::var("YAZI_TEST_DIR").unwrap()).unwrap();
+		std::fs::write(tmp.path().join("a"), "a").unwrap();
+
+		let targets = vec![yazi_shared::fs::Url::from(tmp.path().join("a"))];
+		app
+			.dispatch(Event::Call(Cmd::new("copy_to").with_any("targets", targets), Layer::Manager))
+			.unwrap();
+		dispatch_until(&mut app, &mut rx, |app| app.cx.input.visible).await;
+
+		// The typed path is expanded and cleaned, and created as it ends with a `/`
+		let name = tmp.path().file_name().unwrap().to_str().unwrap();
+		app.cx.input.type_str(&format!("$YAZI_TEST_DIR/{name}/b/../c/"));
+		app.dispatch(Event::Call(Cmd::new("close").with_bool("submit", true), Layer::Input)).unwrap();
+		let cmd = loop {
+			match timeout(Duration::from_secs(3), rx.recv()).await.unwrap().unwrap() {
+				Event::Call(cmd, Layer::Manager) if cmd.name == "copy_to" => break cmd,
+				_ => {}
+			}
+		};
+		assert!(tmp.path().join("c").is_dir());
+		assert!(!tmp.path().join("b").exists());
+
+		// Then copied into it, through the scheduler
+		app.dispatch(Event::Call(cmd, Layer::Manager)).unwrap();
+		let copied = tmp.path().join("c/a");
+		timeout(Duration::from_secs(3), async {
+			while !copied.exists() {
+				tokio::time::sleep(Duration::from_millis(10)).await;
+			}
+		})
+		.await
+		.unwrap();
+	}
+}
@@ -7,5 +7,6 @@ mod render;
 mod resize;
 mod resume;
 mod stop;
+mod timer;
 mod update_notify;
 mod update_progress;
@@ -3,8 +3,7 @@ use std::fmt::Display;
 use mlua::TableExt;
 use scopeguard::defer;
 use tracing::warn;
-use yazi_dds::Sendable;
-use yazi_plugin::{loader::LOADER, RtRef, LUA};
+use yazi_plugin::{entry_args, has_command, loader::LOADER, run_command, RtRef, LUA};
 use yazi_shared::{emit, event::Cmd, Layer};
 
 use crate::{app::App, lives::Lives};
@@ -16,8 +15,13 @@ impl App {
 			Err(e) => return warn!("{e}"),
 		};
 
+		if opt.reload {
+			if let Err(e) = LOADER.unload(&LUA, &opt.id) {
+				warn!("Failed to unload plugin `{}`: {e}", opt.id);
+			}
+		}
 		if !opt.sync {
-			return self.cx.tasks.plugin_micro(opt.id, opt.args);
+			return self.cx.tasks.plugin_micro(opt.id, opt.args, opt.pairs);
 		}
 
 		if LOADER.read().contains_key(&opt.id) {
@@ -58,8 +62,21 @@ impl App {
 			if let Some(cb) = opt.cb {
 				cb(&LUA, plugin)
 			} else {
-				plugin.call_method("entry", Sendable::list_to_table(&LUA, opt.args)?)
+				plugin.call_method("entry", entry_args(&LUA, opt.args, opt.pairs)?)
 			}
 		});
 	}
+
+	// Runs a command registered by a plugin, returning `false` if there's none
+	pub(crate) fn plugin_command(&mut self, cmd: Cmd) -> bool {
+		if !has_command(&LUA, &cmd.name) {
+			return false;
+		}
+
+		let name = cmd.name.clone();
+		if let Err(e) = Lives::scope(&self.cx, |_| run_command(&LUA, cmd)) {
+			warn!("Failed to run command `{name}`: {e}");
+		}
+		true
+	}
 }
@@ -7,8 +7,7 @@ use crate::{app::App, Term};
 
 impl App {
 	pub(crate) fn quit(&mut self, opt: EventQuit) -> ! {
-		self.cx.tasks.shutdown();
-		self.cx.manager.shutdown();
+		self.cx.wind_down(opt.force);
 		futures::executor::block_on(yazi_dds::shutdown());
 		futures::executor::block_on(yazi_dds::STATE.drain()).ok();
 
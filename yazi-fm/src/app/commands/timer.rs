@@ -0,0 +1,15 @@
+use tracing::warn;
+use yazi_plugin::{fire_timer, LUA};
+use yazi_shared::event::{Cmd, Data};
+
+use crate::{app::App, lives::Lives};
+
+impl App {
+	// Runs the callback of a timer set by `ya.set_timeout()` or `ya.set_interval()`
+	pub(crate) fn timer(&mut self, cmd: Cmd) {
+		let Some(id) = cmd.first().and_then(Data::as_usize) else { return };
+		if let Err(e) = Lives::scope(&self.cx, |_| fire_timer(&LUA, id)) {
+			warn!("Failed to run timer callback: {e}");
+		}
+	}
+}
@@ -0,0 +1,38 @@
+use ratatui::{buffer::Buffer, layout::Rect, text::{Line, Span}, widgets::Widget};
+use yazi_config::{LAYOUT, THEME};
+
+use crate::Ctx;
+
+pub(crate) struct Labels<'a> {
+	cx: &'a Ctx,
+}
+
+impl<'a> Labels<'a> {
+	#[inline]
+	pub(crate) fn new(cx: &'a Ctx) -> Self { Self { cx } }
+}
+
+impl Widget for Labels<'_> {
+	fn render(self, _: Rect, buf: &mut Buffer) {
+		let tab = self.cx.manager.active();
+		let Some(labels) = &tab.labels else {
+			return;
+		};
+
+		let area = LAYOUT.load().current;
+		for (label, idx) in labels.matches() {
+			let Some(y) = idx.checked_sub(tab.current.offset) else { continue };
+			if y >= area.height as usize {
+				continue;
+			}
+
+			// Dim the part already typed, so the keys left to press stand out
+			let (typed, rest) = label.split_at(labels.typed.len());
+			let line = Line::from(vec![
+				Span::styled(typed, THEME.which.rest),
+				Span::styled(rest, THEME.which.cand),
+			]);
+			buf.set_line(area.x, area.y + y as u16, &line, area.width);
+		}
+	}
+}
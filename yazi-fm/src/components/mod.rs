@@ -1,7 +1,9 @@
 #![allow(clippy::module_inception)]
 
+mod labels;
 mod preview;
 mod progress;
 
+pub(super) use labels::*;
 pub(super) use preview::*;
 pub(super) use progress::*;
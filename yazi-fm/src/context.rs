@@ -1,5 +1,12 @@
+use std::path::Path;
+
 use ratatui::layout::Rect;
-use yazi_core::{completion::Completion, confirm::Confirm, help::Help, input::Input, manager::Manager, notify::Notify, select::Select, tasks::Tasks, which::Which};
+use tokio::{runtime::Handle, task::block_in_place};
+use tracing::error;
+use yazi_boot::BOOT;
+use yazi_config::keymap::KeyMode;
+use yazi_core::{completion::Completion, confirm::Confirm, help::Help, input::{Input, InputMode}, manager::Manager, notify::Notify, select::Select, tasks::Tasks, which::Which};
+use yazi_shared::Layer;
 
 pub struct Ctx {
 	pub manager:    Manager,
@@ -28,6 +35,33 @@ impl Ctx {
 		}
 	}
 
+	// Unlike a kill, even a forced quit lets the running task steps finish for a
+	// bit, and saves the state before the terminal is restored
+	pub fn wind_down(&mut self, force: bool) {
+		if force {
+			let tasks = &self.tasks;
+			block_in_place(|| Handle::current().block_on(tasks.shutdown_gracefully()));
+		} else {
+			self.tasks.shutdown();
+		}
+		self.manager.shutdown();
+		self.flush_state(&BOOT.state_dir);
+	}
+
+	// The state is saved in the background as it changes, which the exit could
+	// cut short, so it's written out once more
+	fn flush_state(&self, dir: &Path) {
+		let results = [
+			self.manager.recents.flush(dir),
+			self.manager.log.flush(dir),
+			self.input.flush_histories(dir),
+			yazi_fs::TAGS.read().flush(),
+		];
+		for e in results.into_iter().filter_map(Result::err) {
+			error!("Failed to save the state: {e}");
+		}
+	}
+
 	#[inline]
 	pub fn cursor(&self) -> Option<(u16, u16)> {
 		if self.input.visible {
@@ -39,4 +73,79 @@ impl Ctx {
 		}
 		None
 	}
+
+	// Whether a popup is waiting for the user to respond
+	#[inline]
+	pub fn prompting(&self) -> bool {
+		self.input.visible || self.select.visible || self.confirm.visible
+	}
+
+	// The topmost layer that receives the keys
+	#[inline]
+	pub fn layer(&self) -> Layer {
+		if self.completion.visible {
+			Layer::Completion
+		} else if self.help.visible {
+			Layer::Help
+		} else if self.input.visible {
+			Layer::Input
+		} else if self.confirm.visible {
+			Layer::Confirm
+		} else if self.select.visible {
+			Layer::Select
+		} else if self.tasks.visible {
+			Layer::Tasks
+		} else {
+			Layer::Manager
+		}
+	}
+
+	#[inline]
+	pub fn mode(&self, layer: Layer) -> KeyMode {
+		match layer {
+			Layer::Manager if self.manager.active().preview.focused => KeyMode::Preview,
+			Layer::Manager if self.manager.active().mode.is_visual() => KeyMode::Visual,
+			Layer::Input | Layer::Completion if self.input.mode() == InputMode::Insert => KeyMode::Insert,
+			_ => KeyMode::Normal,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use yazi_shared::{event::Cmd, fs::Url};
+
+	use super::*;
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_wind_down() {
+		crate::init_tests();
+		let dir = &BOOT.state_dir;
+
+		// Loaded on start, then lost, so only the flush on quit can bring it back
+		fs::write(dir.join("recents"), format!("{}\n", dir.display())).unwrap();
+		let mut cx = Ctx::make();
+		fs::remove_file(dir.join("recents")).unwrap();
+		yazi_fs::TAGS.write().add([&Url::from(dir)], "red");
+
+		cx.wind_down(true);
+		assert_eq!(fs::read_to_string(dir.join("recents")).unwrap(), format!("{}\n", dir.display()));
+		assert!(fs::read_to_string(dir.join("tags")).unwrap().contains("red"));
+	}
+
+	#[tokio::test]
+	async fn test_mode() {
+		crate::init_tests();
+		let mut cx = Ctx::make();
+		assert_eq!(cx.mode(Layer::Manager), KeyMode::Normal);
+
+		cx.manager.active_mut().focus(Cmd::args("focus", &["preview"]));
+		assert_eq!(cx.mode(Layer::Manager), KeyMode::Preview);
+		assert_eq!(cx.mode(Layer::Tasks), KeyMode::Normal);
+
+		cx.manager.active_mut().focus(Cmd::new("focus"));
+		assert_eq!(cx.mode(Layer::Manager), KeyMode::Normal);
+	}
 }
@@ -1,5 +1,5 @@
 use yazi_core::input::InputMode;
-use yazi_shared::{event::Cmd, Layer};
+use yazi_shared::{event::{Cmd, Data, Outcome}, Layer};
 
 use crate::app::App;
 
@@ -12,10 +12,10 @@ impl<'a> Executor<'a> {
 	pub(super) fn new(app: &'a mut App) -> Self { Self { app } }
 
 	#[inline]
-	pub(super) fn execute(&mut self, cmd: Cmd, layer: Layer) {
-		match layer {
+	pub(super) fn execute(&mut self, cmd: Cmd, layer: Layer) -> Outcome {
+		let known = match layer {
 			Layer::App => self.app(cmd),
-			Layer::Manager => self.manager(cmd),
+			Layer::Manager => return self.manager(cmd),
 			Layer::Tasks => self.tasks(cmd),
 			Layer::Select => self.select(cmd),
 			Layer::Input => self.input(cmd),
@@ -23,14 +23,16 @@ impl<'a> Executor<'a> {
 			Layer::Help => self.help(cmd),
 			Layer::Completion => self.completion(cmd),
 			Layer::Which => self.which(cmd),
-		}
+		};
+		if known { Outcome::Done } else { Outcome::Unknown }
 	}
 
-	fn app(&mut self, cmd: Cmd) {
+	fn app(&mut self, cmd: Cmd) -> bool {
 		macro_rules! on {
 			($name:ident) => {
 				if cmd.name == stringify!($name) {
-					return self.app.$name(cmd);
+					self.app.$name(cmd);
+					return true;
 				}
 			};
 		}
@@ -44,23 +46,28 @@ impl<'a> Executor<'a> {
 		on!(resize);
 		on!(stop);
 		on!(resume);
+		on!(timer);
+		false
 	}
 
-	fn manager(&mut self, cmd: Cmd) {
+	// The commands that can open a prompt say so with an `Outcome`
+	fn manager(&mut self, cmd: Cmd) -> Outcome {
 		macro_rules! on {
 			(MANAGER, $name:ident $(,$args:expr)*) => {
 				if cmd.name == stringify!($name) {
-					return self.app.cx.manager.$name(cmd, $($args),*);
+					return self.app.cx.manager.$name(cmd, $($args),*).into();
 				}
 			};
 			(ACTIVE, $name:ident $(,$args:expr)*) => {
 				if cmd.name == stringify!($name) {
-					return self.app.cx.manager.active_mut().$name(cmd, $($args),*);
+					// Commands following up on a prompt go to the tab it was opened in
+					let tab = cmd.get("tab").and_then(Data::as_usize);
+					return self.app.cx.manager.active_or_mut(tab).$name(cmd, $($args),*).into();
 				}
 			};
 			(TABS, $name:ident) => {
 				if cmd.name == concat!("tab_", stringify!($name)) {
-					return self.app.cx.manager.tabs.$name(cmd);
+					return self.app.cx.manager.tabs.$name(cmd).into();
 				}
 			};
 		}
@@ -70,13 +77,19 @@ impl<'a> Executor<'a> {
 		on!(MANAGER, update_mimetype, &self.app.cx.tasks);
 		on!(MANAGER, update_paged, &self.app.cx.tasks);
 		on!(MANAGER, update_yanked);
+		on!(MANAGER, update_recents);
+		on!(MANAGER, update_log);
+		on!(MANAGER, update_disk);
 		on!(MANAGER, hover);
 		on!(MANAGER, peek);
 		on!(MANAGER, seek);
 		on!(MANAGER, refresh, &self.app.cx.tasks);
+		on!(MANAGER, layout);
 		on!(MANAGER, quit, &self.app.cx.tasks);
 		on!(MANAGER, close, &self.app.cx.tasks);
 		on!(MANAGER, suspend);
+		on!(MANAGER, command);
+		on!(MANAGER, palette);
 		on!(ACTIVE, escape);
 		on!(ACTIVE, preview);
 
@@ -88,10 +101,17 @@ impl<'a> Executor<'a> {
 		on!(ACTIVE, forward);
 		on!(ACTIVE, cd);
 		on!(ACTIVE, reveal);
+		on!(ACTIVE, reveal_in_gui);
+		on!(ACTIVE, run_file);
+		on!(ACTIVE, fuzzy);
+		on!(ACTIVE, goto);
+		on!(ACTIVE, mounts);
 
 		// Selection
 		on!(ACTIVE, select);
 		on!(ACTIVE, select_all);
+		on!(ACTIVE, selection_save);
+		on!(ACTIVE, selection_load);
 		on!(ACTIVE, visual_mode);
 
 		// Operation
@@ -101,26 +121,46 @@ impl<'a> Executor<'a> {
 		on!(MANAGER, unyank);
 		on!(MANAGER, paste, &self.app.cx.tasks);
 		on!(MANAGER, link, &self.app.cx.tasks);
+		on!(MANAGER, duplicate, &self.app.cx.tasks);
+		on!(MANAGER, copy_to, &self.app.cx.tasks);
+		on!(MANAGER, move_to, &self.app.cx.tasks);
+		on!(MANAGER, move_do, &self.app.cx.tasks);
 		on!(MANAGER, hardlink, &self.app.cx.tasks);
-		on!(MANAGER, remove, &self.app.cx.tasks);
+		on!(MANAGER, remove);
 		on!(MANAGER, remove_do, &self.app.cx.tasks);
+		on!(MANAGER, restore, &self.app.cx.tasks);
 		on!(MANAGER, create);
 		on!(MANAGER, rename);
+		on!(MANAGER, calculate_size, &self.app.cx.tasks);
+		on!(MANAGER, recents);
+		on!(MANAGER, log);
 		on!(ACTIVE, copy);
 		on!(ACTIVE, shell);
+		on!(ACTIVE, terminal);
+		on!(ACTIVE, tree);
+		on!(ACTIVE, focus);
 		on!(ACTIVE, hidden);
+		on!(ACTIVE, collapse);
+		on!(ACTIVE, extensions);
+		on!(ACTIVE, readme);
+		on!(ACTIVE, dir_tree);
+		on!(ACTIVE, stats);
 		on!(ACTIVE, linemode);
 		on!(ACTIVE, search);
 		on!(ACTIVE, search_do);
+		on!(ACTIVE, search_contents);
 
 		// Filter
 		on!(ACTIVE, filter);
 		on!(ACTIVE, filter_do);
+		on!(ACTIVE, tag);
 
 		// Find
 		on!(ACTIVE, find);
 		on!(ACTIVE, find_do);
 		on!(ACTIVE, find_arrow);
+		on!(ACTIVE, jump);
+		on!(ACTIVE, jump_labels);
 
 		// Sorting
 		on!(ACTIVE, sort, &self.app.cx.tasks);
@@ -138,20 +178,28 @@ impl<'a> Executor<'a> {
 			b"help" => self.app.cx.help.toggle(Layer::Manager),
 			// Plugin
 			b"plugin" => self.app.plugin(cmd),
-			_ => {}
+			// Registered by plugins
+			_ => {
+				if !self.app.plugin_command(cmd) {
+					return Outcome::Unknown;
+				}
+			}
 		}
+		Outcome::Done
 	}
 
-	fn tasks(&mut self, cmd: Cmd) {
+	fn tasks(&mut self, cmd: Cmd) -> bool {
 		macro_rules! on {
 			($name:ident) => {
 				if cmd.name == stringify!($name) {
-					return self.app.cx.tasks.$name(cmd);
+					self.app.cx.tasks.$name(cmd);
+					return true;
 				}
 			};
 			($name:ident, $alias:literal) => {
 				if cmd.name == $alias {
-					return self.app.cx.tasks.$name(cmd);
+					self.app.cx.tasks.$name(cmd);
+					return true;
 				}
 			};
 		}
@@ -168,15 +216,17 @@ impl<'a> Executor<'a> {
 			"help" => self.app.cx.help.toggle(Layer::Tasks),
 			// Plugin
 			"plugin" => self.app.plugin(cmd),
-			_ => {}
+			_ => return false,
 		}
+		true
 	}
 
-	fn select(&mut self, cmd: Cmd) {
+	fn select(&mut self, cmd: Cmd) -> bool {
 		macro_rules! on {
 			($name:ident) => {
 				if cmd.name == stringify!($name) {
-					return self.app.cx.select.$name(cmd);
+					self.app.cx.select.$name(cmd);
+					return true;
 				}
 			};
 		}
@@ -184,26 +234,30 @@ impl<'a> Executor<'a> {
 		on!(show);
 		on!(close);
 		on!(arrow);
+		on!(extend);
 
 		match cmd.name.as_str() {
 			// Help
 			"help" => self.app.cx.help.toggle(Layer::Select),
 			// Plugin
 			"plugin" => self.app.plugin(cmd),
-			_ => {}
+			_ => return false,
 		}
+		true
 	}
 
-	fn input(&mut self, cmd: Cmd) {
+	fn input(&mut self, cmd: Cmd) -> bool {
 		macro_rules! on {
 			($name:ident) => {
 				if cmd.name == stringify!($name) {
-					return self.app.cx.input.$name(cmd);
+					self.app.cx.input.$name(cmd);
+					return true;
 				}
 			};
 			($name:ident, $alias:literal) => {
 				if cmd.name == $alias {
-					return self.app.cx.input.$name(cmd);
+					self.app.cx.input.$name(cmd);
+					return true;
 				}
 			};
 		}
@@ -214,13 +268,16 @@ impl<'a> Executor<'a> {
 		on!(move_, "move");
 		on!(backward);
 		on!(forward);
+		on!(history);
+		on!(remember);
 
 		if cmd.name.as_str() == "complete" {
-			return if cmd.bool("trigger") {
-				self.app.cx.completion.trigger(cmd)
+			if cmd.bool("trigger") {
+				self.app.cx.completion.trigger(cmd);
 			} else {
-				self.app.cx.input.complete(cmd)
-			};
+				self.app.cx.input.complete(cmd);
+			}
+			return true;
 		}
 
 		match self.app.cx.input.mode() {
@@ -240,21 +297,24 @@ impl<'a> Executor<'a> {
 					"help" => self.app.cx.help.toggle(Layer::Input),
 					// Plugin
 					"plugin" => self.app.plugin(cmd),
-					_ => {}
+					_ => return false,
 				}
 			}
 			InputMode::Insert => {
 				on!(backspace);
 				on!(kill);
+				return false;
 			}
 		}
+		true
 	}
 
-	fn confirm(&mut self, cmd: Cmd) {
+	fn confirm(&mut self, cmd: Cmd) -> bool {
 		macro_rules! on {
 			($name:ident $(,$args:expr)*) => {
 				if cmd.name == stringify!($name) {
-					return self.app.cx.confirm.$name(cmd, $($args),*);
+					self.app.cx.confirm.$name(cmd, $($args),*);
+					return true;
 				}
 			};
 		}
@@ -262,13 +322,15 @@ impl<'a> Executor<'a> {
 		on!(arrow, &self.app.cx.manager);
 		on!(show);
 		on!(close);
+		false
 	}
 
-	fn help(&mut self, cmd: Cmd) {
+	fn help(&mut self, cmd: Cmd) -> bool {
 		macro_rules! on {
 			($name:ident) => {
 				if cmd.name == stringify!($name) {
-					return self.app.cx.help.$name(cmd);
+					self.app.cx.help.$name(cmd);
+					return true;
 				}
 			};
 		}
@@ -276,26 +338,30 @@ impl<'a> Executor<'a> {
 		on!(escape);
 		on!(arrow);
 		on!(filter);
+		on!(run);
 
 		match cmd.name.as_str() {
 			"close" => self.app.cx.help.toggle(Layer::Help),
 			// Plugin
 			"plugin" => self.app.plugin(cmd),
-			_ => {}
+			_ => return false,
 		}
+		true
 	}
 
-	fn completion(&mut self, cmd: Cmd) {
+	fn completion(&mut self, cmd: Cmd) -> bool {
 		macro_rules! on {
 			($name:ident) => {
 				if cmd.name == stringify!($name) {
-					return self.app.cx.completion.$name(cmd);
+					self.app.cx.completion.$name(cmd);
+					return true;
 				}
 			};
 		}
 
 		on!(trigger);
 		on!(show);
+		on!(cycle);
 		on!(close);
 		on!(arrow);
 
@@ -305,20 +371,73 @@ impl<'a> Executor<'a> {
 			"help" => self.app.cx.help.toggle(Layer::Completion),
 			// Plugin
 			"plugin" => self.app.plugin(cmd),
-			_ => {}
+			_ => return false,
 		}
+		true
 	}
 
-	fn which(&mut self, cmd: Cmd) {
+	fn which(&mut self, cmd: Cmd) -> bool {
 		macro_rules! on {
 			($name:ident) => {
 				if cmd.name == stringify!($name) {
-					return self.app.cx.which.$name(cmd);
+					self.app.cx.which.$name(cmd);
+					return true;
 				}
 			};
 		}
 
 		on!(show);
 		on!(callback);
+		false
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use yazi_config::COMMANDS;
+
+	use super::*;
+
+	// Sent by Yazi itself to follow up on other commands, so they aren't offered
+	// in the `:` prompt
+	const INTERNAL: &[&str] = &[
+		"filter_do",
+		"find_do",
+		"hover",
+		"move_do",
+		"open_do",
+		"preview",
+		"refresh",
+		"remove_do",
+		"search_do",
+		"update_disk",
+		"update_files",
+		"update_log",
+		"update_mimetype",
+		"update_paged",
+		"update_recents",
+		"update_task",
+		"update_yanked",
+	];
+
+	// Would stop the test process itself, or write to the system clipboard
+	const SKIPPED: &[&str] = &["copy", "suspend"];
+
+	#[tokio::test]
+	async fn test_builtin_commands() {
+		crate::init_tests();
+		let _rx = crate::events().await;
+		let mut app = App::dummy();
+
+		// The prompts and processes they start are spawned onto this runtime, and
+		// never get to run, as nothing is awaited from here on
+		let names = COMMANDS.names();
+		for name in names.iter().filter(|&n| !SKIPPED.contains(&n.as_str())) {
+			assert!(!INTERNAL.contains(&name.as_str()), "`{name}` is internal");
+
+			let outcome = Executor::new(&mut app).execute(Cmd::new(name), Layer::Manager);
+			assert!(!matches!(outcome, Outcome::Unknown), "`{name}` isn't handled");
+		}
+		assert!(SKIPPED.iter().all(|&s| names.iter().any(|n| n == s)));
 	}
 }
@@ -20,7 +20,7 @@ impl Widget for Bindings<'_> {
 
 		// On
 		let col1: Vec<_> =
-			bindings.iter().map(|c| ListItem::new(c.on()).style(THEME.help.on)).collect();
+			bindings.iter().map(|c| ListItem::new(c.on_with_mode()).style(THEME.help.on)).collect();
 
 		// Run
 		let col2: Vec<_> =
@@ -1,6 +1,7 @@
 use std::ops::Deref;
 
 use mlua::{AnyUserData, Lua, UserDataFields};
+use yazi_config::manager::SortDirs;
 
 use super::SCOPE;
 
@@ -25,11 +26,15 @@ impl Config {
 			reg.add_field_method_get("sort_by", |_, me| Ok(me.sort_by.to_string()));
 			reg.add_field_method_get("sort_sensitive", |_, me| Ok(me.sort_sensitive));
 			reg.add_field_method_get("sort_reverse", |_, me| Ok(me.sort_reverse));
-			reg.add_field_method_get("sort_dir_first", |_, me| Ok(me.sort_dir_first));
+			reg.add_field_method_get("sort_dir_first", |_, me| Ok(me.sort_dirs == SortDirs::First));
 			reg.add_field_method_get("sort_translit", |_, me| Ok(me.sort_translit));
 
 			reg.add_field_method_get("linemode", |_, me| Ok(me.linemode.to_owned()));
+			reg.add_field_method_get("time_format", |_, me| Ok(me.time_format.to_owned()));
+			reg.add_field_method_get("size_format", |_, me| Ok(me.size_format.to_owned()));
 			reg.add_field_method_get("show_hidden", |_, me| Ok(me.show_hidden));
+			reg.add_field_method_get("show_extensions", |_, me| Ok(me.show_extensions));
+			reg.add_field_method_get("dir_readme", |_, me| Ok(me.dir_readme));
 		})
 	}
 }
@@ -41,10 +41,18 @@ impl File {
 			reg.add_method("size", |_, me, ()| {
 				Ok(if me.is_dir() { me.folder().files.sizes.get(&me.url).copied() } else { Some(me.len) })
 			});
+			reg.add_method("sizing", |lua, me, ()| {
+				let cx = lua.named_registry_value::<CtxRef>("cx")?;
+				Ok(me.is_dir() && cx.tasks.is_sizing(&me.url))
+			});
 			reg.add_method("mime", |lua, me, ()| {
 				let cx = lua.named_registry_value::<CtxRef>("cx")?;
 				Ok(cx.manager.mimetype.get(&me.url).cloned())
 			});
+			reg.add_method("tags", |lua, me, ()| {
+				let tags = yazi_fs::TAGS.read();
+				lua.create_sequence_from(tags.get(&me.url).into_iter().flatten().map(String::as_str))
+			});
 			reg.add_method("prefix", |lua, me, ()| {
 				if !me.folder().cwd.is_search() {
 					return Ok(None);
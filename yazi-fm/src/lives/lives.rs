@@ -1,6 +1,6 @@
 use std::mem;
 
-use mlua::Scope;
+use mlua::{LuaSerdeExt, Scope};
 use scopeguard::defer;
 use tracing::error;
 use yazi_plugin::LUA;
@@ -43,15 +43,16 @@ impl Lives {
 			LUA.set_named_registry_value("cx", scope.create_any_userdata_ref(cx)?)?;
 
 			let globals = LUA.globals();
-			globals.raw_set(
-				"cx",
-				LUA.create_table_from([
-					("active", super::Tab::make(cx.manager.active())?),
-					("tabs", super::Tabs::make(&cx.manager.tabs)?),
-					("tasks", super::Tasks::make(&cx.tasks)?),
-					("yanked", super::Yanked::make(&cx.manager.yanked)?),
-				])?,
-			)?;
+			let cx_ = LUA.create_table_from([
+				("active", super::Tab::make(cx.manager.active())?),
+				("tabs", super::Tabs::make(&cx.manager.tabs)?),
+				("tasks", super::Tasks::make(&cx.tasks)?),
+				("yanked", super::Yanked::make(&cx.manager.yanked)?),
+			])?;
+			cx_.raw_set("disk", LUA.to_value(&cx.manager.disk.space)?)?;
+			cx_.raw_set("layout", LUA.to_value(&cx.manager.panes.ratio())?)?;
+			cx_.raw_set("mode", cx.mode(cx.layer()).to_string())?;
+			globals.raw_set("cx", cx_)?;
 
 			f(scope)
 		});
@@ -44,6 +44,8 @@ async fn main() -> anyhow::Result<()> {
 
 	yazi_shared::init();
 
+	yazi_boot::init_args();
+
 	yazi_config::init()?;
 
 	yazi_adapter::init();
@@ -58,6 +60,60 @@ async fn main() -> anyhow::Result<()> {
 
 	yazi_core::init();
 
+	yazi_core::serve();
 	yazi_dds::serve();
 	app::App::serve().await
 }
+
+#[cfg(test)]
+fn init_tests() {
+	static INIT: std::sync::Once = std::sync::Once::new();
+	INIT.call_once(|| {
+		// Kept for the whole test run, since it's shared by all the tests
+		let dir = tempfile::tempdir().unwrap().into_path();
+		std::fs::create_dir_all(dir.join("cwd")).unwrap();
+		std::fs::create_dir_all(dir.join("state")).unwrap();
+
+		// The environment is only written here, before any test reads it
+		std::env::set_var("YAZI_TEST_DIR", &dir);
+
+		yazi_shared::init();
+		yazi_boot::ARGS.with(<_>::default);
+		yazi_boot::BOOT.with(|| yazi_boot::Boot {
+			cwds: vec![dir.join("cwd")],
+			files: vec![Default::default()],
+			state_dir: dir.join("state"),
+			..Default::default()
+		});
+		yazi_dds::init();
+		yazi_adapter::init_tests();
+
+		let toml = include_str!("../../yazi-config/preset/yazi.toml");
+		yazi_config::LAYOUT.with(<_>::default);
+		yazi_config::MANAGER.init(toml.parse().unwrap());
+		yazi_config::PREVIEW.init(toml.parse().unwrap());
+		yazi_config::TASKS.init(toml.parse().unwrap());
+		yazi_config::INPUT.init(toml.parse().unwrap());
+		yazi_config::KEYMAP.init(include_str!("../../yazi-config/preset/keymap.toml").parse().unwrap());
+		yazi_config::COMMANDS.with(<_>::default);
+		yazi_core::init();
+	});
+}
+
+// The events emitted so far, for tests on the functions that emit them, which
+// are run one at a time as they share the channel
+#[cfg(test)]
+async fn events()
+-> tokio::sync::MutexGuard<'static, tokio::sync::mpsc::UnboundedReceiver<yazi_shared::event::Event>>
+{
+	use std::sync::OnceLock;
+
+	use tokio::sync::{mpsc::UnboundedReceiver, Mutex};
+	use yazi_shared::event::Event;
+
+	static RX: OnceLock<Mutex<UnboundedReceiver<Event>>> = OnceLock::new();
+	let mut rx = RX.get_or_init(|| Mutex::new(Event::take())).lock().await;
+
+	while rx.try_recv().is_ok() {}
+	rx
+}
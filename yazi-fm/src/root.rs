@@ -28,6 +28,7 @@ impl<'a> Widget for Root<'a> {
 		}
 
 		components::Preview::new(self.cx).render(area, buf);
+		components::Labels::new(self.cx).render(area, buf);
 
 		if self.cx.tasks.visible {
 			tasks::Layout::new(self.cx).render(area, buf);
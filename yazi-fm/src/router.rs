@@ -1,5 +1,7 @@
-use yazi_config::{keymap::{Control, Key}, KEYMAP};
-use yazi_shared::{emit, Layer};
+use std::mem;
+
+use yazi_config::{keymap::{Control, Key, KeyMode}, KEYMAP};
+use yazi_shared::{emit, event::CmdSeq, Layer};
 
 use crate::app::App;
 
@@ -25,32 +27,34 @@ impl<'a> Router<'a> {
 			return true;
 		}
 
-		if cx.completion.visible {
-			self.matches(Layer::Completion, key) || self.matches(Layer::Input, key)
-		} else if cx.help.visible {
-			self.matches(Layer::Help, key)
-		} else if cx.input.visible {
-			self.matches(Layer::Input, key)
-		} else if cx.confirm.visible {
-			self.matches(Layer::Confirm, key)
-		} else if cx.select.visible {
-			self.matches(Layer::Select, key)
-		} else if cx.tasks.visible {
-			self.matches(Layer::Tasks, key)
-		} else {
-			self.matches(Layer::Manager, key)
+		match cx.layer() {
+			Layer::Manager if cx.manager.active_mut().jump_labels_type(&key) => true,
+			Layer::Completion => self.matches(Layer::Completion, key) || self.matches(Layer::Input, key),
+			Layer::Manager if cx.mode(Layer::Manager) == KeyMode::Preview && self.count(&key) => true,
+			layer => self.matches(layer, key),
 		}
 	}
 
 	#[inline]
 	fn matches(&mut self, layer: Layer, key: Key) -> bool {
-		for ctrl @ Control { on, .. } in KEYMAP.get(layer) {
+		let mode = self.app.cx.mode(layer);
+		let count = if mode == KeyMode::Preview {
+			mem::take(&mut self.app.cx.manager.active_mut().preview.count)
+		} else {
+			0
+		};
+
+		for ctrl @ Control { on, .. } in KEYMAP.get_in(layer, mode) {
 			if on.is_empty() || on[0] != key {
 				continue;
 			}
 
 			if on.len() > 1 {
-				self.app.cx.which.show_with(&key, layer);
+				self.app.cx.which.show_with(&key, layer, mode);
+			} else if count > 0 {
+				let cmds: Vec<_> =
+					ctrl.run.iter().map(|c| c.shallow_clone().with("count", count)).collect();
+				emit!(Seq(CmdSeq::new(cmds, ctrl.continue_on_error), layer));
 			} else {
 				emit!(Seq(ctrl.to_seq(), layer));
 			}
@@ -58,4 +62,67 @@ impl<'a> Router<'a> {
 		}
 		false
 	}
+
+	// The digits typed in the focused preview make up the count of the next key,
+	// e.g. `5j` seeks down 5 lines
+	fn count(&mut self, key: &Key) -> bool {
+		let preview = &mut self.app.cx.manager.active_mut().preview;
+		let Some(d) = key.plain().and_then(|c| c.to_digit(10)) else { return false };
+		if d == 0 && preview.count == 0 {
+			return false;
+		}
+
+		preview.count = preview.count.saturating_mul(10).saturating_add(d as usize);
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+
+	use yazi_shared::event::Event;
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_count() {
+		crate::init_tests();
+		let mut rx = crate::events().await;
+		let mut app = App::dummy();
+
+		let mut route = |app: &mut App, keys: &[&str]| {
+			keys.iter().for_each(|k| _ = Router::new(app).route(Key::from_str(k).unwrap()));
+			let seq = std::iter::from_fn(|| rx.try_recv().ok())
+				.find_map(|e| if let Event::Seq(seq, _) = e { Some(seq) } else { None })
+				.unwrap();
+			seq.cmds.iter().map(|c| format!("{} {:?}", c.name, c.get("count"))).collect::<Vec<_>>()
+		};
+
+		// The digits switch tabs as usual, unless the preview has the focus
+		assert_eq!(route(&mut app, &["2"]), ["tab_switch None"]);
+		app.cx.manager.active_mut().preview.focused = true;
+
+		assert_eq!(route(&mut app, &["1", "2", "j"]), [r#"seek Some(String("12"))"#]);
+		assert_eq!(route(&mut app, &["j"]), ["seek None"]);
+		assert_eq!(route(&mut app, &["3", "<C-d>"]), [r#"seek Some(String("3"))"#]);
+		assert_eq!(app.cx.manager.active().preview.count, 0);
+	}
+
+	#[tokio::test]
+	async fn test_jump_labels_layer() {
+		crate::init_tests();
+		let _rx = crate::events().await;
+		let mut app = App::dummy();
+		app.cx.manager.active_mut().labels = Some(yazi_core::tab::Labels::new(0, 3));
+
+		// Another layer on top gets the keys, and the labels are left as they are
+		app.cx.tasks.visible = true;
+		Router::new(&mut app).route(Key::from_str("a").unwrap());
+		assert!(app.cx.manager.active().labels.as_ref().is_some_and(|l| l.typed.is_empty()));
+
+		app.cx.tasks.visible = false;
+		assert!(Router::new(&mut app).route(Key::from_str("<Esc>").unwrap()));
+		assert!(app.cx.manager.active().labels.is_none());
+	}
 }
@@ -17,6 +17,9 @@ impl Signals {
 		Ok(Self { tx })
 	}
 
+	#[cfg(test)]
+	pub(super) fn dummy() -> Self { Self { tx: mpsc::unbounded_channel().0 } }
+
 	pub(super) fn stop(&mut self, cb: Option<oneshot::Sender<()>>) { self.tx.send((false, cb)).ok(); }
 
 	pub(super) fn resume(&mut self, cb: Option<oneshot::Sender<()>>) {
@@ -36,6 +39,16 @@ impl Signals {
 				return false;
 			}
 			SIGTSTP => {
+				// A blocking process owns the terminal, and has received the same signal.
+				// Let it restore the terminal on its own, and only stop ourselves, since
+				// `SIGSTOP`ping the whole group would interrupt it halfway through.
+				if HIDER.try_acquire().is_err() {
+					if unsafe { libc::raise(SIGSTOP) } != 0 {
+						error!("Failed to stop the process:\n{}", std::io::Error::last_os_error());
+					}
+					return true;
+				}
+
 				tokio::spawn(async move {
 					AppProxy::stop().await;
 					if unsafe { libc::kill(0, SIGSTOP) } != 0 {
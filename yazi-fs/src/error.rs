@@ -0,0 +1,110 @@
+use std::{error::Error, fmt::{self, Display}, io};
+
+use yazi_shared::fs::Url;
+
+#[derive(Debug)]
+pub enum FsError {
+	NotFound(Option<Url>),
+	PermissionDenied(Option<Url>),
+	AlreadyExists(Option<Url>),
+	CrossDevice(Option<Url>),
+	Other(Option<Url>, io::Error),
+}
+
+impl FsError {
+	#[inline]
+	pub fn new(url: &Url, error: io::Error) -> Self { Self::from(error).with_url(url) }
+
+	pub fn with_url(mut self, url: &Url) -> Self {
+		match &mut self {
+			Self::NotFound(u)
+			| Self::PermissionDenied(u)
+			| Self::AlreadyExists(u)
+			| Self::CrossDevice(u)
+			| Self::Other(u, _) => *u = Some(url.clone()),
+		}
+		self
+	}
+
+	pub fn url(&self) -> Option<&Url> {
+		match self {
+			Self::NotFound(u)
+			| Self::PermissionDenied(u)
+			| Self::AlreadyExists(u)
+			| Self::CrossDevice(u)
+			| Self::Other(u, _) => u.as_ref(),
+		}
+	}
+}
+
+impl Display for FsError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if let Some(u) = self.url() {
+			write!(f, "`{}`: ", u.display())?;
+		}
+		match self {
+			Self::NotFound(_) => write!(f, "No such file or directory"),
+			Self::PermissionDenied(_) => write!(f, "Permission denied"),
+			Self::AlreadyExists(_) => write!(f, "Already exists"),
+			Self::CrossDevice(_) => write!(f, "Cannot move across filesystems"),
+			Self::Other(_, e) => write!(f, "{e}"),
+		}
+	}
+}
+
+impl Error for FsError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Other(_, e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for FsError {
+	fn from(error: io::Error) -> Self {
+		// EXDEV on Unix, and ERROR_NOT_SAME_DEVICE on Windows
+		#[cfg(unix)]
+		const CROSS_DEVICE: i32 = 18;
+		#[cfg(windows)]
+		const CROSS_DEVICE: i32 = 17;
+
+		if error.raw_os_error() == Some(CROSS_DEVICE) {
+			return Self::CrossDevice(None);
+		}
+		match error.kind() {
+			io::ErrorKind::NotFound => Self::NotFound(None),
+			io::ErrorKind::PermissionDenied => Self::PermissionDenied(None),
+			io::ErrorKind::AlreadyExists => Self::AlreadyExists(None),
+			_ => Self::Other(None, error),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_from_io_error() {
+		let url = Url::from("/tmp/a");
+		let of = |kind| FsError::new(&url, io::Error::from(kind));
+
+		assert!(matches!(of(io::ErrorKind::NotFound), FsError::NotFound(Some(ref u)) if *u == url));
+		assert!(matches!(of(io::ErrorKind::PermissionDenied), FsError::PermissionDenied(_)));
+		assert!(matches!(of(io::ErrorKind::AlreadyExists), FsError::AlreadyExists(_)));
+		assert!(matches!(of(io::ErrorKind::Interrupted), FsError::Other(Some(_), _)));
+
+		#[cfg(unix)]
+		assert!(matches!(FsError::from(io::Error::from_raw_os_error(18)), FsError::CrossDevice(None)));
+		#[cfg(windows)]
+		assert!(matches!(FsError::from(io::Error::from_raw_os_error(17)), FsError::CrossDevice(None)));
+	}
+
+	#[test]
+	fn test_display() {
+		let e = FsError::new(&Url::from("/tmp/a"), io::Error::from(io::ErrorKind::PermissionDenied));
+		assert_eq!(e.to_string(), "`/tmp/a`: Permission denied");
+		assert_eq!(FsError::NotFound(None).to_string(), "No such file or directory");
+	}
+}
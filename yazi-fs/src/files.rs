@@ -1,10 +1,10 @@
-use std::{collections::{HashMap, HashSet}, mem, ops::Deref, sync::atomic::Ordering};
+use std::{collections::{HashMap, HashSet}, mem, ops::Deref, sync::atomic::Ordering, time::SystemTime};
 
-use tokio::{fs::{self, DirEntry}, select, sync::mpsc::{self, UnboundedReceiver}};
+use tokio::sync::mpsc::UnboundedReceiver;
 use yazi_config::{manager::SortBy, MANAGER};
 use yazi_shared::fs::{maybe_exists, Cha, File, FilesOp, Url, FILES_TICKET};
 
-use super::{FilesSorter, Filter};
+use super::{FilesSorter, Filter, SortKeys, PROVIDERS, SORT_KEYER};
 
 pub struct Files {
 	hidden:       Vec<File>,
@@ -13,7 +13,9 @@ pub struct Files {
 	version:      u64,
 	pub revision: u64,
 
-	pub sizes: HashMap<Url, u64>,
+	pub sizes:   HashMap<Url, u64>,
+	size_mtimes: HashMap<Url, Option<SystemTime>>,
+	keys:        SortKeys,
 
 	sorter:      FilesSorter,
 	filter:      Option<Filter>,
@@ -29,7 +31,9 @@ impl Default for Files {
 			version:  Default::default(),
 			revision: Default::default(),
 
-			sizes: Default::default(),
+			sizes:       Default::default(),
+			size_mtimes: Default::default(),
+			keys:        Default::default(),
 
 			sorter:      Default::default(),
 			filter:      Default::default(),
@@ -45,59 +49,22 @@ impl Deref for Files {
 }
 
 impl Files {
+	#[inline]
 	pub async fn from_dir(url: &Url) -> std::io::Result<UnboundedReceiver<File>> {
-		let mut it = fs::read_dir(url).await?;
-		let (tx, rx) = mpsc::unbounded_channel();
-
-		tokio::spawn(async move {
-			while let Ok(Some(item)) = it.next_entry().await {
-				select! {
-					_ = tx.closed() => break,
-					result = item.metadata() => {
-						let url = Url::from(item.path());
-						_ = tx.send(match result {
-							Ok(meta) => File::from_meta(url, meta).await,
-							Err(_) => File::from_dummy(url, item.file_type().await.ok())
-						});
-					}
-				}
-			}
-		});
-		Ok(rx)
+		PROVIDERS.get(url)?.read_dir(url).await
 	}
 
+	#[inline]
 	pub async fn from_dir_bulk(url: &Url) -> std::io::Result<Vec<File>> {
-		let mut it = fs::read_dir(url).await?;
-		let mut items = Vec::with_capacity(5000);
-		while let Ok(Some(item)) = it.next_entry().await {
-			items.push(item);
-		}
-
-		let (first, rest) = items.split_at(items.len() / 3);
-		let (second, third) = rest.split_at(items.len() / 3);
-		async fn go(entities: &[DirEntry]) -> Vec<File> {
-			let mut files = Vec::with_capacity(entities.len() / 3 + 1);
-			for entry in entities {
-				let url = Url::from(entry.path());
-				files.push(match entry.metadata().await {
-					Ok(meta) => File::from_meta(url, meta).await,
-					Err(_) => File::from_dummy(url, entry.file_type().await.ok()),
-				});
-			}
-			files
-		}
-
-		Ok(
-			futures::future::join_all([go(first), go(second), go(third)])
-				.await
-				.into_iter()
-				.flatten()
-				.collect(),
-		)
+		PROVIDERS.get(url)?.read_dir_bulk(url).await
 	}
 
 	pub async fn assert_stale(url: &Url, cha: Cha) -> Option<Cha> {
-		match fs::metadata(url).await.map(Cha::from) {
+		let result = match PROVIDERS.get(url) {
+			Ok(p) => p.metadata(url).await,
+			Err(e) => Err(e),
+		};
+		match result {
 			Ok(c) if !c.is_dir() => {
 				// FIXME: use `ErrorKind::NotADirectory` instead once it gets stabilized
 				FilesOp::IOErr(url.clone(), std::io::ErrorKind::AlreadyExists).emit();
@@ -105,7 +72,7 @@ impl Files {
 			Ok(c) if c.hits(cha) => {}
 			Ok(c) => return Some(c),
 			Err(e) => {
-				if maybe_exists(url).await {
+				if e.kind() == std::io::ErrorKind::Unsupported || maybe_exists(url).await {
 					FilesOp::IOErr(url.clone(), e.kind()).emit();
 				} else if let Some(p) = url.parent_url() {
 					FilesOp::Deleting(p, vec![url.clone()]).emit();
@@ -124,6 +91,14 @@ impl Files {
 		if !self.items.is_empty() {
 			self.revision += 1;
 		}
+
+		// Sizes of directories that have been modified or gone are no longer valid
+		if !self.sizes.is_empty() {
+			let mtimes: HashMap<_, _> =
+				self.items.iter().chain(&self.hidden).map(|f| (&f.url, f.cha.mtime)).collect();
+			self.size_mtimes.retain(|u, m| mtimes.get(u) == Some(m));
+			self.sizes.retain(|u, _| self.size_mtimes.contains_key(u));
+		}
 	}
 
 	pub fn update_part(&mut self, files: Vec<File>, ticket: u64) {
@@ -158,6 +133,12 @@ impl Files {
 		if self.sorter.by == SortBy::Size {
 			self.revision += 1;
 		}
+
+		let mtimes: HashMap<_, _> =
+			self.items.iter().chain(&self.hidden).map(|f| (&f.url, f.cha.mtime)).collect();
+		for url in sizes.keys() {
+			self.size_mtimes.insert(url.clone(), mtimes.get(url).copied().flatten());
+		}
 		self.sizes.extend(sizes);
 	}
 
@@ -215,9 +196,7 @@ impl Files {
 		}
 
 		let (hidden, items) = if let Some(filter) = &self.filter {
-			urls.into_iter().partition(|u| {
-				(!self.show_hidden && u.is_hidden()) || !u.file_name().is_some_and(|s| filter.matches(s))
-			})
+			urls.into_iter().partition(|u| (!self.show_hidden && u.is_hidden()) || !filter.accepts(u))
 		} else if self.show_hidden {
 			(vec![], urls)
 		} else {
@@ -262,6 +241,13 @@ impl Files {
 			return Default::default();
 		}
 
+		for (url, f) in &files {
+			if self.size_mtimes.get(url).is_some_and(|&m| m != f.cha.mtime) {
+				self.size_mtimes.remove(url);
+				self.sizes.remove(url);
+			}
+		}
+
 		macro_rules! go {
 			($dist:expr, $src:expr, $inc:literal) => {
 				let mut b = true;
@@ -280,10 +266,9 @@ impl Files {
 		}
 
 		let (mut hidden, mut items) = if let Some(filter) = &self.filter {
-			files.into_iter().partition(|(_, f)| {
-				(f.is_hidden() && !self.show_hidden)
-					|| !f.url.file_name().is_some_and(|s| filter.matches(s))
-			})
+			files
+				.into_iter()
+				.partition(|(_, f)| (f.is_hidden() && !self.show_hidden) || !filter.accepts(&f.url))
 		} else if self.show_hidden {
 			(HashMap::new(), files)
 		} else {
@@ -324,16 +309,22 @@ impl Files {
 		}
 
 		self.version = self.revision;
-		self.sorter.sort(&mut self.items, &self.sizes);
+		self.sort();
 		true
 	}
 
+	fn sort(&mut self) {
+		if let SortBy::Plugin(name) = &self.sorter.by {
+			self.keys.refresh(name, &self.items, *SORT_KEYER);
+		}
+		self.sorter.sort(&mut self.items, &self.sizes, &self.keys);
+	}
+
 	fn split_files(&self, files: impl IntoIterator<Item = File>) -> (Vec<File>, Vec<File>) {
 		if let Some(filter) = &self.filter {
-			files.into_iter().partition(|f| {
-				(f.is_hidden() && !self.show_hidden)
-					|| !f.url.file_name().is_some_and(|s| filter.matches(s))
-			})
+			files
+				.into_iter()
+				.partition(|f| (f.is_hidden() && !self.show_hidden) || !filter.accepts(&f.url))
 		} else if self.show_hidden {
 			(vec![], files.into_iter().collect())
 		} else {
@@ -357,6 +348,9 @@ impl Files {
 
 	pub fn set_sorter(&mut self, sorter: FilesSorter) {
 		if self.sorter != sorter {
+			if self.sorter.by != sorter.by {
+				self.keys.clear();
+			}
 			self.sorter = sorter;
 			self.revision += 1;
 		}
@@ -379,14 +373,14 @@ impl Files {
 			self.hidden = hidden;
 			if !items.is_empty() {
 				self.items.extend(items);
-				self.sorter.sort(&mut self.items, &self.sizes);
+				self.sort();
 			}
 			return true;
 		}
 
 		let it = mem::take(&mut self.items).into_iter().chain(mem::take(&mut self.hidden));
 		(self.hidden, self.items) = self.split_files(it);
-		self.sorter.sort(&mut self.items, &self.sizes);
+		self.sort();
 		true
 	}
 
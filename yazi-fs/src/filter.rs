@@ -2,11 +2,13 @@ use std::{ffi::OsStr, fmt::Display, ops::Range};
 
 use anyhow::Result;
 use regex::bytes::{Regex, RegexBuilder};
-use yazi_shared::event::Cmd;
+use yazi_shared::{event::Cmd, fs::Url};
+
+use crate::TAGS;
 
 pub struct Filter {
 	raw:   String,
-	regex: Regex,
+	regex: Option<Regex>,
 }
 
 impl Filter {
@@ -19,15 +21,28 @@ impl Filter {
 			FilterCase::Sensitive => Regex::new(s)?,
 			FilterCase::Insensitive => RegexBuilder::new(s).case_insensitive(true).build()?,
 		};
-		Ok(Self { raw: s.to_owned(), regex })
+		Ok(Self { raw: s.to_owned(), regex: Some(regex) })
 	}
 
+	// Matches the files tagged with `tag` instead of their names
+	#[inline]
+	pub fn tag(tag: &str) -> Self { Self { raw: format!("#{tag}"), regex: None } }
+
 	#[inline]
-	pub fn matches(&self, name: &OsStr) -> bool { self.regex.is_match(name.as_encoded_bytes()) }
+	pub fn matches(&self, name: &OsStr) -> bool {
+		self.regex.as_ref().is_some_and(|r| r.is_match(name.as_encoded_bytes()))
+	}
+
+	pub fn accepts(&self, url: &Url) -> bool {
+		match &self.regex {
+			Some(_) => url.file_name().is_some_and(|s| self.matches(s)),
+			None => TAGS.read().has(url, &self.raw[1..]),
+		}
+	}
 
 	#[inline]
 	pub fn highlighted(&self, name: &OsStr) -> Option<Vec<Range<usize>>> {
-		self.regex.find(name.as_encoded_bytes()).map(|m| vec![m.range()])
+		self.regex.as_ref()?.find(name.as_encoded_bytes()).map(|m| vec![m.range()])
 	}
 }
 
@@ -66,10 +66,19 @@ impl Folder {
 		let b = if self.files.is_empty() {
 			(self.cursor, self.offset, self.tracing) = (0, 0, false);
 			false
-		} else if step.is_positive() {
-			self.next(step)
 		} else {
-			self.prev(step)
+			let old = (self.cursor, self.offset);
+			let (len, limit) = (self.files.len(), LAYOUT.load().current.height as usize);
+			let scrolloff = (limit / 2).min(MANAGER.scrolloff as usize);
+
+			if step.is_positive() {
+				self.next(step, len, limit, scrolloff);
+			} else {
+				self.prev(step, len, limit, scrolloff);
+			}
+			// Also for `arrow(0)` on a refresh, where the view may have been resized
+			self.offset = clamp_offset(self.cursor, self.offset, len, limit, scrolloff);
+			old != (self.cursor, self.offset)
 		};
 
 		self.sync_page(false);
@@ -103,38 +112,22 @@ impl Folder {
 		}
 	}
 
-	fn next(&mut self, step: Step) -> bool {
-		let old = (self.cursor, self.offset);
-		let len = self.files.len();
-
-		let limit = LAYOUT.load().current.height as usize;
-		let scrolloff = (limit / 2).min(MANAGER.scrolloff as usize);
-
-		self.cursor = step.add(self.cursor, limit).min(len.saturating_sub(1));
-		self.offset = if self.cursor >= (self.offset + limit).min(len).saturating_sub(scrolloff) {
-			len.saturating_sub(limit).min(self.offset + self.cursor - old.0)
-		} else {
-			self.offset.min(len.saturating_sub(1))
-		};
-
-		old != (self.cursor, self.offset)
+	// Scrolls the view along with the cursor once it gets within `scrolloff` of
+	// the bottom, so that a page down keeps the cursor at the same row
+	fn next(&mut self, step: Step, len: usize, limit: usize, scrolloff: usize) {
+		let old = self.cursor;
+		self.cursor = step.add(self.cursor, limit).min(len - 1);
+		if self.cursor >= (self.offset + limit).min(len).saturating_sub(scrolloff) {
+			self.offset = len.saturating_sub(limit).min(self.offset + self.cursor - old);
+		}
 	}
 
-	fn prev(&mut self, step: Step) -> bool {
-		let old = (self.cursor, self.offset);
-		let max = self.files.len().saturating_sub(1);
-
-		let limit = LAYOUT.load().current.height as usize;
-		let scrolloff = (limit / 2).min(MANAGER.scrolloff as usize);
-
-		self.cursor = step.add(self.cursor, limit).min(max);
-		self.offset = if self.cursor < self.offset + scrolloff {
-			self.offset.saturating_sub(old.0 - self.cursor)
-		} else {
-			self.offset.min(max)
-		};
-
-		old != (self.cursor, self.offset)
+	fn prev(&mut self, step: Step, len: usize, limit: usize, scrolloff: usize) {
+		let old = self.cursor;
+		self.cursor = step.add(self.cursor, limit).min(len - 1);
+		if self.cursor < self.offset + scrolloff {
+			self.offset = self.offset.saturating_sub(old - self.cursor);
+		}
 	}
 }
 
@@ -151,3 +144,43 @@ impl Folder {
 		&self.files[start..end]
 	}
 }
+
+// Keeps `scrolloff` rows visible above and below the cursor, except at the ends
+// of the list, and never scrolls a list that fits in the view.
+fn clamp_offset(cursor: usize, offset: usize, len: usize, limit: usize, scrolloff: usize) -> usize {
+	if len <= limit || limit == 0 {
+		return 0;
+	}
+
+	let scrolloff = scrolloff.min((limit - 1) / 2);
+	let min = cursor.saturating_sub(limit - 1 - scrolloff);
+	let max = cursor.saturating_sub(scrolloff);
+	offset.clamp(min, max).min(len - limit)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_clamp_offset() {
+		// Keeps the context while moving in the middle
+		assert_eq!(clamp_offset(7, 0, 100, 10, 3), 1);
+		assert_eq!(clamp_offset(2, 5, 100, 10, 3), 0);
+		assert_eq!(clamp_offset(50, 48, 100, 10, 3), 47);
+		assert_eq!(clamp_offset(50, 44, 100, 10, 3), 44);
+
+		// Clamps at the top and the bottom
+		assert_eq!(clamp_offset(0, 30, 100, 10, 3), 0);
+		assert_eq!(clamp_offset(99, 0, 100, 10, 3), 90);
+		assert_eq!(clamp_offset(98, 95, 100, 10, 3), 90);
+
+		// Never scrolls a list that fits
+		assert_eq!(clamp_offset(9, 3, 10, 10, 3), 0);
+		assert_eq!(clamp_offset(4, 2, 5, 10, 3), 0);
+
+		// A larger `scrolloff` than the view allows centers the cursor
+		assert_eq!(clamp_offset(50, 0, 100, 10, 20), 45);
+		assert_eq!(clamp_offset(50, 0, 100, 0, 3), 0);
+	}
+}
@@ -1,15 +1,21 @@
 #![allow(clippy::if_same_then_else)]
 
+mod error;
 mod files;
 mod filter;
 mod folder;
+mod provider;
 mod sorter;
 mod stage;
 mod step;
+mod tags;
 
+pub use error::*;
 pub use files::*;
 pub use filter::*;
 pub use folder::*;
+pub use provider::*;
 pub use sorter::*;
 pub use stage::*;
 pub use step::*;
+pub use tags::*;
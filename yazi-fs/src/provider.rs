@@ -0,0 +1,173 @@
+use std::{io, sync::{Arc, OnceLock}};
+
+use futures::future::BoxFuture;
+use parking_lot::RwLock;
+use tokio::{fs::{self, DirEntry}, select, sync::mpsc::{self, UnboundedReceiver}};
+use yazi_shared::fs::{Cha, File, Url, UrlScheme};
+
+pub static PROVIDERS: Providers = Providers::new();
+
+// Only listing directories and reading their metadata go through a provider for
+// now. Everything else, such as previewing and watching, still works on the path
+// of a Url, so it's limited to the local filesystem, and the file operations of
+// the scheduler refuse a Url that's not on it.
+pub trait Provider: Send + Sync {
+	fn read_dir<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, io::Result<UnboundedReceiver<File>>>;
+
+	fn read_dir_bulk<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, io::Result<Vec<File>>> {
+		Box::pin(async move {
+			let mut rx = self.read_dir(url).await?;
+			let mut files = vec![];
+			while let Some(file) = rx.recv().await {
+				files.push(file);
+			}
+			Ok(files)
+		})
+	}
+
+	fn metadata<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, io::Result<Cha>>;
+}
+
+pub struct Providers {
+	all: RwLock<Vec<(UrlScheme, Arc<dyn Provider>)>>,
+}
+
+impl Providers {
+	const fn new() -> Self { Self { all: RwLock::new(Vec::new()) } }
+
+	// Replaces the provider previously registered for the same scheme, if any
+	pub fn register(&self, scheme: UrlScheme, provider: Arc<dyn Provider>) {
+		let mut all = self.all.write();
+		all.retain(|(s, _)| *s != scheme);
+		all.push((scheme, provider));
+	}
+
+	// Regular and search urls point at real paths, so they fall back to the local
+	// filesystem unless something else has been registered for them
+	pub fn get(&self, url: &Url) -> io::Result<Arc<dyn Provider>> {
+		static LOCAL: OnceLock<Arc<dyn Provider>> = OnceLock::new();
+
+		let scheme = url.scheme();
+		if let Some((_, p)) = self.all.read().iter().find(|(s, _)| *s == scheme) {
+			return Ok(p.clone());
+		}
+
+		match scheme {
+			UrlScheme::Regular | UrlScheme::Search => Ok(LOCAL.get_or_init(|| Arc::new(Local)).clone()),
+			UrlScheme::Archive => Err(io::Error::new(
+				io::ErrorKind::Unsupported,
+				format!("no provider registered for `{url}`"),
+			)),
+		}
+	}
+}
+
+pub struct Local;
+
+impl Provider for Local {
+	fn read_dir<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, io::Result<UnboundedReceiver<File>>> {
+		Box::pin(async move {
+			let mut it = fs::read_dir(url).await?;
+			let (tx, rx) = mpsc::unbounded_channel();
+
+			tokio::spawn(async move {
+				while let Ok(Some(item)) = it.next_entry().await {
+					select! {
+						_ = tx.closed() => break,
+						result = item.metadata() => {
+							let url = Url::from(item.path());
+							_ = tx.send(match result {
+								Ok(meta) => File::from_meta(url, meta).await,
+								Err(_) => File::from_dummy(url, item.file_type().await.ok())
+							});
+						}
+					}
+				}
+			});
+			Ok(rx)
+		})
+	}
+
+	fn read_dir_bulk<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, io::Result<Vec<File>>> {
+		Box::pin(async move {
+			let mut it = fs::read_dir(url).await?;
+			let mut items = Vec::with_capacity(5000);
+			while let Ok(Some(item)) = it.next_entry().await {
+				items.push(item);
+			}
+
+			let (first, rest) = items.split_at(items.len() / 3);
+			let (second, third) = rest.split_at(items.len() / 3);
+			async fn go(entities: &[DirEntry]) -> Vec<File> {
+				let mut files = Vec::with_capacity(entities.len() / 3 + 1);
+				for entry in entities {
+					let url = Url::from(entry.path());
+					files.push(match entry.metadata().await {
+						Ok(meta) => File::from_meta(url, meta).await,
+						Err(_) => File::from_dummy(url, entry.file_type().await.ok()),
+					});
+				}
+				files
+			}
+
+			Ok(
+				futures::future::join_all([go(first), go(second), go(third)])
+					.await
+					.into_iter()
+					.flatten()
+					.collect(),
+			)
+		})
+	}
+
+	fn metadata<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, io::Result<Cha>> {
+		Box::pin(async move { fs::metadata(url).await.map(Cha::from) })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct Fake;
+
+	impl Provider for Fake {
+		fn read_dir<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, io::Result<UnboundedReceiver<File>>> {
+			Box::pin(async move {
+				let (tx, rx) = mpsc::unbounded_channel();
+				for name in ["a", "b"] {
+					_ = tx.send(File::from_dummy(url.join(name), None));
+				}
+				Ok(rx)
+			})
+		}
+
+		fn metadata<'a>(&'a self, _: &'a Url) -> BoxFuture<'a, io::Result<Cha>> {
+			Box::pin(async { Ok(Cha::dummy()) })
+		}
+	}
+
+	#[tokio::test]
+	async fn test_dispatch() {
+		let providers = Providers::new();
+		let archive = Url::from("archive:///yazi-test-provider.zip");
+		assert_eq!(providers.get(&archive).err().map(|e| e.kind()), Some(io::ErrorKind::Unsupported));
+
+		providers.register(UrlScheme::Archive, Arc::new(Fake));
+		let files = providers.get(&archive).unwrap().read_dir_bulk(&archive).await.unwrap();
+		assert_eq!(files.iter().map(|f| f.url.to_string()).collect::<Vec<_>>(), [
+			"archive:///yazi-test-provider.zip/a",
+			"archive:///yazi-test-provider.zip/b"
+		]);
+
+		// The local one is shared, rather than made for every lookup
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = Url::from(tmp.path());
+		let local = providers.get(&dir).unwrap();
+		assert!(Arc::ptr_eq(&local, &providers.get(&dir).unwrap()));
+
+		std::fs::write(tmp.path().join("c"), "").unwrap();
+		let files = local.read_dir_bulk(&dir).await.unwrap();
+		assert_eq!(files.iter().map(|f| f.url.as_path()).collect::<Vec<_>>(), [tmp.path().join("c")]);
+	}
+}
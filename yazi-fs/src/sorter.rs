@@ -1,53 +1,106 @@
-use std::{cmp::Ordering, collections::HashMap, mem};
+use std::{cmp::Ordering, collections::HashMap, mem, time::SystemTime};
 
-use yazi_config::manager::SortBy;
-use yazi_shared::{fs::{File, Url}, natsort, LcgRng, Transliterator};
+use yazi_config::manager::{SortBy, SortDirs};
+use yazi_shared::{fs::{File, Url}, natsort, LcgRng, RoCell, Transliterator};
 
-#[derive(Clone, Copy, Default, PartialEq)]
+// Asks the plugin that registered a sort mode for the key of a file
+pub static SORT_KEYER: RoCell<fn(&str, &File) -> Option<SortKey>> = RoCell::new();
+
+#[derive(Clone, Debug)]
+pub enum SortKey {
+	Number(f64),
+	String(String),
+}
+
+impl Ord for SortKey {
+	fn cmp(&self, other: &Self) -> Ordering {
+		match (self, other) {
+			(Self::Number(a), Self::Number(b)) => a.total_cmp(b),
+			(Self::String(a), Self::String(b)) => natsort(a.as_bytes(), b.as_bytes(), false),
+			(Self::Number(_), Self::String(_)) => Ordering::Less,
+			(Self::String(_), Self::Number(_)) => Ordering::Greater,
+		}
+	}
+}
+
+impl PartialOrd for SortKey {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl PartialEq for SortKey {
+	fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl Eq for SortKey {}
+
+// Keys of a plugin sort mode, kept until the file is modified
+#[derive(Default)]
+pub struct SortKeys(HashMap<Url, (Option<SystemTime>, Option<SortKey>)>);
+
+impl SortKeys {
+	pub fn refresh(
+		&mut self,
+		name: &str,
+		items: &[File],
+		keyer: impl Fn(&str, &File) -> Option<SortKey>,
+	) {
+		let mut old = mem::take(&mut self.0);
+		self.0 = items
+			.iter()
+			.map(|f| match old.remove(&f.url) {
+				Some(v) if v.0 == f.cha.mtime => (f.url.clone(), v),
+				_ => (f.url.clone(), (f.cha.mtime, keyer(name, f))),
+			})
+			.collect();
+	}
+
+	#[inline]
+	pub fn clear(&mut self) { self.0.clear() }
+
+	#[inline]
+	fn get(&self, url: &Url) -> Option<&SortKey> { self.0.get(url).and_then(|(_, k)| k.as_ref()) }
+}
+
+#[derive(Clone, Default, PartialEq)]
 pub struct FilesSorter {
 	pub by:        SortBy,
 	pub sensitive: bool,
 	pub reverse:   bool,
-	pub dir_first: bool,
+	pub dirs:      SortDirs,
 	pub translit:  bool,
 }
 
 impl FilesSorter {
-	pub(super) fn sort(&self, items: &mut Vec<File>, sizes: &HashMap<Url, u64>) {
+	pub fn sort(&self, items: &mut Vec<File>, sizes: &HashMap<Url, u64>, keys: &SortKeys) {
 		if items.is_empty() {
 			return;
 		}
 
 		let by_alphabetical = |a: &File, b: &File| {
 			if self.sensitive {
-				return self.cmp(&*a.url, &*b.url, self.promote(a, b));
+				return self.cmp(&*a.url, &*b.url);
 			}
 
-			self.cmp(
-				a.url.as_os_str().to_ascii_uppercase(),
-				b.url.as_os_str().to_ascii_uppercase(),
-				self.promote(a, b),
-			)
+			self.cmp(a.url.as_os_str().to_ascii_uppercase(), b.url.as_os_str().to_ascii_uppercase())
 		};
 
 		match self.by {
-			SortBy::None => {}
+			SortBy::None => return,
 			SortBy::Modified => items.sort_unstable_by(|a, b| {
-				let ord = self.cmp(a.mtime, b.mtime, self.promote(a, b));
+				let ord = self.cmp(a.mtime, b.mtime);
 				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
 			}),
 			SortBy::Created => items.sort_unstable_by(|a, b| {
-				let ord = self.cmp(a.ctime, b.ctime, self.promote(a, b));
+				let ord = self.cmp(a.ctime, b.ctime);
 				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
 			}),
 			SortBy::Extension => items.sort_unstable_by(|a, b| {
 				let ord = if self.sensitive {
-					self.cmp(a.url.extension(), b.url.extension(), self.promote(a, b))
+					self.cmp(a.url.extension(), b.url.extension())
 				} else {
 					self.cmp(
 						a.url.extension().map(|s| s.to_ascii_lowercase()),
 						b.url.extension().map(|s| s.to_ascii_lowercase()),
-						self.promote(a, b),
 					)
 				};
 				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
@@ -57,14 +110,37 @@ impl FilesSorter {
 			SortBy::Size => items.sort_unstable_by(|a, b| {
 				let aa = if a.is_dir() { sizes.get(&a.url).copied() } else { None };
 				let bb = if b.is_dir() { sizes.get(&b.url).copied() } else { None };
-				let ord = self.cmp(aa.unwrap_or(a.len), bb.unwrap_or(b.len), self.promote(a, b));
+				let ord = self.cmp(aa.unwrap_or(a.len), bb.unwrap_or(b.len));
+				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
+			}),
+			// Files without a key, e.g. the plugin failed on them, go last in name order
+			SortBy::Plugin(_) => items.sort_unstable_by(|a, b| {
+				let (aa, bb) = (keys.get(&a.url), keys.get(&b.url));
+				let ord = aa.is_none().cmp(&bb.is_none()).then_with(|| self.cmp(aa, bb));
 				if ord == Ordering::Equal { by_alphabetical(a, b) } else { ord }
 			}),
 			SortBy::Random => {
 				let mut rng = LcgRng::default();
-				items.sort_unstable_by(|a, b| self.cmp(rng.next(), rng.next(), self.promote(a, b)))
+				items.sort_unstable_by(|_, _| self.cmp(rng.next(), rng.next()))
 			}
 		}
+
+		self.group(items);
+	}
+
+	// Moves directories to the top or bottom, keeping the order within each group,
+	// so it isn't affected by `reverse`
+	fn group(&self, items: &mut Vec<File>) {
+		let first = match self.dirs {
+			SortDirs::First => true,
+			SortDirs::Last => false,
+			SortDirs::Mixed => return,
+		};
+
+		let (mut a, b): (Vec<_>, Vec<_>) =
+			mem::take(items).into_iter().partition(|f| f.is_dir() == first);
+		a.extend(b);
+		*items = a;
 	}
 
 	fn sort_naturally(&self, items: &mut Vec<File>) {
@@ -72,11 +148,6 @@ impl FilesSorter {
 		indices.sort_unstable_by(|&a, &b| {
 			let (a, b) = (&items[a], &items[b]);
 
-			let promote = self.promote(a, b);
-			if promote != Ordering::Equal {
-				return promote;
-			}
-
 			let ordering = if self.translit {
 				natsort(
 					a.url.as_os_str().as_encoded_bytes().transliterate().as_bytes(),
@@ -98,17 +169,40 @@ impl FilesSorter {
 	}
 
 	#[inline(always)]
-	#[allow(clippy::collapsible_else_if)]
-	fn cmp<T: Ord>(&self, a: T, b: T, promote: Ordering) -> Ordering {
-		if promote != Ordering::Equal {
-			promote
-		} else {
-			if self.reverse { b.cmp(&a) } else { a.cmp(&b) }
-		}
+	fn cmp<T: Ord>(&self, a: T, b: T) -> Ordering { if self.reverse { b.cmp(&a) } else { a.cmp(&b) } }
+}
+
+#[cfg(test)]
+mod tests {
+	use yazi_shared::fs::{Cha, ChaKind};
+
+	use super::*;
+
+	fn sort(reverse: bool, dirs: SortDirs) -> Vec<String> {
+		let file = |name: &str, kind| File {
+			cha: Cha { kind, ..Default::default() },
+			url: Url::from(name),
+			..Default::default()
+		};
+		let mut items = vec![
+			file("b", ChaKind::DIR),
+			file("c", ChaKind::empty()),
+			file("d", ChaKind::DIR),
+			file("a", ChaKind::empty()),
+		];
+
+		let sorter = FilesSorter { by: SortBy::Alphabetical, reverse, dirs, ..Default::default() };
+		sorter.sort(&mut items, &HashMap::new(), &SortKeys::default());
+		items.iter().map(|f| f.url.to_string_lossy().into_owned()).collect()
 	}
 
-	#[inline(always)]
-	fn promote(&self, a: &File, b: &File) -> Ordering {
-		if self.dir_first { b.is_dir().cmp(&a.is_dir()) } else { Ordering::Equal }
+	#[test]
+	fn test_group() {
+		assert_eq!(sort(false, SortDirs::First), ["b", "d", "a", "c"]);
+		assert_eq!(sort(true, SortDirs::First), ["d", "b", "c", "a"]);
+		assert_eq!(sort(false, SortDirs::Last), ["a", "c", "b", "d"]);
+		assert_eq!(sort(true, SortDirs::Last), ["c", "a", "d", "b"]);
+		assert_eq!(sort(false, SortDirs::Mixed), ["a", "b", "c", "d"]);
+		assert_eq!(sort(true, SortDirs::Mixed), ["d", "c", "b", "a"]);
 	}
 }
@@ -37,12 +37,18 @@ impl Step {
 impl Step {
 	#[inline]
 	pub fn add(self, pos: usize, limit: usize) -> usize {
-		let fixed = match self {
+		let fixed = self.fixed(limit);
+		if fixed > 0 { pos + fixed as usize } else { pos.saturating_sub(fixed.unsigned_abs()) }
+	}
+
+	// The number of items it moves by, where a percentage is of `limit`
+	#[inline]
+	pub fn fixed(self, limit: usize) -> isize {
+		match self {
 			Self::Fixed(n) => n,
 			Self::Percent(0) => 0,
 			Self::Percent(n) => n as isize * limit as isize / 100,
-		};
-		if fixed > 0 { pos + fixed as usize } else { pos.saturating_sub(fixed.unsigned_abs()) }
+		}
 	}
 
 	#[inline]
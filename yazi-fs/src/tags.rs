@@ -0,0 +1,147 @@
+use std::{collections::{BTreeSet, HashMap}, io, path::PathBuf};
+
+use parking_lot::RwLock;
+use yazi_shared::{fs::{Saver, Url}, RoCell};
+
+pub static TAGS: RoCell<RwLock<Tags>> = RoCell::new();
+
+static SAVER: Saver = Saver::new();
+
+#[derive(Default)]
+pub struct Tags {
+	map:  HashMap<Url, BTreeSet<String>>,
+	path: PathBuf,
+}
+
+impl Tags {
+	// Each line is `tag1,tag2\tpath`, and the files that no longer exist are
+	// pruned on load
+	pub fn load(path: PathBuf) -> Self {
+		let s = std::fs::read_to_string(&path).unwrap_or_default();
+		let map = s
+			.lines()
+			.filter_map(|l| l.split_once('\t'))
+			.map(|(tags, p)| (PathBuf::from(p), tags))
+			.filter(|(p, _)| p.symlink_metadata().is_ok())
+			.map(|(p, tags)| (Url::from(p), tags.split(',').filter(|t| Self::valid(t)).map(Into::into)))
+			.map(|(u, tags)| (u, tags.collect::<BTreeSet<_>>()))
+			.filter(|(_, tags)| !tags.is_empty())
+			.collect();
+
+		Self { map, path }
+	}
+
+	#[inline]
+	pub fn valid(tag: &str) -> bool { !tag.is_empty() && !tag.contains([',', '\t', '\n']) }
+
+	#[inline]
+	pub fn get(&self, url: &Url) -> Option<&BTreeSet<String>> { self.map.get(url) }
+
+	#[inline]
+	pub fn has(&self, url: &Url, tag: &str) -> bool {
+		self.map.get(url).is_some_and(|t| t.contains(tag))
+	}
+
+	pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a Url> {
+		self.map.iter().filter(move |(_, t)| t.contains(tag)).map(|(u, _)| u)
+	}
+
+	pub fn add<'a>(&mut self, urls: impl IntoIterator<Item = &'a Url>, tag: &str) -> bool {
+		let mut b = false;
+		for url in urls {
+			b |= self.map.entry(url.clone()).or_default().insert(tag.to_owned());
+		}
+		b
+	}
+
+	// Removes `tag` from `urls`, or all their tags if it's `None`
+	pub fn remove<'a>(&mut self, urls: impl IntoIterator<Item = &'a Url>, tag: Option<&str>) -> bool {
+		let mut b = false;
+		for url in urls {
+			let Some(tags) = self.map.get_mut(url) else { continue };
+			b |= match tag {
+				Some(tag) => tags.remove(tag),
+				None => !tags.is_empty(),
+			};
+			if tag.is_none() || tags.is_empty() {
+				self.map.remove(url);
+			}
+		}
+		b
+	}
+
+	// Moves the tags of `from` and everything under it to `to`
+	pub fn rename(&mut self, from: &Url, to: &Url) -> bool {
+		let moved: Vec<_> = self.map.keys().filter(|u| u.starts_with(from)).cloned().collect();
+		for old in &moved {
+			let Some(rel) = old.strip_prefix(from) else { continue };
+			let new = if rel.as_os_str().is_empty() { to.clone() } else { to.join(rel) };
+
+			let tags = self.map.remove(old).unwrap();
+			self.map.insert(new, tags);
+		}
+		!moved.is_empty()
+	}
+
+	// Moves the tags along with the files renamed or moved within Yazi
+	pub fn follow<'a>(moved: impl IntoIterator<Item = (&'a Url, &'a Url)>) {
+		let mut tags = TAGS.write();
+		if moved.into_iter().fold(false, |b, (from, to)| tags.rename(from, to) || b) {
+			tags.save();
+		}
+	}
+
+	fn dump(&self) -> String {
+		self.map.iter().fold(String::new(), |mut s, (u, tags)| {
+			s.push_str(&tags.iter().map(String::as_str).collect::<Vec<_>>().join(","));
+			s.push('\t');
+			s.push_str(&u.to_string_lossy());
+			s.push('\n');
+			s
+		})
+	}
+
+	#[inline]
+	pub fn flush(&self) -> io::Result<()> { SAVER.flush(&self.path, &self.dump()) }
+
+	#[inline]
+	pub fn save(&self) { SAVER.save(self.path.clone(), self.dump()) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_rename() {
+		let mut tags = Tags::default();
+		let (a, b, c) = (Url::from("/d/a"), Url::from("/d/b"), Url::from("/d/ab"));
+		tags.add([&a, &c], "red");
+		tags.add([&Url::from("/d/a/x/y")], "blue");
+
+		assert!(tags.rename(&a, &Url::from("/e/a")));
+		assert!(!tags.has(&a, "red"));
+		assert!(tags.has(&Url::from("/e/a"), "red"));
+		assert!(tags.has(&Url::from("/e/a/x/y"), "blue"));
+		assert!(tags.has(&c, "red"));
+
+		assert!(!tags.rename(&b, &a));
+		assert!(tags.remove([&c], None));
+		assert_eq!(tags.tagged("red").collect::<Vec<_>>(), [&Url::from("/e/a")]);
+	}
+
+	#[test]
+	fn test_load() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		std::fs::write(dir.join("a"), "").unwrap();
+
+		let path = dir.join("tags");
+		let (a, b) = (dir.join("a"), dir.join("b"));
+		std::fs::write(&path, format!("red,,blue\t{}\nred\t{}\n", a.display(), b.display())).unwrap();
+
+		let tags = Tags::load(path);
+		assert_eq!(tags.map.len(), 1);
+		assert_eq!(tags.get(&Url::from(a)).unwrap().iter().collect::<Vec<_>>(), ["blue", "red"]);
+	}
+}
@@ -29,6 +29,10 @@ end
 
 function Entity:highlights()
 	local name = self._file.name:gsub("\r", "?", 1)
+	if not cx.active.conf.show_extensions and not self._file.cha.is_dir then
+		name = ya.strip_extension(name)
+	end
+
 	local highlights = self._file:highlights()
 	if not highlights or #highlights == 0 then
 		return ui.Line(name)
@@ -36,11 +40,13 @@ function Entity:highlights()
 
 	local spans, last = {}, 0
 	for _, h in ipairs(highlights) do
-		if h[1] > last then
+		if h[1] >= #name then
+			break
+		elseif h[1] > last then
 			spans[#spans + 1] = ui.Span(name:sub(last + 1, h[1]))
 		end
-		spans[#spans + 1] = ui.Span(name:sub(h[1] + 1, h[2])):style(THEME.manager.find_keyword)
-		last = h[2]
+		last = math.min(h[2], #name)
+		spans[#spans + 1] = ui.Span(name:sub(h[1] + 1, last)):style(THEME.manager.find_keyword)
 	end
 	if last < #name then
 		spans[#spans + 1] = ui.Span(name:sub(last + 1))
@@ -26,24 +26,37 @@ end
 
 function Linemode:size()
 	local size = self._file:size()
-	return ui.Line(size and ya.readable_size(size) or "")
+	if size then
+		return ui.Line(self:_size(size))
+	elseif self._file:sizing() then
+		local frames = { "⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏" }
+		return ui.Line(frames[math.floor(ya.time() * 10) % #frames + 1])
+	end
+	return ui.Line("")
 end
 
-function Linemode:ctime()
-	local time = (self._file.cha.created or 0) // 1
-	if time == 0 then
-		return ui.Line("")
-	elseif os.date("%Y", time) == os.date("%Y") then
-		return ui.Line(os.date("%m/%d %H:%M", time))
+function Linemode:_size(size)
+	local format = cx.active.conf.size_format
+	if format == "bytes" then
+		return ya.readable_bytes(size)
 	else
-		return ui.Line(os.date("%m/%d  %Y", time))
+		return ya.readable_size(size, format == "decimal" and 1000 or 1024, MANAGER.size_precision)
 	end
 end
 
-function Linemode:mtime()
-	local time = (self._file.cha.modified or 0) // 1
+function Linemode:ctime() return self:_time(self._file.cha.created) end
+
+function Linemode:mtime() return self:_time(self._file.cha.modified) end
+
+function Linemode:_time(time)
+	time = (time or 0) // 1
+	local format = cx.active.conf.time_format
 	if time == 0 then
 		return ui.Line("")
+	elseif format == "relative" then
+		return ui.Line(ya.readable_time(os.time() - time))
+	elseif format ~= "" then
+		return ui.Line(os.date(format, time))
 	elseif os.date("%Y", time) == os.date("%Y") then
 		return ui.Line(os.date("%m/%d %H:%M", time))
 	else
@@ -59,6 +72,14 @@ function Linemode:owner()
 	return ui.Line(string.format("%s:%s", user or "-", group or "-"))
 end
 
+function Linemode:tags()
+	local tags = self._file:tags()
+	if #tags == 0 then
+		return ui.Line("")
+	end
+	return ui.Line { ui.Span("#" .. table.concat(tags, " #")):style(THEME.manager.tag) }
+end
+
 function Linemode:render()
 	local lines = {}
 	for _, c in ipairs(self._children) do
@@ -10,6 +10,7 @@ Status = {
 		{ "name", id = 3, order = 3000 },
 	},
 	_right = {
+		{ "disk", id = 7, order = 500 },
 		{ "permissions", id = 4, order = 1000 },
 		{ "percentage", id = 5, order = 2000 },
 		{ "position", id = 6, order = 3000 },
@@ -34,7 +35,7 @@ function Status:style()
 end
 
 function Status:mode()
-	local mode = tostring(self._tab.mode):sub(1, 3):upper()
+	local mode = self._tab.preview.focused and "PRE" or tostring(self._tab.mode):sub(1, 3):upper()
 
 	local style = self:style()
 	return ui.Line {
@@ -66,6 +67,18 @@ function Status:name()
 	return ui.Line(" " .. h.name)
 end
 
+function Status:disk()
+	local d = cx.disk
+	if not d then
+		return ui.Line {}
+	end
+
+	return ui.Line {
+		ui.Span(string.format("%s free / %s ", ya.readable_size(d.free), ya.readable_size(d.total)))
+			:style(THEME.status.disk_space),
+	}
+end
+
 function Status:permissions()
 	local h = self._tab.current.hovered
 	if not h then
@@ -133,6 +146,7 @@ end
 function Status:render()
 	local left = self:children_render(self.LEFT)
 	local right = self:children_render(self.RIGHT)
+	left:truncate(math.max(0, self._area.w - right:width()))
 	return {
 		ui.Paragraph(self._area, { left }),
 		ui.Paragraph(self._area, { right }):align(ui.Paragraph.RIGHT),
@@ -168,10 +182,19 @@ function Status:children_remove(id, side)
 	end
 end
 
+-- A failing child is rendered as a marker instead, and its error is logged once
 function Status:children_render(side)
 	local lines = {}
 	for _, c in ipairs(side == self.RIGHT and self._right or self._left) do
-		lines[#lines + 1] = (type(c[1]) == "string" and self[c[1]] or c[1])(self)
+		local ok, line = pcall(type(c[1]) == "string" and self[c[1]] or c[1], self)
+		if not ok then
+			if not c.failed then
+				c.failed = true
+				ya.err("Failed to render a status child: " .. tostring(line))
+			end
+			line = ui.Line { ui.Span(" ! "):fg("red") }
+		end
+		lines[#lines + 1] = line
 	end
 	return ui.Line(lines)
 end
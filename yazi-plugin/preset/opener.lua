@@ -0,0 +1,5 @@
+Opener = {}
+
+-- Returns the name of an `[opener]` entry to open `file`, whose mimetype is `mime`, with.
+-- It's asked before the `[open]` rules are matched, and returning nil falls through to them.
+function Opener:pick(file, mime) end
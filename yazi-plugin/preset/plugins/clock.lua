@@ -0,0 +1,12 @@
+-- Shows the time on the right of the status bar:
+--
+--   require("clock"):setup { format = "%H:%M:%S", interval = 1 }
+local function setup(_, opts)
+	opts = opts or {}
+	local format = opts.format or "%H:%M"
+
+	Status:children_add(function() return ui.Line(" " .. os.date(format) .. " ") end, opts.order or 100, Status.RIGHT)
+	ya.render_every(opts.interval or 1)
+end
+
+return { setup = setup }
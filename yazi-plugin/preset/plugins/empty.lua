@@ -35,12 +35,11 @@ function M:peek()
 	end
 end
 
-function M:seek(units)
+function M:seek(_, lines)
 	local h = cx.active.current.hovered
 	if h and h.url == self.file.url then
-		local step = math.floor(units * self.area.h / 10)
 		ya.manager_emit("peek", {
-			math.max(0, cx.active.preview.skip + step),
+			math.max(0, cx.active.preview.skip + lines),
 			only_if = self.file.url,
 		})
 	end
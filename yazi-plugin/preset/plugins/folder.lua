@@ -30,13 +30,12 @@ function M:peek()
 	})
 end
 
-function M:seek(units)
+function M:seek(_, lines)
 	local folder = cx.active.preview.folder
 	if folder and folder.cwd == self.file.url then
-		local step = math.floor(units * self.area.h / 10)
 		local bound = math.max(0, #folder.files - self.area.h)
 		ya.manager_emit("peek", {
-			ya.clamp(0, cx.active.preview.skip + step, bound),
+			ya.clamp(0, cx.active.preview.skip + lines, bound),
 			only_if = self.file.url,
 		})
 	end
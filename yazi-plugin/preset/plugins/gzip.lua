@@ -0,0 +1,107 @@
+local M = {}
+
+-- Only the leading part of the decompressed data is kept for preview
+local LIMIT = 1024 * 1024
+
+local function cache_of(file)
+	if tostring(file.url):find("%.tar%.gz$") then
+		return nil
+	end
+	-- Ignore `skip`, so that scrolling reuses the same decompressed file
+	return ya.file_cache { file = file }
+end
+
+-- The leading lines of the decompressed data, or an empty list if it's not text
+local function decompress(file)
+	local child, code = Command("gzip"):args({ "-dc", tostring(file.url) }):stdout(Command.PIPED):spawn()
+	if not child then
+		return nil, "spawn `gzip` command returns " .. tostring(code)
+	end
+
+	local lines, size = {}, 0
+	while size < LIMIT do
+		local line, event = child:read_line()
+		if event ~= 0 then
+			break
+		elseif line:find("\0", 1, true) then
+			lines = {}
+			break
+		end
+		lines[#lines + 1], size = line, size + #line
+	end
+
+	child:start_kill()
+	return lines
+end
+
+function M:peek()
+	local cache = cache_of(self.file)
+	if not cache then
+		return false
+	end
+
+	local cha = fs.cha(cache)
+	if not cha then
+		-- Not preloaded yet, so decompress it here without writing the cache,
+		-- which is left to the preloader
+		return self:peek_once()
+	elseif cha.length == 0 then
+		-- Not text, leave it to the next previewer
+		return false
+	end
+
+	local err, bound = ya.preview_code { file = self.file, skip = self.skip, area = self.area, window = self.window, source = cache }
+	if bound then
+		ya.manager_emit("peek", { bound, only_if = self.file.url, upper_bound = true })
+	elseif err and not err:find("cancelled", 1, true) then
+		ya.preview_widgets(self, {
+			ui.Paragraph(self.area, { ui.Line(err):reverse() }),
+		})
+	end
+end
+
+function M:peek_once()
+	local lines = decompress(self.file)
+	if not lines or #lines == 0 then
+		return false
+	end
+
+	local limit = self.skip + self.area.h
+	if self.skip > 0 and #lines < limit then
+		ya.manager_emit("peek", { math.max(0, #lines - self.area.h), only_if = self.file.url, upper_bound = true })
+		return
+	end
+
+	local shown = {}
+	for i = self.skip + 1, math.min(#lines, limit) do
+		shown[#shown + 1] = ui.Line((lines[i]:gsub("\r?\n$", "")))
+	end
+	ya.preview_widgets(self, { ui.Paragraph(self.area, shown) })
+end
+
+function M:seek(_, lines)
+	local h = cx.active.current.hovered
+	if h and h.url == self.file.url then
+		ya.manager_emit("peek", {
+			math.max(0, cx.active.preview.skip + lines),
+			only_if = self.file.url,
+		})
+	end
+end
+
+-- Binary contents are cached as an empty file too, so they're only looked at once
+function M:preload()
+	local cache = cache_of(self.file)
+	if not cache or fs.cha(cache) then
+		return 1
+	end
+
+	local lines, err = decompress(self.file)
+	if not lines then
+		ya.err(err)
+		return 0
+	end
+	return fs.write(cache, table.concat(lines)) and 1 or 2
+end
+
+return M
@@ -0,0 +1,5 @@
+Sorter = { _keys = {} }
+
+-- Registers `fn` as the sort mode `name`, to be used with `sort plugin:<name>`. It's given a file, and returns
+-- its sort key, a number or a string. Numbers go before strings, and files without a key go last.
+function Sorter:register(name, fn) self._keys[name] = fn end
@@ -23,14 +23,29 @@ end
 
 function ya.basename(s) return s:gsub("(.*[/\\])(.*)", "%2") end
 
-function ya.readable_size(size)
-	local units = { "B", "K", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q" }
-	local i = 1
-	while size > 1024.0 and i < #units do
-		size = size / 1024.0
-		i = i + 1
+-- Strips only the last extension, so `archive.tar.gz` becomes `archive.tar`.
+-- A leading dot doesn't start one, and `.bashrc` is kept as is.
+function ya.strip_extension(name) return name:match("^(.+)%.[^.]+$") or name end
+
+function ya.readable_bytes(size)
+	local s = string.format("%d", size // 1)
+	local grouped = s:reverse():gsub("(%d%d%d)", "%1,"):reverse():gsub("^,", "")
+	return grouped
+end
+
+function ya.readable_time(secs)
+	-- Times in the future are likely clock skew
+	if secs < 10 then
+		return "just now"
+	end
+
+	local units = { { 604800, "week" }, { 86400, "day" }, { 3600, "hour" }, { 60, "minute" }, { 1, "second" } }
+	for _, u in ipairs(units) do
+		local n = secs // u[1]
+		if n > 0 then
+			return string.format("%d %s%s ago", n, u[2], n == 1 and "" or "s")
+		end
 	end
-	return string.format("%.1f%s", size, units[i])
 end
 
 function ya.readable_path(path)
@@ -52,6 +52,63 @@ impl Clipboard {
 		self.content.lock().clone()
 	}
 
+	// Returns the image on the clipboard and its extension, if there is one.
+	// The clipboard over SSH only ever holds text, so it's never consulted there.
+	#[cfg(unix)]
+	pub async fn get_image(&self) -> Option<(Vec<u8>, &'static str)> {
+		use tokio::process::Command;
+		use yazi_shared::in_ssh_connection;
+
+		if in_ssh_connection() {
+			return None;
+		}
+
+		let run = |bin: &'static str, args: &'static [&'static str]| async move {
+			let output = Command::new(bin).args(args).kill_on_drop(true).output().await.ok()?;
+			output.status.success().then_some(output.stdout)
+		};
+
+		// macOS prints the data as `«data PNGf89504E47...»`
+		if let Some(out) = run("osascript", &["-e", "the clipboard as «class PNGf»"]).await {
+			let s = String::from_utf8_lossy(&out);
+			let hex = s.trim().strip_prefix("«data PNGf")?.strip_suffix('»')?;
+			return (0..hex.len())
+				.step_by(2)
+				.map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+				.collect::<Option<_>>()
+				.map(|b| (b, "png"));
+		}
+
+		let all = [
+			("wl-paste", &["--list-types"][..], &["--type", "image/png"][..]),
+			("xclip", &["-o", "-selection", "clipboard", "-t", "TARGETS"], &[
+				"-o",
+				"-selection",
+				"clipboard",
+				"-t",
+				"image/png",
+			]),
+		];
+
+		for (bin, list, get) in all {
+			let Some(types) = run(bin, list).await else { continue };
+			if !String::from_utf8_lossy(&types).lines().any(|l| l.trim() == "image/png") {
+				return None;
+			}
+			return run(bin, get).await.filter(|b| !b.is_empty()).map(|b| (b, "png"));
+		}
+		None
+	}
+
+	// Windows only offers the image as a bitmap
+	#[cfg(windows)]
+	pub async fn get_image(&self) -> Option<(Vec<u8>, &'static str)> {
+		use clipboard_win::{formats, get_clipboard};
+
+		let result = tokio::task::spawn_blocking(|| get_clipboard::<Vec<u8>, _>(formats::Bitmap));
+		result.await.ok()?.ok().filter(|b| !b.is_empty()).map(|b| (b, "bmp"))
+	}
+
 	#[cfg(unix)]
 	pub async fn set(&self, s: impl AsRef<std::ffi::OsStr>) {
 		use std::{io::{stderr, BufWriter}, process::Stdio};
@@ -92,6 +149,71 @@ impl Clipboard {
 		}
 	}
 
+	// Puts a PNG on the clipboard, returning whether any tool took it
+	#[cfg(unix)]
+	pub async fn set_image(&self, png: Vec<u8>) -> bool {
+		use std::process::Stdio;
+
+		use tokio::{io::AsyncWriteExt, process::Command};
+		use yazi_config::PREVIEW;
+		use yazi_shared::in_ssh_connection;
+
+		if in_ssh_connection() {
+			return false;
+		}
+
+		// AppleScript can only read the image from a file
+		if cfg!(target_os = "macos") {
+			let tmp = PREVIEW.tmpfile("clipboard");
+			if tokio::fs::write(&tmp, &png).await.is_ok() {
+				let script =
+					format!("set the clipboard to (read (POSIX file {:?}) as «class PNGf»)", tmp.display());
+				let status = Command::new("osascript").args(["-e", &script]).status().await;
+				tokio::fs::remove_file(&tmp).await.ok();
+				if status.is_ok_and(|s| s.success()) {
+					return true;
+				}
+			}
+		}
+
+		let all = [
+			("wl-copy", &["--type", "image/png"][..]),
+			("xclip", &["-selection", "clipboard", "-t", "image/png", "-i"]),
+		];
+
+		for (bin, args) in all {
+			let cmd = Command::new(bin)
+				.args(args)
+				.stdin(Stdio::piped())
+				.stdout(Stdio::null())
+				.stderr(Stdio::null())
+				.kill_on_drop(true)
+				.spawn();
+
+			let Ok(mut child) = cmd else { continue };
+
+			let mut stdin = child.stdin.take().unwrap();
+			if stdin.write_all(&png).await.is_err() {
+				continue;
+			}
+			drop(stdin);
+
+			if child.wait().await.map(|s| s.success()).unwrap_or_default() {
+				return true;
+			}
+		}
+		false
+	}
+
+	// Puts a BMP on the clipboard, as a `CF_DIB`
+	#[cfg(windows)]
+	pub async fn set_image(&self, bmp: Vec<u8>) -> bool {
+		use clipboard_win::{formats, set_clipboard};
+
+		let result = tokio::task::spawn_blocking(move || set_clipboard(formats::Bitmap, bmp));
+		matches!(result.await, Ok(Ok(())))
+	}
+
 	#[cfg(windows)]
 	pub async fn set(&self, s: impl AsRef<std::ffi::OsStr>) {
 		use clipboard_win::{formats, set_clipboard};
@@ -0,0 +1,105 @@
+use mlua::{ExternalResult, Function, Lua, Table};
+use yazi_config::{command::Command, COMMANDS};
+use yazi_dds::Sendable;
+use yazi_shared::event::{Cmd, Data};
+
+// `Commands:register(name, { desc = "...", flags = { ... } }, fn)` adds `name`
+// as a command that can be bound in the keymap, or run from the palette, like
+// a built-in one. `fn` gets its positional arguments in order, and its flags
+// by name, as well as its `key=value` arguments if `pairs = true` is given,
+// except for the ones after a `--`.
+pub(super) fn install(lua: &Lua) -> mlua::Result<()> {
+	let commands = lua.create_table_from([("_handlers", lua.create_table()?)])?;
+	commands.raw_set(
+		"register",
+		lua.create_function(|_, (t, name, opts, f): (Table, String, Table, Function)| {
+			let desc: Option<String> = opts.raw_get("desc")?;
+			let flags: Option<Vec<String>> = opts.raw_get("flags")?;
+
+			let mut cmd = Command::new(&name, desc.unwrap_or_default());
+			cmd.flags = flags.unwrap_or_default();
+			cmd.pairs = opts.raw_get::<_, Option<bool>>("pairs")?.unwrap_or(false);
+			COMMANDS.register(cmd).into_lua_err()?;
+
+			t.raw_get::<_, Table>("_handlers")?.raw_set(name, f)
+		})?,
+	)?;
+
+	lua.globals().raw_set("Commands", commands)
+}
+
+fn handler<'a>(lua: &'a Lua, name: &str) -> mlua::Result<Option<Function<'a>>> {
+	lua.globals().raw_get::<_, Table>("Commands")?.raw_get::<_, Table>("_handlers")?.raw_get(name)
+}
+
+#[inline]
+pub fn has_command(lua: &Lua, name: &str) -> bool { handler(lua, name).is_ok_and(|f| f.is_some()) }
+
+// Calls the handler a plugin registered for `cmd`
+pub fn run_command(lua: &Lua, mut cmd: Cmd) -> mlua::Result<()> {
+	let Some(f) = handler(lua, &cmd.name)? else {
+		return Err(format!("unknown command `{}`", cmd.name)).into_lua_err();
+	};
+
+	let args = lua.create_table()?;
+	if COMMANDS.get(&cmd.name).is_some_and(|c| c.pairs) {
+		for (k, v) in cmd.take_pairs().into_lua_err()? {
+			args.raw_set(k, v)?;
+		}
+	}
+	for (k, v) in cmd.args {
+		if matches!(v, Data::Any(_)) {
+			continue;
+		}
+		let v = Sendable::data_to_value(lua, v)?;
+		match k.parse::<usize>() {
+			Ok(i) => args.raw_set(i + 1, v)?,
+			Err(_) => args.raw_set(k, v)?,
+		}
+	}
+	f.call(args)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+
+	use super::*;
+
+	#[test]
+	fn test_run_command() {
+		COMMANDS.with(<_>::default);
+		let lua = Lua::new();
+		install(&lua).unwrap();
+
+		lua
+			.load(
+				r#"
+				Commands:register("greet", { desc = "Say hello", flags = { "loud" } }, function(args)
+					said = string.format("%s %s %s", args[1], args[2], args.loud and args.to)
+				end)
+				"#,
+			)
+			.exec()
+			.unwrap();
+		assert_eq!(COMMANDS.get("greet").unwrap().flags, ["loud"]);
+		assert!(lua.load(r#"Commands:register("cd", {}, function() end)"#).exec().is_err());
+
+		assert!(has_command(&lua, "greet"));
+		assert!(!has_command(&lua, "cd"));
+		run_command(&lua, Cmd::from_str("greet hello 'big world' --loud --to=you").unwrap()).unwrap();
+		assert_eq!(lua.globals().get::<_, String>("said").unwrap(), "hello big world you");
+
+		lua
+			.load(
+				r#"
+				Commands:register("pair", { pairs = true }, function(args) said = args[1] .. args.key end)
+				"#,
+			)
+			.exec()
+			.unwrap();
+		run_command(&lua, Cmd::from_str("pair key='a b' c").unwrap()).unwrap();
+		assert_eq!(lua.globals().get::<_, String>("said").unwrap(), "ca b");
+		assert!(run_command(&lua, Cmd::from_str("pair =1").unwrap()).is_err());
+	}
+}
@@ -2,6 +2,7 @@ use std::mem;
 
 use ansi_to_tui::IntoText;
 use mlua::{AnyUserData, ExternalError, ExternalResult, FromLua, IntoLua, Lua, Table, UserData, UserDataMethods, Value};
+use unicode_width::UnicodeWidthChar;
 
 use super::{Span, Style};
 
@@ -78,6 +79,42 @@ impl Line {
 
 		ui.raw_set("Line", line)
 	}
+
+	// Cuts the line to fit in `max` columns, ending it with an ellipsis if needed
+	fn truncate(&mut self, max: usize) {
+		if self.0.width() <= max {
+			return;
+		}
+
+		let mut left = max.saturating_sub(1);
+		let mut spans = Vec::with_capacity(self.0.spans.len());
+		for mut span in mem::take(&mut self.0.spans) {
+			let width = span.width();
+			if width <= left {
+				left -= width;
+				spans.push(span);
+				continue;
+			}
+
+			let mut end = 0;
+			for (i, c) in span.content.char_indices() {
+				let w = c.width().unwrap_or(0);
+				if w > left {
+					break;
+				}
+				(left, end) = (left - w, i + c.len_utf8());
+			}
+			let content = span.content[..end].to_owned();
+			span.content = content.into();
+			spans.push(span);
+			break;
+		}
+
+		if max > 0 {
+			spans.push(ratatui::text::Span::raw("…"));
+		}
+		self.0.spans = spans;
+	}
 }
 
 impl UserData for Line {
@@ -85,6 +122,10 @@ impl UserData for Line {
 		crate::impl_style_shorthands!(methods, 0.style);
 
 		methods.add_function("width", |_, ud: AnyUserData| Ok(ud.borrow_mut::<Self>()?.0.width()));
+		methods.add_function("truncate", |_, (ud, max): (AnyUserData, usize)| {
+			ud.borrow_mut::<Self>()?.truncate(max);
+			Ok(ud)
+		});
 		methods.add_function("style", |_, (ud, value): (AnyUserData, Value)| {
 			{
 				let mut me = ud.borrow_mut::<Self>()?;
@@ -107,3 +148,26 @@ impl UserData for Line {
 		});
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use ratatui::text::Span;
+
+	use super::*;
+
+	#[test]
+	fn test_truncate() {
+		let line = || Line(ratatui::text::Line::from(vec![Span::raw("ab"), Span::raw("中文")]));
+		let text = |max| {
+			let mut line = line();
+			line.truncate(max);
+			line.0.spans.iter().map(|s| s.content.as_ref()).collect::<String>()
+		};
+
+		assert_eq!(text(6), "ab中文");
+		assert_eq!(text(5), "ab中…");
+		assert_eq!(text(4), "ab…");
+		assert_eq!(text(1), "…");
+		assert_eq!(text(0), "");
+	}
+}
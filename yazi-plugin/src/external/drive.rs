@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use anyhow::{bail, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DriveOp {
+	Mount,
+	Unmount,
+	Eject,
+}
+
+// All operations take the block device, e.g. `/dev/sdb1`, and fail with the
+// output of the platform tool, such as when the device is busy.
+pub async fn drive(op: DriveOp, device: &Path) -> Result<()> {
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	{
+		let sub = match op {
+			DriveOp::Mount => "mount",
+			DriveOp::Unmount | DriveOp::Eject => "unmount",
+		};
+		run("udisksctl", &[sub, "--no-user-interaction", "-b"], device).await?;
+		if op == DriveOp::Eject {
+			run("udisksctl", &["power-off", "--no-user-interaction", "-b"], device).await?;
+		}
+		Ok(())
+	}
+
+	#[cfg(any(target_os = "macos", target_os = "ios"))]
+	{
+		let sub = match op {
+			DriveOp::Mount => "mount",
+			DriveOp::Unmount => "unmount",
+			DriveOp::Eject => "eject",
+		};
+		run("diskutil", &[sub], device).await
+	}
+
+	#[cfg(not(any(
+		target_os = "linux",
+		target_os = "android",
+		target_os = "macos",
+		target_os = "ios"
+	)))]
+	{
+		_ = (op, device);
+		bail!("Not supported on this platform")
+	}
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios"))]
+async fn run(program: &str, args: &[&str], device: &Path) -> Result<()> {
+	use std::{io, process::Stdio};
+
+	use tokio::process::Command;
+
+	let output = match Command::new(program)
+		.args(args)
+		.arg(device)
+		.stdin(Stdio::null())
+		.kill_on_drop(true)
+		.output()
+		.await
+	{
+		Ok(o) => o,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => bail!("`{program}` was not found"),
+		Err(e) => Err(e)?,
+	};
+
+	if !output.status.success() {
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		let stdout = String::from_utf8_lossy(&output.stdout);
+		bail!("{}", if stderr.trim().is_empty() { stdout.trim() } else { stderr.trim() });
+	}
+	Ok(())
+}
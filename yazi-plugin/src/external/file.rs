@@ -0,0 +1,19 @@
+use std::{env, path::Path, process::Stdio};
+
+use tokio::process::Command;
+
+// Detects the mimetype of a file with `file(1)`, as the `mime` plugin does
+pub async fn mimetype(path: &Path) -> Option<String> {
+	let output = Command::new(env::var_os("YAZI_FILE_ONE").unwrap_or("file".into()))
+		.args(["-bL", "--mime-type", "--"])
+		.arg(path)
+		.kill_on_drop(true)
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.output()
+		.await
+		.ok()?;
+
+	let mime = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+	mime.contains('/').then_some(mime)
+}
@@ -1,22 +1,25 @@
-use std::{borrow::Cow, io::Cursor, mem, path::{Path, PathBuf}, sync::atomic::{AtomicUsize, Ordering}};
+use std::{io::Cursor, mem, path::{Path, PathBuf}, sync::atomic::{AtomicUsize, Ordering}};
 
 use anyhow::{anyhow, Result};
 use ratatui::{layout::Rect, text::{Line, Span, Text}};
 use syntect::{dumps, easy::HighlightLines, highlighting::{self, Theme, ThemeSet}, parsing::{SyntaxReference, SyntaxSet}, LoadingError};
 use tokio::{fs::File, io::{AsyncBufReadExt, BufReader}, sync::OnceCell};
-use yazi_config::{preview::PreviewWrap, PREVIEW, THEME};
-use yazi_shared::PeekError;
+use yazi_config::{PREVIEW, THEME};
+use yazi_shared::{spawn_blocking_nice, Pace, PeekError};
+
+use super::wrap_height;
 
 static INCR: AtomicUsize = AtomicUsize::new(0);
 static SYNTECT: OnceCell<(Theme, SyntaxSet)> = OnceCell::const_new();
 
 pub struct Highlighter {
 	path: PathBuf,
+	wrap: bool,
 }
 
 impl Highlighter {
 	#[inline]
-	pub fn new(path: &Path) -> Self { Self { path: path.to_owned() } }
+	pub fn new(path: &Path, wrap: bool) -> Self { Self { path: path.to_owned(), wrap } }
 
 	pub async fn init() -> (&'static Theme, &'static SyntaxSet) {
 		let fut = async {
@@ -72,12 +75,12 @@ impl Highlighter {
 			i += if i >= skip {
 				buf.iter_mut().for_each(Self::carriage_return_to_line_feed);
 				after.push(String::from_utf8_lossy(&buf).into_owned());
-				Self::line_height(&after[after.len() - 1], area.width)
+				Self::line_height(&after[after.len() - 1], area.width, self.wrap)
 			} else if !plain {
 				before.push(String::from_utf8_lossy(&buf).into_owned());
-				Self::line_height(&before[before.len() - 1], area.width)
-			} else if PREVIEW.wrap == PreviewWrap::Yes {
-				Self::line_height(&String::from_utf8_lossy(&buf), area.width)
+				Self::line_height(&before[before.len() - 1], area.width, self.wrap)
+			} else if self.wrap {
+				Self::line_height(&String::from_utf8_lossy(&buf), area.width, true)
 			} else {
 				1
 			};
@@ -107,9 +110,10 @@ impl Highlighter {
 		let ticket = INCR.load(Ordering::Relaxed);
 		let (theme, syntaxes) = Self::init().await;
 
-		tokio::task::spawn_blocking(move || {
-			let mut h = HighlightLines::new(syntax, theme);
+		spawn_blocking_nice(PREVIEW.worker_nice, move || {
+			let (mut h, mut pace) = (HighlightLines::new(syntax, theme), Pace::new(PREVIEW.cpu_limit));
 			for line in before {
+				pace.tick(|| ticket != INCR.load(Ordering::Relaxed));
 				if ticket != INCR.load(Ordering::Relaxed) {
 					return Err("Highlighting cancelled".into());
 				}
@@ -119,6 +123,7 @@ impl Highlighter {
 			let indent = PREVIEW.indent();
 			let mut lines = Vec::with_capacity(after.len());
 			for line in after {
+				pace.tick(|| ticket != INCR.load(Ordering::Relaxed));
 				if ticket != INCR.load(Ordering::Relaxed) {
 					return Err("Highlighting cancelled".into());
 				}
@@ -160,29 +165,14 @@ impl Highlighter {
 		}
 	}
 
-	fn line_height(s: &str, width: u16) -> usize {
-		if PREVIEW.wrap != PreviewWrap::Yes {
-			return 1;
+	fn line_height(s: &str, width: u16, wrap: bool) -> usize {
+		if !wrap {
+			1
+		} else if s.contains('\t') {
+			wrap_height(&s.replace('\t', &PREVIEW.indent()), width)
+		} else {
+			wrap_height(s, width)
 		}
-
-		let pad = PREVIEW
-			.tab_size
-			.checked_sub(1)
-			.map(|n| s.bytes().filter(|&b| b == b'\t').count() * n as usize)
-			.map(|n| yazi_config::preview::Preview::indent_with(n))
-			.unwrap_or_default();
-
-		let line = Line {
-			spans: vec![Span { content: pad, style: Default::default() }, Span {
-				content: Cow::Borrowed(s),
-				..Default::default()
-			}],
-			..Default::default()
-		};
-
-		ratatui::widgets::Paragraph::new(line)
-			.wrap(ratatui::widgets::Wrap { trim: false })
-			.line_count(width)
 	}
 
 	#[inline(always)]
@@ -1,7 +1,17 @@
+mod drive;
 mod fd;
+mod file;
 mod highlighter;
+mod reveal;
 mod rg;
+mod stats;
+mod wrap;
 
+pub use drive::*;
 pub use fd::*;
+pub use file::*;
 pub use highlighter::*;
+pub use reveal::*;
 pub use rg::*;
+pub use stats::*;
+pub use wrap::*;
@@ -0,0 +1,82 @@
+use std::{path::Path, process::Stdio};
+
+use anyhow::{bail, Result};
+use tokio::process::Command;
+
+// Shows `path` in the platform file manager, selected where that's supported,
+// otherwise its parent directory is opened instead. The file manager is left
+// running on its own, so this returns as soon as it has been launched.
+pub async fn reveal(path: &Path) -> Result<()> {
+	#[cfg(any(target_os = "macos", target_os = "ios"))]
+	{
+		spawn("open", &["-R".as_ref(), path.as_os_str()]).await
+	}
+
+	#[cfg(windows)]
+	{
+		let mut arg = std::ffi::OsString::from("/select,");
+		arg.push(path);
+		spawn("explorer", &[&arg]).await
+	}
+
+	#[cfg(not(any(target_os = "macos", target_os = "ios", windows)))]
+	{
+		let uri = file_uri(path);
+		let shown = Command::new("dbus-send")
+			.args(["--session", "--print-reply", "--dest=org.freedesktop.FileManager1"])
+			.args([
+				"/org/freedesktop/FileManager1",
+				"org.freedesktop.FileManager1.ShowItems",
+				&format!("array:string:{uri}"),
+				"string:",
+			])
+			.stdin(Stdio::null())
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.status()
+			.await
+			.is_ok_and(|s| s.success());
+
+		if shown {
+			return Ok(());
+		}
+		spawn("xdg-open", &[path.parent().unwrap_or(path).as_os_str()]).await
+	}
+}
+
+async fn spawn(program: &str, args: &[&std::ffi::OsStr]) -> Result<()> {
+	let child = Command::new(program)
+		.args(args)
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn();
+
+	match child {
+		Ok(mut c) => _ = tokio::spawn(async move { c.wait().await }),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => bail!("`{program}` was not found"),
+		Err(e) => Err(e)?,
+	}
+	Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "ios", windows)))]
+fn file_uri(path: &Path) -> String {
+	use percent_encoding::{percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+	const SET: &AsciiSet =
+		&NON_ALPHANUMERIC.remove(b'/').remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+	format!("file://{}", percent_encode(path.as_os_str().as_encoded_bytes(), SET))
+}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(not(any(target_os = "macos", target_os = "ios", windows)))]
+	#[test]
+	fn test_file_uri() {
+		use super::*;
+
+		assert_eq!(file_uri(Path::new("/tmp/a b/c#1.txt")), "file:///tmp/a%20b/c%231.txt");
+		assert_eq!(file_uri(Path::new("/home/ü/.x~")), "file:///home/%C3%BC/.x~");
+	}
+}
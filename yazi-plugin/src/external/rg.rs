@@ -36,3 +36,77 @@ pub fn rg(opt: RgOpt) -> Result<UnboundedReceiver<File>> {
 	});
 	Ok(rx)
 }
+
+#[derive(Clone, Debug)]
+pub struct RgMatch {
+	pub url:    Url,
+	pub path:   String,
+	pub line:   usize,
+	pub column: usize,
+	pub text:   String,
+}
+
+impl RgMatch {
+	fn parse(cwd: &Url, s: &str) -> Option<Self> {
+		let v: serde_json::Value = serde_json::from_str(s).ok()?;
+		if v["type"] != "match" {
+			return None;
+		}
+
+		let data = &v["data"];
+		let path = data["path"]["text"].as_str()?;
+		Some(Self {
+			url:    cwd.join(path),
+			path:   path.to_owned(),
+			line:   data["line_number"].as_u64()? as usize,
+			column: data["submatches"][0]["start"].as_u64().map_or(1, |n| n as usize + 1),
+			text:   data["lines"]["text"].as_str().unwrap_or_default().trim().to_owned(),
+		})
+	}
+}
+
+pub fn rg_matches(opt: RgOpt) -> Result<UnboundedReceiver<RgMatch>> {
+	let mut child = Command::new("rg")
+		.current_dir(&opt.cwd)
+		.args(["--color=never", "--json", "--smart-case"])
+		.arg(if opt.hidden { "--hidden" } else { "--no-hidden" })
+		.args(opt.args)
+		.arg(opt.subject)
+		.kill_on_drop(true)
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()?;
+
+	let mut it = BufReader::new(child.stdout.take().unwrap()).lines();
+	let (tx, rx) = mpsc::unbounded_channel();
+
+	tokio::spawn(async move {
+		while let Ok(Some(line)) = it.next_line().await {
+			let Some(m) = RgMatch::parse(&opt.cwd, &line) else { continue };
+			if tx.send(m).is_err() {
+				return; // The receiver is gone, kill the child by dropping it
+			}
+		}
+		child.wait().await.ok();
+	});
+	Ok(rx)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+		let cwd = Url::from("/tmp");
+		let s = r#"{"type":"match","data":{"path":{"text":"src/main.rs"},"lines":{"text":"\tfn main() {\n"},"line_number":3,"absolute_offset":10,"submatches":[{"match":{"text":"main"},"start":4,"end":8}]}}"#;
+
+		let m = RgMatch::parse(&cwd, s).unwrap();
+		assert_eq!(m.url, Url::from("/tmp/src/main.rs"));
+		assert_eq!((m.line, m.column), (3, 5));
+		assert_eq!(m.text, "fn main() {");
+
+		assert!(RgMatch::parse(&cwd, r#"{"type":"begin","data":{}}"#).is_none());
+		assert!(RgMatch::parse(&cwd, "not json").is_none());
+	}
+}
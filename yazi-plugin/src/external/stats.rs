@@ -0,0 +1,108 @@
+use std::{collections::HashMap, fmt::{self, Display}, io::{self, Read}, path::{Path, PathBuf}, time::SystemTime};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use yazi_config::PREVIEW;
+use yazi_shared::{readable_size, spawn_blocking_nice};
+
+// Counted files by their path and modification time, emptied once it's full
+type Cache = HashMap<(PathBuf, Option<SystemTime>), TextStats>;
+static CACHE: Mutex<Option<Cache>> = Mutex::new(None);
+const CACHE_SIZE: usize = 500;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextStats {
+	pub lines:   usize,
+	pub words:   usize,
+	pub bytes:   u64,
+	// Whether the lines and words were only counted in part of the file
+	pub partial: bool,
+}
+
+impl TextStats {
+	pub async fn of(path: &Path) -> Result<Self> {
+		let file = std::fs::File::open(path)?;
+		let meta = file.metadata()?;
+
+		let key = (path.to_owned(), meta.modified().ok());
+		if let Some(&stats) = CACHE.lock().as_ref().and_then(|c| c.get(&key)) {
+			return Ok(stats);
+		}
+
+		let stats =
+			spawn_blocking_nice(PREVIEW.worker_nice, move || Self::count(file, PREVIEW.text_stats_max))
+				.await??;
+		let stats = Self { bytes: stats.bytes.max(meta.len()), ..stats };
+
+		let mut cache = CACHE.lock();
+		let cache = cache.get_or_insert_with(Default::default);
+		if cache.len() >= CACHE_SIZE {
+			cache.clear();
+		}
+		cache.insert(key, stats);
+		Ok(stats)
+	}
+
+	// Counts the same way `wc` does, in the first `max` bytes of `r`
+	pub fn count(r: impl Read, max: u64) -> io::Result<Self> {
+		let (mut stats, mut in_word) = (Self::default(), false);
+		let (mut r, mut buf) = (r.take(max), vec![0; 64 * 1024]);
+		loop {
+			let n = match r.read(&mut buf) {
+				Ok(0) => break,
+				Ok(n) => n,
+				Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+				Err(e) => return Err(e),
+			};
+
+			for &b in &buf[..n] {
+				if b.is_ascii_whitespace() {
+					stats.lines += (b == b'\n') as usize;
+					in_word = false;
+				} else if !in_word {
+					stats.words += 1;
+					in_word = true;
+				}
+			}
+			stats.bytes += n as u64;
+		}
+
+		stats.partial = r.limit() == 0 && r.into_inner().read(&mut [0])? > 0;
+		Ok(stats)
+	}
+}
+
+impl Display for TextStats {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let approx = if self.partial { "≥" } else { "" };
+		write!(
+			f,
+			"{approx}{} lines  {approx}{} words  {}",
+			self.lines,
+			self.words,
+			readable_size(self.bytes)
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const FIXTURE: &[u8] = b"The quick brown fox\n\tjumps over\r\n\nthe  lazy dog";
+
+	#[test]
+	fn test_count() {
+		let stats = TextStats::count(FIXTURE, u64::MAX).unwrap();
+		assert_eq!(stats, TextStats { lines: 3, words: 9, bytes: 47, partial: false });
+		assert_eq!(stats.to_string(), "3 lines  9 words  47.0B");
+
+		// Cut in the middle of "jumps"
+		let stats = TextStats::count(FIXTURE, 23).unwrap();
+		assert_eq!(stats, TextStats { lines: 1, words: 5, bytes: 23, partial: true });
+		assert_eq!(stats.to_string(), "≥1 lines  ≥5 words  23.0B");
+
+		let stats = TextStats::count(FIXTURE, FIXTURE.len() as u64).unwrap();
+		assert!(!stats.partial);
+	}
+}
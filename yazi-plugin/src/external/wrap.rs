@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+use ratatui::{style::Style, text::{Line, Span}};
+use unicode_width::UnicodeWidthChar;
+
+// Breaks `line` into ones no wider than `width`, after the last whitespace that
+// fits, or right where it overflows if a word is longer than that. Wide
+// characters take two columns, and are moved to the next line instead of cut.
+pub fn wrap_line(line: Line<'static>, width: u16) -> Vec<Line<'static>> {
+	let cells: Vec<(char, Style)> = line
+		.spans
+		.iter()
+		.flat_map(|s| s.content.chars().filter(|&c| c != '\n').map(|c| (c, s.style)))
+		.collect();
+
+	let chars: Vec<_> = cells.iter().map(|&(c, _)| c).collect();
+	rows(&chars, width)
+		.into_iter()
+		.map(|r| {
+			let mut spans: Vec<Span> = vec![];
+			for &(c, style) in &cells[r] {
+				match spans.last_mut() {
+					Some(s) if s.style == style => s.content.to_mut().push(c),
+					_ => spans.push(Span::styled(c.to_string(), style)),
+				}
+			}
+			Line { spans, style: line.style, alignment: line.alignment }
+		})
+		.collect()
+}
+
+// The number of lines `s` takes up when wrapped the same way as `wrap_line`
+pub fn wrap_height(s: &str, width: u16) -> usize {
+	rows(&s.chars().filter(|&c| c != '\n').collect::<Vec<_>>(), width).len()
+}
+
+fn rows(chars: &[char], width: u16) -> Vec<Range<usize>> {
+	let width = width.max(1) as usize;
+	let (mut rows, mut start, mut used, mut brk) = (vec![], 0, 0, None);
+
+	for (i, &c) in chars.iter().enumerate() {
+		let w = c.width().unwrap_or(0);
+		if used + w > width && i > start {
+			// Whitespace hangs off the end rather than starting the next line
+			if c.is_whitespace() {
+				rows.push(start..i + 1);
+				(start, used, brk) = (i + 1, 0, None);
+				continue;
+			}
+
+			let end = match brk {
+				Some(b) if b > start => b,
+				_ => i,
+			};
+			rows.push(start..end);
+
+			start = end;
+			used = chars[start..i].iter().map(|c| c.width().unwrap_or(0)).sum();
+			brk = None;
+		}
+
+		used += w;
+		if c.is_whitespace() {
+			brk = Some(i + 1);
+		}
+	}
+
+	rows.push(start..chars.len());
+	rows
+}
+
+#[cfg(test)]
+mod tests {
+	use ratatui::style::Stylize;
+
+	use super::*;
+
+	fn wrap(s: &str, width: u16) -> Vec<String> {
+		wrap_line(Line::from(s.to_owned()), width).iter().map(|l| l.to_string()).collect()
+	}
+
+	#[test]
+	fn test_wrap_words() {
+		assert_eq!(wrap("hello world wide", 11), ["hello world ", "wide"]);
+		assert_eq!(wrap("hello world wide", 10), ["hello ", "world wide"]);
+		assert_eq!(wrap("hello world wide", 6), ["hello ", "world ", "wide"]);
+		assert_eq!(wrap("hello world wide\n", 100), ["hello world wide"]);
+		assert_eq!(wrap("", 10), [""]);
+
+		// Words longer than the width are broken anywhere
+		assert_eq!(wrap("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+		assert_eq!(wrap("ab cdefghij", 4), ["ab ", "cdef", "ghij"]);
+		assert_eq!(wrap_height("hello world wide", 6), 3);
+	}
+
+	#[test]
+	fn test_wrap_wide() {
+		assert_eq!(wrap("你好世界", 8), ["你好世界"]);
+		assert_eq!(wrap("你好世界", 7), ["你好世", "界"]);
+		assert_eq!(wrap("你好世界", 3), ["你", "好", "世", "界"]);
+		assert_eq!(wrap("a你好", 4), ["a你", "好"]);
+		assert_eq!(wrap("你好 world", 8), ["你好 ", "world"]);
+		assert_eq!(wrap_height("你好世界你好", 4), 3);
+	}
+
+	#[test]
+	fn test_wrap_styles() {
+		let line = Line::from(vec![Span::raw("ab "), Span::styled("cd", Style::new().bold())]);
+		let lines = wrap_line(line, 3);
+		assert_eq!(lines.len(), 2);
+		assert_eq!(lines[1].spans, [Span::styled("cd", Style::new().bold())]);
+	}
+}
@@ -1,6 +1,8 @@
+use std::{io::{self, BufRead, BufReader, Read}, path::Path};
+
 use globset::GlobBuilder;
 use mlua::{ExternalError, ExternalResult, IntoLuaMulti, Lua, Table, Value};
-use tokio::fs;
+use tokio::{fs, task::spawn_blocking};
 use yazi_shared::fs::remove_dir_clean;
 
 use crate::{bindings::Cast, cha::Cha, file::File, url::{Url, UrlRef}};
@@ -24,6 +26,29 @@ pub fn install(lua: &Lua) -> mlua::Result<()> {
 					}
 				})?,
 			),
+			(
+				"read",
+				lua.create_async_function(|lua, (url, options): (UrlRef, Option<Table>)| async move {
+					let (limit, lines, lossy) = match options {
+						Some(t) => (
+							t.raw_get("limit").unwrap_or(READ_LIMIT),
+							t.raw_get("lines").ok(),
+							t.raw_get("lossy").unwrap_or(false),
+						),
+						None => (READ_LIMIT, None, false),
+					};
+
+					let path = url.to_path_buf();
+					let result = spawn_blocking(move || read(&path, limit, lines)).await.into_lua_err()?;
+					match result {
+						Ok((data, truncated)) if lossy => {
+							(String::from_utf8_lossy(&data), truncated).into_lua_multi(lua)
+						}
+						Ok((data, truncated)) => (lua.create_string(data)?, truncated).into_lua_multi(lua),
+						Err(e) => (Value::Nil, e.raw_os_error()).into_lua_multi(lua),
+					}
+				})?,
+			),
 			(
 				"write",
 				lua.create_async_function(|lua, (url, data): (UrlRef, mlua::String)| async move {
@@ -119,3 +144,66 @@ pub fn install(lua: &Lua) -> mlua::Result<()> {
 		])?,
 	)
 }
+
+// Bytes read by `fs.read()` unless a `limit` is given
+const READ_LIMIT: usize = 1 << 20;
+
+// Reads at most `limit` bytes of a file, or of its first `lines` lines if
+// given, and whether there was more to read
+fn read(path: &Path, limit: usize, lines: Option<usize>) -> io::Result<(Vec<u8>, bool)> {
+	let mut r = BufReader::new(std::fs::File::open(path)?).take(limit as u64 + 1);
+	let mut buf = vec![];
+
+	let truncated = match lines {
+		None => {
+			r.read_to_end(&mut buf)?;
+			buf.len() > limit
+		}
+		Some(n) => {
+			for _ in 0..n {
+				if r.read_until(b'\n', &mut buf)? == 0 {
+					break;
+				}
+			}
+			buf.len() > limit || !r.fill_buf()?.is_empty()
+		}
+	};
+
+	buf.truncate(limit);
+	Ok((buf, truncated))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_read() {
+		let tmp = tempfile::tempdir().unwrap();
+		let path = tmp.path().join("a.txt");
+		std::fs::write(&path, b"one\ntwo\nthree\n\xff").unwrap();
+
+		let lua = Lua::new();
+		crate::url::pour(&lua).unwrap();
+		install(&lua).unwrap();
+		lua.globals().raw_set("path", path.to_string_lossy()).unwrap();
+
+		let read = |options: &str| {
+			let chunk = format!("return fs.read(Url(path), {options})");
+			let lua = &lua;
+			async move { lua.load(chunk).eval_async::<(mlua::String, bool)>().await.unwrap() }
+		};
+
+		let (s, truncated) = read("{ lines = 2 }").await;
+		assert_eq!((s.as_bytes(), truncated), (&b"one\ntwo\n"[..], true));
+
+		let (s, truncated) = read("{ lines = 2, limit = 6 }").await;
+		assert_eq!((s.as_bytes(), truncated), (&b"one\ntw"[..], true));
+
+		let (s, truncated) = read("{ lines = 10 }").await;
+		assert_eq!((s.as_bytes(), truncated), (&b"one\ntwo\nthree\n\xff"[..], false));
+
+		let (s, truncated) = read("{ lossy = true }").await;
+		assert_eq!((s.to_str().unwrap(), truncated), ("one\ntwo\nthree\n\u{fffd}", false));
+	}
+}
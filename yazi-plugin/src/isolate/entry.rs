@@ -1,12 +1,17 @@
+use std::collections::HashMap;
+
 use mlua::{ExternalError, ExternalResult, Table, TableExt};
 use tokio::runtime::Handle;
-use yazi_dds::Sendable;
 use yazi_shared::event::Data;
 
 use super::slim_lua;
-use crate::loader::LOADER;
+use crate::{entry_args, loader::LOADER};
 
-pub async fn entry(name: String, args: Vec<Data>) -> mlua::Result<()> {
+pub async fn entry(
+	name: String,
+	args: Vec<Data>,
+	pairs: HashMap<String, String>,
+) -> mlua::Result<()> {
 	LOADER.ensure(&name).await.into_lua_err()?;
 
 	tokio::task::spawn_blocking(move || {
@@ -17,8 +22,7 @@ pub async fn entry(name: String, args: Vec<Data>) -> mlua::Result<()> {
 			return Err("unloaded plugin".into_lua_err());
 		};
 
-		Handle::current()
-			.block_on(plugin.call_async_method("entry", Sendable::list_to_table(&lua, args)))
+		Handle::current().block_on(plugin.call_async_method("entry", entry_args(&lua, args, pairs)))
 	})
 	.await
 	.into_lua_err()?
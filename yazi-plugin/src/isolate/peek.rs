@@ -1,26 +1,36 @@
-use mlua::{ExternalError, ExternalResult, HookTriggers, Table, TableExt};
+use mlua::{ExternalError, ExternalResult, HookTriggers, Table, TableExt, Value};
+use parking_lot::Mutex;
 use tokio::{runtime::Handle, select};
 use tokio_util::sync::CancellationToken;
 use tracing::error;
-use yazi_config::LAYOUT;
-use yazi_shared::{emit, event::Cmd, Layer};
+use yazi_config::{plugin::Previewer, LAYOUT, PREVIEW};
+use yazi_proxy::ManagerProxy;
+use yazi_shared::{emit, event::Cmd, spawn_blocking_nice, Layer, Pace};
 
 use super::slim_lua;
 use crate::{bindings::{Cast, Window}, elements::Rect, file::File, loader::LOADER, Opt, OptCallback, LUA};
 
-pub fn peek(cmd: &Cmd, file: yazi_shared::fs::File, skip: usize) -> CancellationToken {
+pub fn peek(
+	previewer: &Previewer,
+	file: yazi_shared::fs::File,
+	skip: usize,
+	rule: usize,
+	stats: bool,
+) -> CancellationToken {
 	let ct = CancellationToken::new();
 
-	let name = cmd.name.to_owned();
+	let (name, url, wrap) = (previewer.run.name.to_owned(), file.url(), previewer.wraps());
 	let (ct1, ct2) = (ct.clone(), ct.clone());
-	tokio::task::spawn_blocking(move || {
+	spawn_blocking_nice(PREVIEW.worker_nice, move || {
 		let future = async {
 			LOADER.ensure(&name).await.into_lua_err()?;
 
 			let lua = slim_lua(&name)?;
+			let pace = Mutex::new(Pace::new(PREVIEW.cpu_limit));
 			lua.set_hook(
 				HookTriggers::new().on_calls().on_returns().every_nth_instruction(2000),
 				move |_, _| {
+					pace.lock().tick(|| ct1.is_cancelled());
 					if ct1.is_cancelled() { Err("Peek task cancelled".into_lua_err()) } else { Ok(()) }
 				},
 			);
@@ -32,40 +42,117 @@ pub fn peek(cmd: &Cmd, file: yazi_shared::fs::File, skip: usize) -> Cancellation
 			};
 			plugin.raw_set("file", File::cast(&lua, file)?)?;
 			plugin.raw_set("skip", skip)?;
+			plugin.raw_set("stats", stats)?;
+			plugin.raw_set("wrap", wrap)?;
 			plugin.raw_set("area", Rect::cast(&lua, LAYOUT.load().preview)?)?;
 			plugin.raw_set("window", Window::default())?;
 
-			if ct2.is_cancelled() { Ok(()) } else { plugin.call_async_method("peek", ()).await }
+			if ct2.is_cancelled() {
+				return Ok(false);
+			}
+			// Returning `false` declines the file, and passes it on to the next previewer
+			let value: Value = plugin.call_async_method("peek", ()).await?;
+			Ok(matches!(value, Value::Boolean(false)))
 		};
 
 		let result = Handle::current().block_on(async {
 			select! {
-				_ = ct2.cancelled() => Ok(()),
+				_ = ct2.cancelled() => Ok(false),
 				r = future => r,
 			}
 		});
 
-		if let Err(e) = result {
-			if !e.to_string().contains("Peek task cancelled") {
-				error!("{e:?}");
-			}
+		match result {
+			Ok(true) => ManagerProxy::peek_next(&url, rule + 1),
+			Ok(false) => {}
+			Err(e) if e.to_string().contains("Peek task cancelled") => {}
+			Err(e) => error!("{e:?}"),
 		}
 	});
 
 	ct
 }
 
-pub fn peek_sync(cmd: &Cmd, file: yazi_shared::fs::File, skip: usize) {
+pub fn peek_sync(
+	previewer: &Previewer,
+	file: yazi_shared::fs::File,
+	skip: usize,
+	rule: usize,
+	stats: bool,
+) {
+	let (url, wrap) = (file.url(), previewer.wraps());
 	let cb: OptCallback = Box::new(move |_, plugin| {
 		plugin.raw_set("file", File::cast(&LUA, file)?)?;
 		plugin.raw_set("skip", skip)?;
+		plugin.raw_set("stats", stats)?;
+		plugin.raw_set("wrap", wrap)?;
 		plugin.raw_set("area", Rect::cast(&LUA, LAYOUT.load().preview)?)?;
 		plugin.raw_set("window", Window::default())?;
-		plugin.call_method("peek", ())
+		if let Value::Boolean(false) = plugin.call_method("peek", ())? {
+			ManagerProxy::peek_next(&url, rule + 1);
+		}
+		Ok(())
 	});
 
 	let cmd: Cmd =
-		Opt { id: cmd.name.to_owned(), sync: true, cb: Some(cb), ..Default::default() }.into();
+		Opt { id: previewer.run.name.to_owned(), sync: true, cb: Some(cb), ..Default::default() }
+			.into();
 
 	emit!(Call(cmd.with_name("plugin"), Layer::App));
 }
+
+#[cfg(test)]
+mod tests {
+	use std::{borrow::Cow, sync::Once, time::Duration};
+
+	use tokio::time::timeout;
+	use yazi_shared::{event::{Data, Event}, fs::Url};
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_decline() {
+		static INIT: Once = Once::new();
+		INIT.call_once(|| {
+			#[cfg(unix)]
+			yazi_shared::USERS_CACHE.with(<_>::default);
+			crate::loader::init();
+			yazi_config::LAYOUT.with(<_>::default);
+			yazi_config::PREVIEW
+				.init(include_str!("../../../yazi-config/preset/yazi.toml").parse().unwrap());
+		});
+		let mut rx = crate::events().await;
+
+		LOADER.write().extend([
+			("decline".to_owned(), Cow::Borrowed(&b"return { peek = function() return false end }"[..])),
+			("accept".to_owned(), Cow::Borrowed(&b"return { peek = function() end }"[..])),
+		]);
+
+		let url = Url::from("/yazi-test-decline");
+		let previewer = |name: &str| Previewer {
+			name: None,
+			mime: None,
+			run:  Cmd::new(name),
+			sync: false,
+			wrap: None,
+		};
+
+		// A declined file is peeked again, with the rule after this one
+		let file = yazi_shared::fs::File::from_dummy(url.clone(), None);
+		peek(&previewer("decline"), file, 0, 2, false);
+		let Event::Call(cmd, Layer::Manager) =
+			timeout(Duration::from_secs(3), rx.recv()).await.unwrap().unwrap()
+		else {
+			panic!("expected a `peek` call");
+		};
+		assert_eq!(cmd.name, "peek");
+		assert_eq!(cmd.get("rule").and_then(Data::as_usize), Some(3));
+		assert!(matches!(cmd.get("only-if"), Some(Data::Url(u)) if *u == url));
+
+		// While a kept one isn't
+		let file = yazi_shared::fs::File::from_dummy(url, None);
+		let ct = peek(&previewer("accept"), file, 0, 2, false);
+		assert!(timeout(Duration::from_millis(200), rx.recv()).await.is_err());
+		ct.cancel();
+	}
+}
@@ -1,16 +1,33 @@
-use mlua::{ExternalError, ExternalResult, Table, TableExt};
+use mlua::{ExternalError, ExternalResult, HookTriggers, Table, TableExt};
 use tokio::runtime::Handle;
+use tokio_util::sync::CancellationToken;
 use yazi_config::LAYOUT;
 
 use super::slim_lua;
 use crate::{bindings::Cast, elements::Rect, file::File, loader::LOADER};
 
-pub async fn preload(name: &str, file: yazi_shared::fs::File) -> mlua::Result<u8> {
+pub async fn preload(
+	name: &str,
+	file: yazi_shared::fs::File,
+	ct: CancellationToken,
+) -> mlua::Result<u8> {
 	LOADER.ensure(name).await.into_lua_err()?;
 
 	let name = name.to_owned();
 	tokio::task::spawn_blocking(move || {
+		if ct.is_cancelled() {
+			return Err("Preload task cancelled".into_lua_err());
+		}
+
 		let lua = slim_lua(&name)?;
+		let ct_ = ct.clone();
+		lua.set_hook(
+			HookTriggers::new().on_calls().on_returns().every_nth_instruction(2000),
+			move |_, _| {
+				if ct_.is_cancelled() { Err("Preload task cancelled".into_lua_err()) } else { Ok(()) }
+			},
+		);
+
 		let plugin: Table = if let Some(b) = LOADER.read().get(&name) {
 			lua.load(b.as_ref()).set_name(name).call(())?
 		} else {
@@ -21,7 +38,12 @@ pub async fn preload(name: &str, file: yazi_shared::fs::File) -> mlua::Result<u8
 		plugin.raw_set("area", Rect::cast(&lua, LAYOUT.load().preview)?)?;
 		plugin.raw_set("file", File::cast(&lua, file)?)?;
 
-		Handle::current().block_on(plugin.call_async_method("preload", ()))
+		Handle::current().block_on(async {
+			tokio::select! {
+				_ = ct.cancelled() => Err("Preload task cancelled".into_lua_err()),
+				r = plugin.call_async_method("preload", ()) => r,
+			}
+		})
 	})
 	.await
 	.into_lua_err()?
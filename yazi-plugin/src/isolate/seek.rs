@@ -4,11 +4,14 @@ use yazi_shared::{emit, event::Cmd, Layer};
 
 use crate::{bindings::Cast, elements::Rect, file::File, Opt, OptCallback, LUA};
 
-pub fn seek_sync(cmd: &Cmd, file: yazi_shared::fs::File, units: i16) {
+// Calls `seek(units, lines)` of the previewer, where `units` are tenths of the
+// preview's height, as they were before `lines` was added for the previewers
+// that scroll line by line, and `lines` is the exact number of lines to seek by
+pub fn seek_sync(cmd: &Cmd, file: yazi_shared::fs::File, units: isize, lines: isize) {
 	let cb: OptCallback = Box::new(move |_, plugin| {
 		plugin.raw_set("file", File::cast(&LUA, file)?)?;
 		plugin.raw_set("area", Rect::cast(&LUA, LAYOUT.load().preview)?)?;
-		plugin.call_method("seek", units)
+		plugin.call_method("seek", (units, lines))
 	});
 
 	let cmd: Cmd =
@@ -4,6 +4,7 @@ pub mod bindings;
 mod cast;
 pub mod cha;
 mod clipboard;
+mod command;
 mod config;
 pub mod elements;
 pub mod external;
@@ -13,24 +14,56 @@ pub mod isolate;
 pub mod loader;
 mod lua;
 mod macros;
+mod opener;
 mod opt;
 pub mod process;
 pub mod pubsub;
 mod runtime;
+mod sorter;
+mod timer;
 pub mod url;
 pub mod utils;
 
 pub use cast::*;
 pub use clipboard::*;
+pub use command::*;
 pub use config::*;
 pub use lua::*;
+pub use opener::*;
 pub use opt::*;
 pub use runtime::*;
+pub use sorter::*;
+pub use timer::*;
 
 pub fn init() -> anyhow::Result<()> {
 	CLIPBOARD.with(<_>::default);
 
 	crate::loader::init();
 	crate::init_lua()?;
+	yazi_fs::SORT_KEYER.init(|name, file| sort_key(&LUA, name, file));
 	Ok(())
 }
+
+// The events emitted so far, for tests on the functions that emit them, which
+// are run one at a time as they share the channel
+#[cfg(test)]
+pub(crate) async fn events()
+-> tokio::sync::MutexGuard<'static, tokio::sync::mpsc::UnboundedReceiver<yazi_shared::event::Event>>
+{
+	use std::sync::OnceLock;
+
+	use tokio::sync::{mpsc::UnboundedReceiver, Mutex};
+	use yazi_shared::event::Event;
+
+	static RX: OnceLock<Mutex<UnboundedReceiver<Event>>> = OnceLock::new();
+	let mut rx = RX
+		.get_or_init(|| {
+			Event::init();
+			Mutex::new(Event::take())
+		})
+		.lock()
+		.await;
+
+	while rx.try_recv().is_ok() {}
+	rx
+}
@@ -1,7 +1,7 @@
 use std::{borrow::Cow, collections::HashMap, ops::Deref};
 
 use anyhow::{Context, Result};
-use mlua::{ExternalError, Lua, Table};
+use mlua::{ExternalError, Lua, Table, Value};
 use parking_lot::RwLock;
 use tokio::fs;
 use yazi_boot::BOOT;
@@ -20,27 +20,16 @@ impl Loader {
 			return Ok(());
 		}
 
-		let preset = match name {
-			"archive" => &include_bytes!("../../preset/plugins/archive.lua")[..],
-			"code" => include_bytes!("../../preset/plugins/code.lua"),
-			"dds" => include_bytes!("../../preset/plugins/dds.lua"),
-			"empty" => include_bytes!("../../preset/plugins/empty.lua"),
-			"extract" => include_bytes!("../../preset/plugins/extract.lua"),
-			"file" => include_bytes!("../../preset/plugins/file.lua"),
-			"folder" => include_bytes!("../../preset/plugins/folder.lua"),
-			"font" => include_bytes!("../../preset/plugins/font.lua"),
-			"fzf" => include_bytes!("../../preset/plugins/fzf.lua"),
-			"image" => include_bytes!("../../preset/plugins/image.lua"),
-			"json" => include_bytes!("../../preset/plugins/json.lua"),
-			"magick" => include_bytes!("../../preset/plugins/magick.lua"),
-			"mime" => include_bytes!("../../preset/plugins/mime.lua"),
-			"noop" => include_bytes!("../../preset/plugins/noop.lua"),
-			"pdf" => include_bytes!("../../preset/plugins/pdf.lua"),
-			"session" => include_bytes!("../../preset/plugins/session.lua"),
-			"video" => include_bytes!("../../preset/plugins/video.lua"),
-			"zoxide" => include_bytes!("../../preset/plugins/zoxide.lua"),
-			_ => b"",
-		};
+		macro_rules! preset {
+			($($name:literal),*) => {
+				match name {
+					$($name => &include_bytes!(concat!("../../preset/plugins/", $name, ".lua"))[..],)*
+					_ => b"",
+				}
+			};
+		}
+
+		let preset = yazi_config::preset_plugins!(preset);
 
 		let b = if preset.is_empty() {
 			let p = BOOT.plugin_dir.join(format!("{name}.yazi/init.lua"));
@@ -70,6 +59,18 @@ impl Loader {
 	}
 }
 
+impl Loader {
+	// Drops plugin `id` along with the timers it set, so that it's loaded afresh
+	// the next time it's run
+	pub fn unload(&self, lua: &Lua, id: &str) -> mlua::Result<()> {
+		self.cache.write().remove(id);
+
+		let loaded: Table = lua.globals().raw_get::<_, Table>("package")?.raw_get("loaded")?;
+		loaded.raw_set(id, Value::Nil)?;
+		crate::cancel_timers(lua, id)
+	}
+}
+
 impl Deref for Loader {
 	type Target = RwLock<HashMap<String, Cow<'static, [u8]>>>;
 
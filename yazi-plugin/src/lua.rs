@@ -28,6 +28,8 @@ fn stage_1(lua: &'static Lua) -> Result<()> {
 	crate::loader::install(lua)?;
 	crate::pubsub::install(lua)?;
 	crate::cha::pour(lua)?;
+	crate::command::install(lua)?;
+	crate::timer::install(lua)?;
 	crate::file::pour(lua)?;
 	crate::url::pour(lua)?;
 
@@ -45,6 +47,10 @@ fn stage_1(lua: &'static Lua) -> Result<()> {
 	lua.load(include_str!("../preset/components/status.lua")).set_name("status.lua").exec()?;
 	lua.load(include_str!("../preset/components/tab.lua")).set_name("tab.lua").exec()?;
 
+	// Hooks
+	lua.load(include_str!("../preset/opener.lua")).set_name("opener.lua").exec()?;
+	lua.load(include_str!("../preset/sorter.lua")).set_name("sorter.lua").exec()?;
+
 	Ok(())
 }
 
@@ -58,3 +64,53 @@ fn stage_2(lua: &'static Lua) -> mlua::Result<()> {
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_readable_time() {
+		let lua = Lua::new();
+		lua.load(include_str!("../preset/ya.lua")).exec().unwrap();
+
+		let time = |secs: i64| -> String {
+			lua.load(format!("return ya.readable_time({secs})")).eval().unwrap()
+		};
+		assert_eq!(time(-3600), "just now");
+		assert_eq!(time(5), "just now");
+		assert_eq!(time(42), "42 seconds ago");
+		assert_eq!(time(60), "1 minute ago");
+		assert_eq!(time(7199), "1 hour ago");
+		assert_eq!(time(2 * 86400 + 1), "2 days ago");
+		assert_eq!(time(21 * 86400), "3 weeks ago");
+	}
+
+	#[test]
+	fn test_strip_extension() {
+		let lua = Lua::new();
+		lua.load(include_str!("../preset/ya.lua")).exec().unwrap();
+
+		let strip = |name: &str| -> String {
+			lua.load(format!("return ya.strip_extension({name:?})")).eval().unwrap()
+		};
+		assert_eq!(strip("main.rs"), "main");
+		assert_eq!(strip("archive.tar.gz"), "archive.tar");
+		assert_eq!(strip(".bashrc"), ".bashrc");
+		assert_eq!(strip(".config.json"), ".config");
+		assert_eq!(strip("Makefile"), "Makefile");
+		assert_eq!(strip("trailing."), "trailing.");
+	}
+
+	#[test]
+	fn test_readable_bytes() {
+		let lua = Lua::new();
+		lua.load(include_str!("../preset/ya.lua")).exec().unwrap();
+
+		let size = |expr: &str| -> String { lua.load(format!("return ya.{expr}")).eval().unwrap() };
+		assert_eq!(size("readable_bytes(0)"), "0");
+		assert_eq!(size("readable_bytes(999)"), "999");
+		assert_eq!(size("readable_bytes(1000)"), "1,000");
+		assert_eq!(size("readable_bytes(123456789)"), "123,456,789");
+	}
+}
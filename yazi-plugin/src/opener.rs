@@ -0,0 +1,59 @@
+use mlua::{Lua, Table, TableExt};
+use tracing::error;
+use yazi_shared::fs::File;
+
+use crate::{bindings::Cast, file};
+
+// Asks `Opener:pick()` for the name of the opener to open `file` with
+pub fn pick_opener(lua: &Lua, file: &File, mime: &str) -> Option<String> {
+	let f = || {
+		let opener = lua.globals().raw_get::<_, Table>("Opener")?;
+		opener.call_method::<_, Option<String>>("pick", (file::File::cast(lua, file.clone())?, mime))
+	};
+
+	match f() {
+		Ok(name) => name.filter(|s| !s.is_empty()),
+		Err(e) => {
+			error!("Failed to pick an opener for `{}`:\n{e}", file.url.display());
+			None
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_pick_opener() {
+		let lua = Lua::new();
+		crate::cha::pour(&lua).unwrap();
+		crate::file::pour(&lua).unwrap();
+		crate::url::pour(&lua).unwrap();
+
+		let file = |path: &str| File::from_dummy(path.into(), None);
+		lua.load(include_str!("../preset/opener.lua")).exec().unwrap();
+		assert_eq!(pick_opener(&lua, &file("/a.sh"), "text/plain"), None);
+
+		lua
+			.load(
+				r#"
+				function Opener:pick(file, mime)
+					if file.name:find("%.sh$") then
+						return "run"
+					elseif mime == "text/x-empty" then
+						return ""
+					end
+				end
+				"#,
+			)
+			.exec()
+			.unwrap();
+		assert_eq!(pick_opener(&lua, &file("/a.sh"), "text/plain").as_deref(), Some("run"));
+		assert_eq!(pick_opener(&lua, &file("/a.txt"), "text/plain"), None);
+		assert_eq!(pick_opener(&lua, &file("/b.txt"), "text/x-empty"), None);
+
+		lua.load("function Opener:pick() error('oops') end").exec().unwrap();
+		assert_eq!(pick_opener(&lua, &file("/a.sh"), "text/plain"), None);
+	}
+}
@@ -1,21 +1,30 @@
+use std::collections::HashMap;
+
 use anyhow::bail;
 use mlua::{Lua, Table};
+use yazi_dds::Sendable;
 use yazi_shared::event::{Cmd, Data};
 
 pub(super) type OptCallback = Box<dyn FnOnce(&Lua, Table) -> mlua::Result<()> + Send>;
 
 #[derive(Default)]
 pub struct Opt {
-	pub id:   String,
-	pub sync: bool,
-	pub args: Vec<Data>,
-	pub cb:   Option<OptCallback>,
+	pub id:     String,
+	pub sync:   bool,
+	pub reload: bool,
+	pub args:   Vec<Data>,
+	pub pairs:  HashMap<String, String>,
+	pub cb:     Option<OptCallback>,
 }
 
 impl TryFrom<Cmd> for Opt {
 	type Error = anyhow::Error;
 
 	fn try_from(mut c: Cmd) -> Result<Self, Self::Error> {
+		let pairs = match c.take_any("pairs") {
+			Some(pairs) => pairs,
+			None => c.take_pairs()?,
+		};
 		let Some(id) = c.take_first_str().filter(|s| !s.is_empty()) else {
 			bail!("plugin id cannot be empty");
 		};
@@ -26,14 +35,23 @@ impl TryFrom<Cmd> for Opt {
 			c.take_any::<Vec<Data>>("args").unwrap_or_default()
 		};
 
-		Ok(Self { id, sync: c.bool("sync"), args, cb: c.take_any("callback") })
+		Ok(Self {
+			id,
+			sync: c.bool("sync"),
+			reload: c.bool("reload"),
+			args,
+			pairs,
+			cb: c.take_any("callback"),
+		})
 	}
 }
 
 impl From<Opt> for Cmd {
 	fn from(value: Opt) -> Self {
-		let mut cmd =
-			Cmd::args("", &[value.id]).with_bool("sync", value.sync).with_any("args", value.args);
+		let mut cmd = Cmd::args("", &[value.id])
+			.with_bool("sync", value.sync)
+			.with_any("args", value.args)
+			.with_any("pairs", value.pairs);
 
 		if let Some(cb) = value.cb {
 			cmd = cmd.with_any("callback", cb);
@@ -41,3 +59,39 @@ impl From<Opt> for Cmd {
 		cmd
 	}
 }
+
+// The arguments of a plugin's `entry()`, with the positional ones in order, and
+// the `key=value` ones by their keys
+pub fn entry_args(
+	lua: &Lua,
+	args: Vec<Data>,
+	pairs: HashMap<String, String>,
+) -> mlua::Result<Table<'_>> {
+	let t = Sendable::list_to_table(lua, args)?;
+	for (k, v) in pairs {
+		t.raw_set(k, v)?;
+	}
+	Ok(t)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+
+	use super::*;
+
+	#[test]
+	fn test_pairs() {
+		let opt = Opt::try_from(Cmd::from_str("plugin foo key='a b' --args='x y'").unwrap()).unwrap();
+		assert_eq!(opt.id, "foo");
+		assert_eq!(opt.pairs["key"], "a b");
+
+		let lua = Lua::new();
+		let t = entry_args(&lua, opt.args, opt.pairs).unwrap();
+		assert_eq!(t.raw_get::<_, String>(1).unwrap(), "x");
+		assert_eq!(t.raw_get::<_, String>(2).unwrap(), "y");
+		assert_eq!(t.raw_get::<_, String>("key").unwrap(), "a b");
+
+		assert!(Opt::try_from(Cmd::from_str("plugin foo =1").unwrap()).is_err());
+	}
+}
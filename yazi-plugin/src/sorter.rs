@@ -0,0 +1,80 @@
+use mlua::{Function, Lua, Table, Value};
+use tracing::error;
+use yazi_fs::SortKey;
+use yazi_shared::fs::File;
+
+use crate::{bindings::Cast, file};
+
+// Gets the key of `file` from the sorter registered as `name`
+pub fn sort_key(lua: &Lua, name: &str, file: &File) -> Option<SortKey> {
+	let f = || -> mlua::Result<_> {
+		let keys = lua.globals().raw_get::<_, Table>("Sorter")?.raw_get::<_, Table>("_keys")?;
+		let Some(f) = keys.raw_get::<_, Option<Function>>(name)? else {
+			return Ok(None);
+		};
+
+		Ok(match f.call::<_, Value>(file::File::cast(lua, file.clone())?)? {
+			Value::Integer(n) => Some(SortKey::Number(n as f64)),
+			Value::Number(n) => Some(SortKey::Number(n)),
+			Value::String(s) => Some(SortKey::String(s.to_string_lossy().into_owned())),
+			_ => None,
+		})
+	};
+
+	match f() {
+		Ok(key) => key,
+		Err(e) => {
+			error!("Failed to get the `{name}` sort key of `{}`:\n{}", file.url.display(), e);
+			None
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use yazi_config::manager::SortBy;
+	use yazi_fs::{FilesSorter, SortKeys};
+	use yazi_shared::fs::Url;
+
+	use super::*;
+
+	#[test]
+	fn test_sort_key() {
+		let lua = Lua::new();
+		crate::cha::pour(&lua).unwrap();
+		crate::file::pour(&lua).unwrap();
+		crate::url::pour(&lua).unwrap();
+
+		lua.load(include_str!("../preset/sorter.lua")).exec().unwrap();
+		lua
+			.load(
+				r#"
+				Sorter:register("version", function(file)
+					local n = file.name:match("^v(%d+)")
+					if not n then
+						error("not versioned")
+					end
+					return tonumber(n)
+				end)
+				"#,
+			)
+			.exec()
+			.unwrap();
+
+		let names = ["v10.txt", "readme", "v9.txt", "v10.bin", "v1.txt"];
+		let mut files: Vec<_> = names.iter().map(|s| File::from_dummy(Url::from(*s), None)).collect();
+
+		let by = SortBy::Plugin("version".to_owned());
+		let mut keys = SortKeys::default();
+		keys.refresh("version", &files, |name, file| sort_key(&lua, name, file));
+
+		let sorter = FilesSorter { by, sensitive: true, ..Default::default() };
+		sorter.sort(&mut files, &HashMap::new(), &keys);
+		let sorted: Vec<_> = files.iter().map(|f| f.url.to_string_lossy().into_owned()).collect();
+		assert_eq!(sorted, ["v1.txt", "v9.txt", "v10.bin", "v10.txt", "readme"]);
+
+		assert_eq!(sort_key(&lua, "unknown", &files[0]), None);
+	}
+}
@@ -0,0 +1,227 @@
+use std::{collections::HashMap, time::Duration};
+
+use mlua::{ExternalError, Function, Lua, RegistryKey, Table, UserData, UserDataMethods, UserDataRefMut};
+use tokio::{select, time::{interval_at, Instant, MissedTickBehavior}};
+use tokio_util::sync::CancellationToken;
+use yazi_shared::{emit, event::Cmd, Layer};
+
+use crate::RtRef;
+
+// Shorter timers would keep the event loop from ever going idle
+const MIN_PERIOD: Duration = Duration::from_millis(10);
+
+// `ya.set_timeout(secs, fn)` calls `fn` once after `secs` seconds, and
+// `ya.set_interval(secs, fn)` every `secs` seconds, until `:cancel()` is called
+// on the handle they return, or the plugin that set them is unloaded. The
+// callbacks are run by the event loop between renders, like the other main
+// thread code is.
+pub(super) fn install(lua: &Lua) -> mlua::Result<()> {
+	lua.set_named_registry_value("timers", Timers::default())?;
+
+	let ya: Table = lua.globals().raw_get("ya")?;
+	ya.raw_set(
+		"set_timeout",
+		lua.create_function(|lua, (secs, f): (f64, Function)| Timers::add(lua, secs, f, false))?,
+	)?;
+	ya.raw_set(
+		"set_interval",
+		lua.create_function(|lua, (secs, f): (f64, Function)| Timers::add(lua, secs, f, true))?,
+	)?;
+	Ok(())
+}
+
+// Runs the callback of timer `id`, if it hasn't been cancelled, on behalf of
+// the plugin that set it, so the timers it sets in turn belong to that plugin
+pub fn fire_timer(lua: &Lua, id: usize) -> mlua::Result<()> {
+	let (f, owner): (Function, _) = {
+		let mut timers = lua.named_registry_value::<UserDataRefMut<Timers>>("timers")?;
+		let Some(timer) = timers.all.get(&id) else { return Ok(()) };
+
+		let (f, owner) = (lua.registry_value(&timer.key)?, timer.owner.clone());
+		if !timer.repeat {
+			timers.remove(lua, id)?;
+		}
+		(f, owner)
+	};
+
+	let Some(owner) = owner else { return f.call(()) };
+	lua.named_registry_value::<RtRef>("rt")?.push(&owner);
+	let result = f.call(());
+	lua.named_registry_value::<RtRef>("rt")?.pop();
+	result
+}
+
+// Cancels the timers set by plugin `id`, as it's being unloaded
+pub fn cancel_timers(lua: &Lua, id: &str) -> mlua::Result<()> {
+	let mut timers = lua.named_registry_value::<UserDataRefMut<Timers>>("timers")?;
+	let ids: Vec<_> =
+		timers.all.iter().filter(|(_, t)| t.owner.as_deref() == Some(id)).map(|(&id, _)| id).collect();
+	ids.into_iter().try_for_each(|id| timers.remove(lua, id))
+}
+
+#[derive(Default)]
+struct Timers {
+	incr: usize,
+	all:  HashMap<usize, Timer>,
+}
+
+struct Timer {
+	key:    RegistryKey,
+	repeat: bool,
+	ct:     CancellationToken,
+	// The plugin that set it, if any
+	owner:  Option<String>,
+}
+
+impl Timers {
+	fn add(lua: &Lua, secs: f64, f: Function, repeat: bool) -> mlua::Result<TimerHandle> {
+		if !secs.is_finite() || secs < 0.0 {
+			return Err("invalid timer duration".into_lua_err());
+		}
+
+		let owner =
+			lua.named_registry_value::<RtRef>("rt").ok().and_then(|rt| rt.current().map(Into::into));
+		let mut timers = lua.named_registry_value::<UserDataRefMut<Self>>("timers")?;
+		timers.incr += 1;
+
+		let (id, ct) = (timers.incr, CancellationToken::new());
+		let key = lua.create_registry_value(f)?;
+		timers.all.insert(id, Timer { key, repeat, ct: ct.clone(), owner });
+
+		every(secs, ct, move || {
+			emit!(Call(Cmd::args("timer", &[id]), Layer::App));
+			repeat
+		});
+		Ok(TimerHandle { id })
+	}
+
+	fn remove(&mut self, lua: &Lua, id: usize) -> mlua::Result<()> {
+		if let Some(timer) = self.all.remove(&id) {
+			timer.ct.cancel();
+			lua.remove_registry_value(timer.key)?;
+		}
+		Ok(())
+	}
+}
+
+// Calls `f` every `secs` seconds, but no more often than `MIN_PERIOD`, for as
+// long as it returns true and `ct` isn't cancelled. Ticks that are missed while
+// the event loop is busy are skipped, rather than piling up.
+pub(crate) fn every(
+	secs: f64,
+	ct: CancellationToken,
+	mut f: impl FnMut() -> bool + Send + 'static,
+) {
+	let period = Duration::from_secs_f64(secs).max(MIN_PERIOD);
+	tokio::spawn(async move {
+		let mut interval = interval_at(Instant::now() + period, period);
+		interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+		loop {
+			select! {
+				_ = ct.cancelled() => break,
+				_ = interval.tick() => if !f() { break },
+			}
+		}
+	});
+}
+
+// The timers are stopped along with the Lua state they were set in
+impl Drop for Timers {
+	fn drop(&mut self) { self.all.values().for_each(|t| t.ct.cancel()); }
+}
+
+impl UserData for Timers {}
+
+struct TimerHandle {
+	id: usize,
+}
+
+impl UserData for TimerHandle {
+	fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+		methods.add_method("cancel", |lua, me, ()| {
+			lua.named_registry_value::<UserDataRefMut<Timers>>("timers")?.remove(lua, me.id)
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+
+	use yazi_shared::event::{Data, Event};
+
+	use super::*;
+
+	#[tokio::test(start_paused = true)]
+	async fn test_interval() {
+		let mut rx = crate::events().await;
+
+		let lua = Lua::new();
+		lua.globals().raw_set("ya", lua.create_table().unwrap()).unwrap();
+		lua.set_named_registry_value("rt", crate::runtime::Runtime::new("init")).unwrap();
+		install(&lua).unwrap();
+
+		lua
+			.load(
+				r#"
+				ticks, once = 0, 0
+				handle = ya.set_interval(0.02, function() ticks = ticks + 1 end)
+				ya.set_timeout(0.01, function() once = once + 1 end)
+				"#,
+			)
+			.exec()
+			.unwrap();
+
+		let fire = |rx: &mut tokio::sync::mpsc::UnboundedReceiver<Event>| {
+			while let Ok(Event::Call(cmd, _)) = rx.try_recv() {
+				fire_timer(&lua, cmd.first().and_then(Data::as_usize).unwrap()).unwrap();
+			}
+		};
+		let get = |name: &str| lua.globals().raw_get::<_, usize>(name).unwrap();
+
+		// Ticks at 20, 40, 60, 80 and 100 ms
+		tokio::time::sleep(Duration::from_millis(110)).await;
+		fire(&mut rx);
+		assert_eq!((get("ticks"), get("once")), (5, 1));
+
+		lua.load("handle:cancel()").exec().unwrap();
+		tokio::time::sleep(Duration::from_millis(60)).await;
+		fire(&mut rx);
+		assert_eq!(get("ticks"), 5);
+
+		// The timers of a plugin are cancelled once it's unloaded, including the ones
+		// set from the callbacks of its timers
+		lua.named_registry_value::<RtRef>("rt").unwrap().push("clock");
+		lua
+			.load(
+				"ya.set_timeout(0.01, function() ya.set_interval(0.01, function() ticks = ticks + 1 end) end)",
+			)
+			.exec()
+			.unwrap();
+		lua.named_registry_value::<RtRef>("rt").unwrap().pop();
+
+		tokio::time::sleep(Duration::from_millis(15)).await;
+		fire(&mut rx);
+		tokio::time::sleep(Duration::from_millis(15)).await;
+		fire(&mut rx);
+		assert_eq!(get("ticks"), 6);
+
+		cancel_timers(&lua, "clock").unwrap();
+		tokio::time::sleep(Duration::from_millis(30)).await;
+		fire(&mut rx);
+		assert_eq!(get("ticks"), 6);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_every() {
+		// A period too short to be represented is held to `MIN_PERIOD`, rather than
+		// making the interval panic
+		let (n, ct) = (Arc::new(AtomicUsize::new(0)), CancellationToken::new());
+		let n_ = n.clone();
+		every(1e-10, ct.clone(), move || n_.fetch_add(1, Ordering::Relaxed) < 2);
+
+		tokio::time::sleep(MIN_PERIOD * 5).await;
+		assert_eq!(n.load(Ordering::Relaxed), 3);
+		ct.cancel();
+	}
+}
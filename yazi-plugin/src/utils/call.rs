@@ -1,13 +1,18 @@
 use std::{collections::HashMap, sync::Arc};
 
 use mlua::{ExternalError, Lua, Table, TableExt, Value};
+use parking_lot::{const_mutex, Mutex};
+use tokio_util::sync::CancellationToken;
 use tracing::error;
 use yazi_config::LAYOUT;
 use yazi_dds::Sendable;
 use yazi_shared::{emit, event::{Cmd, Data}, render, Layer};
 
 use super::Utils;
-use crate::elements::RectRef;
+use crate::{elements::RectRef, timer};
+
+// The shortest interval requested by `ya.render_every()`, and its ticker
+static TICKER: Mutex<Option<(f64, CancellationToken)>> = const_mutex(None);
 
 impl Utils {
 	fn parse_args(t: Table) -> mlua::Result<HashMap<String, Data>> {
@@ -36,6 +41,30 @@ impl Utils {
 			})?,
 		)?;
 
+		ya.raw_set(
+			"render_every",
+			lua.create_function(|_, secs: f64| {
+				if !secs.is_finite() || secs <= 0.0 {
+					return Err("render interval must be a positive finite number".into_lua_err());
+				}
+
+				let mut ticker = TICKER.lock();
+				if ticker.as_ref().is_some_and(|&(s, _)| s <= secs) {
+					return Ok(());
+				}
+
+				let ct = CancellationToken::new();
+				timer::every(secs, ct.clone(), || {
+					emit!(Render);
+					true
+				});
+				if let Some((_, old)) = ticker.replace((secs, ct)) {
+					old.cancel();
+				}
+				Ok(())
+			})?,
+		)?;
+
 		ya.raw_set(
 			"render_with",
 			lua.create_function(|lua, c: Table| {
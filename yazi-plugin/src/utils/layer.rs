@@ -40,6 +40,7 @@ impl Utils {
 						on:   Self::parse_keys(cand.raw_get("on")?)?,
 						run:  vec![Cmd::args("callback", &[i]).with_any("tx", tx.clone())],
 						desc: cand.raw_get("desc").ok(),
+						..Default::default()
 					});
 				}
 
@@ -67,7 +68,10 @@ impl Utils {
 					position: Position::try_from(t.raw_get::<_, Table>("position")?)?.into(),
 					realtime,
 					completion: false,
+					eager: false,
 					highlight: false,
+					history: None,
+					deferred: false,
 				}));
 
 				if !realtime {
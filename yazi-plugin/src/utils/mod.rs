@@ -8,6 +8,7 @@ mod layer;
 mod log;
 mod preview;
 mod sync;
+mod tab;
 mod target;
 mod text;
 mod time;
@@ -1,9 +1,10 @@
 use mlua::{AnyUserData, IntoLuaMulti, Lua, Table, Value};
+use ratatui::{layout::{Alignment, Rect}, style::Stylize, text::Line};
 use yazi_config::{preview::PreviewWrap, PREVIEW};
 use yazi_shared::{emit, event::Cmd, Layer, PeekError};
 
 use super::Utils;
-use crate::{bindings::Window, cast_to_renderable, elements::{Paragraph, RectRef, Renderable, WRAP, WRAP_NO}, external::Highlighter, file::FileRef};
+use crate::{bindings::Window, cast_to_renderable, elements::{Paragraph, RectRef, Renderable}, external::{wrap_line, Highlighter, TextStats}, file::FileRef, url::UrlRef};
 
 pub struct PreviewLock {
 	pub url: yazi_shared::fs::Url,
@@ -34,23 +35,45 @@ impl Utils {
 		ya.raw_set(
 			"preview_code",
 			lua.create_async_function(|lua, t: Table| async move {
-				let area: RectRef = t.raw_get("area")?;
+				let mut area = *t.raw_get::<_, RectRef>("area")?;
+				let source = t.raw_get::<_, Option<UrlRef>>("source")?.map(|u| u.to_path_buf());
+				let stats = t.raw_get::<_, Option<bool>>("stats")?.unwrap_or(false);
+				let wrap =
+					t.raw_get::<_, Option<bool>>("wrap")?.unwrap_or(PREVIEW.wrap == PreviewWrap::Yes);
 				let mut lock = PreviewLock::try_from(t)?;
 
-				let text = match Highlighter::new(&lock.url).highlight(lock.skip, *area).await {
+				// The counts take the bottom line, below the text
+				let footer = (stats && area.height > 1).then(|| {
+					area.height -= 1;
+					Rect { y: area.bottom(), height: 1, ..area }
+				});
+
+				let path = source.as_deref().unwrap_or(&lock.url);
+				let text = match Highlighter::new(path, wrap).highlight(lock.skip, area).await {
 					Ok(text) => text,
 					Err(e @ PeekError::Exceed(max)) => return (e.to_string(), max).into_lua_multi(lua),
 					Err(e @ PeekError::Unexpected(_)) => {
 						return (e.to_string(), Value::Nil).into_lua_multi(lua);
 					}
 				};
+				let text = if wrap {
+					text.lines.into_iter().flat_map(|l| wrap_line(l, area.width)).collect()
+				} else {
+					text
+				};
 
-				lock.data = vec![Box::new(Paragraph {
-					area: *area,
-					text,
-					wrap: if PREVIEW.wrap == PreviewWrap::Yes { WRAP } else { WRAP_NO },
-					..Default::default()
-				})];
+				lock.data = vec![Box::new(Paragraph { area, text, ..Default::default() })];
+
+				if let Some(area) = footer {
+					if let Ok(stats) = TextStats::of(path).await {
+						lock.data.push(Box::new(Paragraph {
+							area,
+							text: Line::from(stats.to_string()).dim().into(),
+							alignment: Alignment::Right,
+							..Default::default()
+						}));
+					}
+				}
 
 				emit!(Call(Cmd::new("preview").with_any("lock", lock), Layer::Manager));
 				(Value::Nil, Value::Nil).into_lua_multi(lua)
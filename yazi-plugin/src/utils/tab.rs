@@ -0,0 +1,94 @@
+use mlua::{ExternalError, Lua, Table};
+use yazi_shared::{emit, event::Cmd, Layer};
+
+use super::Utils;
+use crate::url::UrlRef;
+
+impl Utils {
+	// Tab indices are 1-based, the same as `cx.tabs`, which is where the
+	// current tabs can be queried from a sync context.
+	pub(super) fn tab(lua: &Lua, ya: &Table) -> mlua::Result<()> {
+		ya.raw_set(
+			"tab_create",
+			lua.create_function(|_, url: UrlRef| {
+				match std::fs::metadata(&*url) {
+					Ok(m) if m.is_dir() => {}
+					Ok(_) => return Err(format!("`{}` is not a directory", url.display()).into_lua_err()),
+					Err(e) => return Err(format!("Cannot open `{}`: {e}", url.display()).into_lua_err()),
+				}
+
+				emit!(Call(Cmd::args("tab_create", &[&*url]), Layer::Manager));
+				Ok(())
+			})?,
+		)?;
+
+		ya.raw_set(
+			"tab_close",
+			lua.create_function(|_, idx: usize| {
+				if idx == 0 {
+					return Err("Tab index must start from 1".into_lua_err());
+				}
+
+				emit!(Call(Cmd::args("tab_close", &[idx - 1]), Layer::Manager));
+				Ok(())
+			})?,
+		)?;
+
+		ya.raw_set(
+			"tab_switch",
+			lua.create_function(|_, idx: usize| {
+				if idx == 0 {
+					return Err("Tab index must start from 1".into_lua_err());
+				}
+
+				emit!(Call(Cmd::args("tab_switch", &[idx - 1]), Layer::Manager));
+				Ok(())
+			})?,
+		)?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use yazi_shared::event::Event;
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_tab() {
+		let mut rx = crate::events().await;
+
+		let lua = Lua::new();
+		crate::url::pour(&lua).unwrap();
+		let ya = lua.create_table().unwrap();
+		Utils::tab(&lua, &ya).unwrap();
+		lua.globals().raw_set("ya", ya).unwrap();
+
+		let tmp = tempfile::tempdir().unwrap();
+		let (dir, file) = (tmp.path(), tmp.path().join("file"));
+		std::fs::write(&file, "").unwrap();
+
+		lua.globals().raw_set("dir", dir.to_str().unwrap()).unwrap();
+		lua.globals().raw_set("file", file.to_str().unwrap()).unwrap();
+		lua.load("ya.tab_create(Url(dir)); ya.tab_switch(2); ya.tab_close(1)").exec().unwrap();
+
+		let cmds: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok())
+			.filter_map(|e| match e {
+				Event::Call(cmd, Layer::Manager) => Some((cmd.name.clone(), cmd.str("0")?.to_owned())),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(cmds, [
+			("tab_create".to_owned(), dir.to_string_lossy().into_owned()),
+			("tab_switch".to_owned(), "1".to_owned()),
+			("tab_close".to_owned(), "0".to_owned()),
+		]);
+
+		for code in ["ya.tab_create(Url(file))", "ya.tab_switch(0)", "ya.tab_close(0)"] {
+			assert!(lua.load(code).exec().is_err(), "{code}");
+		}
+		assert!(rx.try_recv().is_err());
+	}
+}
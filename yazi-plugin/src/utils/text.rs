@@ -28,6 +28,13 @@ impl Utils {
 			})?,
 		)?;
 
+		ya.raw_set(
+			"readable_size",
+			lua.create_function(|_, (size, base, precision): (f64, Option<u16>, Option<usize>)| {
+				Ok(yazi_shared::readable_size_in(size, base.unwrap_or(1024), precision.unwrap_or(1)))
+			})?,
+		)?;
+
 		ya.raw_set(
 			"truncate",
 			lua.create_function(|_, (text, t): (mlua::String, Table)| {
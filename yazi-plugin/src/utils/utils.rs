@@ -14,6 +14,7 @@ pub fn install(lua: &'static mlua::Lua) -> mlua::Result<()> {
 	Utils::log(lua, &ya)?;
 	Utils::preview(lua, &ya)?;
 	Utils::sync(lua, &ya)?;
+	Utils::tab(lua, &ya)?;
 	Utils::target(lua, &ya)?;
 	Utils::text(lua, &ya)?;
 	Utils::time(lua, &ya)?;
@@ -33,6 +34,7 @@ pub fn install_isolate(lua: &mlua::Lua) -> mlua::Result<()> {
 	Utils::log(lua, &ya)?;
 	Utils::preview(lua, &ya)?;
 	Utils::sync_isolate(lua, &ya)?;
+	Utils::tab(lua, &ya)?;
 	Utils::target(lua, &ya)?;
 	Utils::text(lua, &ya)?;
 	Utils::time(lua, &ya)?;
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use yazi_shared::{emit, event::Cmd, Layer};
 
 pub struct CompletionProxy;
@@ -12,4 +14,33 @@ impl CompletionProxy {
 	pub fn trigger(word: &str, ticket: usize) {
 		emit!(Call(Cmd::args("trigger", &[word]).with("ticket", ticket), Layer::Completion));
 	}
+
+	#[inline]
+	pub fn trigger_cached(ticket: usize) {
+		emit!(Call(
+			Cmd::new("trigger").with("ticket", ticket).with_bool("cached", true),
+			Layer::Completion
+		));
+	}
+
+	#[inline]
+	pub fn show(cache: Arc<Vec<String>>, cache_name: &str, word: &str, ticket: usize, whole: bool) {
+		emit!(Call(
+			Cmd::new("show")
+				.with_any("cache", cache)
+				.with("cache-name", cache_name)
+				.with("word", word)
+				.with("ticket", ticket)
+				.with_bool("whole", whole),
+			Layer::Completion
+		));
+	}
+
+	#[inline]
+	pub fn trigger_command(before: &str, ticket: usize) {
+		emit!(Call(
+			Cmd::args("trigger", &[before]).with("ticket", ticket).with_bool("command", true),
+			Layer::Completion
+		));
+	}
 }
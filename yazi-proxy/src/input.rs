@@ -13,7 +13,15 @@ impl InputProxy {
 	}
 
 	#[inline]
-	pub fn complete(word: &str, ticket: usize) {
-		emit!(Call(Cmd::args("complete", &[word]).with("ticket", ticket), Layer::Input));
+	pub fn remember(kind: &str, value: &str) {
+		emit!(Call(Cmd::args("remember", &[value]).with("kind", kind), Layer::Input));
+	}
+
+	#[inline]
+	pub fn complete(word: &str, ticket: usize, whole: bool) {
+		emit!(Call(
+			Cmd::args("complete", &[word]).with("ticket", ticket).with_bool("whole", whole),
+			Layer::Input
+		));
 	}
 }
@@ -1,4 +1,4 @@
-use yazi_shared::{emit, event::Cmd, fs::Url, Layer};
+use yazi_shared::{emit, event::{Cmd, Data}, fs::{DiskSpace, Url}, Layer};
 
 use crate::options::OpenDoOpt;
 
@@ -10,6 +10,13 @@ impl ManagerProxy {
 		emit!(Call(Cmd::new("peek").with_bool("force", force), Layer::Manager));
 	}
 
+	#[inline]
+	pub fn peek_next(url: &Url, rule: usize) {
+		let mut cmd = Cmd::new("peek").with_bool("force", true).with("rule", rule);
+		cmd.args.insert("only-if".to_owned(), Data::Url(url.clone()));
+		emit!(Call(cmd, Layer::Manager));
+	}
+
 	#[inline]
 	pub fn hover(url: Option<Url>, tab: usize) {
 		emit!(Call(
@@ -18,6 +25,36 @@ impl ManagerProxy {
 		));
 	}
 
+	#[inline]
+	pub fn open_hovered(interactive: bool) {
+		emit!(Call(
+			Cmd::new("open").with_bool("hovered", true).with_bool("interactive", interactive),
+			Layer::Manager
+		));
+	}
+
+	#[inline]
+	pub fn update_recents(urls: Vec<Url>) {
+		emit!(Call(Cmd::new("update_recents").with_any("urls", urls), Layer::Manager));
+	}
+
+	#[inline]
+	pub fn update_disk(space: Option<DiskSpace>) {
+		let mut cmd = Cmd::new("update_disk");
+		if let Some(space) = space {
+			cmd = cmd.with_any("space", space);
+		}
+		emit!(Call(cmd, Layer::Manager));
+	}
+
+	#[inline]
+	pub fn open_at(line: usize, column: usize) {
+		emit!(Call(
+			Cmd::new("open").with_bool("hovered", true).with("line", line).with("column", column),
+			Layer::Manager
+		));
+	}
+
 	#[inline]
 	pub fn refresh() {
 		emit!(Call(Cmd::new("refresh"), Layer::Manager));
@@ -36,11 +73,27 @@ impl ManagerProxy {
 		));
 	}
 
+	#[inline]
+	pub fn move_do(targets: Vec<Url>, dest: Url, force: bool) {
+		emit!(Call(
+			Cmd::new("move_do")
+				.with_bool("force", force)
+				.with_any("targets", targets)
+				.with_any("dest", dest),
+			Layer::Manager
+		));
+	}
+
 	#[inline]
 	pub fn update_task(url: &Url) {
 		emit!(Call(Cmd::new("update_task").with_any("url", url.clone()), Layer::Manager));
 	}
 
+	#[inline]
+	pub fn update_log(text: impl ToString) {
+		emit!(Call(Cmd::args("update_log", &[text]), Layer::Manager));
+	}
+
 	#[inline]
 	pub fn update_paged() {
 		emit!(Call(Cmd::new("update_paged"), Layer::Manager));
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+use tokio::sync::oneshot;
 use yazi_config::open::Opener;
 use yazi_shared::{event::Cmd, fs::Url};
 
@@ -9,6 +10,10 @@ pub struct OpenDoOpt {
 	pub hovered:     Url,
 	pub targets:     Vec<(Url, String)>,
 	pub interactive: bool,
+	pub which:       bool,
+	pub line:        Option<usize>,
+	pub column:      Option<usize>,
+	pub spread:      Option<bool>,
 }
 
 impl From<Cmd> for OpenDoOpt {
@@ -19,6 +24,10 @@ impl From<Cmd> for OpenDoOpt {
 pub struct OpenWithOpt {
 	pub targets: Vec<Url>,
 	pub opener:  Cow<'static, Opener>,
+	// Whether the targets but the first become recent ones once opened
+	pub recents: bool,
+	// Told once the first process has started
+	pub spawned: Option<oneshot::Sender<()>>,
 }
 
 impl TryFrom<Cmd> for OpenWithOpt {
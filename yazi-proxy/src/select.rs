@@ -11,4 +11,14 @@ impl SelectProxy {
 		emit!(Call(Cmd::new("show").with_any("tx", tx).with_any("cfg", cfg), Layer::Select));
 		rx.await?
 	}
+
+	#[inline]
+	pub fn close() {
+		emit!(Call(Cmd::new("close"), Layer::Select));
+	}
+
+	#[inline]
+	pub fn extend(items: Vec<String>) {
+		emit!(Call(Cmd::new("extend").with_any("items", items), Layer::Select));
+	}
 }
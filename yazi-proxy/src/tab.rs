@@ -10,15 +10,31 @@ impl TabProxy {
 		emit!(Call(Cmd::args("cd", &[target]), Layer::Manager));
 	}
 
+	#[inline]
+	pub fn cd_in(target: &Url, tab: usize) {
+		emit!(Call(Cmd::args("cd", &[target]).with("tab", tab), Layer::Manager));
+	}
+
 	#[inline]
 	pub fn reveal(target: &Url) {
 		emit!(Call(Cmd::args("reveal", &[target]), Layer::Manager));
 	}
 
 	#[inline]
-	pub fn search_do(opt: SearchOpt) {
+	pub fn reveal_at(target: &Url, line: usize, column: usize) {
+		emit!(Call(
+			Cmd::args("reveal", &[target]).with("line", line).with("column", column),
+			Layer::Manager
+		));
+	}
+
+	#[inline]
+	pub fn search_do(opt: SearchOpt, tab: usize) {
 		emit!(Call(
-			Cmd::args("search_do", &[opt.subject]).with("via", opt.via).with("args", opt.args_raw),
+			Cmd::args("search_do", &[opt.subject])
+				.with("via", opt.via)
+				.with("args", opt.args_raw)
+				.with("tab", tab),
 			Layer::Manager
 		));
 	}
@@ -12,11 +12,44 @@ impl TasksProxy {
 	#[inline]
 	pub fn open_with(targets: Vec<Url>, opener: Cow<'static, Opener>) {
 		emit!(Call(
-			Cmd::new("open_with").with_any("option", OpenWithOpt { targets, opener }),
+			Cmd::new("open_with").with_any("option", OpenWithOpt {
+				targets,
+				opener,
+				recents: false,
+				spawned: None
+			}),
 			Layer::Tasks
 		));
 	}
 
+	#[inline]
+	pub fn open_with_recents(targets: Vec<Url>, opener: Cow<'static, Opener>) {
+		emit!(Call(
+			Cmd::new("open_with").with_any("option", OpenWithOpt {
+				targets,
+				opener,
+				recents: true,
+				spawned: None
+			}),
+			Layer::Tasks
+		));
+	}
+
+	#[inline]
+	pub async fn open_with_spawned(targets: Vec<Url>, opener: Cow<'static, Opener>) -> bool {
+		let (tx, rx) = oneshot::channel();
+		emit!(Call(
+			Cmd::new("open_with").with_any("option", OpenWithOpt {
+				targets,
+				opener,
+				recents: false,
+				spawned: Some(tx)
+			}),
+			Layer::Tasks
+		));
+		rx.await.is_ok()
+	}
+
 	#[inline]
 	pub async fn process_exec(args: Vec<OsString>, opener: Cow<'static, Opener>) {
 		let (tx, rx) = oneshot::channel();
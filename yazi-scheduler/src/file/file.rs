@@ -4,9 +4,10 @@ use anyhow::{anyhow, Result};
 use tokio::{fs, io::{self, ErrorKind::{AlreadyExists, NotFound}}, sync::mpsc};
 use tracing::warn;
 use yazi_config::TASKS;
-use yazi_shared::fs::{calculate_size, copy_with_progress, maybe_exists, ok_or_not_found, path_relative_to, Url};
+use yazi_fs::FsError;
+use yazi_shared::fs::{calculate_size, copy_attrs, copy_with_progress, maybe_exists, ok_or_not_found, path_relative_to, Url};
 
-use super::{FileOp, FileOpDelete, FileOpHardlink, FileOpLink, FileOpPaste, FileOpTrash};
+use super::{trash::{restore, trash}, FileOp, FileOpDelete, FileOpHardlink, FileOpLink, FileOpPaste, FileOpRestore, FileOpTrash};
 use crate::{TaskOp, TaskProg, LOW, NORMAL};
 
 pub struct File {
@@ -25,8 +26,9 @@ impl File {
 	pub async fn work(&self, op: FileOp) -> Result<()> {
 		match op {
 			FileOp::Paste(mut task) => {
-				ok_or_not_found(fs::remove_file(&task.to).await)?;
-				let mut it = copy_with_progress(&task.from, &task.to, task.meta.as_ref().unwrap());
+				ok_or_not_found(fs::remove_file(&task.to).await).map_err(|e| FsError::new(&task.to, e))?;
+				let mut it =
+					copy_with_progress(&task.from, &task.to, task.meta.as_ref().unwrap(), task.times);
 
 				while let Some(res) = it.recv().await {
 					match res {
@@ -34,6 +36,9 @@ impl File {
 							if task.cut {
 								fs::remove_file(&task.from).await.ok();
 							}
+							if task.archive {
+								self.copy_attrs(task.id, &task.to, task.meta.as_ref().unwrap())?;
+							}
 							break;
 						}
 						Ok(n) => self.prog.send(TaskProg::Adv(task.id, 0, n))?,
@@ -52,7 +57,7 @@ impl File {
 							self.queue(FileOp::Paste(task), LOW).await?;
 							return Ok(());
 						}
-						Err(e) => Err(e)?,
+						Err(e) => Err(FsError::new(&task.from, e))?,
 					}
 				}
 				self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
@@ -67,35 +72,39 @@ impl File {
 							warn!("Link task partially done: {task:?}");
 							return Ok(self.prog.send(TaskProg::Adv(task.id, 1, meta.len()))?);
 						}
-						Err(e) => Err(e)?,
+						Err(e) => Err(FsError::new(&task.from, e))?,
 					}
 				} else {
 					Cow::Borrowed(task.from.as_path())
 				};
 
 				let src = if task.relative {
-					path_relative_to(&src, &fs::canonicalize(task.to.parent().unwrap()).await?)
+					let parent = fs::canonicalize(task.to.parent().unwrap()).await;
+					path_relative_to(&src, &parent.map_err(|e| FsError::new(&task.to, e))?)
 				} else {
 					src
 				};
 
-				ok_or_not_found(fs::remove_file(&task.to).await)?;
+				ok_or_not_found(fs::remove_file(&task.to).await).map_err(|e| FsError::new(&task.to, e))?;
 				#[cfg(unix)]
 				{
-					fs::symlink(src, &task.to).await?;
+					fs::symlink(src, &task.to).await.map_err(|e| FsError::new(&task.to, e))?;
 				}
 				#[cfg(windows)]
 				{
 					if meta.is_dir() {
-						fs::symlink_dir(src, &task.to).await?;
+						fs::symlink_dir(src, &task.to).await.map_err(|e| FsError::new(&task.to, e))?;
 					} else {
-						fs::symlink_file(src, &task.to).await?;
+						fs::symlink_file(src, &task.to).await.map_err(|e| FsError::new(&task.to, e))?;
 					}
 				}
 
 				if task.delete {
 					fs::remove_file(&task.from).await.ok();
 				}
+				if task.archive {
+					self.copy_attrs(task.id, &task.to, meta)?;
+				}
 				self.prog.send(TaskProg::Adv(task.id, 1, meta.len()))?;
 			}
 			FileOp::Hardlink(task) => {
@@ -108,12 +117,13 @@ impl File {
 					Cow::Borrowed(task.from.as_path())
 				};
 
-				ok_or_not_found(fs::remove_file(&task.to).await)?;
+				ok_or_not_found(fs::remove_file(&task.to).await).map_err(|e| FsError::new(&task.to, e))?;
 				match fs::hard_link(src, &task.to).await {
 					Err(e) if e.kind() == NotFound => {
 						warn!("Hardlink task partially done: {task:?}");
 					}
-					v => v?,
+					Err(e) => Err(FsError::new(&task.to, e))?,
+					Ok(()) => {}
 				}
 
 				self.prog.send(TaskProg::Adv(task.id, 1, meta.len()))?;
@@ -121,28 +131,15 @@ impl File {
 			FileOp::Delete(task) => {
 				if let Err(e) = fs::remove_file(&task.target).await {
 					if e.kind() != NotFound && maybe_exists(&task.target).await {
-						self.fail(task.id, format!("Delete task failed: {:?}, {e}", task))?;
+						let e = FsError::new(&task.target, e);
+						self.fail(task.id, format!("Delete task failed: {e}"))?;
 						Err(e)?
 					}
 				}
 				self.prog.send(TaskProg::Adv(task.id, 1, task.length))?
 			}
 			FileOp::Trash(task) => {
-				tokio::task::spawn_blocking(move || {
-					#[cfg(target_os = "macos")]
-					{
-						use trash::{macos::{DeleteMethod, TrashContextExtMacos}, TrashContext};
-						let mut ctx = TrashContext::default();
-						ctx.set_delete_method(DeleteMethod::NsFileManager);
-						ctx.delete(&task.target)?;
-					}
-					#[cfg(all(not(target_os = "macos"), not(target_os = "android")))]
-					{
-						trash::delete(&task.target)?;
-					}
-					Ok::<_, anyhow::Error>(())
-				})
-				.await??;
+				trash(&task.target, TASKS.trash, &TASKS.trash_run).await?;
 				self.prog.send(TaskProg::Adv(task.id, 1, task.length))?;
 			}
 		}
@@ -150,8 +147,23 @@ impl File {
 	}
 
 	pub async fn paste(&self, mut task: FileOpPaste) -> Result<()> {
-		if task.cut && ok_or_not_found(fs::rename(&task.from, &task.to).await).is_ok() {
-			return self.succ(task.id);
+		if task.cut {
+			match fs::rename(&task.from, &task.to).await.map_err(FsError::from) {
+				Ok(()) => return self.succ(task.id),
+				// Moved across filesystems by copying it, and deleting it afterwards
+				Err(FsError::CrossDevice(_)) => {}
+				Err(FsError::NotFound(_)) if !maybe_exists(&task.from).await => {
+					warn!("Cut task partially done: {task:?}");
+					return self.succ(task.id);
+				}
+				// Copying wouldn't get any further, or would leave the source behind
+				Err(e) => {
+					let e = e.with_url(&task.to);
+					self.prog.send(TaskProg::New(task.id, 0))?;
+					self.fail(task.id, format!("Cut task failed: {e}"))?;
+					Err(e)?
+				}
+			}
 		}
 
 		if task.meta.is_none() {
@@ -172,11 +184,12 @@ impl File {
 		}
 
 		macro_rules! continue_unless_ok {
-			($result:expr) => {
+			($result:expr, $url:expr) => {
 				match $result {
 					Ok(v) => v,
 					Err(e) => {
 						self.prog.send(TaskProg::New(task.id, 0))?;
+						let e = FsError::new($url, e);
 						self.fail(task.id, format!("An error occurred while pasting: {e}"))?;
 						continue;
 					}
@@ -190,15 +203,18 @@ impl File {
 
 		while let Some(src) = dirs.pop_front() {
 			let dest = root.join(src.components().skip(skip).collect::<PathBuf>());
-			continue_unless_ok!(match fs::create_dir(&dest).await {
-				Err(e) if e.kind() != AlreadyExists => Err(e),
-				_ => Ok(()),
-			});
-
-			let mut it = continue_unless_ok!(fs::read_dir(&src).await);
+			continue_unless_ok!(
+				match fs::create_dir(&dest).await {
+					Err(e) if e.kind() != AlreadyExists => Err(e),
+					_ => Ok(()),
+				},
+				&dest
+			);
+
+			let mut it = continue_unless_ok!(fs::read_dir(&src).await, &src);
 			while let Ok(Some(entry)) = it.next_entry().await {
 				let from = Url::from(entry.path());
-				let meta = continue_unless_ok!(Self::metadata(&from, task.follow).await);
+				let meta = continue_unless_ok!(Self::metadata(&from, task.follow).await, &from);
 
 				if meta.is_dir() {
 					dirs.push_back(from);
@@ -243,11 +259,12 @@ impl File {
 		}
 
 		macro_rules! continue_unless_ok {
-			($result:expr) => {
+			($result:expr, $url:expr) => {
 				match $result {
 					Ok(v) => v,
 					Err(e) => {
 						self.prog.send(TaskProg::New(task.id, 0))?;
+						let e = FsError::new($url, e);
 						self.fail(task.id, format!("An error occurred while hardlinking: {e}"))?;
 						continue;
 					}
@@ -261,15 +278,18 @@ impl File {
 
 		while let Some(src) = dirs.pop_front() {
 			let dest = root.join(src.components().skip(skip).collect::<PathBuf>());
-			continue_unless_ok!(match fs::create_dir(&dest).await {
-				Err(e) if e.kind() != AlreadyExists => Err(e),
-				_ => Ok(()),
-			});
-
-			let mut it = continue_unless_ok!(fs::read_dir(&src).await);
+			continue_unless_ok!(
+				match fs::create_dir(&dest).await {
+					Err(e) if e.kind() != AlreadyExists => Err(e),
+					_ => Ok(()),
+				},
+				&dest
+			);
+
+			let mut it = continue_unless_ok!(fs::read_dir(&src).await, &src);
 			while let Ok(Some(entry)) = it.next_entry().await {
 				let from = Url::from(entry.path());
-				let meta = continue_unless_ok!(Self::metadata(&from, task.follow).await);
+				let meta = continue_unless_ok!(Self::metadata(&from, task.follow).await, &from);
 
 				if meta.is_dir() {
 					dirs.push_back(from);
@@ -324,6 +344,18 @@ impl File {
 		self.succ(id)
 	}
 
+	pub async fn restore(&self, task: FileOpRestore) -> Result<()> {
+		self.prog.send(TaskProg::New(task.id, 0))?;
+
+		if let Err(e) = restore(task.targets, task.backend).await {
+			self.fail(task.id, format!("Restore task failed: {e}"))?;
+			return Err(e);
+		}
+
+		self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
+		self.succ(task.id)
+	}
+
 	#[inline]
 	async fn metadata(path: &Path, follow: bool) -> io::Result<Metadata> {
 		if !follow {
@@ -336,6 +368,14 @@ impl File {
 }
 
 impl File {
+	fn copy_attrs(&self, id: usize, to: &Url, meta: &Metadata) -> Result<()> {
+		match copy_attrs(to, meta) {
+			Ok(true) => Ok(()),
+			Ok(false) => self.log(id, format!("Owner of {to:?} not preserved, which requires root")),
+			Err(e) => Err(FsError::new(to, e))?,
+		}
+	}
+
 	#[inline]
 	fn succ(&self, id: usize) -> Result<()> { Ok(self.prog.send(TaskProg::Succ(id))?) }
 
@@ -354,3 +394,98 @@ impl File {
 		self.macro_.send(op.into(), priority).await.map_err(|_| anyhow!("Failed to send task"))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_paste_archive() {
+		use std::os::unix::fs::{symlink, PermissionsExt};
+
+		use yazi_shared::fs::copy_dir_meta;
+
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		std::fs::create_dir_all(dir.join("from/d")).unwrap();
+		std::fs::write(dir.join("from/a"), "a").unwrap();
+		symlink("a", dir.join("from/l")).unwrap();
+
+		let mode =
+			|p: &str| std::fs::symlink_metadata(dir.join(p)).unwrap().permissions().mode() & 0o7777;
+		let set_mode = |p: &str, m| {
+			std::fs::set_permissions(dir.join(p), std::fs::Permissions::from_mode(m)).unwrap()
+		};
+		set_mode("from/a", 0o604);
+		set_mode("from/d", 0o700);
+
+		let ((macro_, ops), (prog, _prog)) =
+			(async_priority_channel::unbounded(), mpsc::unbounded_channel());
+		let file = File::new(macro_, prog);
+		let (from, to) = (Url::from(dir.join("from")), Url::from(dir.join("to")));
+		file
+			.paste(FileOpPaste {
+				id:      0,
+				from:    from.clone(),
+				to:      to.clone(),
+				meta:    None,
+				cut:     false,
+				follow:  false,
+				times:   true,
+				archive: true,
+				retry:   0,
+			})
+			.await
+			.unwrap();
+
+		while let Ok((TaskOp::File(op), _)) = ops.try_recv() {
+			file.work(*op).await.unwrap();
+		}
+		copy_dir_meta(&from, &to, true).await;
+
+		assert_eq!(mode("to/a"), 0o604);
+		assert_eq!(mode("to/d"), 0o700);
+		assert!(std::fs::symlink_metadata(dir.join("to/l")).unwrap().is_symlink());
+		assert_eq!(std::fs::read_link(dir.join("to/l")).unwrap(), Path::new("a"));
+	}
+
+	#[tokio::test]
+	async fn test_cut_rename() {
+		let tmp = tempfile::tempdir().unwrap();
+		std::fs::write(tmp.path().join("a"), "a").unwrap();
+
+		let ((macro_, ops), (prog, mut progs)) =
+			(async_priority_channel::unbounded(), mpsc::unbounded_channel());
+		let file = File::new(macro_, prog);
+		let cut = |from: &str, to: &str| FileOpPaste {
+			id:      0,
+			from:    Url::from(tmp.path().join(from)),
+			to:      Url::from(tmp.path().join(to)),
+			meta:    None,
+			cut:     true,
+			follow:  false,
+			times:   false,
+			archive: false,
+			retry:   0,
+		};
+
+		// A destination that can't be renamed to fails the task, rather than being
+		// copied to
+		let e = file.paste(cut("a", "missing/a")).await.unwrap_err();
+		let url = Url::from(tmp.path().join("missing/a"));
+		assert!(matches!(e.downcast_ref(), Some(FsError::NotFound(Some(u))) if *u == url));
+		assert!(matches!(progs.try_recv(), Ok(TaskProg::New(0, 0))));
+		assert!(matches!(progs.try_recv(), Ok(TaskProg::Fail(0, _))));
+		assert!(tmp.path().join("a").exists());
+		assert!(ops.try_recv().is_err());
+
+		// A source that's gone already has nothing left to move
+		file.paste(cut("gone", "b")).await.unwrap();
+		assert!(matches!(progs.try_recv(), Ok(TaskProg::Succ(0))));
+
+		file.paste(cut("a", "b")).await.unwrap();
+		assert!(matches!(progs.try_recv(), Ok(TaskProg::Succ(0))));
+		assert_eq!(std::fs::read_to_string(tmp.path().join("b")).unwrap(), "a");
+	}
+}
@@ -2,6 +2,7 @@
 
 mod file;
 mod op;
+mod trash;
 
 pub use file::*;
 pub use op::*;
@@ -1,5 +1,6 @@
 use std::fs::Metadata;
 
+use yazi_config::tasks::TrashBackend;
 use yazi_shared::fs::Url;
 
 #[derive(Debug)]
@@ -26,13 +27,15 @@ impl FileOp {
 // --- Paste
 #[derive(Clone, Debug)]
 pub struct FileOpPaste {
-	pub id:     usize,
-	pub from:   Url,
-	pub to:     Url,
-	pub meta:   Option<Metadata>,
-	pub cut:    bool,
-	pub follow: bool,
-	pub retry:  u8,
+	pub id:      usize,
+	pub from:    Url,
+	pub to:      Url,
+	pub meta:    Option<Metadata>,
+	pub cut:     bool,
+	pub follow:  bool,
+	pub times:   bool,
+	pub archive: bool,
+	pub retry:   u8,
 }
 
 impl FileOpPaste {
@@ -44,6 +47,8 @@ impl FileOpPaste {
 			meta: Some(meta),
 			cut: self.cut,
 			follow: self.follow,
+			times: self.times,
+			archive: self.archive,
 			retry: self.retry,
 		}
 	}
@@ -59,6 +64,7 @@ pub struct FileOpLink {
 	pub resolve:  bool,
 	pub relative: bool,
 	pub delete:   bool,
+	pub archive:  bool,
 }
 
 impl From<FileOpPaste> for FileOpLink {
@@ -71,6 +77,7 @@ impl From<FileOpPaste> for FileOpLink {
 			resolve:  true,
 			relative: false,
 			delete:   value.cut,
+			archive:  value.archive,
 		}
 	}
 }
@@ -106,3 +113,11 @@ pub struct FileOpTrash {
 	pub target: Url,
 	pub length: u64,
 }
+
+// --- Restore
+#[derive(Clone, Debug)]
+pub struct FileOpRestore {
+	pub id:      usize,
+	pub targets: Vec<Url>,
+	pub backend: TrashBackend,
+}
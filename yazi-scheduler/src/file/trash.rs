@@ -0,0 +1,121 @@
+use std::{path::Path, process::Stdio};
+
+use anyhow::{bail, Result};
+use tokio::process::Command;
+use yazi_config::tasks::TrashBackend;
+use yazi_shared::fs::Url;
+
+use crate::process::{shell, ShellOpt};
+
+pub(super) async fn trash(target: &Path, backend: TrashBackend, run: &str) -> Result<()> {
+	match backend {
+		TrashBackend::Native => {
+			let target = target.to_owned();
+			tokio::task::spawn_blocking(move || native(&target)).await?
+		}
+		TrashBackend::TrashCli => external(Command::new("trash-put").arg("--").arg(target)).await,
+		TrashBackend::Gio => external(Command::new("gio").args(["trash", "--"]).arg(target)).await,
+		TrashBackend::Command => {
+			let child = shell(ShellOpt {
+				cmd: run.into(),
+				args: vec!["".into(), target.into()],
+				piped: true,
+				..Default::default()
+			})?;
+			check(run, child.wait_with_output().await?)
+		}
+	}
+}
+
+fn native(target: &Path) -> Result<()> {
+	#[cfg(target_os = "macos")]
+	{
+		use trash::{macos::{DeleteMethod, TrashContextExtMacos}, TrashContext};
+		let mut ctx = TrashContext::default();
+		ctx.set_delete_method(DeleteMethod::NsFileManager);
+		ctx.delete(target)?;
+	}
+	#[cfg(all(not(target_os = "macos"), not(target_os = "android")))]
+	{
+		trash::delete(target)?;
+	}
+	Ok(())
+}
+
+async fn external(cmd: &mut Command) -> Result<()> {
+	let name = cmd.as_std().get_program().to_string_lossy().into_owned();
+	let output = cmd.stdin(Stdio::null()).kill_on_drop(true).output().await;
+	match output {
+		Ok(output) => check(&name, output),
+		Err(e) => bail!("Failed to run `{name}`: {e}"),
+	}
+}
+
+fn check(name: &str, output: std::process::Output) -> Result<()> {
+	if output.status.success() {
+		return Ok(());
+	}
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	bail!("`{name}` exited with {}: {}", output.status, stderr.trim())
+}
+
+pub(super) async fn restore(targets: Vec<Url>, backend: TrashBackend) -> Result<()> {
+	if !backend.restorable() {
+		bail!("Files trashed with the `{}` backend cannot be restored", backend.as_str());
+	}
+	tokio::task::spawn_blocking(move || restore_native(&targets)).await?
+}
+
+// Puts the most recently trashed entry of each target back where it was
+#[cfg(any(
+	target_os = "windows",
+	all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+))]
+fn restore_native(targets: &[Url]) -> Result<()> {
+	let mut items = trash::os_limited::list()?;
+	items.sort_unstable_by_key(|i| std::cmp::Reverse(i.time_deleted));
+
+	let mut found = Vec::with_capacity(targets.len());
+	for target in targets {
+		match items.iter().position(|i| i.original_path() == **target) {
+			Some(n) => found.push(items.remove(n)),
+			None => bail!("`{}` is not in the trash", target.display()),
+		}
+	}
+	Ok(trash::os_limited::restore_all(found)?)
+}
+
+#[cfg(not(any(
+	target_os = "windows",
+	all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+)))]
+fn restore_native(_: &[Url]) -> Result<()> {
+	bail!("Restoring from the trash is not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_command() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		std::fs::create_dir_all(dir.join("bin")).unwrap();
+
+		let target = dir.join("it's a file");
+		std::fs::write(&target, "").unwrap();
+
+		let run = format!("mv -- \"$1\" '{}'", dir.join("bin").display());
+		trash(&target, TrashBackend::Command, &run).await.unwrap();
+		assert!(!target.exists());
+		assert!(dir.join("bin/it's a file").exists());
+
+		let e = trash(&target, TrashBackend::Command, &run).await.unwrap_err();
+		assert!(e.to_string().starts_with(&format!("`{run}` exited with")), "{e}");
+
+		let e = restore(vec![Url::from(&target)], TrashBackend::Command).await.unwrap_err();
+		assert_eq!(e.to_string(), "Files trashed with the `command` backend cannot be restored");
+	}
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use futures::future::BoxFuture;
 use yazi_config::TASKS;
+use yazi_proxy::ManagerProxy;
 
 use super::{Task, TaskStage};
 use crate::TaskKind;
@@ -73,7 +74,10 @@ impl Ongoing {
 				TaskStage::Hooked => {}
 			}
 
-			self.all.remove(&id);
+			let task = self.all.remove(&id);
+			if let Some(task) = task.filter(|t| t.kind == TaskKind::User && !t.cancelled) {
+				ManagerProxy::update_log(task.name);
+			}
 		}
 		None
 	}
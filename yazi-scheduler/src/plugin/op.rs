@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use yazi_shared::event::Data;
 
 #[derive(Debug)]
@@ -15,7 +17,8 @@ impl PluginOp {
 
 #[derive(Debug)]
 pub struct PluginOpEntry {
-	pub id:   usize,
-	pub name: String,
-	pub args: Vec<Data>,
+	pub id:    usize,
+	pub name:  String,
+	pub args:  Vec<Data>,
+	pub pairs: HashMap<String, String>,
 }
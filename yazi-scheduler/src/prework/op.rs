@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
 use yazi_config::plugin::{FetcherProps, PreloaderProps};
 use yazi_shared::{fs::Url, Throttle};
 
@@ -32,11 +34,21 @@ pub struct PreworkOpLoad {
 	pub id:     usize,
 	pub plugin: PreloaderProps,
 	pub target: yazi_shared::fs::File,
+	pub ct:     CancellationToken,
 }
 
 #[derive(Debug)]
 pub struct PreworkOpSize {
 	pub id:       usize,
 	pub target:   Url,
-	pub throttle: Arc<Throttle<(Url, u64)>>,
+	pub throttle: Arc<Throttle<(Url, Option<u64>)>>,
+	pub cancel:   mpsc::Receiver<()>,
+}
+
+#[derive(Debug)]
+pub struct PreworkOpMime {
+	pub id:      usize,
+	pub targets: Vec<Url>,
+	pub ct:      CancellationToken,
+	pub done:    oneshot::Sender<Vec<yazi_shared::fs::File>>,
 }
@@ -1,22 +1,31 @@
-use std::collections::{HashMap, HashSet};
+use std::{collections::{HashMap, HashSet}, future::Future, time::SystemTime};
 
 use anyhow::{anyhow, Result};
 use parking_lot::{Mutex, RwLock};
-use tokio::sync::mpsc;
+use tokio::{select, sync::{mpsc, Semaphore}};
+use tokio_util::sync::CancellationToken;
 use tracing::error;
 use yazi_config::Priority;
 use yazi_plugin::isolate;
-use yazi_shared::fs::{calculate_size, FilesOp, Url};
+use yazi_shared::fs::{calculate_size, File, FilesOp, Url};
 
-use super::{PreworkOp, PreworkOpFetch, PreworkOpLoad, PreworkOpSize};
+use super::{PreworkOp, PreworkOpFetch, PreworkOpLoad, PreworkOpMime, PreworkOpSize};
 use crate::{TaskOp, TaskProg, HIGH, NORMAL};
 
+// The maximum number of directories whose size is being calculated at the same time
+const SIZE_CONCURRENCY: usize = 3;
+
+// The number of files whose mimetype is detected at a time
+pub const MIME_BATCH: usize = 100;
+
 pub struct Prework {
 	macro_: async_priority_channel::Sender<TaskOp, u8>,
 	prog:   mpsc::UnboundedSender<TaskProg>,
 
 	pub loaded:       Mutex<HashMap<Url, u32>>,
 	pub size_loading: RwLock<HashSet<Url>>,
+	size_permits:     Semaphore,
+	mime_loading:     Mutex<HashSet<(Url, Option<SystemTime>)>>,
 }
 
 impl Prework {
@@ -24,7 +33,14 @@ impl Prework {
 		macro_: async_priority_channel::Sender<TaskOp, u8>,
 		prog: mpsc::UnboundedSender<TaskProg>,
 	) -> Self {
-		Self { macro_, prog, loaded: Default::default(), size_loading: Default::default() }
+		Self {
+			macro_,
+			prog,
+			loaded: Default::default(),
+			size_loading: Default::default(),
+			size_permits: Semaphore::new(SIZE_CONCURRENCY),
+			mime_loading: Default::default(),
+		}
 	}
 
 	pub async fn work(&self, op: PreworkOp) -> Result<()> {
@@ -62,7 +78,14 @@ impl Prework {
 			}
 			PreworkOp::Load(task) => {
 				let url = task.target.url();
-				let result = isolate::preload(&task.plugin.name, task.target).await;
+				let result = isolate::preload(&task.plugin.name, task.target, task.ct.clone()).await;
+				if task.ct.is_cancelled() {
+					// Forget about it, so that it can be preloaded again next time
+					if let Some(x) = self.loaded.lock().get_mut(&url) {
+						*x &= !(1 << task.plugin.id);
+					}
+					return Ok(());
+				}
 				if let Err(e) = result {
 					self.fail(
 						task.id,
@@ -81,8 +104,15 @@ impl Prework {
 				}
 				self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
 			}
-			PreworkOp::Size(task) => {
-				let length = calculate_size(&task.target).await;
+			PreworkOp::Size(mut task) => {
+				let length = select! {
+					Ok(_permit) = self.size_permits.acquire() => select! {
+						n = calculate_size(&task.target) => Some(n),
+						Some(()) = task.cancel.recv() => None,
+					},
+					Some(()) = task.cancel.recv() => None,
+				};
+
 				task.throttle.done((task.target, length), |buf| {
 					{
 						let mut loading = self.size_loading.write();
@@ -91,8 +121,14 @@ impl Prework {
 						}
 					}
 
-					let parent = buf[0].0.parent_url().unwrap();
-					FilesOp::Size(parent, HashMap::from_iter(buf)).emit();
+					let mut parents: HashMap<_, HashMap<_, _>> = HashMap::new();
+					for (url, length) in buf {
+						let (Some(parent), Some(length)) = (url.parent_url(), length) else { continue };
+						parents.entry(parent).or_default().insert(url, length);
+					}
+					for (parent, sizes) in parents {
+						FilesOp::Size(parent, sizes).emit();
+					}
 				});
 				self.prog.send(TaskProg::Adv(task.id, 1, 0))?;
 			}
@@ -131,6 +167,74 @@ impl Prework {
 		self.work(PreworkOp::Size(task)).await?;
 		self.succ(id)
 	}
+
+	pub async fn mime(&self, task: PreworkOpMime) -> Result<()> {
+		let id = task.id;
+		self.prog.send(TaskProg::New(id, task.targets.len() as u64))?;
+
+		let files = self
+			.detect(id, task.targets, MIME_BATCH, &task.ct, |files| async {
+				if let Err(e) = isolate::fetch("mime", files).await {
+					error!("Fetch `mime` failed in detecting: {e}");
+				}
+			})
+			.await;
+
+		// Whatever has been detected so far is handed over even if cancelled
+		task.done.send(files).ok();
+		if task.ct.is_cancelled() {
+			return Ok(());
+		}
+
+		self.prog.send(TaskProg::Adv(id, 1, 0))?;
+		self.succ(id)
+	}
+
+	// Runs `f` over `targets` in batches of `batch`, skipping the files that are
+	// already being detected by another task for the same mtime, and stops
+	// before the next batch once `ct` is cancelled
+	async fn detect<F, Fut>(
+		&self,
+		id: usize,
+		targets: Vec<Url>,
+		batch: usize,
+		ct: &CancellationToken,
+		mut f: F,
+	) -> Vec<File>
+	where
+		F: FnMut(Vec<File>) -> Fut,
+		Fut: Future<Output = ()>,
+	{
+		let mut done = Vec::with_capacity(targets.len());
+		for chunk in targets.chunks(batch) {
+			if ct.is_cancelled() {
+				break;
+			}
+
+			let (mut files, mut keys) = (Vec::with_capacity(chunk.len()), vec![]);
+			for url in chunk {
+				let Ok(file) = File::from(url.clone()).await else { continue };
+				let key = (file.url(), file.cha.mtime);
+				if self.mime_loading.lock().insert(key.clone()) {
+					files.push(file.clone());
+					keys.push(key);
+				}
+				done.push(file);
+			}
+
+			if !files.is_empty() {
+				select! {
+					_ = f(files) => {},
+					_ = ct.cancelled() => {},
+				}
+			}
+
+			let mut loading = self.mime_loading.lock();
+			keys.iter().for_each(|k| _ = loading.remove(k));
+			self.prog.send(TaskProg::Adv(id, 0, chunk.len() as u64)).ok();
+		}
+		done
+	}
 }
 
 impl Prework {
@@ -147,3 +251,45 @@ impl Prework {
 		self.macro_.send(op.into(), priority).await.map_err(|_| anyhow!("Failed to send task"))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	use super::*;
+
+	#[tokio::test]
+	async fn test_detect_cancel() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		let targets: Vec<_> = (0..5)
+			.map(|i| {
+				std::fs::write(dir.join(i.to_string()), "").unwrap();
+				Url::from(dir.join(i.to_string()))
+			})
+			.collect();
+
+		let (macro_, _) = async_priority_channel::unbounded();
+		let (prog, _rx) = mpsc::unbounded_channel();
+		let prework = Prework::new(macro_, prog);
+
+		// The batch that's being detected when cancelled is the last one
+		let (ct, calls) = (CancellationToken::new(), AtomicUsize::new(0));
+		let done = prework
+			.detect(1, targets, 2, &ct, |files| {
+				let (n, ct) = (calls.fetch_add(1, Ordering::Relaxed), &ct);
+				async move {
+					assert_eq!(files.len(), 2);
+					if n == 1 {
+						ct.cancel();
+						std::future::pending::<()>().await;
+					}
+				}
+			})
+			.await;
+
+		assert_eq!(calls.load(Ordering::Relaxed), 2);
+		assert_eq!(done.len(), 4);
+		assert!(prework.mime_loading.lock().is_empty());
+	}
+}
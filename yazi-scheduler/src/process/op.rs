@@ -1,14 +1,15 @@
 use std::ffi::OsString;
 
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
 use super::ShellOpt;
 
 #[derive(Debug)]
 pub struct ProcessOpBlock {
-	pub id:   usize,
-	pub cmd:  OsString,
-	pub args: Vec<OsString>,
+	pub id:      usize,
+	pub cmd:     OsString,
+	pub args:    Vec<OsString>,
+	pub spawned: Option<oneshot::Sender<()>>,
 }
 
 impl From<ProcessOpBlock> for ShellOpt {
@@ -19,9 +20,10 @@ impl From<ProcessOpBlock> for ShellOpt {
 
 #[derive(Debug)]
 pub struct ProcessOpOrphan {
-	pub id:   usize,
-	pub cmd:  OsString,
-	pub args: Vec<OsString>,
+	pub id:      usize,
+	pub cmd:     OsString,
+	pub args:    Vec<OsString>,
+	pub spawned: Option<oneshot::Sender<()>>,
 }
 
 impl From<ProcessOpOrphan> for ShellOpt {
@@ -32,10 +34,11 @@ impl From<ProcessOpOrphan> for ShellOpt {
 
 #[derive(Debug)]
 pub struct ProcessOpBg {
-	pub id:     usize,
-	pub cmd:    OsString,
-	pub args:   Vec<OsString>,
-	pub cancel: mpsc::Receiver<()>,
+	pub id:      usize,
+	pub cmd:     OsString,
+	pub args:    Vec<OsString>,
+	pub cancel:  mpsc::Receiver<()>,
+	pub spawned: Option<oneshot::Sender<()>>,
 }
 
 impl From<ProcessOpBg> for ShellOpt {
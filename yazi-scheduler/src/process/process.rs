@@ -13,17 +13,20 @@ pub struct Process {
 impl Process {
 	pub fn new(prog: mpsc::UnboundedSender<TaskProg>) -> Self { Self { prog } }
 
-	pub async fn block(&self, task: ProcessOpBlock) -> Result<()> {
+	pub async fn block(&self, mut task: ProcessOpBlock) -> Result<()> {
 		let _permit = HIDER.acquire().await.unwrap();
 		defer!(AppProxy::resume());
 		AppProxy::stop().await;
 
-		let (id, cmd) = (task.id, task.cmd.clone());
+		let (id, cmd, spawned) = (task.id, task.cmd.clone(), task.spawned.take());
 		let result = super::shell(task.into());
 		if let Err(e) = result {
 			AppProxy::notify_warn(&cmd.to_string_lossy(), format!("Failed to spawn process: {e}"));
 			return self.succ(id);
 		}
+		if let Some(tx) = spawned {
+			tx.send(()).ok();
+		}
 
 		let status = result.unwrap().wait().await?;
 		if !status.success() {
@@ -38,10 +41,15 @@ impl Process {
 		self.succ(id)
 	}
 
-	pub async fn orphan(&self, task: ProcessOpOrphan) -> Result<()> {
-		let id = task.id;
+	pub async fn orphan(&self, mut task: ProcessOpOrphan) -> Result<()> {
+		let (id, spawned) = (task.id, task.spawned.take());
 		match super::shell(task.into()) {
-			Ok(_) => self.succ(id)?,
+			Ok(_) => {
+				if let Some(tx) = spawned {
+					tx.send(()).ok();
+				}
+				self.succ(id)?
+			}
 			Err(e) => {
 				self.prog.send(TaskProg::New(id, 0))?;
 				self.fail(id, format!("Failed to spawn process: {e}"))?;
@@ -55,6 +63,9 @@ impl Process {
 		self.prog.send(TaskProg::New(task.id, 0))?;
 		let mut child =
 			super::shell(ShellOpt { cmd: task.cmd, args: task.args, piped: true, ..Default::default() })?;
+		if let Some(tx) = task.spawned {
+			tx.send(()).ok();
+		}
 
 		let mut stdout = BufReader::new(child.stdout.take().unwrap()).lines();
 		let mut stderr = BufReader::new(child.stderr.take().unwrap()).lines();
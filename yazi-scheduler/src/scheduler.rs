@@ -1,16 +1,18 @@
-use std::{borrow::Cow, ffi::OsString, sync::Arc, time::Duration};
+use std::{borrow::Cow, collections::HashMap, ffi::OsString, sync::Arc, time::Duration};
 
 use anyhow::Result;
 use futures::{future::BoxFuture, FutureExt};
 use parking_lot::Mutex;
-use tokio::{fs, select, sync::{mpsc::{self, UnboundedReceiver}, oneshot}, task::JoinHandle};
-use yazi_config::{open::Opener, plugin::{Fetcher, Preloader}, TASKS};
+use tokio::{fs, select, sync::{mpsc::{self, UnboundedReceiver}, oneshot}, task::JoinHandle, time::{sleep, timeout}};
+use tokio_util::sync::CancellationToken;
+use yazi_config::{open::Opener, plugin::{Fetcher, Preloader}, tasks::TrashBackend, TASKS};
 use yazi_dds::Pump;
+use yazi_fs::Tags;
 use yazi_proxy::ManagerProxy;
-use yazi_shared::{event::Data, fs::{remove_dir_clean, unique_name, Url}, Throttle};
+use yazi_shared::{event::Data, fs::{copy_dir_meta, remove_dir_clean, unique_name, Url}, Throttle};
 
 use super::{Ongoing, TaskProg, TaskStage};
-use crate::{file::{File, FileOpDelete, FileOpHardlink, FileOpLink, FileOpPaste, FileOpTrash}, plugin::{Plugin, PluginOpEntry}, prework::{Prework, PreworkOpFetch, PreworkOpLoad, PreworkOpSize}, process::{Process, ProcessOpBg, ProcessOpBlock, ProcessOpOrphan}, TaskKind, TaskOp, HIGH, LOW, NORMAL};
+use crate::{file::{File, FileOpDelete, FileOpHardlink, FileOpLink, FileOpPaste, FileOpRestore, FileOpTrash}, plugin::{Plugin, PluginOpEntry}, prework::{Prework, PreworkOpFetch, PreworkOpLoad, PreworkOpMime, PreworkOpSize, MIME_BATCH}, process::{Process, ProcessOpBg, ProcessOpBlock, ProcessOpOrphan}, TaskKind, TaskOp, HIGH, LOW, NORMAL};
 
 pub struct Scheduler {
 	pub file:    Arc<File>,
@@ -21,6 +23,7 @@ pub struct Scheduler {
 	micro:       async_priority_channel::Sender<BoxFuture<'static, ()>, u8>,
 	prog:        mpsc::UnboundedSender<TaskProg>,
 	handles:     Vec<JoinHandle<()>>,
+	stop:        CancellationToken,
 	pub ongoing: Arc<Mutex<Ongoing>>,
 }
 
@@ -39,6 +42,7 @@ impl Scheduler {
 			micro:   micro_tx,
 			prog:    prog_tx,
 			handles: Vec::with_capacity(TASKS.micro_workers as usize + TASKS.macro_workers as usize + 1),
+			stop:    CancellationToken::new(),
 			ongoing: Default::default(),
 		};
 
@@ -56,6 +60,9 @@ impl Scheduler {
 		let mut ongoing = self.ongoing.lock();
 
 		if let Some(hook) = ongoing.hooks.remove(&id) {
+			if let Some(task) = ongoing.get_mut(id) {
+				task.cancelled = true;
+			}
 			self.micro.try_send(hook(true), HIGH).ok();
 			return false;
 		}
@@ -69,10 +76,30 @@ impl Scheduler {
 		}
 	}
 
+	// Stops the workers from taking on anything new, and gives them `wait` to
+	// finish what they're doing before they're aborted, so a file being written
+	// isn't cut off halfway where it can be helped
+	pub async fn shutdown_gracefully(&self, wait: Duration) {
+		self.stop.cancel();
+
+		let finished = async {
+			while !self.handles.iter().all(|h| h.is_finished()) {
+				sleep(Duration::from_millis(10)).await;
+			}
+		};
+		timeout(wait, finished).await.ok();
+		self.shutdown();
+	}
+
 	pub fn file_cut(&self, from: Url, mut to: Url, force: bool) {
 		let mut ongoing = self.ongoing.lock();
 		let id = ongoing.add(TaskKind::User, format!("Cut {:?} to {:?}", from, to));
 
+		if let Some(reason) = unsupported(&[&from, &to]) {
+			self.new_and_fail(id, &reason).ok();
+			return;
+		}
+
 		if to.starts_with(&from) && to != from {
 			self.new_and_fail(id, "Cannot cut directory into itself").ok();
 			return;
@@ -86,6 +113,7 @@ impl Scheduler {
 				async move {
 					if !canceled {
 						remove_dir_clean(&from).await;
+						Tags::follow([(&from, &to)]);
 						Pump::push_move(from, to);
 					}
 					ongoing.lock().try_remove(id, TaskStage::Hooked);
@@ -101,7 +129,17 @@ impl Scheduler {
 					to = unique_name(to).await;
 				}
 				file
-					.paste(FileOpPaste { id, from, to, meta: None, cut: true, follow: false, retry: 0 })
+					.paste(FileOpPaste {
+						id,
+						from,
+						to,
+						meta: None,
+						cut: true,
+						follow: false,
+						times: true,
+						archive: false,
+						retry: 0,
+					})
 					.await
 					.ok();
 			}
@@ -110,23 +148,63 @@ impl Scheduler {
 		);
 	}
 
-	pub fn file_copy(&self, from: Url, mut to: Url, force: bool, follow: bool) {
+	pub fn file_copy(
+		&self,
+		from: Url,
+		mut to: Url,
+		force: bool,
+		follow: bool,
+		times: bool,
+		archive: bool,
+	) {
 		let name = format!("Copy {:?} to {:?}", from, to);
 		let id = self.ongoing.lock().add(TaskKind::User, name);
 
+		if let Some(reason) = unsupported(&[&from, &to]) {
+			self.new_and_fail(id, &reason).ok();
+			return;
+		}
+
 		if to.starts_with(&from) && to != from {
 			self.new_and_fail(id, "Cannot copy directory into itself").ok();
 			return;
 		}
 
-		let file = self.file.clone();
+		let (file, ongoing) = (self.file.clone(), self.ongoing.clone());
 		_ = self.micro.try_send(
 			async move {
 				if !force {
 					to = unique_name(to).await;
 				}
+				if times || archive {
+					// Copying into a directory touches it, so its timestamps are set last
+					let (from, to) = (from.clone(), to.clone());
+					let ongoing_ = ongoing.clone();
+					ongoing.lock().hooks.insert(
+						id,
+						Box::new(move |canceled: bool| {
+							async move {
+								if !canceled {
+									copy_dir_meta(&from, &to, archive).await;
+								}
+								ongoing_.lock().try_remove(id, TaskStage::Hooked);
+							}
+							.boxed()
+						}),
+					);
+				}
 				file
-					.paste(FileOpPaste { id, from, to, meta: None, cut: false, follow, retry: 0 })
+					.paste(FileOpPaste {
+						id,
+						from,
+						to,
+						meta: None,
+						cut: false,
+						follow,
+						times,
+						archive,
+						retry: 0,
+					})
 					.await
 					.ok();
 			}
@@ -139,6 +217,11 @@ impl Scheduler {
 		let name = format!("Link {from:?} to {to:?}");
 		let id = self.ongoing.lock().add(TaskKind::User, name);
 
+		if let Some(reason) = unsupported(&[&from, &to]) {
+			self.new_and_fail(id, &reason).ok();
+			return;
+		}
+
 		let file = self.file.clone();
 		_ = self.micro.try_send(
 			async move {
@@ -146,7 +229,16 @@ impl Scheduler {
 					to = unique_name(to).await;
 				}
 				file
-					.link(FileOpLink { id, from, to, meta: None, resolve: false, relative, delete: false })
+					.link(FileOpLink {
+						id,
+						from,
+						to,
+						meta: None,
+						resolve: false,
+						relative,
+						delete: false,
+						archive: false,
+					})
 					.await
 					.ok();
 			}
@@ -159,6 +251,11 @@ impl Scheduler {
 		let name = format!("Hardlink {:?} to {:?}", from, to);
 		let id = self.ongoing.lock().add(TaskKind::User, name);
 
+		if let Some(reason) = unsupported(&[&from, &to]) {
+			self.new_and_fail(id, &reason).ok();
+			return;
+		}
+
 		if to.starts_with(&from) && to != from {
 			self.new_and_fail(id, "Cannot hardlink directory into itself").ok();
 			return;
@@ -181,6 +278,11 @@ impl Scheduler {
 		let mut ongoing = self.ongoing.lock();
 		let id = ongoing.add(TaskKind::User, format!("Delete {:?}", target));
 
+		if let Some(reason) = unsupported(&[&target]) {
+			self.new_and_fail(id, &reason).ok();
+			return;
+		}
+
 		ongoing.hooks.insert(id, {
 			let target = target.clone();
 			let ongoing = self.ongoing.clone();
@@ -212,6 +314,11 @@ impl Scheduler {
 		let mut ongoing = self.ongoing.lock();
 		let id = ongoing.add(TaskKind::User, format!("Trash {:?}", target));
 
+		if let Some(reason) = unsupported(&[&target]) {
+			self.new_and_fail(id, &reason).ok();
+			return;
+		}
+
 		ongoing.hooks.insert(id, {
 			let target = target.clone();
 			let ongoing = self.ongoing.clone();
@@ -238,23 +345,37 @@ impl Scheduler {
 		);
 	}
 
-	pub fn plugin_micro(&self, name: String, args: Vec<Data>) {
+	pub fn file_restore(&self, targets: Vec<Url>, backend: TrashBackend) {
+		let id =
+			self.ongoing.lock().add(TaskKind::User, format!("Restore {} trashed file(s)", targets.len()));
+
+		let file = self.file.clone();
+		_ = self.micro.try_send(
+			async move {
+				file.restore(FileOpRestore { id, targets, backend }).await.ok();
+			}
+			.boxed(),
+			NORMAL,
+		);
+	}
+
+	pub fn plugin_micro(&self, name: String, args: Vec<Data>, pairs: HashMap<String, String>) {
 		let id = self.ongoing.lock().add(TaskKind::User, format!("Run micro plugin `{name}`"));
 
 		let plugin = self.plugin.clone();
 		_ = self.micro.try_send(
 			async move {
-				plugin.micro(PluginOpEntry { id, name, args }).await.ok();
+				plugin.micro(PluginOpEntry { id, name, args, pairs }).await.ok();
 			}
 			.boxed(),
 			NORMAL,
 		);
 	}
 
-	pub fn plugin_macro(&self, name: String, args: Vec<Data>) {
+	pub fn plugin_macro(&self, name: String, args: Vec<Data>, pairs: HashMap<String, String>) {
 		let id = self.ongoing.lock().add(TaskKind::User, format!("Run macro plugin `{name}`"));
 
-		self.plugin.macro_(PluginOpEntry { id, name, args }).ok();
+		self.plugin.macro_(PluginOpEntry { id, name, args, pairs }).ok();
 	}
 
 	pub fn fetch_paged(&self, fetcher: &Fetcher, targets: Vec<yazi_shared::fs::File>) {
@@ -274,16 +395,59 @@ impl Scheduler {
 		);
 	}
 
+	// A small number of files are detected quietly, as a preload task would, while
+	// a large one shows up in the task list so that it can be followed or cancelled
+	pub fn fetch_mimetype(&self, targets: Vec<Url>) -> oneshot::Receiver<Vec<yazi_shared::fs::File>> {
+		let ct = CancellationToken::new();
+		let mut ongoing = self.ongoing.lock();
+		let kind = if targets.len() > MIME_BATCH { TaskKind::User } else { TaskKind::Preload };
+		let id = ongoing.add(kind, format!("Detect the mimetype of {} file(s)", targets.len()));
+		ongoing.hooks.insert(id, {
+			let (ongoing, ct) = (self.ongoing.clone(), ct.clone());
+			Box::new(move |canceled: bool| {
+				async move {
+					if canceled {
+						ct.cancel();
+					}
+					ongoing.lock().try_remove(id, TaskStage::Hooked);
+				}
+				.boxed()
+			})
+		});
+
+		// Detecting a large selection can take a long time, so it's done outside of
+		// the worker pool like calculating the sizes
+		let (done, rx) = oneshot::channel();
+		let prework = self.prework.clone();
+		tokio::spawn(async move {
+			prework.mime(PreworkOpMime { id, targets, ct, done }).await.ok();
+		});
+		rx
+	}
+
 	pub fn preload_paged(&self, preloader: &Preloader, target: &yazi_shared::fs::File) {
-		let id =
-			self.ongoing.lock().add(TaskKind::Preload, format!("Run preloader `{}`", preloader.run.name));
+		let ct = CancellationToken::new();
+		let mut ongoing = self.ongoing.lock();
+		let id = ongoing.add(TaskKind::Preload, format!("Run preloader `{}`", preloader.run.name));
+		ongoing.hooks.insert(id, {
+			let (ongoing, ct) = (self.ongoing.clone(), ct.clone());
+			Box::new(move |canceled: bool| {
+				async move {
+					if canceled {
+						ct.cancel();
+					}
+					ongoing.lock().try_remove(id, TaskStage::Hooked);
+				}
+				.boxed()
+			})
+		});
 
 		let plugin = preloader.into();
 		let target = target.clone();
 		let prework = self.prework.clone();
 		_ = self.micro.try_send(
 			async move {
-				prework.load(PreworkOpLoad { id, plugin, target }).await.ok();
+				prework.load(PreworkOpLoad { id, plugin, target, ct }).await.ok();
 			}
 			.boxed(),
 			NORMAL,
@@ -295,35 +459,44 @@ impl Scheduler {
 		let mut ongoing = self.ongoing.lock();
 
 		for target in targets {
+			let (cancel_tx, cancel) = mpsc::channel(1);
 			let id = ongoing.add(TaskKind::Preload, format!("Calculate the size of {:?}", target));
+			ongoing.hooks.insert(id, {
+				let ongoing = self.ongoing.clone();
+				Box::new(move |canceled: bool| {
+					async move {
+						if canceled {
+							cancel_tx.send(()).await.ok();
+						}
+						ongoing.lock().try_remove(id, TaskStage::Hooked);
+					}
+					.boxed()
+				})
+			});
+
 			let target = target.clone();
 			let throttle = throttle.clone();
 
+			// Walking a large directory can take a long time, so it's done outside of
+			// the worker pool to keep other tasks from being stalled
 			let prework = self.prework.clone();
-			_ = self.micro.try_send(
-				async move {
-					prework.size(PreworkOpSize { id, target, throttle }).await.ok();
-				}
-				.boxed(),
-				NORMAL,
-			);
+			tokio::spawn(async move {
+				prework.size(PreworkOpSize { id, target, throttle, cancel }).await.ok();
+			});
 		}
 	}
 
+	// `spawned` is told once the process has started, and dropped if it can't be
 	pub fn process_open(
 		&self,
 		opener: Cow<'static, Opener>,
 		args: Vec<OsString>,
 		done: Option<oneshot::Sender<()>>,
+		spawned: Option<oneshot::Sender<()>>,
 	) {
-		let name = {
-			let args = args.iter().map(|a| a.to_string_lossy()).collect::<Vec<_>>().join(" ");
-			if args.is_empty() {
-				format!("Run {:?}", opener.run)
-			} else {
-				format!("Run {:?} with `{args}`", opener.run)
-			}
-		};
+		// Strip the position placeholders that haven't been filled in
+		let opener = Opener::with_position(opener, None, None);
+		let name = process_name(&opener, &args);
 
 		let (cancel_tx, cancel_rx) = mpsc::channel(1);
 		let mut ongoing = self.ongoing.lock();
@@ -351,11 +524,11 @@ impl Scheduler {
 		_ = self.micro.try_send(
 			async move {
 				if opener.block {
-					process.block(ProcessOpBlock { id, cmd, args }).await.ok();
+					process.block(ProcessOpBlock { id, cmd, args, spawned }).await.ok();
 				} else if opener.orphan {
-					process.orphan(ProcessOpOrphan { id, cmd, args }).await.ok();
+					process.orphan(ProcessOpOrphan { id, cmd, args, spawned }).await.ok();
 				} else {
-					process.bg(ProcessOpBg { id, cmd, args, cancel: cancel_rx }).await.ok();
+					process.bg(ProcessOpBg { id, cmd, args, cancel: cancel_rx, spawned }).await.ok();
 				}
 			}
 			.boxed(),
@@ -367,10 +540,13 @@ impl Scheduler {
 		&self,
 		rx: async_priority_channel::Receiver<BoxFuture<'static, ()>, u8>,
 	) -> JoinHandle<()> {
+		let stop = self.stop.clone();
 		tokio::spawn(async move {
 			loop {
-				if let Ok((fut, _)) = rx.recv().await {
-					fut.await;
+				select! {
+					biased;
+					_ = stop.cancelled() => break,
+					Ok((fut, _)) = rx.recv() => fut.await,
 				}
 			}
 		})
@@ -388,9 +564,12 @@ impl Scheduler {
 		let prog = self.prog.clone();
 		let ongoing = self.ongoing.clone();
 
+		let stop = self.stop.clone();
 		tokio::spawn(async move {
 			loop {
 				select! {
+					biased;
+					_ = stop.cancelled() => break,
 					Ok((fut, _)) = micro.recv() => {
 						fut.await;
 					}
@@ -477,3 +656,57 @@ impl Scheduler {
 		Ok(())
 	}
 }
+
+// The file operations work on the path of a Url, so one that's not on the local
+// filesystem, such as a file in an archive, is refused instead
+fn unsupported(urls: &[&Url]) -> Option<String> {
+	let url = urls.iter().find(|u| !u.is_regular() && !u.is_search())?;
+	Some(format!("{url:?} is not on the local filesystem"))
+}
+
+// The name of a process task, which is also how it appears in the action log
+fn process_name(opener: &Opener, args: &[OsString]) -> String {
+	if opener.prompted {
+		return "Run shell command".to_owned();
+	}
+
+	let args = args.iter().map(|a| a.to_string_lossy()).collect::<Vec<_>>().join(" ");
+	if args.is_empty() {
+		format!("Run {:?}", opener.run)
+	} else {
+		format!("Run {:?} with `{args}`", opener.run)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_process_name() {
+		let opener = |run: &str, prompted| Opener {
+			run: run.to_owned(),
+			block: false,
+			orphan: false,
+			desc: String::new(),
+			for_: None,
+			spread: true,
+			prompted,
+		};
+		let args = [OsString::from("a.txt")];
+
+		assert_eq!(process_name(&opener("vi", false), &args), r#"Run "vi" with `a.txt`"#);
+		assert_eq!(process_name(&opener("vi", false), &[]), r#"Run "vi""#);
+		assert_eq!(process_name(&opener("sudo -S rm <<< hunter2", true), &args), "Run shell command");
+	}
+
+	#[test]
+	fn test_unsupported() {
+		let (regular, archive) = (Url::from("/a"), Url::from("archive:///a.zip"));
+		let search = Url::from("/").into_search("a".to_owned());
+
+		assert_eq!(unsupported(&[&regular, &search]), None);
+		let reason = unsupported(&[&regular, &archive]).unwrap();
+		assert!(reason.ends_with("is not on the local filesystem"), "{reason}");
+	}
+}
@@ -16,6 +16,9 @@ pub struct Task {
 
 	pub logs:   String,
 	pub logger: Option<mpsc::UnboundedSender<String>>,
+
+	// Cancelled ones aren't logged as done when their hooks run
+	pub cancelled: bool,
 }
 
 impl Task {
@@ -94,6 +94,43 @@ impl Cmd {
 		self.args.remove(name).and_then(|d| d.into_any())
 	}
 
+	// Takes the positional arguments in the form of `key=value`, for the commands
+	// that have named arguments, and renumbers the rest. A later key overrides
+	// an earlier one. The ones after a `--` are left as they are, and the `--`
+	// is dropped.
+	pub fn take_pairs(&mut self) -> anyhow::Result<HashMap<String, String>> {
+		let mut pairs = HashMap::new();
+		let (mut rest, mut literal) = (vec![], false);
+		for i in 0.. {
+			let Some(arg) = self.args.remove(&i.to_string()) else { break };
+			if literal {
+				rest.push(arg);
+				continue;
+			} else if arg.as_str() == Some("--") {
+				literal = true;
+				continue;
+			}
+
+			let Some((key, value)) = arg.as_str().and_then(|s| s.split_once('=')) else {
+				rest.push(arg);
+				continue;
+			};
+
+			if key.is_empty()
+				|| key.starts_with('-')
+				|| !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+			{
+				bail!("invalid argument `{}`, expected `key=value`", arg.as_str().unwrap());
+			}
+			pairs.insert(key.to_owned(), value.to_owned());
+		}
+
+		for (i, arg) in rest.into_iter().enumerate() {
+			self.args.insert(i.to_string(), arg);
+		}
+		Ok(pairs)
+	}
+
 	// --- Clone
 	pub fn shallow_clone(&self) -> Self {
 		Self {
@@ -136,13 +173,21 @@ impl FromStr for Cmd {
 		}
 
 		let mut cmd = Cmd { name: mem::take(&mut args[0]), ..Default::default() };
-		let mut i = 0usize;
+		let (mut i, mut literal) = (0usize, false);
 		for arg in args.into_iter().skip(1) {
-			let Some(arg) = arg.strip_prefix("--") else {
+			// Everything after a `--` is positional, and the `--` is kept as one too,
+			// for `take_pairs()` to tell where the literal arguments start
+			let Some(arg) = arg.strip_prefix("--").filter(|_| !literal) else {
 				cmd.args.insert(i.to_string(), Data::String(arg));
 				i += 1;
 				continue;
 			};
+			if arg.is_empty() {
+				literal = true;
+				cmd.args.insert(i.to_string(), Data::String("--".to_owned()));
+				i += 1;
+				continue;
+			}
 
 			let mut parts = arg.splitn(2, '=');
 			let Some(key) = parts.next().map(|s| s.to_owned()) else {
@@ -167,3 +212,41 @@ impl<'de> Deserialize<'de> for Cmd {
 		<_>::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_take_pairs() {
+		let mut cmd =
+			Cmd::from_str(r#"x a name="big world" b 'path=/t m p' --flag name=z e=="#).unwrap();
+		let pairs = cmd.take_pairs().unwrap();
+		assert_eq!(pairs.len(), 3);
+		assert_eq!(pairs["name"], "z");
+		assert_eq!(pairs["path"], "/t m p");
+		assert_eq!(pairs["e"], "=");
+		assert_eq!(cmd.str("0"), Some("a"));
+		assert_eq!(cmd.str("1"), Some("b"));
+		assert!(cmd.get("2").is_none());
+		assert!(cmd.bool("flag"));
+
+		let mut cmd = Cmd::from_str(r#"x a=1 "b c=2""#).unwrap();
+		assert_eq!(
+			cmd.take_pairs().unwrap_err().to_string(),
+			"invalid argument `b c=2`, expected `key=value`"
+		);
+		assert!(Cmd::from_str("x =1").unwrap().take_pairs().is_err());
+
+		// Escaped by a `--`
+		let mut cmd = Cmd::from_str("x k=v a -- b=c --d").unwrap();
+		let pairs = cmd.take_pairs().unwrap();
+		assert_eq!(pairs.len(), 1);
+		assert_eq!(pairs["k"], "v");
+		assert_eq!(cmd.str("0"), Some("a"));
+		assert_eq!(cmd.str("1"), Some("b=c"));
+		assert_eq!(cmd.str("2"), Some("--d"));
+		assert!(!cmd.bool("d"));
+		assert!(Cmd::from_str("x -- =1").unwrap().take_pairs().is_ok());
+	}
+}
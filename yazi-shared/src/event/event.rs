@@ -1,9 +1,9 @@
-use std::{collections::VecDeque, ffi::OsString};
+use std::ffi::OsString;
 
 use crossterm::event::{KeyEvent, MouseEvent};
 use tokio::sync::mpsc;
 
-use super::Cmd;
+use super::{Cmd, CmdSeq};
 use crate::{Layer, RoCell};
 
 static TX: RoCell<mpsc::UnboundedSender<Event>> = RoCell::new();
@@ -12,7 +12,7 @@ static RX: RoCell<mpsc::UnboundedReceiver<Event>> = RoCell::new();
 #[derive(Debug)]
 pub enum Event {
 	Call(Cmd, Layer),
-	Seq(VecDeque<Cmd>, Layer),
+	Seq(CmdSeq, Layer),
 	Render,
 	Key(KeyEvent),
 	Mouse(MouseEvent),
@@ -24,6 +24,7 @@ pub enum Event {
 #[derive(Debug, Default)]
 pub struct EventQuit {
 	pub no_cwd_file: bool,
+	pub force:       bool,
 	pub selected:    Option<OsString>,
 }
 
@@ -4,8 +4,10 @@ mod cmd;
 mod data;
 mod event;
 mod render;
+mod seq;
 
 pub use cmd::*;
 pub use data::*;
 pub use event::*;
 pub use render::*;
+pub use seq::*;
@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+
+use super::Cmd;
+
+// What came of running a command
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+	Done,
+	// It opened a prompt, which may only show up after the next command of the
+	// sequence is run, so that one waits till it's answered
+	Prompted,
+	// There's no command by that name
+	Unknown,
+}
+
+impl From<()> for Outcome {
+	fn from(_: ()) -> Self { Self::Done }
+}
+
+// The commands bound to a single key, run one after another
+#[derive(Debug, Default)]
+pub struct CmdSeq {
+	pub cmds:              VecDeque<Cmd>,
+	pub continue_on_error: bool,
+}
+
+impl CmdSeq {
+	#[inline]
+	pub fn new(cmds: impl Into<VecDeque<Cmd>>, continue_on_error: bool) -> Self {
+		Self { cmds: cmds.into(), continue_on_error }
+	}
+
+	// Runs the next command with `f`. An unknown command drops the rest of the
+	// sequence, unless `continue_on_error` is set.
+	pub fn step(&mut self, f: impl FnOnce(Cmd) -> Outcome) -> Outcome {
+		let Some(cmd) = self.cmds.pop_front() else { return Outcome::Unknown };
+		let outcome = f(cmd);
+		if outcome == Outcome::Unknown && !self.continue_on_error {
+			self.cmds.clear();
+		}
+		outcome
+	}
+
+	#[inline]
+	pub fn is_empty(&self) -> bool { self.cmds.is_empty() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn run(seq: &mut CmdSeq, fails: &str) -> (Vec<String>, usize) {
+		let (mut ran, mut failed) = (vec![], 0);
+		while !seq.is_empty() {
+			let outcome = seq.step(|c| {
+				ran.push(c.name.clone());
+				if c.name == fails { Outcome::Unknown } else { Outcome::Done }
+			});
+			failed += (outcome == Outcome::Unknown) as usize;
+		}
+		(ran, failed)
+	}
+
+	#[test]
+	fn test_step() {
+		let cmds = || ["select_all", "yank", "escape"].map(Cmd::new);
+
+		let (ran, failed) = run(&mut CmdSeq::new(cmds(), false), "");
+		assert_eq!(ran, ["select_all", "yank", "escape"]);
+		assert_eq!(failed, 0);
+
+		let (ran, failed) = run(&mut CmdSeq::new(cmds(), false), "yank");
+		assert_eq!(ran, ["select_all", "yank"]);
+		assert_eq!(failed, 1);
+
+		let (ran, failed) = run(&mut CmdSeq::new(cmds(), true), "yank");
+		assert_eq!(ran, ["select_all", "yank", "escape"]);
+		assert_eq!(failed, 1);
+	}
+}
@@ -0,0 +1,56 @@
+use std::{io, path::Path};
+
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DiskSpace {
+	pub free:  u64,
+	pub total: u64,
+}
+
+impl DiskSpace {
+	#[inline]
+	pub fn used(&self) -> u64 { self.total.saturating_sub(self.free) }
+}
+
+#[cfg(unix)]
+pub fn disk_space(path: &Path) -> io::Result<DiskSpace> {
+	use std::{ffi::CString, mem, os::unix::ffi::OsStrExt};
+
+	let path = CString::new(path.as_os_str().as_bytes())?;
+	let mut st: libc::statvfs = unsafe { mem::zeroed() };
+	if unsafe { libc::statvfs(path.as_ptr(), &mut st) } != 0 {
+		return Err(io::Error::last_os_error());
+	}
+
+	let size = st.f_frsize as u64;
+	Ok(DiskSpace { free: st.f_bavail as u64 * size, total: st.f_blocks as u64 * size })
+}
+
+#[cfg(windows)]
+pub fn disk_space(path: &Path) -> io::Result<DiskSpace> {
+	use std::{os::windows::ffi::OsStrExt, ptr};
+
+	use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+	let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+	let (mut free, mut total) = (0u64, 0u64);
+	if unsafe { GetDiskFreeSpaceExW(path.as_ptr(), &mut free, &mut total, ptr::null_mut()) } == 0 {
+		return Err(io::Error::last_os_error());
+	}
+
+	Ok(DiskSpace { free, total })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_disk_space() {
+		let space = disk_space(Path::new(".")).unwrap();
+		assert!(space.total > 0);
+		assert!(space.free <= space.total);
+		assert_eq!(space.used(), space.total - space.free);
+	}
+}
@@ -1,7 +1,9 @@
-use std::{borrow::Cow, collections::{HashMap, VecDeque}, ffi::{OsStr, OsString}, fs::Metadata, path::{Path, PathBuf}};
+use std::{borrow::Cow, collections::{BTreeMap, HashMap, VecDeque}, ffi::{OsStr, OsString}, fs::Metadata, path::{Path, PathBuf}, sync::atomic::{AtomicUsize, Ordering}};
 
 use anyhow::{bail, Result};
+use parking_lot::Mutex;
 use tokio::{fs, io, select, sync::{mpsc, oneshot}, time};
+use tracing::error;
 
 #[inline]
 pub async fn must_exists(p: impl AsRef<Path>) -> bool { fs::symlink_metadata(p).await.is_ok() }
@@ -23,6 +25,62 @@ pub fn ok_or_not_found(result: io::Result<()>) -> io::Result<()> {
 	}
 }
 
+// Writes `s` to `path` through a temporary file, so it's never half-written,
+// blocking until it's done
+pub fn write_atomic(path: &Path, s: &str) -> io::Result<()> {
+	let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+		return Err(io::Error::new(io::ErrorKind::InvalidInput, "no file name"));
+	};
+
+	let tmp = dir.join(format!(".{}.{}.tmp", name.to_string_lossy(), std::process::id()));
+	std::fs::create_dir_all(dir)?;
+	std::fs::write(&tmp, s)?;
+	std::fs::rename(&tmp, path)
+}
+
+// Saves files in the background with `write_atomic()`, one at a time, where a
+// save that's been overtaken by a newer one of the same file is skipped
+pub struct Saver {
+	revision: AtomicUsize,
+	saved:    Mutex<BTreeMap<PathBuf, usize>>,
+}
+
+impl Default for Saver {
+	fn default() -> Self { Self::new() }
+}
+
+impl Saver {
+	pub const fn new() -> Self {
+		Self { revision: AtomicUsize::new(0), saved: Mutex::new(BTreeMap::new()) }
+	}
+
+	pub fn save(&'static self, path: PathBuf, s: String) {
+		let rev = self.revision.fetch_add(1, Ordering::Relaxed) + 1;
+
+		tokio::task::spawn_blocking(move || {
+			let mut saved = self.saved.lock();
+			if saved.get(&path).is_some_and(|&r| r > rev) {
+				return;
+			}
+			if let Err(e) = write_atomic(&path, &s) {
+				error!("Failed to save {path:?}: {e}");
+			}
+			saved.insert(path, rev);
+		});
+	}
+
+	// Saves it right away, as the process is about to exit. It takes a revision
+	// of its own, so the saves still queued are skipped instead of undoing it
+	pub fn flush(&self, path: &Path, s: &str) -> io::Result<()> {
+		let mut saved = self.saved.lock();
+		let rev = self.revision.fetch_add(1, Ordering::Relaxed) + 1;
+
+		write_atomic(path, s)?;
+		saved.insert(path.to_owned(), rev);
+		Ok(())
+	}
+}
+
 #[inline]
 pub async fn paths_to_same_file(a: impl AsRef<Path>, b: impl AsRef<Path>) -> bool {
 	_paths_to_same_file(a.as_ref(), b.as_ref()).await.unwrap_or(false)
@@ -157,6 +215,30 @@ pub async fn symlink_realname<'a>(
 	Ok(name.into())
 }
 
+#[tokio::test]
+async fn test_symlink_realname() {
+	let tmp = tempfile::tempdir().unwrap();
+	for name in ["README", "Makefile", "lower"] {
+		std::fs::write(tmp.path().join(name), "").unwrap();
+	}
+
+	// Names are matched on their bytes regardless of ASCII case, whether or not the
+	// filesystem is case-insensitive
+	let mut cached = HashMap::new();
+	for (typed, real) in [
+		("readme", "README"),
+		("ReadMe", "README"),
+		("MAKEFILE", "Makefile"),
+		("LOWER", "lower"),
+		("lower", "lower"),
+		("Missing", "Missing"),
+	] {
+		let path = tmp.path().join(typed);
+		let name = symlink_realname(&path, &mut cached).await.unwrap();
+		assert_eq!(&*name, OsStr::new(real), "{typed}");
+	}
+}
+
 pub async fn calculate_size(path: &Path) -> u64 {
 	let mut total = 0;
 	let mut stack = VecDeque::from([path.to_path_buf()]);
@@ -181,38 +263,47 @@ pub async fn calculate_size(path: &Path) -> u64 {
 	total
 }
 
+fn file_times(meta: &Metadata) -> std::fs::FileTimes {
+	let mut ft = std::fs::FileTimes::new();
+	meta.accessed().map(|t| ft = ft.set_accessed(t)).ok();
+	meta.modified().map(|t| ft = ft.set_modified(t)).ok();
+	#[cfg(target_os = "macos")]
+	{
+		use std::os::macos::fs::FileTimesExt;
+		meta.created().map(|t| ft = ft.set_created(t)).ok();
+	}
+	#[cfg(windows)]
+	{
+		use std::os::windows::fs::FileTimesExt;
+		meta.created().map(|t| ft = ft.set_created(t)).ok();
+	}
+	ft
+}
+
+// Copies `from` to `to`, reporting the bytes copied so far. The timestamps of
+// `meta` are given to `to` if `times` is set, otherwise they're left as now.
 pub fn copy_with_progress(
 	from: &Path,
 	to: &Path,
 	meta: &Metadata,
+	times: bool,
 ) -> mpsc::Receiver<Result<u64, io::Error>> {
 	let (tx, rx) = mpsc::channel(1);
 	let (tick_tx, mut tick_rx) = oneshot::channel();
 
 	tokio::spawn({
 		let (from, to) = (from.to_owned(), to.to_owned());
-
-		let mut ft = std::fs::FileTimes::new();
-		meta.accessed().map(|t| ft = ft.set_accessed(t)).ok();
-		meta.modified().map(|t| ft = ft.set_modified(t)).ok();
-		#[cfg(target_os = "macos")]
-		{
-			use std::os::macos::fs::FileTimesExt;
-			meta.created().map(|t| ft = ft.set_created(t)).ok();
-		}
-		#[cfg(windows)]
-		{
-			use std::os::windows::fs::FileTimesExt;
-			meta.created().map(|t| ft = ft.set_created(t)).ok();
-		}
+		let ft = times.then(|| file_times(meta));
 
 		async move {
 			_ = match fs::copy(&from, &to).await {
 				Ok(len) => {
-					_ = tokio::task::spawn_blocking(move || {
-						std::fs::File::options().write(true).open(to).and_then(|f| f.set_times(ft)).ok();
-					})
-					.await;
+					if let Some(ft) = ft {
+						_ = tokio::task::spawn_blocking(move || {
+							std::fs::File::options().write(true).open(to).and_then(|f| f.set_times(ft)).ok();
+						})
+						.await;
+					}
 					tick_tx.send(Ok(len))
 				}
 				Err(e) => tick_tx.send(Err(e)),
@@ -261,6 +352,63 @@ pub fn copy_with_progress(
 	rx
 }
 
+// Gives `to` the mode and, where permitted, the owner of `meta`. Returns
+// `Ok(false)` if the owner can't be changed without privileges.
+#[cfg(unix)]
+pub fn copy_attrs(to: &Path, meta: &Metadata) -> io::Result<bool> {
+	use std::os::unix::fs::{lchown, MetadataExt, PermissionsExt};
+
+	let owned = match lchown(to, Some(meta.uid()), Some(meta.gid())) {
+		Ok(()) => true,
+		Err(e) if e.raw_os_error() == Some(libc::EPERM) => false,
+		Err(e) => return Err(e),
+	};
+
+	// Changing the owner clears the setuid and setgid bits, so the mode goes last
+	if !meta.is_symlink() {
+		std::fs::set_permissions(to, std::fs::Permissions::from_mode(meta.mode()))?;
+	}
+	Ok(owned)
+}
+
+#[cfg(windows)]
+pub fn copy_attrs(to: &Path, meta: &Metadata) -> io::Result<bool> {
+	if !meta.is_symlink() {
+		std::fs::set_permissions(to, meta.permissions())?;
+	}
+	Ok(true)
+}
+
+// Gives the directories under `to` the timestamps, and the attributes too if
+// `attrs` is set, of their counterparts under `from`. It's done once their
+// contents have been copied, as that updates them, or a read-only one would
+// have nothing copied into it.
+pub async fn copy_dir_meta(from: &Path, to: &Path, attrs: bool) {
+	let (from, to) = (from.to_owned(), to.to_owned());
+	_ = tokio::task::spawn_blocking(move || {
+		let mut dirs = vec![PathBuf::new()];
+		while let Some(rel) = dirs.pop() {
+			let Ok(meta) = std::fs::symlink_metadata(from.join(&rel)) else { continue };
+			if !meta.is_dir() {
+				continue;
+			}
+
+			for entry in std::fs::read_dir(from.join(&rel)).into_iter().flatten().flatten() {
+				if entry.file_type().is_ok_and(|t| t.is_dir()) {
+					dirs.push(rel.join(entry.file_name()));
+				}
+			}
+
+			let dest = to.join(&rel);
+			if attrs {
+				copy_attrs(&dest, &meta).ok();
+			}
+			std::fs::File::open(&dest).and_then(|f| f.set_times(file_times(&meta))).ok();
+		}
+	})
+	.await;
+}
+
 pub async fn remove_dir_clean(dir: &Path) {
 	let Ok(mut it) = fs::read_dir(dir).await else { return };
 
@@ -375,3 +523,51 @@ fn test_max_common_root() {
 		"/aa/bb"
 	);
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_copy_times() {
+	use std::time::{Duration, SystemTime};
+
+	let tmp = tempfile::tempdir().unwrap();
+	let dir = tmp.path();
+	std::fs::create_dir_all(dir.join("from/d")).unwrap();
+	std::fs::create_dir_all(dir.join("to/d")).unwrap();
+
+	let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+	let times = std::fs::FileTimes::new().set_accessed(old).set_modified(old);
+	let src = dir.join("from/d/a");
+	std::fs::write(&src, "a").unwrap();
+	std::fs::File::options().write(true).open(&src).unwrap().set_times(times).unwrap();
+	std::fs::File::open(dir.join("from/d")).unwrap().set_times(times).unwrap();
+
+	let meta = std::fs::metadata(&src).unwrap();
+	let mtime = |p: &str| std::fs::metadata(dir.join(p)).unwrap().modified().unwrap();
+	for (to, times) in [("to/d/a", true), ("to/d/b", false)] {
+		let mut rx = copy_with_progress(&src, &dir.join(to), &meta, times);
+		while let Some(Ok(n)) = rx.recv().await {
+			if n == 0 {
+				break;
+			}
+		}
+	}
+	assert_eq!(mtime("to/d/a"), old);
+	assert_ne!(mtime("to/d/b"), old);
+
+	copy_dir_meta(&dir.join("from"), &dir.join("to"), false).await;
+	assert_eq!(mtime("to/d"), old);
+}
+
+#[tokio::test]
+async fn test_saver_flush() {
+	static SAVER: Saver = Saver::new();
+
+	let tmp = tempfile::tempdir().unwrap();
+	let path = tmp.path().join("state");
+
+	SAVER.save(path.clone(), "old".to_owned());
+	SAVER.flush(&path, "new").unwrap();
+
+	time::sleep(time::Duration::from_millis(200)).await;
+	assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+}
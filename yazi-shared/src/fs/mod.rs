@@ -1,13 +1,17 @@
 mod cha;
+mod disk;
 mod file;
 mod fns;
+mod mounts;
 mod op;
 mod path;
 mod url;
 
 pub use cha::*;
+pub use disk::*;
 pub use file::*;
 pub use fns::*;
+pub use mounts::*;
 pub use op::*;
 pub use path::*;
 pub use url::*;
@@ -0,0 +1,330 @@
+use std::{io, path::{Path, PathBuf}};
+
+use super::{disk_space, DiskSpace};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mount {
+	pub path:      PathBuf,
+	pub device:    Option<PathBuf>,
+	pub space:     DiskSpace,
+	pub removable: bool,
+}
+
+// Filesystems that can't be queried, or report no capacity (pseudo filesystems
+// such as `proc` or `cgroup`), are left out, as are those unmounted meanwhile.
+pub fn mounts() -> io::Result<Vec<Mount>> {
+	let mut mounts: Vec<Mount> = vec![];
+	for (path, device) in mount_points()? {
+		if mounts.iter().any(|m| m.path == path) {
+			continue;
+		}
+		match disk_space(&path) {
+			Ok(space) if space.total > 0 => {
+				let removable = is_removable(&path, device.as_deref());
+				mounts.push(Mount { path, device, space, removable });
+			}
+			_ => {}
+		}
+	}
+	Ok(mounts)
+}
+
+// Where filesystems are mounted, including pseudo filesystems.
+#[inline]
+pub fn mount_roots() -> io::Result<Vec<PathBuf>> {
+	Ok(mount_points()?.into_iter().map(|(p, _)| p).collect())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn mount_points() -> io::Result<Vec<(PathBuf, Option<PathBuf>)>> {
+	Ok(proc_mounts()?.iter().map(|(path, device, _)| (path.clone(), device.clone())).collect())
+}
+
+// The mount point, the device, and the filesystem type of each entry
+#[cfg(any(target_os = "linux", target_os = "android", test))]
+type ProcMounts = Vec<(PathBuf, Option<PathBuf>, String)>;
+
+// `/proc/mounts` is read at most once a second, as it's looked at on every
+// remove and move, and by the watcher for each directory it watches.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn proc_mounts() -> io::Result<std::sync::Arc<ProcMounts>> {
+	use std::{sync::Arc, time::{Duration, Instant}};
+
+	use parking_lot::Mutex;
+
+	static CACHE: Mutex<Option<(Instant, Arc<ProcMounts>)>> = Mutex::new(None);
+
+	let mut cache = CACHE.lock();
+	match &*cache {
+		Some((at, mounts)) if at.elapsed() < Duration::from_secs(1) => Ok(mounts.clone()),
+		_ => {
+			let mounts = Arc::new(parse_proc_mounts(&std::fs::read_to_string("/proc/mounts")?));
+			*cache = Some((Instant::now(), mounts.clone()));
+			Ok(mounts)
+		}
+	}
+}
+
+#[cfg(any(
+	target_os = "macos",
+	target_os = "ios",
+	target_os = "freebsd",
+	target_os = "dragonfly",
+	target_os = "openbsd"
+))]
+fn mount_points() -> io::Result<Vec<(PathBuf, Option<PathBuf>)>> {
+	use std::{ffi::{CStr, OsStr}, os::unix::ffi::OsStrExt, ptr};
+
+	let mut buf: *mut libc::statfs = ptr::null_mut();
+	let n = unsafe { libc::getmntinfo(&mut buf, libc::MNT_NOWAIT) };
+	if n <= 0 {
+		return Err(io::Error::last_os_error());
+	}
+
+	let list = unsafe { std::slice::from_raw_parts(buf, n as usize) };
+	Ok(
+		list
+			.iter()
+			.map(|s| unsafe {
+				let path = CStr::from_ptr(s.f_mntonname.as_ptr()).to_bytes();
+				let device = CStr::from_ptr(s.f_mntfromname.as_ptr()).to_bytes();
+				(
+					PathBuf::from(OsStr::from_bytes(path)),
+					device.starts_with(b"/").then(|| OsStr::from_bytes(device).into()),
+				)
+			})
+			.collect(),
+	)
+}
+
+#[cfg(windows)]
+fn mount_points() -> io::Result<Vec<(PathBuf, Option<PathBuf>)>> {
+	use windows_sys::Win32::Storage::FileSystem::GetLogicalDrives;
+
+	let mask = unsafe { GetLogicalDrives() };
+	if mask == 0 {
+		return Err(io::Error::last_os_error());
+	}
+
+	Ok(
+		(0..26u8)
+			.filter(|&i| mask & (1 << i) != 0)
+			.map(|i| (format!("{}:\\", (b'A' + i) as char).into(), None))
+			.collect(),
+	)
+}
+
+#[cfg(not(any(
+	target_os = "linux",
+	target_os = "android",
+	target_os = "macos",
+	target_os = "ios",
+	target_os = "freebsd",
+	target_os = "dragonfly",
+	target_os = "openbsd",
+	windows
+)))]
+fn mount_points() -> io::Result<Vec<(PathBuf, Option<PathBuf>)>> { Ok(vec![("/".into(), None)]) }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn is_removable(_: &Path, device: Option<&Path>) -> bool {
+	device.and_then(|d| d.file_name()).and_then(sys_block).is_some_and(|s| sys_removable(&s))
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn is_removable(path: &Path, device: Option<&Path>) -> bool {
+	device.is_some() && path.starts_with("/Volumes")
+}
+
+#[cfg(not(any(
+	target_os = "linux",
+	target_os = "android",
+	target_os = "macos",
+	target_os = "ios"
+)))]
+fn is_removable(_: &Path, _: Option<&Path>) -> bool { false }
+
+// Whether `path` is on a network filesystem, whose changes the watcher may not
+// be notified of.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn is_remote(path: &Path) -> bool {
+	let Ok(mounts) = proc_mounts() else { return false };
+	mounts
+		.iter()
+		.filter(|(p, ..)| path.starts_with(p))
+		.max_by_key(|(p, ..)| p.as_os_str().len())
+		.is_some_and(|(.., fstype)| remote_fstype(fstype))
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub fn is_remote(path: &Path) -> bool {
+	use std::{ffi::{CStr, CString}, os::unix::ffi::OsStrExt};
+
+	let Ok(path) = CString::new(path.as_os_str().as_bytes()) else { return false };
+	let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+	if unsafe { libc::statfs(path.as_ptr(), &mut stat) } != 0 {
+		return false;
+	}
+
+	let fstype = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+	remote_fstype(&fstype.to_string_lossy())
+}
+
+#[cfg(not(any(
+	target_os = "linux",
+	target_os = "android",
+	target_os = "macos",
+	target_os = "ios"
+)))]
+pub fn is_remote(_: &Path) -> bool { false }
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "android",
+	target_os = "macos",
+	target_os = "ios",
+	test
+))]
+fn remote_fstype(fstype: &str) -> bool {
+	const REMOTE: &[&str] = &[
+		"nfs",
+		"nfs4",
+		"cifs",
+		"smb3",
+		"smbfs",
+		"afpfs",
+		"webdav",
+		"ncpfs",
+		"afs",
+		"9p",
+		"ceph",
+		"glusterfs",
+		"lustre",
+		"davfs",
+		"macfuse",
+		"osxfuse",
+	];
+	const FUSE: &[&str] = &["sshfs", "rclone", "s3fs", "gcsfuse", "davfs2"];
+
+	match fstype.strip_prefix("fuse.") {
+		Some(t) => FUSE.contains(&t),
+		None => REMOTE.contains(&fstype),
+	}
+}
+
+// Removable block devices that have no filesystem mounted yet. Only on Linux,
+// where `/sys/block` tells which ones are removable.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn unmounted_devices() -> io::Result<Vec<PathBuf>> {
+	let mounted: Vec<_> = mount_points()?.into_iter().filter_map(|(_, d)| d).collect();
+
+	let mut devices = vec![];
+	for entry in std::fs::read_dir("/sys/block")?.flatten() {
+		let disk = entry.path().canonicalize().unwrap_or_else(|_| entry.path());
+		if !sys_removable(&disk) {
+			continue;
+		}
+
+		let name = entry.file_name();
+		let mut parts: Vec<_> = std::fs::read_dir(&disk)
+			.into_iter()
+			.flatten()
+			.flatten()
+			.filter(|e| e.path().join("partition").exists())
+			.map(|e| e.file_name())
+			.collect();
+		if parts.is_empty() {
+			parts.push(name);
+		}
+
+		parts.sort();
+		devices.extend(parts.into_iter().map(|p| Path::new("/dev").join(p)));
+	}
+
+	devices.retain(|d| !mounted.contains(d));
+	Ok(devices)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn sys_block(name: &std::ffi::OsStr) -> Option<PathBuf> {
+	Path::new("/sys/class/block").join(name).canonicalize().ok()
+}
+
+// A device counts as removable if it, or the disk it's a partition of, is
+// flagged removable by the kernel, or if it hangs off a USB bus.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn sys_removable(sys: &Path) -> bool {
+	let flagged =
+		|p: &Path| std::fs::read_to_string(p.join("removable")).is_ok_and(|s| s.trim() == "1");
+
+	flagged(sys)
+		|| (sys.join("partition").exists() && sys.parent().is_some_and(flagged))
+		|| sys.components().any(|c| c.as_os_str().to_str().is_some_and(|s| s.starts_with("usb")))
+}
+
+// Mount points in `/proc/mounts` have spaces, tabs, newlines and backslashes
+// escaped as octal sequences, e.g. `\040` for a space.
+#[cfg(any(target_os = "linux", target_os = "android", test))]
+fn parse_proc_mounts(s: &str) -> ProcMounts {
+	fn unescape(s: &str) -> String {
+		let b = s.as_bytes();
+		let mut out = Vec::with_capacity(b.len());
+		let mut i = 0;
+		while i < b.len() {
+			if b[i] == b'\\' && i + 3 < b.len() {
+				let oct = std::str::from_utf8(&b[i + 1..i + 4]).ok();
+				if let Some(n) = oct.and_then(|o| u8::from_str_radix(o, 8).ok()) {
+					out.push(n);
+					i += 4;
+					continue;
+				}
+			}
+			out.push(b[i]);
+			i += 1;
+		}
+		String::from_utf8_lossy(&out).into_owned()
+	}
+
+	s.lines()
+		.filter_map(|l| {
+			let mut it = l.split_whitespace();
+			let device = it.next()?;
+			let path = it.next()?;
+			let fstype = it.next()?;
+			Some((
+				unescape(path).into(),
+				device.starts_with('/').then(|| unescape(device).into()),
+				fstype.to_owned(),
+			))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_proc_mounts() {
+		let s = "/dev/sda1 / ext4 rw,relatime 0 0\n\
+		         proc /proc proc rw,nosuid 0 0\n\
+		         /dev/sdb1 /mnt/My\\040Disk ext4 rw 0 0\n\
+		         /dev/sdc1 /mnt/a\\134b vfat rw 0 0\n";
+		assert_eq!(parse_proc_mounts(s), [
+			("/".into(), Some("/dev/sda1".into()), "ext4".to_owned()),
+			("/proc".into(), None, "proc".to_owned()),
+			("/mnt/My Disk".into(), Some("/dev/sdb1".into()), "ext4".to_owned()),
+			("/mnt/a\\b".into(), Some("/dev/sdc1".into()), "vfat".to_owned()),
+		]);
+	}
+
+	#[test]
+	fn test_remote_fstype() {
+		assert!(remote_fstype("nfs4"));
+		assert!(remote_fstype("cifs"));
+		assert!(remote_fstype("fuse.sshfs"));
+		assert!(!remote_fstype("ext4"));
+		assert!(!remote_fstype("fuse.gvfsd-fuse"));
+		assert!(!remote_fstype("tmpfs"));
+	}
+}
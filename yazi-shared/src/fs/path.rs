@@ -1,4 +1,6 @@
-use std::{borrow::Cow, env, ffi::OsString, path::{Component, Path, PathBuf}};
+use std::{borrow::Cow, env, ffi::{OsStr, OsString}, path::{Component, Path, PathBuf}, sync::OnceLock};
+
+use regex::Regex;
 
 use super::maybe_exists;
 use crate::fs::Url;
@@ -37,19 +39,8 @@ fn _clean_path(path: &Path) -> PathBuf {
 pub fn expand_path(p: impl AsRef<Path>) -> PathBuf { _expand_path(p.as_ref()) }
 
 fn _expand_path(p: &Path) -> PathBuf {
-	// ${HOME} or $HOME
-	#[cfg(unix)]
-	let re = regex::Regex::new(r"\$(?:\{([^}]+)\}|([a-zA-Z\d_]+))").unwrap();
-
-	// %USERPROFILE%
-	#[cfg(windows)]
-	let re = regex::Regex::new(r"%([^%]+)%").unwrap();
-
 	let s = p.to_string_lossy();
-	let s = re.replace_all(&s, |caps: &regex::Captures| {
-		let name = caps.get(2).or_else(|| caps.get(1)).unwrap();
-		env::var(name.as_str()).unwrap_or_else(|_| caps.get(0).unwrap().as_str().to_owned())
-	});
+	let s = expand_str(&s);
 
 	// Windows paths that only have a drive letter but no root, e.g. "D:"
 	#[cfg(windows)]
@@ -61,9 +52,7 @@ fn _expand_path(p: &Path) -> PathBuf {
 	}
 
 	let p = Path::new(s.as_ref());
-	if let Ok(rest) = p.strip_prefix("~") {
-		clean_path(dirs::home_dir().unwrap_or_default().join(rest))
-	} else if p.is_absolute() {
+	if p.is_absolute() {
 		clean_path(p)
 	} else if let Some(cwd) = current_cwd() {
 		clean_path(cwd.join(p))
@@ -72,6 +61,63 @@ fn _expand_path(p: &Path) -> PathBuf {
 	}
 }
 
+// Expand `~`, `~user`, and environment variables, i.e. `$VAR` and `${VAR}` on
+// Unix, `%VAR%` on Windows. Unset variables are left as they are.
+pub fn expand_str(s: &str) -> Cow<'_, str> {
+	let s = var_regex().replace_all(s, |caps: &regex::Captures| {
+		let name = caps.get(2).or_else(|| caps.get(1)).unwrap();
+		env::var(name.as_str()).unwrap_or_else(|_| caps.get(0).unwrap().as_str().to_owned())
+	});
+
+	match expand_home(&s) {
+		Some(home) => Cow::Owned(home),
+		None => s,
+	}
+}
+
+// The first variable in `s` that isn't set, which `expand_str()` would leave
+pub fn unset_var(s: &str) -> Option<&str> {
+	var_regex().captures_iter(s).find_map(|caps| {
+		let name = caps.get(2).or_else(|| caps.get(1)).unwrap().as_str();
+		env::var_os(name).is_none().then_some(name)
+	})
+}
+
+fn var_regex() -> &'static Regex {
+	static RE: OnceLock<Regex> = OnceLock::new();
+
+	// ${HOME} or $HOME
+	#[cfg(unix)]
+	return RE.get_or_init(|| Regex::new(r"\$(?:\{([^}]+)\}|([a-zA-Z_][a-zA-Z\d_]*))").unwrap());
+
+	// %USERPROFILE%
+	#[cfg(windows)]
+	return RE.get_or_init(|| Regex::new(r"%([^%]+)%").unwrap());
+}
+
+fn expand_home(s: &str) -> Option<String> {
+	let rest = s.strip_prefix('~')?;
+
+	let (name, rest) = match rest.find(std::path::is_separator) {
+		Some(i) => rest.split_at(i),
+		None => (rest, ""),
+	};
+
+	let home = if name.is_empty() {
+		dirs::home_dir()
+	} else {
+		#[cfg(unix)]
+		{
+			use uzers::os::unix::UserExt;
+			uzers::get_user_by_name(name).map(|u| u.home_dir().to_owned())
+		}
+		#[cfg(windows)]
+		None
+	};
+
+	home.map(|home| format!("{}{rest}", home.to_string_lossy()))
+}
+
 // FIXME: should return a `std::io::Result` to handle errors such as
 // permission denied
 pub async fn unique_name(mut u: Url) -> Url {
@@ -103,6 +149,49 @@ pub async fn unique_name(mut u: Url) -> Url {
 	u
 }
 
+// The name of the `n`th duplicate of `name`, with `suffix` going before the
+// extension of a file. `{n}` in `suffix` is replaced with `n`, or if there's no
+// `{n}`, ` n` is appended to it from the second one on.
+pub fn duplicate_name(name: &OsStr, is_dir: bool, suffix: &str, n: u64) -> OsString {
+	let suffix = if suffix.contains("{n}") {
+		suffix.replace("{n}", &n.to_string())
+	} else if n < 2 {
+		suffix.to_owned()
+	} else {
+		format!("{suffix} {n}")
+	};
+
+	let path = Path::new(name);
+	let (stem, ext) = match (path.file_stem(), path.extension()) {
+		(Some(stem), Some(ext)) if !is_dir => (stem, Some(ext)),
+		_ => (name, None),
+	};
+
+	let mut s = OsString::with_capacity(name.len() + suffix.len());
+	s.push(stem);
+	s.push(suffix);
+	if let Some(ext) = ext {
+		s.push(".");
+		s.push(ext);
+	}
+	s
+}
+
+// The first duplicate of `u` that doesn't exist yet
+pub async fn duplicate_url(u: &Url, suffix: &str) -> Option<Url> {
+	let name = u.file_name()?;
+	let is_dir = tokio::fs::symlink_metadata(u).await.is_ok_and(|m| m.is_dir());
+
+	let mut new = u.clone();
+	for n in 1.. {
+		new.set_file_name(duplicate_name(name, is_dir, suffix, n));
+		if !maybe_exists(&new).await {
+			return Some(new);
+		}
+	}
+	None
+}
+
 // Parameters
 // * `path`: The absolute path(contains no `/./`) to get relative path.
 // * `root`: The absolute path(contains no `/./`) to be compared.
@@ -152,7 +241,37 @@ pub fn path_relative_to<'a>(path: &'a Path, root: &Path) -> Cow<'a, Path> {
 mod tests {
 	use std::{borrow::Cow, path::Path};
 
-	use super::path_relative_to;
+	use super::{duplicate_name, duplicate_url, expand_str, path_relative_to, unset_var};
+	use crate::fs::Url;
+
+	#[test]
+	fn test_duplicate_name() {
+		let name = |name: &str, is_dir, suffix, n| {
+			duplicate_name(name.as_ref(), is_dir, suffix, n).into_string().unwrap()
+		};
+
+		assert_eq!(name("a.txt", false, " ({n})", 1), "a (1).txt");
+		assert_eq!(name("a.tar.gz", false, " ({n})", 3), "a.tar (3).gz");
+		assert_eq!(name("a.txt", true, " ({n})", 1), "a.txt (1)");
+		assert_eq!(name(".bashrc", false, "_{n}", 2), ".bashrc_2");
+		assert_eq!(name("a.txt", false, " copy", 1), "a copy.txt");
+		assert_eq!(name("a.txt", false, " copy", 2), "a copy 2.txt");
+	}
+
+	#[tokio::test]
+	async fn test_duplicate_url() {
+		let tmp = tempfile::tempdir().unwrap();
+		let dir = tmp.path();
+		std::fs::create_dir_all(dir.join("d")).unwrap();
+		for name in ["a.txt", "a copy.txt", "a copy 2.txt"] {
+			std::fs::write(dir.join(name), "").unwrap();
+		}
+
+		let url = |name: &str| Url::from(dir.join(name));
+		assert_eq!(duplicate_url(&url("a.txt"), " copy").await, Some(url("a copy 3.txt")));
+		assert_eq!(duplicate_url(&url("a.txt"), " ({n})").await, Some(url("a (1).txt")));
+		assert_eq!(duplicate_url(&url("d"), " ({n})").await, Some(url("d (1)")));
+	}
 
 	#[cfg(unix)]
 	#[test]
@@ -168,6 +287,32 @@ mod tests {
 		assert("/a/a/b", "/a/b/b", "../../a/b");
 	}
 
+	#[cfg(unix)]
+	#[test]
+	fn test_expand_str() {
+		let home = dirs::home_dir().unwrap();
+		let home = home.to_string_lossy();
+
+		assert_eq!(expand_str("~"), home);
+		assert_eq!(expand_str("~/foo"), format!("{home}/foo"));
+		assert_eq!(expand_str("foo/~"), "foo/~");
+
+		use uzers::os::unix::UserExt;
+		let root = uzers::get_user_by_uid(0).unwrap();
+		let (name, home) = (root.name().to_string_lossy(), root.home_dir().to_string_lossy());
+		assert_eq!(expand_str(&format!("~{name}/foo")), format!("{home}/foo"));
+		assert_eq!(expand_str("~no-such-user/foo"), "~no-such-user/foo");
+
+		let home = std::env::var("HOME").unwrap();
+		assert_eq!(expand_str("/foo/${HOME}/baz"), format!("/foo/{home}/baz"));
+		assert_eq!(expand_str("/foo/$HOME"), format!("/foo/{home}"));
+		assert_eq!(expand_str("/foo/${YAZI_TEST_UNSET}/baz"), "/foo/${YAZI_TEST_UNSET}/baz");
+		assert_eq!(expand_str("/foo/$5"), "/foo/$5");
+
+		assert_eq!(unset_var("$HOME/${YAZI_TEST_UNSET}"), Some("YAZI_TEST_UNSET"));
+		assert_eq!(unset_var("~/$HOME"), None);
+	}
+
 	#[cfg(windows)]
 	#[test]
 	fn test_path_relative_to() {
@@ -3,7 +3,7 @@ use std::{ffi::{OsStr, OsString}, fmt::{Debug, Display, Formatter}, ops::{Deref,
 use percent_encoding::{percent_decode_str, percent_encode, AsciiSet, CONTROLS};
 use serde::{Deserialize, Serialize};
 
-const ENCODE_SET: &AsciiSet = &CONTROLS.add(b'#');
+const ENCODE_SET: &AsciiSet = &CONTROLS.add(b'#').add(b'%');
 
 #[derive(Clone, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Url {
@@ -201,6 +201,10 @@ impl Url {
 	#[inline]
 	pub fn set_path(&mut self, path: PathBuf) { self.path = path; }
 
+	// --- Scheme
+	#[inline]
+	pub fn scheme(&self) -> UrlScheme { self.scheme }
+
 	// --- Frag
 	#[inline]
 	pub fn frag(&self) -> &str { &self.frag }
@@ -231,3 +235,40 @@ impl<'de> Deserialize<'de> for Url {
 		Ok(Url::from(s))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use serde::de::{value::{Error, StrDeserializer}, IntoDeserializer};
+
+	use super::*;
+
+	fn round_trip(url: &Url) -> Url {
+		let s = url.to_string();
+		let de: StrDeserializer<Error> = s.as_str().into_deserializer();
+		Url::deserialize(de).unwrap()
+	}
+
+	#[test]
+	fn test_round_trip() {
+		let urls = [
+			Url::from("/tmp/a b#c"),
+			Url::from("/tmp/a%20b").into_search("rg: foo#bar".to_owned()),
+			Url::from("/tmp/a.zip/dir").into_archive(),
+		];
+
+		for url in urls {
+			let new = round_trip(&url);
+			assert_eq!(new, url);
+			assert!(new.scheme == url.scheme);
+			assert_eq!(new.frag(), url.frag());
+		}
+	}
+
+	#[test]
+	fn test_scheme_kept_on_join() {
+		let url = Url::from("archive:///tmp/a.zip");
+		assert!(url.is_archive());
+		assert!(url.join("b").is_archive());
+		assert!(Url::from("regular:///tmp").is_regular());
+	}
+}
@@ -0,0 +1,117 @@
+// A fuzzy scorer, in the fashion of fzy. The characters of `pattern` have to
+// appear in `text` in order, ignoring case, and the best placement of them is
+// scored: matches at the start of a word or right after one another count for
+// more, and the characters skipped in between count against it.
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 16;
+const BONUS_START: i64 = 12;
+const BONUS_BOUNDARY: i64 = 10;
+const BONUS_CAMEL: i64 = 8;
+const PENALTY_GAP: i64 = 1;
+
+const NONE: i64 = i64::MIN / 2;
+
+// The score of `pattern` in `text`, higher is better, or `None` if it doesn't
+// match. An empty pattern matches anything with a score of 0.
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<i64> {
+	let pat: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+	if pat.is_empty() {
+		return Some(0);
+	}
+
+	let text: Vec<char> = text.chars().collect();
+	if text.len() < pat.len() || !is_subsequence(&pat, &text) {
+		return None;
+	}
+
+	let bonus: Vec<_> = (0..text.len()).map(|j| bonus_at(&text, j)).collect();
+
+	// `ended[j]` is the best score of the pattern so far with its last character
+	// matched right at `text[j]`, and `best[j]` the best one within `text[..=j]`
+	let (mut ended, mut best) = (vec![NONE; text.len()], vec![NONE; text.len()]);
+	for (i, &p) in pat.iter().enumerate() {
+		let (prev_ended, prev_best) = (ended.clone(), best.clone());
+		let mut run = NONE;
+		for j in 0..text.len() {
+			ended[j] = if !eq(p, text[j]) {
+				NONE
+			} else if i == 0 {
+				SCORE_MATCH + bonus[j]
+			} else if j == 0 {
+				NONE
+			} else {
+				let consecutive = prev_ended[j - 1].saturating_add(BONUS_CONSECUTIVE);
+				SCORE_MATCH + bonus[j] + consecutive.max(prev_best[j - 1])
+			};
+
+			run = run.saturating_sub(PENALTY_GAP).max(ended[j]);
+			best[j] = run;
+		}
+	}
+
+	ended.into_iter().max().filter(|&s| s > NONE / 2)
+}
+
+#[inline]
+fn eq(p: char, c: char) -> bool { c == p || c.to_lowercase().eq(std::iter::once(p)) }
+
+fn is_subsequence(pat: &[char], text: &[char]) -> bool {
+	let mut it = text.iter();
+	pat.iter().all(|&p| it.any(|&c| eq(p, c)))
+}
+
+fn bonus_at(text: &[char], j: usize) -> i64 {
+	let Some(&prev) = j.checked_sub(1).and_then(|i| text.get(i)) else {
+		return BONUS_START;
+	};
+
+	let cur = text[j];
+	if !prev.is_alphanumeric() && cur.is_alphanumeric() {
+		BONUS_BOUNDARY
+	} else if prev.is_lowercase() && cur.is_uppercase() {
+		BONUS_CAMEL
+	} else {
+		0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn rank<'a>(pattern: &str, texts: &[&'a str]) -> Vec<&'a str> {
+		let mut v: Vec<_> =
+			texts.iter().filter_map(|&t| fuzzy_score(pattern, t).map(|s| (s, t))).collect();
+		v.sort_by_key(|&(s, _)| std::cmp::Reverse(s));
+		v.into_iter().map(|(_, t)| t).collect()
+	}
+
+	#[test]
+	fn test_matches() {
+		assert_eq!(fuzzy_score("", "anything"), Some(0));
+		assert!(fuzzy_score("abc", "a_b_c").is_some());
+		assert!(fuzzy_score("ABC", "abc").is_some());
+		assert!(fuzzy_score("abc", "AbC").is_some());
+		assert!(fuzzy_score("acb", "abc").is_none());
+		assert!(fuzzy_score("abcd", "abc").is_none());
+		assert!(fuzzy_score("x", "").is_none());
+	}
+
+	#[test]
+	fn test_rank() {
+		// Consecutive matches, and then word starts, win over scattered ones
+		assert_eq!(rank("tab", &["toggle_all_boxes", "xtab", "tab_create"]), [
+			"tab_create",
+			"xtab",
+			"toggle_all_boxes"
+		]);
+		assert_eq!(rank("tc", &["attic", "tab_create", "tabClose"]), ["tab_create", "tabClose", "attic"]);
+
+		// Fewer characters skipped in between
+		assert_eq!(rank("ab", &["a--------b", "a--b"]), ["a--b", "a--------b"]);
+
+		// The best placement is found, not just the first one
+		assert!(fuzzy_score("ab", "a-xab").unwrap() > fuzzy_score("ab", "a-xxb").unwrap());
+	}
+}
@@ -7,8 +7,10 @@ mod env;
 mod errors;
 pub mod event;
 pub mod fs;
+mod fuzzy;
 mod layer;
 mod natsort;
+mod nice;
 mod number;
 mod os;
 mod rand;
@@ -26,8 +28,10 @@ pub use condition::*;
 pub use debounce::*;
 pub use env::*;
 pub use errors::*;
+pub use fuzzy::*;
 pub use layer::*;
 pub use natsort::*;
+pub use nice::*;
 pub use number::*;
 #[cfg(unix)]
 pub use os::*;
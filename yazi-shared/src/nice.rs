@@ -0,0 +1,208 @@
+use std::{thread, time::Duration};
+
+use tokio::{runtime::Handle, task::JoinHandle};
+
+// Runs `f` like `spawn_blocking()` does, but at a lower OS priority if `nice`
+// isn't 0. A thread can't get its priority back without privileges once it's
+// been lowered, so it's given a thread of its own instead of a pooled one.
+pub fn spawn_blocking_nice<F, R>(nice: u8, f: F) -> JoinHandle<R>
+where
+	F: FnOnce() -> R + Send + 'static,
+	R: Send + 'static,
+{
+	if nice == 0 {
+		return tokio::task::spawn_blocking(f);
+	}
+
+	let handle = Handle::current();
+	tokio::task::spawn_blocking(move || {
+		thread::spawn(move || {
+			let _guard = handle.enter();
+			lower_priority(nice);
+			f()
+		})
+		.join()
+		.unwrap_or_else(|e| std::panic::resume_unwind(e))
+	})
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn lower_priority(nice: u8) {
+	// Linux keeps a nice value for each thread, rather than for the process
+	unsafe {
+		let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+		libc::setpriority(libc::PRIO_PROCESS, tid, nice as libc::c_int);
+	}
+}
+
+#[cfg(target_os = "macos")]
+fn lower_priority(_: u8) {
+	// The nice value is shared by the whole process on macOS, but a thread can be
+	// moved to the background band of its own, where both its CPU and I/O are
+	// throttled, how much lower `nice` asks for being all the same
+	unsafe {
+		libc::setpriority(libc::PRIO_DARWIN_THREAD, 0, libc::PRIO_DARWIN_BG);
+	}
+}
+
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android", target_os = "macos"))))]
+fn lower_priority(_: u8) {
+	// The nice value is shared by the whole process on the BSDs, so only the CPU
+	// limit applies there
+}
+
+#[cfg(windows)]
+fn lower_priority(_: u8) {
+	use windows_sys::Win32::System::Threading::{GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_BELOW_NORMAL};
+
+	unsafe { SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY_BELOW_NORMAL) };
+}
+
+// Work is paused for in slices of at least this long, as sleeping for any
+// shorter than that is imprecise
+const SLICE: Duration = Duration::from_millis(10);
+
+// A single pause doesn't go on for longer than this, or the one owed for a
+// `SLICE` of work if that's longer, so that a long stretch of work isn't
+// followed by the preview going quiet for as long
+const MAX_PAUSE: Duration = Duration::from_millis(40);
+
+// Keeps a loop busy for no more than `limit` percent of the time, by pausing in
+// proportion to the CPU time the thread spent since the last pause. The time
+// it's been waiting, e.g. on a subprocess, isn't counted, and the part of a long
+// stretch of work that a capped pause doesn't make up for is left to the next.
+pub struct Pace {
+	limit: u8,
+	last:  Duration,
+}
+
+impl Pace {
+	pub fn new(limit: u8) -> Self { Self { limit: limit.clamp(1, 100), last: thread_cpu_time() } }
+
+	// Pauses if there's been enough work, and stops pausing as soon as
+	// `cancelled` returns true, which is checked between the slices
+	pub fn tick(&mut self, cancelled: impl Fn() -> bool) {
+		if self.limit >= 100 {
+			return;
+		}
+
+		let now = thread_cpu_time();
+		let busy = now.saturating_sub(self.last);
+		if busy < SLICE {
+			return;
+		}
+
+		let (mut pause, left) = Self::pause_for(busy, self.limit);
+		while !pause.is_zero() && !cancelled() {
+			let slice = pause.min(SLICE);
+			thread::sleep(slice);
+			pause -= slice;
+		}
+		self.last = thread_cpu_time().saturating_sub(left);
+	}
+
+	// The pause owed for `busy`, capped to `max_pause()`, and the work left over
+	// that the cap kept it from making up for
+	fn pause_for(busy: Duration, limit: u8) -> (Duration, Duration) {
+		let (l, max) = (limit as u32, Self::max_pause(limit));
+		let pause = busy * (100 - l) / l;
+		if pause <= max {
+			return (pause, Duration::ZERO);
+		}
+		(max, busy.saturating_sub(max * l / (100 - l)))
+	}
+
+	#[inline]
+	fn max_pause(limit: u8) -> Duration {
+		let l = limit as u32;
+		MAX_PAUSE.max(SLICE * (100 - l) / l)
+	}
+}
+
+#[cfg(unix)]
+fn thread_cpu_time() -> Duration {
+	let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+	unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut ts) };
+	Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+#[cfg(windows)]
+fn thread_cpu_time() -> Duration {
+	use windows_sys::Win32::{Foundation::FILETIME, System::Threading::{GetCurrentThread, GetThreadTimes}};
+
+	let mut t = [FILETIME { dwLowDateTime: 0, dwHighDateTime: 0 }; 4];
+	let [created, exited, kernel, user] = &mut t;
+	unsafe { GetThreadTimes(GetCurrentThread(), created, exited, kernel, user) };
+
+	// In 100-nanosecond units
+	let ticks = |t: &FILETIME| ((t.dwHighDateTime as u64) << 32) | t.dwLowDateTime as u64;
+	Duration::from_nanos((ticks(&t[2]) + ticks(&t[3])) * 100)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Instant};
+
+	use super::*;
+
+	fn work(d: Duration) {
+		let start = thread_cpu_time();
+		while thread_cpu_time() - start < d {}
+	}
+
+	#[test]
+	fn test_pause_for() {
+		let ms = Duration::from_millis;
+		assert_eq!(Pace::pause_for(ms(30), 100), (ms(0), ms(0)));
+		assert_eq!(Pace::pause_for(ms(30), 75), (ms(10), ms(0)));
+		assert_eq!(Pace::pause_for(ms(30), 50), (ms(30), ms(0)));
+
+		// A slice of work is never paused for too little, however low the limit
+		for limit in [1, 10, 20, 50, 99] {
+			let (pause, left) = Pace::pause_for(SLICE, limit);
+			let duty = SLICE.as_secs_f64() / (SLICE + pause).as_secs_f64();
+			assert_eq!((duty * 100.0).round() as u8, limit);
+			assert_eq!(left, Duration::ZERO);
+		}
+		assert_eq!(Pace::max_pause(50), MAX_PAUSE);
+		assert_eq!(Pace::max_pause(10), ms(90));
+
+		// What a capped pause doesn't make up for is left to the next one
+		assert_eq!(Pace::pause_for(ms(1000), 50), (MAX_PAUSE, ms(960)));
+		assert_eq!(Pace::pause_for(ms(100), 10), (ms(90), ms(90)));
+	}
+
+	#[test]
+	fn test_pace_cancel() {
+		let (waiting, cancelled) = (Arc::new(AtomicBool::new(false)), Arc::new(AtomicBool::new(false)));
+		let worker = thread::spawn({
+			let (waiting, cancelled) = (waiting.clone(), cancelled.clone());
+			move || {
+				let mut pace = Pace::new(1);
+				work(Duration::from_millis(20));
+
+				let start = Instant::now();
+				pace.tick(|| {
+					waiting.store(true, Ordering::Relaxed);
+					cancelled.load(Ordering::Relaxed)
+				});
+				start.elapsed()
+			}
+		});
+
+		// Cancelled once it's started pausing, and gets out after the slice it's in
+		while !waiting.load(Ordering::Relaxed) {
+			thread::yield_now();
+		}
+		cancelled.store(true, Ordering::Relaxed);
+
+		let waited = worker.join().unwrap();
+		assert!(waited < MAX_PAUSE, "waited for {waited:?}");
+	}
+
+	#[tokio::test]
+	async fn test_spawn_nice() {
+		let n = spawn_blocking_nice(5, || Handle::current().block_on(async { 1 + 1 })).await;
+		assert_eq!(n.unwrap(), 2);
+	}
+}
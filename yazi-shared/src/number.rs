@@ -26,3 +26,37 @@ impl PartialEq for OrderedFloat {
 }
 
 impl Eq for OrderedFloat {}
+
+#[inline]
+pub fn readable_size(size: u64) -> String { readable_size_in(size as f64, 1024, 1) }
+
+// `size` in the units of `base`, 1000 or 1024, with `precision` digits after the
+// point. It backs `ya.readable_size()` as well.
+pub fn readable_size_in(mut size: f64, base: u16, precision: usize) -> String {
+	const DECIMAL: [&str; 11] = ["B", "kB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB", "RB", "QB"];
+	const BINARY: [&str; 11] = ["B", "K", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
+
+	let units = if base == 1000 { DECIMAL } else { BINARY };
+	let (base, mut i) = (base as f64, 0);
+	while size >= base && i < units.len() - 1 {
+		size /= base;
+		i += 1;
+	}
+	format!("{size:.precision$}{}", units[i])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_readable_size() {
+		assert_eq!(readable_size(1000), "1000.0B");
+		assert_eq!(readable_size(1024), "1.0K");
+		assert_eq!(readable_size_in(1000.0, 1000, 1), "1.0kB");
+		assert_eq!(readable_size_in(1536.0, 1024, 2), "1.50K");
+		assert_eq!(readable_size_in(1500000.0, 1000, 0), "2MB");
+		assert_eq!(readable_size(5 * 1024u64.pow(3)), "5.0G");
+		assert_eq!(readable_size(u64::MAX), "16.0E");
+	}
+}